@@ -48,11 +48,13 @@ jest.mock("@prisma/client", () => {
 const mockCreateMarketRecord = jest.fn();
 const mockUpdateMarketPools = jest.fn();
 const mockUpdateMarketStatus = jest.fn();
+const mockAddToMarketPool = jest.fn();
 
 jest.mock("../market.service", () => ({
   createMarketRecord: (...args: unknown[]) => mockCreateMarketRecord(...args),
   updateMarketPools: (...args: unknown[]) => mockUpdateMarketPools(...args),
   updateMarketStatus: (...args: unknown[]) => mockUpdateMarketStatus(...args),
+  addToMarketPool: (...args: unknown[]) => mockAddToMarketPool(...args),
 }));
 
 // ── Mock bet.service ─────────────────────────────────────────────────────────
@@ -77,6 +79,8 @@ import {
   handleMarketCancelledEvent,
   handleWinningsClaimedEvent,
   handleRefundClaimedEvent,
+  decodeContractEvent,
+  EVENT_SCHEMA_VERSION,
   SorobanEvent,
   LedgerData,
 } from "../indexer.service";
@@ -1119,3 +1123,133 @@ describe("Task 4 — handleDisputeEvent (DisputeRaised)", () => {
     expect(mockUpdateMarketStatus).toHaveBeenCalledWith("MARKET_123", "Resolved");
   });
 });
+
+// ─────────────────────────────────────────────────────────────────────────────
+// decodeContractEvent — versioned contract topics
+// ─────────────────────────────────────────────────────────────────────────────
+
+describe("decodeContractEvent — versioned contract topics", () => {
+  beforeEach(() => {
+    jest.clearAllMocks();
+  });
+
+  it("skips events without the schema version topic", () => {
+    expect(decodeContractEvent(["market_resolved"], {})).toBeNull();
+    expect(decodeContractEvent(["market_resolved", EVENT_SCHEMA_VERSION + 1], {})).toBeNull();
+    expect(decodeContractEvent(["MarketResolved", EVENT_SCHEMA_VERSION - 1], {})).toBeNull();
+  });
+
+  it("maps market_created to the MarketCreated body keyed by numeric id", () => {
+    const decoded = decodeContractEvent(["market_created", EVENT_SCHEMA_VERSION], {
+      market_id: Buffer.from("ab", "hex"),
+      numeric_id: 7n,
+      market_address: "CMARKET",
+      creator: "GCREATOR",
+      fighter_a: "Ali",
+      fighter_b: "Frazier",
+      oracle: "GORACLE",
+      lock_time: 1_748_563_200n,
+      end_time: 1_748_736_000n,
+      created_at: 1_735_689_600n,
+    });
+
+    expect(decoded).toEqual({
+      type: "MarketCreated",
+      body: {
+        market_id: "7",
+        contractAddress: "CMARKET",
+        fighterA: { name: "Ali" },
+        fighterB: { name: "Frazier" },
+        scheduledAt: 1_748_736_000,
+        bettingEndsAt: 1_748_563_200,
+        oracleAddress: "GORACLE",
+        createdBy: "GCREATOR",
+      },
+    });
+  });
+
+  it("maps bet_placed with hex bet id, enum side and stringified amount", () => {
+    const decoded = decodeContractEvent(["bet_placed", EVENT_SCHEMA_VERSION], {
+      market_id: 7n,
+      bet_id: Buffer.from("deadbeef", "hex"),
+      bettor: "GBETTOR",
+      side: ["FighterB"],
+      amount: 5_000_000n,
+      placed_at: 1_735_689_600n,
+    });
+
+    expect(decoded).toEqual({
+      type: "BetPlaced",
+      body: {
+        bet_id: "deadbeef",
+        market_id: "7",
+        bettor: "GBETTOR",
+        side: "FighterB",
+        amount: "5000000",
+        placed_at: 1_735_689_600,
+      },
+    });
+  });
+
+  it("maps market_resolved and dispute events onto the handler names", () => {
+    expect(
+      decodeContractEvent(["market_resolved", EVENT_SCHEMA_VERSION], {
+        market_id: 7n,
+        outcome: ["Draw"],
+        resolved_at: 1n,
+      })
+    ).toEqual({ type: "MarketResolved", body: { market_id: "7", outcome: "Draw" } });
+
+    expect(
+      decodeContractEvent(["resolution_disputed", EVENT_SCHEMA_VERSION], {
+        market_id: 7n,
+        dispute_id: 0,
+        disputer: "GDISPUTER",
+        reason: Buffer.from("Wrong result"),
+      })
+    ).toEqual({
+      type: "DisputeRaised",
+      body: { market_id: "7", raised_by: "GDISPUTER", reason: "Wrong result" },
+    });
+
+    expect(
+      decodeContractEvent(["dispute_resolved", EVENT_SCHEMA_VERSION], {
+        market_id: 7n,
+        final_outcome: ["FighterA"],
+      })
+    ).toEqual({ type: "DisputeResolved", body: { market_id: "7", resolution: "FighterA" } });
+  });
+
+  it("passes unknown event names through for processLedger to log", () => {
+    const payload = { market_id: 7n };
+    expect(decodeContractEvent(["unclaimed_swept", EVENT_SCHEMA_VERSION], payload)).toEqual({
+      type: "unclaimed_swept",
+      body: payload,
+    });
+  });
+
+  it("adds the stake to its side's pool when a bet carries no pool totals", async () => {
+    mockRecordBet.mockResolvedValue({});
+    mockAddToMarketPool.mockResolvedValue({ poolA: 0n, poolB: 5_000_000n });
+
+    const decoded = decodeContractEvent(["bet_placed", EVENT_SCHEMA_VERSION], {
+      market_id: 7n,
+      bet_id: Buffer.from("deadbeef", "hex"),
+      bettor: "GBETTOR",
+      side: ["FighterB"],
+      amount: 5_000_000n,
+      placed_at: 1_735_689_600n,
+    })!;
+
+    await handleBetPlacedEvent({
+      ...decoded,
+      contractId: "CMARKET",
+      ledger: 1000,
+      ledgerClosedAt: "2025-01-01T00:00:00Z",
+      txHash: "0xBET",
+    });
+
+    expect(mockAddToMarketPool).toHaveBeenCalledWith("7", "FighterB", 5_000_000n);
+    expect(mockUpdateMarketPools).not.toHaveBeenCalled();
+  });
+});
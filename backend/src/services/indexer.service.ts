@@ -1,6 +1,6 @@
 import { PrismaClient } from "@prisma/client";
 import pino from "pino";
-import { SorobanRpc } from "@stellar/stellar-sdk";
+import { SorobanRpc, scValToNative } from "@stellar/stellar-sdk";
 
 import * as marketService from "./market.service";
 import * as betService from "./bet.service";
@@ -23,6 +23,11 @@ export interface SorobanEvent {
   txHash: string;
 }
 
+export interface DecodedEvent {
+  type: string;
+  body: Record<string, unknown>;
+}
+
 export interface LedgerData {
   sequence: number;
   closedAt: string;
@@ -68,14 +73,26 @@ export async function startIndexer(): Promise<void> {
 
       const byLedger = new Map<number, SorobanEvent[]>();
       for (const raw of eventsResponse.events) {
+        const decoded = decodeContractEvent(
+          raw.topic.map((t) => scValToNative(t)),
+          scValToNative(raw.value)
+        );
+        if (!decoded) {
+          logger.warn(
+            { ledger: raw.ledger, txHash: raw.txHash },
+            "Event topics do not match the contract schema version — skipping"
+          );
+          continue;
+        }
+
         const ledger = raw.ledger;
         if (!byLedger.has(ledger)) byLedger.set(ledger, []);
         byLedger.get(ledger)!.push({
-          type: raw.type,
+          type: decoded.type,
           contractId: raw.contractId as unknown as string,
           ledger: raw.ledger,
           ledgerClosedAt: raw.ledgerClosedAt,
-          body: raw.value as unknown as Record<string, unknown>,
+          body: decoded.body,
           txHash: raw.txHash,
         });
       }
@@ -108,6 +125,139 @@ function sleep(ms: number): Promise<void> {
   return new Promise((resolve) => setTimeout(resolve, ms));
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// decodeContractEvent: versioned contract topics → handler event types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/**
+ * Mirrors `EVENT_SCHEMA_VERSION` in contracts/shared/src/events.rs.
+ * Bump both together whenever a topic or payload shape changes.
+ */
+export const EVENT_SCHEMA_VERSION = 2;
+
+/** Contract topic names (snake_case symbols) mapped to the handler event types. */
+const EVENT_TYPES: Record<string, string> = {
+  market_created: "MarketCreated",
+  bet_placed: "BetPlaced",
+  market_resolved: "MarketResolved",
+  market_cancelled: "MarketCancelled",
+  winnings_claimed: "WinningsClaimed",
+  refund_claimed: "RefundClaimed",
+  market_locked: "MarketLocked",
+  resolution_disputed: "DisputeRaised",
+  dispute_resolved: "DisputeResolved",
+};
+
+/**
+ * Decodes a contract event from its native topics and payload
+ * (already passed through scValToNative).
+ *
+ * Contracts publish every event as `(name, EVENT_SCHEMA_VERSION)` with a
+ * struct payload from shared/src/events.rs. Returns null for events whose
+ * topics are not in that shape or carry another schema version, so a
+ * contract upgrade can never be decoded with stale field names.
+ *
+ * Known names are mapped to the handler types and their payloads reshaped
+ * into the bodies the handlers expect. Markets are keyed by the factory's
+ * numeric id, which every market event carries as `market_id`. Unknown
+ * names pass through unchanged and are logged by processLedger.
+ */
+export function decodeContractEvent(
+  topics: unknown[],
+  payload: unknown
+): DecodedEvent | null {
+  const [name, version] = topics;
+  if (typeof name !== "string" || Number(version) !== EVENT_SCHEMA_VERSION) {
+    return null;
+  }
+
+  const p = (payload ?? {}) as Record<string, unknown>;
+  const type = EVENT_TYPES[name] ?? name;
+
+  switch (name) {
+    case "market_created":
+      return {
+        type,
+        body: {
+          market_id: String(p.numeric_id),
+          contractAddress: String(p.market_address),
+          fighterA: { name: p.fighter_a },
+          fighterB: { name: p.fighter_b },
+          scheduledAt: Number(p.end_time),
+          bettingEndsAt: Number(p.lock_time),
+          oracleAddress: String(p.oracle),
+          createdBy: String(p.creator),
+        },
+      };
+    case "bet_placed":
+      return {
+        type,
+        body: {
+          bet_id: toHex(p.bet_id),
+          market_id: String(p.market_id),
+          bettor: String(p.bettor),
+          side: variantName(p.side),
+          amount: String(p.amount),
+          placed_at: Number(p.placed_at),
+        },
+      };
+    case "market_resolved":
+      return {
+        type,
+        body: { market_id: String(p.market_id), outcome: variantName(p.outcome) },
+      };
+    case "winnings_claimed":
+      return {
+        type,
+        body: {
+          market_id: String(p.market_id),
+          bet_id: toHex(p.bet_id),
+          bettor: String(p.bettor),
+          payout: String(p.payout),
+        },
+      };
+    case "refund_claimed":
+      return {
+        type,
+        body: {
+          market_id: String(p.market_id),
+          bet_id: toHex(p.bet_id),
+          bettor: String(p.bettor),
+          amount: String(p.amount),
+        },
+      };
+    case "resolution_disputed":
+      return {
+        type,
+        body: {
+          market_id: String(p.market_id),
+          raised_by: String(p.disputer),
+          reason: Buffer.from(p.reason as Uint8Array).toString("utf8"),
+        },
+      };
+    case "dispute_resolved":
+      return {
+        type,
+        body: { market_id: String(p.market_id), resolution: variantName(p.final_outcome) },
+      };
+    case "market_cancelled":
+    case "market_locked":
+      return { type, body: { market_id: String(p.market_id) } };
+    default:
+      return { type, body: p };
+  }
+}
+
+/** Contract unit enums decode to a single-element array, e.g. ["FighterA"]. */
+function variantName(value: unknown): string {
+  return Array.isArray(value) ? String(value[0]) : String(value);
+}
+
+/** Contract `Bytes` ids decode to a Buffer; the DB stores them as hex. */
+function toHex(value: unknown): string {
+  return Buffer.from(value as Uint8Array).toString("hex");
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // EventLog persistence (idempotent ingestion)
 // ─────────────────────────────────────────────────────────────────────────────
@@ -317,9 +467,12 @@ export async function handleMarketCreatedEvent(event: SorobanEvent): Promise<voi
  *   side:      "FighterA" | "FighterB",
  *   amount:    string | number | bigint,
  *   placed_at: string | number,
- *   pool_a:    string | number | bigint,   // updated totals after this bet
- *   pool_b:    string | number | bigint,
+ *   pool_a?:   string | number | bigint,   // updated totals after this bet
+ *   pool_b?:   string | number | bigint,
  * }
+ *
+ * Versioned `bet_placed` events carry only the stake, so when the pool
+ * totals are absent the stake is added to its side's pool instead.
  */
 export async function handleBetPlacedEvent(event: SorobanEvent): Promise<void> {
   const b = event.body;
@@ -335,19 +488,27 @@ export async function handleBetPlacedEvent(event: SorobanEvent): Promise<void> {
     txHash: event.txHash,
   });
 
-  await marketService.updateMarketPools(
-    b.market_id as string,
-    toBigInt(b.pool_a),
-    toBigInt(b.pool_b)
-  );
+  let poolA: bigint;
+  let poolB: bigint;
+  if (b.pool_a !== undefined && b.pool_b !== undefined) {
+    poolA = toBigInt(b.pool_a);
+    poolB = toBigInt(b.pool_b);
+    await marketService.updateMarketPools(b.market_id as string, poolA, poolB);
+  } else {
+    ({ poolA, poolB } = await marketService.addToMarketPool(
+      b.market_id as string,
+      b.side as "FighterA" | "FighterB",
+      toBigInt(b.amount)
+    ));
+  }
 
   publishMarketEvent(b.market_id as string, "bet_placed", {
     betId: b.bet_id,
     bettor: b.bettor,
     side: b.side,
     amount: String(toBigInt(b.amount)),
-    poolA: String(toBigInt(b.pool_a)),
-    poolB: String(toBigInt(b.pool_b)),
+    poolA: String(poolA),
+    poolB: String(poolB),
   });
 
   logger.info(
@@ -327,6 +327,27 @@ export async function updateMarketPools(
   });
 }
 
+/**
+ * Adds a stake to one side's pool and to the total, returning the new pool
+ * totals. Used for events that carry only the stake rather than the totals.
+ */
+export async function addToMarketPool(
+  market_id: string,
+  side: "FighterA" | "FighterB",
+  amount: bigint
+): Promise<{ poolA: bigint; poolB: bigint }> {
+  const updated = await db.market.update({
+    where: { id: market_id },
+    data: {
+      ...(side === "FighterA"
+        ? { poolA: { increment: amount } }
+        : { poolB: { increment: amount } }),
+      totalPool: { increment: amount },
+    },
+  });
+  return { poolA: updated.poolA, poolB: updated.poolB };
+}
+
 export async function getMarketStats(market_id: string): Promise<MarketStats> {
   const market = await db.market.findUnique({
     where: { id: market_id },
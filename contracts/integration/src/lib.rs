@@ -0,0 +1,172 @@
+//! ============================================================
+//! BOXMEOUT — Full-Stack Test Harness
+//! Stands up the real MarketFactory, Treasury and Market
+//! contracts against a Stellar asset token, so tests can drive
+//! whole market lifecycles through the public entry points and
+//! check token balances end to end. Host-only; nothing here is
+//! deployed.
+//! ============================================================
+
+use market::MarketContractClient;
+use market_factory::{MarketFactory, MarketFactoryClient, MIN_ORACLE_STAKE};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token::{StellarAssetClient, TokenClient},
+    Address, Bytes, Env, String,
+};
+use treasury::{Treasury, TreasuryClient};
+
+/// Ledger time the harness starts at.
+pub const START_TIME: u64 = 1_700_000_000;
+/// Betting on a harness market closes this long after it is created.
+pub const LOCK_AFTER_SEC: u64 = 24 * 60 * 60;
+/// A harness market's fight is scheduled this long after it is created.
+pub const FIGHT_AFTER_SEC: u64 = 2 * 24 * 60 * 60;
+/// Fee the treasury is initialized with, in basis points.
+pub const TREASURY_FEE_BPS: u32 = 200;
+
+/// The Market contract the factory deploys, built by
+/// `scripts/build_market_fixture.sh`.
+const MARKET_WASM: &[u8] = include_bytes!("../../market_factory/test_fixtures/market.wasm");
+
+/// A factory, treasury and bet token wired together, with a staked oracle.
+pub struct Harness {
+    pub env: Env,
+    pub admin: Address,
+    /// Receives the fees the admin withdraws from the treasury.
+    pub fee_recipient: Address,
+    /// Bonded with `MIN_ORACLE_STAKE` and set as every market's oracle.
+    pub oracle: Address,
+    pub token: TokenClient<'static>,
+    pub factory: MarketFactoryClient<'static>,
+    pub treasury: TreasuryClient<'static>,
+    token_admin: StellarAssetClient<'static>,
+}
+
+impl Harness {
+    /// Registers the token, treasury and factory, initializes both
+    /// contracts, and stakes the oracle through the factory.
+    pub fn new() -> Self {
+        let env = Env::default();
+        // Markets call into the treasury and the factory on a bettor's
+        // behalf, so the mocked auths must reach past the root invocation.
+        env.mock_all_auths_allowing_non_root_auth();
+        env.cost_estimate().budget().reset_unlimited();
+        // The fixture is the unoptimized Market build, which is over the
+        // network's contract size limit.
+        env.cost_estimate().disable_resource_limits();
+        env.ledger().with_mut(|l| l.timestamp = START_TIME);
+
+        let admin = Address::generate(&env);
+        let fee_recipient = Address::generate(&env);
+        let oracle = Address::generate(&env);
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(admin.clone())
+            .address();
+        let treasury_id = env.register(Treasury, ());
+        let factory_id = env.register(MarketFactory, ());
+
+        let token = TokenClient::new(&env, &token_id);
+        let token_admin = StellarAssetClient::new(&env, &token_id);
+        let treasury = TreasuryClient::new(&env, &treasury_id);
+        let factory = MarketFactoryClient::new(&env, &factory_id);
+
+        treasury.initialize(
+            &admin,
+            &TREASURY_FEE_BPS,
+            &fee_recipient,
+            &factory_id,
+            &token_id,
+        );
+        let wasm_hash = env.deployer().upload_contract_wasm(MARKET_WASM);
+        factory.initialize(&admin, &wasm_hash, &treasury_id);
+
+        token_admin.mint(&oracle, &MIN_ORACLE_STAKE);
+        factory.stake_oracle(&oracle, &MIN_ORACLE_STAKE);
+
+        Harness {
+            env,
+            admin,
+            fee_recipient,
+            oracle,
+            token,
+            factory,
+            treasury,
+            token_admin,
+        }
+    }
+
+    /// Returns a fresh address holding `balance` of the bet token.
+    pub fn bettor(&self, balance: i128) -> Address {
+        let bettor = Address::generate(&self.env);
+        self.token_admin.mint(&bettor, &balance);
+        bettor
+    }
+
+    /// Bet-token balance of `who`.
+    pub fn balance(&self, who: &Address) -> i128 {
+        self.token.balance(who)
+    }
+
+    /// Creates a market through the factory, which deploys and initializes
+    /// it with the staked oracle and its live config.
+    ///
+    /// # Returns
+    ///
+    /// Returns the factory market id and a client for the market.
+    pub fn create_market(
+        &self,
+        fighter_a: &str,
+        fighter_b: &str,
+        draw_enabled: bool,
+    ) -> (Bytes, MarketContractClient<'static>) {
+        let now = self.env.ledger().timestamp();
+        let market_id = self.factory.create_market(
+            &self.admin,
+            &String::from_str(&self.env, fighter_a),
+            &String::from_str(&self.env, fighter_b),
+            &self.oracle,
+            &(now + LOCK_AFTER_SEC),
+            &(now + FIGHT_AFTER_SEC),
+            &draw_enabled,
+        );
+        let info = self
+            .factory
+            .get_market(&market_id)
+            .expect("market registered");
+        let market = MarketContractClient::new(&self.env, &info.market_address);
+        (market_id, market)
+    }
+
+    /// Moves the ledger clock forward by `secs`.
+    pub fn advance(&self, secs: u64) {
+        self.env.ledger().with_mut(|l| l.timestamp += secs);
+    }
+
+    /// Lets `market`'s dispute window lapse and finalizes its resolution,
+    /// which opens claims.
+    pub fn finalize(&self, market: &MarketContractClient) {
+        let info = market.get_market_info();
+        self.env
+            .ledger()
+            .with_mut(|l| l.timestamp = info.resolved_at + info.dispute_window_sec + 1);
+        market.finalize_resolution(&None);
+    }
+
+    /// Asserts that the treasury and every market in `markets` reconcile.
+    pub fn assert_books(&self, markets: &[&MarketContractClient]) {
+        let treasury = self.treasury.check_invariants();
+        assert!(treasury.ok, "treasury books out of line: {treasury:?}");
+        for market in markets {
+            let report = market.check_invariants();
+            assert!(report.ok, "market books out of line: {report:?}");
+        }
+    }
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,323 @@
+//! =============================================================================
+//! BOXMEOUT — Full-Stack Lifecycle
+//! =============================================================================
+//!
+//! Drives the real factory, markets and treasury against a Stellar asset token:
+//!   - Creation: the factory deploys and initializes each market
+//!   - Resolution: the protocol fee booked once the outcome is final, winners
+//!     paid through factory `claim_many`, unclaimed winnings swept after the
+//!     claim window, and the fees withdrawn by the admin
+//!   - Single-bet claims: `claim_winnings`, `claim_refund` and
+//!     `claim_prop_winnings` pay out tokens
+//!   - Cancellation: a cash-out fee kept, every remaining stake refunded
+//!   - A fight card: one `claim_many` collecting a win and a refund
+//!   - `claim_many` paging through more bets than one `claim_all` page
+//!   - The oracle bond returned after unbonding
+//!
+//! Every test checks that no token is created or lost, and that the market and
+//! treasury invariant reports hold after each step.
+
+use integration::{Harness, LOCK_AFTER_SEC};
+use market::types::{BetSide, MarketStatus, Outcome};
+use market_factory::{MIN_ORACLE_STAKE, ORACLE_UNBONDING_SEC};
+use shared::types::{FightResult, PropSelection, VictoryMethod};
+use soroban_sdk::{vec, Address};
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+const XLM: i128 = 10_000_000;
+const START_BALANCE: i128 = 1_000 * XLM;
+
+/// Tokens held by `holders` and the treasury; constant across a test unless
+/// the harness mints.
+fn supply(h: &Harness, holders: &[&Address]) -> i128 {
+    holders.iter().map(|a| h.balance(a)).sum::<i128>() + h.balance(&h.treasury.address)
+}
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn factory_deploys_initialized_markets() {
+    let h = Harness::new();
+    h.create_market("Fury", "Usyk", false);
+    let (market_id, market) = h.create_market("Taylor", "Serrano", true);
+
+    let info = market.get_market_info();
+    assert_eq!(info.market_id, market_id);
+    assert_eq!(info.numeric_id, 2);
+    assert!(info.draw_enabled);
+    assert_eq!(info.oracle_address, h.oracle);
+    assert_eq!(info.treasury, h.treasury.address);
+    assert_eq!(info.bet_token, h.token.address);
+    assert_eq!(info.protocol_fee_bp, h.factory.get_config().default_fee_bp);
+
+    let dana = h.bettor(START_BALANCE);
+    market.place_bet(&dana, &BetSide::Draw, &(5 * XLM));
+    assert_eq!(h.treasury.get_market_escrow(&market_id), 5 * XLM);
+    assert_eq!(h.balance(&dana), START_BALANCE - 5 * XLM);
+    h.assert_books(&[&market]);
+}
+
+#[test]
+fn resolved_market_books_the_fee_then_sweeps_unclaimed_winnings() {
+    let h = Harness::new();
+    let mut config = h.factory.get_config();
+    config.claim_expiry_sec = 7 * 24 * 60 * 60;
+    h.factory.update_config(&h.admin, &config);
+
+    let (market_id, market) = h.create_market("Fury", "Usyk", false);
+    let alice = h.bettor(START_BALANCE);
+    let bob = h.bettor(START_BALANCE);
+    let carol = h.bettor(START_BALANCE);
+    let everyone = [&alice, &bob, &carol, &h.fee_recipient, &h.oracle];
+    let minted = supply(&h, &everyone);
+
+    market.place_bet(&alice, &BetSide::FighterA, &(30 * XLM));
+    market.place_bet(&bob, &BetSide::FighterB, &(10 * XLM));
+    market.place_bet(&carol, &BetSide::FighterA, &(10 * XLM));
+    assert_eq!(h.treasury.get_market_escrow(&market_id), 50 * XLM);
+    h.assert_books(&[&market]);
+
+    h.advance(LOCK_AFTER_SEC);
+    market.resolve_market(&h.oracle, &Outcome::FighterA);
+    h.finalize(&market);
+    assert_eq!(market.get_market_info().status, MarketStatus::Resolved);
+    assert_eq!(h.treasury.get_total_fees_earned(), XLM);
+    assert_eq!(h.treasury.get_market_escrow(&market_id), 49 * XLM);
+    assert_eq!(
+        h.factory
+            .get_oracle_reputation(&h.oracle)
+            .unwrap()
+            .resolutions,
+        1
+    );
+    h.assert_books(&[&market]);
+
+    // 50 XLM less the 2% fee, split 3:1 between the FighterA backers.
+    let claims = h
+        .factory
+        .claim_many(&alice, &vec![&h.env, market_id.clone()]);
+    assert_eq!(claims.get(0).unwrap().payout, 367_500_000);
+    assert_eq!(market.claim_all(&bob, &0).total_payout, 0);
+    h.assert_books(&[&market]);
+
+    // Carol never claims, so her share is swept once the window closes.
+    h.advance(config.claim_expiry_sec + 1);
+    assert_eq!(market.sweep_unclaimed(), 122_500_000);
+    let fees = XLM + 122_500_000;
+    assert_eq!(h.treasury.get_total_fees_earned(), fees);
+    h.treasury.withdraw_fees(&h.admin, &h.fee_recipient, &fees);
+    h.assert_books(&[&market]);
+
+    assert_eq!(h.balance(&alice), START_BALANCE + 67_500_000);
+    assert_eq!(h.balance(&bob), START_BALANCE - 10 * XLM);
+    assert_eq!(h.balance(&carol), START_BALANCE - 10 * XLM);
+    assert_eq!(h.balance(&h.fee_recipient), fees);
+    assert_eq!(h.balance(&h.treasury.address), MIN_ORACLE_STAKE);
+    assert_eq!(supply(&h, &everyone), minted);
+}
+
+#[test]
+fn single_bet_claims_pay_out_tokens() {
+    let h = Harness::new();
+    let (won_id, won) = h.create_market("Lomachenko", "Haney", false);
+    let (off_id, off) = h.create_market("Spence", "Crawford", false);
+    let ivy = h.bettor(START_BALANCE);
+    let jack = h.bettor(START_BALANCE);
+    let kim = h.bettor(START_BALANCE);
+    let everyone = [&ivy, &jack, &kim, &h.oracle];
+    let minted = supply(&h, &everyone);
+
+    let ivy_bet = won.place_bet(&ivy, &BetSide::FighterA, &(30 * XLM));
+    let jack_bet = won.place_bet(&jack, &BetSide::FighterA, &(10 * XLM));
+    won.place_bet(&kim, &BetSide::FighterB, &(10 * XLM));
+    let kim_refund = off.place_bet(&kim, &BetSide::FighterB, &(5 * XLM));
+
+    h.advance(LOCK_AFTER_SEC);
+    won.resolve_market(&h.oracle, &Outcome::FighterA);
+    h.finalize(&won);
+    off.cancel_market(&h.admin);
+
+    assert_eq!(won.claim_winnings(&ivy, &ivy_bet), 367_500_000);
+    assert_eq!(won.claim_winnings(&jack, &jack_bet), 122_500_000);
+    assert_eq!(off.claim_refund(&kim, &kim_refund), 5 * XLM);
+    h.assert_books(&[&won, &off]);
+
+    assert_eq!(h.balance(&ivy), START_BALANCE + 67_500_000);
+    assert_eq!(h.balance(&jack), START_BALANCE + 22_500_000);
+    assert_eq!(h.balance(&kim), START_BALANCE - 10 * XLM);
+    // The fee left escrow when the resolution was finalized.
+    assert_eq!(h.treasury.get_total_fees_earned(), XLM);
+    assert_eq!(h.treasury.get_market_escrow(&won_id), 0);
+    assert_eq!(h.treasury.get_market_escrow(&off_id), 0);
+    assert_eq!(supply(&h, &everyone), minted);
+}
+
+#[test]
+fn prop_claims_pay_out_tokens() {
+    let h = Harness::new();
+    let (market_id, market) = h.create_market("Bivol", "Beterbiev", false);
+    let lena = h.bettor(START_BALANCE);
+    let mo = h.bettor(START_BALANCE);
+    let nia = h.bettor(START_BALANCE);
+    let everyone = [&lena, &mo, &nia, &h.oracle];
+    let minted = supply(&h, &everyone);
+
+    let ko = PropSelection::WinsByMethod(BetSide::FighterA, VictoryMethod::Knockout);
+    let lena_bet = market.place_prop_bet(&lena, &ko, &(10 * XLM));
+    let mo_bet = market.place_prop_bet(&mo, &PropSelection::EndsInRound(3), &(10 * XLM));
+    let nia_bet = market.place_prop_bet(&nia, &PropSelection::GoesTheDistance, &(20 * XLM));
+    assert_eq!(h.treasury.get_market_escrow(&market_id), 40 * XLM);
+
+    h.advance(LOCK_AFTER_SEC);
+    market.resolve_with_result(
+        &h.oracle,
+        &FightResult {
+            winner: Outcome::FighterA,
+            method: VictoryMethod::Knockout,
+            round: 3,
+        },
+    );
+
+    // 40 XLM less the 2% fee, split evenly across the two winning selections.
+    assert_eq!(market.claim_prop_winnings(&lena, &lena_bet), 196_000_000);
+    assert_eq!(market.claim_prop_winnings(&mo, &mo_bet), 196_000_000);
+    assert!(market.try_claim_prop_winnings(&nia, &nia_bet).is_err());
+    h.assert_books(&[&market]);
+
+    assert_eq!(h.balance(&lena), START_BALANCE + 96_000_000);
+    assert_eq!(h.balance(&mo), START_BALANCE + 96_000_000);
+    assert_eq!(h.balance(&nia), START_BALANCE - 20 * XLM);
+    // Nobody backed a side, so the market settled as cancelled and the prop
+    // fee was booked at once.
+    assert_eq!(h.treasury.get_total_fees_earned(), 8_000_000);
+    assert_eq!(h.treasury.get_market_escrow(&market_id), 0);
+    assert_eq!(supply(&h, &everyone), minted);
+}
+
+#[test]
+fn cancelled_market_refunds_stakes_and_keeps_the_cash_out_fee() {
+    let h = Harness::new();
+    let (market_id, market) = h.create_market("Joshua", "Ngannou", true);
+    let dave = h.bettor(START_BALANCE);
+    let erin = h.bettor(START_BALANCE);
+    let frank = h.bettor(START_BALANCE);
+    let everyone = [&dave, &erin, &frank, &h.oracle];
+    let minted = supply(&h, &everyone);
+
+    let dave_bet = market.place_bet(&dave, &BetSide::FighterB, &(20 * XLM));
+    market.place_bet(&erin, &BetSide::FighterA, &(10 * XLM));
+    market.place_bet(&frank, &BetSide::Draw, &(5 * XLM));
+
+    // The default 5% early-exit fee stays with the treasury.
+    assert_eq!(market.cash_out(&dave, &dave_bet), 19 * XLM);
+    assert_eq!(h.treasury.get_total_fees_earned(), XLM);
+    assert_eq!(h.treasury.get_market_escrow(&market_id), 15 * XLM);
+    h.assert_books(&[&market]);
+
+    market.cancel_market(&h.admin);
+    h.assert_books(&[&market]);
+    assert_eq!(market.claim_all(&erin, &0).total_payout, 10 * XLM);
+    assert_eq!(market.claim_all(&frank, &0).total_payout, 5 * XLM);
+    assert_eq!(market.claim_all(&dave, &0).total_payout, 0);
+    h.assert_books(&[&market]);
+
+    assert_eq!(h.treasury.get_market_escrow(&market_id), 0);
+    assert_eq!(h.balance(&dave), START_BALANCE - XLM);
+    assert_eq!(h.balance(&erin), START_BALANCE);
+    assert_eq!(h.balance(&frank), START_BALANCE);
+    assert_eq!(h.balance(&h.treasury.address), MIN_ORACLE_STAKE + XLM);
+    assert_eq!(supply(&h, &everyone), minted);
+}
+
+#[test]
+fn claim_many_collects_across_a_fight_card() {
+    let h = Harness::new();
+    let (main_id, main_event) = h.create_market("Canelo", "Crawford", false);
+    let (co_id, co_main) = h.create_market("Inoue", "Nery", false);
+    assert_eq!(h.factory.get_market(&co_id).unwrap().numeric_id, 2);
+
+    let gina = h.bettor(START_BALANCE);
+    let hank = h.bettor(START_BALANCE);
+    let everyone = [&gina, &hank, &h.oracle];
+    let minted = supply(&h, &everyone);
+
+    main_event.place_bet(&gina, &BetSide::FighterA, &(10 * XLM));
+    main_event.place_bet(&hank, &BetSide::FighterB, &(10 * XLM));
+    co_main.place_bet(&gina, &BetSide::FighterB, &(5 * XLM));
+    h.assert_books(&[&main_event, &co_main]);
+
+    h.advance(LOCK_AFTER_SEC);
+    main_event.resolve_market(&h.oracle, &Outcome::FighterA);
+    h.finalize(&main_event);
+    co_main.cancel_market(&h.admin);
+    h.assert_books(&[&main_event, &co_main]);
+
+    let claims = h
+        .factory
+        .claim_many(&gina, &vec![&h.env, main_id.clone(), co_id.clone()]);
+    let payouts: Vec<_> = claims
+        .iter()
+        .map(|c| (c.payout, c.succeeded, c.done))
+        .collect();
+    assert_eq!(payouts, [(196_000_000, true, true), (5 * XLM, true, true)]);
+    h.assert_books(&[&main_event, &co_main]);
+
+    // The protocol fee was booked when the main event was finalized.
+    assert_eq!(h.treasury.get_total_fees_earned(), 4_000_000);
+    assert_eq!(h.treasury.get_market_escrow(&main_id), 0);
+    assert_eq!(h.treasury.get_market_escrow(&co_id), 0);
+    assert_eq!(h.balance(&gina), START_BALANCE + 96_000_000);
+    assert_eq!(h.balance(&hank), START_BALANCE - 10 * XLM);
+    assert_eq!(supply(&h, &everyone), minted);
+}
+
+#[test]
+fn claim_many_pages_through_every_bet() {
+    let h = Harness::new();
+    let (market_id, market) = h.create_market("Usyk", "Fury", false);
+
+    let ivy = h.bettor(START_BALANCE);
+    let jack = h.bettor(START_BALANCE);
+    let everyone = [&ivy, &jack, &h.oracle];
+    let minted = supply(&h, &everyone);
+
+    // More bets than one `claim_all` page.
+    for _ in 0..30 {
+        market.place_bet(&ivy, &BetSide::FighterA, &XLM);
+    }
+    market.place_bet(&jack, &BetSide::FighterB, &(10 * XLM));
+
+    h.advance(LOCK_AFTER_SEC);
+    market.resolve_market(&h.oracle, &Outcome::FighterA);
+    h.finalize(&market);
+
+    let claims = h.factory.claim_many(&ivy, &vec![&h.env, market_id.clone()]);
+    let claim = claims.get(0).unwrap();
+    assert_eq!(claim.bets_claimed, 30);
+    assert!(claim.succeeded && claim.done);
+    // The 40 XLM pool less the 2% fee.
+    assert_eq!(claim.payout, 392_000_000);
+    h.assert_books(&[&market]);
+
+    assert_eq!(h.treasury.get_market_escrow(&market_id), 0);
+    assert_eq!(h.balance(&ivy), START_BALANCE + 92_000_000);
+    assert_eq!(supply(&h, &everyone), minted);
+}
+
+#[test]
+fn oracle_bond_returns_after_unbonding() {
+    let h = Harness::new();
+    assert_eq!(h.treasury.get_oracle_stake(&h.oracle), MIN_ORACLE_STAKE);
+    assert_eq!(h.balance(&h.oracle), 0);
+    h.assert_books(&[]);
+
+    h.factory.deregister_oracle(&h.oracle);
+    h.advance(ORACLE_UNBONDING_SEC);
+    assert_eq!(h.factory.withdraw_oracle_stake(&h.oracle), MIN_ORACLE_STAKE);
+
+    assert_eq!(h.balance(&h.oracle), MIN_ORACLE_STAKE);
+    assert_eq!(h.balance(&h.treasury.address), 0);
+    assert_eq!(h.treasury.get_oracle_stake(&h.oracle), 0);
+    h.assert_books(&[]);
+}
@@ -0,0 +1,135 @@
+//! ============================================================
+//! BOXMEOUT — Signed Oracle Attestations
+//! An alternative to `resolve_market` for oracles that sign
+//! results off-chain. Anyone may relay an `OracleAttestation`
+//! together with an ed25519 signature from a registered oracle
+//! key; the oracle key itself never submits a transaction.
+//! ============================================================
+
+use shared::errors::ContractError;
+use shared::events::{self, AttestationAcceptedEvent};
+use soroban_sdk::{contractimpl, panic_with_error, xdr::ToXdr, Address, BytesN, Env, Vec};
+
+use crate::types::{MarketStatus, OracleAttestation};
+use crate::{DataKey, MarketContract, MarketContractArgs, MarketContractClient};
+
+/// Maximum number of oracle keys a market accepts attestations from.
+pub const MAX_ORACLE_KEYS: u32 = 5;
+
+fn read_oracle_keys(env: &Env) -> Vec<BytesN<32>> {
+    env.storage().persistent()
+        .get(&DataKey::OracleKeys)
+        .unwrap_or(Vec::new(env))
+}
+
+#[contractimpl]
+impl MarketContract {
+    /// Resolves the market from a signed oracle result. Permissionless: the
+    /// signature, not the caller, proves the result came from the oracle.
+    ///
+    /// The signed message is `attestation.to_xdr()`. Each nonce can be used
+    /// once. Emits an `attestation_accepted` event, then resolves exactly as
+    /// `resolve_market` would.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `attestation` - The signed result payload.
+    /// * `public_key` - The registered oracle key that signed it.
+    /// * `signature` - ed25519 signature over the attestation's XDR.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `public_key` is not registered (`ContractError::InvalidOracleSignature`).
+    /// - The signature does not verify (host error).
+    /// - The attestation names another market, predates `scheduled_at`, or is
+    ///   dated in the future.
+    /// - The nonce was already used.
+    /// - The market status is not `Locked`, or its resolution deadline has
+    ///   passed (`ContractError::ResolutionWindowExpired`).
+    pub fn resolve_with_attestation(
+        env: Env,
+        attestation: OracleAttestation,
+        public_key: BytesN<32>,
+        signature: BytesN<64>,
+    ) {
+        if !read_oracle_keys(&env).contains(&public_key) {
+            panic_with_error!(&env, ContractError::InvalidOracleSignature);
+        }
+
+        let market = Self::read_market(&env);
+        if market.status != MarketStatus::Locked {
+            panic!("market not locked");
+        }
+        if attestation.market_id != market.market_id {
+            panic!("attestation for another market");
+        }
+        if attestation.timestamp < market.scheduled_at {
+            panic!("attestation predates the fight");
+        }
+        if attestation.timestamp > env.ledger().timestamp() {
+            panic!("attestation dated in the future");
+        }
+        let nonce_key = DataKey::AttestationNonce(attestation.nonce);
+        if env.storage().persistent().has(&nonce_key) {
+            panic!("nonce already used");
+        }
+
+        let message = attestation.clone().to_xdr(&env);
+        env.crypto().ed25519_verify(&public_key, &message, &signature);
+        env.storage().persistent().set(&nonce_key, &true);
+
+        events::emit_attestation_accepted(&env, AttestationAcceptedEvent {
+            market_id: market.numeric_id,
+            public_key,
+            nonce: attestation.nonce,
+            timestamp: attestation.timestamp,
+        });
+
+        Self::record_resolution(&env, market, attestation.outcome);
+    }
+
+    /// Registers an ed25519 key whose signed results may resolve this market.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `admin` is not the factory admin, the key is already
+    /// registered, or `MAX_ORACLE_KEYS` keys are registered.
+    pub fn add_oracle_key(env: Env, admin: Address, public_key: BytesN<32>) {
+        admin.require_auth();
+        if admin != Self::read_config(&env).admin {
+            panic!("not admin");
+        }
+        let mut keys = read_oracle_keys(&env);
+        if keys.contains(&public_key) {
+            panic!("oracle key already registered");
+        }
+        if keys.len() >= MAX_ORACLE_KEYS {
+            panic!("too many oracle keys");
+        }
+        keys.push_back(public_key);
+        env.storage().persistent().set(&DataKey::OracleKeys, &keys);
+    }
+
+    /// Revokes a registered oracle key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `admin` is not the factory admin or the key is not registered.
+    pub fn remove_oracle_key(env: Env, admin: Address, public_key: BytesN<32>) {
+        admin.require_auth();
+        if admin != Self::read_config(&env).admin {
+            panic!("not admin");
+        }
+        let mut keys = read_oracle_keys(&env);
+        let index = keys.first_index_of(&public_key).expect("oracle key not registered");
+        keys.remove(index);
+        env.storage().persistent().set(&DataKey::OracleKeys, &keys);
+    }
+
+    /// Returns the ed25519 keys accepted by `resolve_with_attestation`.
+    pub fn get_oracle_keys(env: Env) -> Vec<BytesN<32>> {
+        read_oracle_keys(&env)
+    }
+}
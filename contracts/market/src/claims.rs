@@ -0,0 +1,378 @@
+//! ============================================================
+//! BOXMEOUT — Batch Claims and Claim Deadline
+//! Settles many bets of one bettor in a single call, paging
+//! through `BetsByAddr` and then `PropBetsByAddr` with a
+//! continuation cursor. Claims
+//! open once the settlement is final (a cancellation, or a
+//! finalized resolution) and expire `claim_expiry_sec` later,
+//! after which the remaining escrow can be swept to the
+//! treasury. Every payout is recorded in a ledger so rounding
+//! dust is accounted for.
+//! ============================================================
+
+use shared::errors::ContractError;
+use shared::events::{self, BatchClaimedEvent, UnclaimedSweptEvent};
+use soroban_sdk::{contractimpl, panic_with_error, Address, Bytes, Env, IntoVal, Symbol, Vec};
+
+use crate::types::{
+    BatchClaim, Bet, BetSide, Market, MarketStatus, Outcome, PayoutLedger, PropBet,
+    ProtocolConfig,
+};
+use crate::{DataKey, MarketContract, MarketContractArgs, MarketContractClient};
+
+/// Maximum number of bets `claim_all` visits per call.
+pub const CLAIM_PAGE_SIZE: u32 = 25;
+
+/// Opens claims once the settlement is final: books the protocol fee on the
+/// winner and prop pools with the treasury, then records when claims expire.
+/// A `claim_expiry_sec` of zero leaves claims open forever.
+///
+/// The fee leaves escrow here rather than at the sweep, so it is collected
+/// even when claims never expire.
+pub(crate) fn start_claim_period(env: &Env, market: &Market, config: &ProtocolConfig) {
+    let (_, _, fee) = settlement_split(market);
+    let fee = fee
+        .checked_add(crate::props::prop_fee(env, market))
+        .expect("fee overflow");
+    if fee > 0 {
+        MarketContract::collect_fee(env, market, fee);
+        record_released(env, fee);
+    }
+
+    if config.claim_expiry_sec == 0 {
+        env.storage().persistent().remove(&DataKey::ClaimDeadline);
+        return;
+    }
+    let deadline = env.ledger().timestamp() + config.claim_expiry_sec;
+    env.storage().persistent().set(&DataKey::ClaimDeadline, &deadline);
+}
+
+/// Records that the resolution can no longer be disputed.
+pub(crate) fn mark_final(env: &Env) {
+    env.storage().persistent().set(&DataKey::Finalized, &true);
+}
+
+/// True once payouts can no longer change: the market was cancelled, or its
+/// resolution was finalized. Only a `Resolved` market can be disputed, so a
+/// cancellation is final straight away.
+pub(crate) fn is_final(env: &Env, market: &Market) -> bool {
+    match market.status {
+        MarketStatus::Cancelled => true,
+        MarketStatus::Resolved => env.storage().persistent().get(&DataKey::Finalized).unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Panics until the market's settlement is final, so no payout is made
+/// against an outcome a dispute could still overturn.
+pub(crate) fn require_final(env: &Env, market: &Market) {
+    if !is_final(env, market) {
+        panic!("resolution not finalized");
+    }
+}
+
+/// Panics with `ContractError::ClaimExpired` once the claim deadline has passed.
+pub(crate) fn require_claim_open(env: &Env) {
+    let deadline: Option<u64> = env.storage().persistent().get(&DataKey::ClaimDeadline);
+    if deadline.is_some_and(|d| env.ledger().timestamp() > d) {
+        panic_with_error!(env, ContractError::ClaimExpired);
+    }
+}
+
+/// Splits the settled pool into `(payable, stake_basis, fee)`: on refunds the
+/// whole pool is shared across every stake with no fee, otherwise the net pool
+/// is shared across the winning side's stake.
+pub(crate) fn settlement_split(market: &Market) -> (i128, i128, i128) {
+    if MarketContract::refunds_all(market) {
+        return (market.total_pool, market.total_pool, 0);
+    }
+    let fee = shared::types::calculate_fee(market.total_pool, market.protocol_fee_bp);
+    let net_pool = market.total_pool.checked_sub(fee).expect("net pool underflow");
+    let winning_pool = market
+        .outcome
+        .outcome()
+        .and_then(|o| MarketContract::winning_pool(market, &o))
+        .unwrap_or(0);
+    (net_pool, winning_pool, fee)
+}
+
+/// Amount owed on `bet` once the market has settled: the full stake when the
+/// outcome refunds, the parimutuel share when the bet won, otherwise zero.
+///
+/// Shares are truncated; the claim that settles the last unclaimed stake
+/// receives whatever the earlier claims left behind, so the pool is always
+/// paid out to the stroop.
+pub(crate) fn settlement_amount(env: &Env, market: &Market, bet: &Bet) -> i128 {
+    if !MarketContract::refunds_all(market) {
+        if market.status != MarketStatus::Resolved {
+            return 0;
+        }
+        let won = matches!(
+            (&bet.side, market.outcome.outcome().as_ref()),
+            (BetSide::FighterA, Some(Outcome::FighterA))
+                | (BetSide::FighterB, Some(Outcome::FighterB))
+                | (BetSide::Draw, Some(Outcome::Draw))
+        );
+        if !won {
+            return 0;
+        }
+    }
+
+    let (payable, stake_basis, _) = settlement_split(market);
+    if stake_basis == 0 {
+        return 0;
+    }
+    let stake_claimed: i128 = env.storage().persistent().get(&DataKey::StakeClaimed).unwrap_or(0);
+    if stake_claimed.checked_add(bet.amount).expect("stake overflow") == stake_basis {
+        let paid_out: i128 = env.storage().persistent().get(&DataKey::PaidOut).unwrap_or(0);
+        return payable.checked_sub(paid_out).expect("paid out exceeds pool");
+    }
+    bet.amount
+        .checked_mul(payable)
+        .expect("payout overflow")
+        .checked_div(stake_basis)
+        .expect("payout div zero")
+}
+
+/// Adds `amount` to what has left escrow since settlement. Bond settlements
+/// are not counted: they leave with the bonds they pay out.
+pub(crate) fn record_released(env: &Env, amount: i128) {
+    let released: i128 = env.storage().persistent().get(&DataKey::Released).unwrap_or(0);
+    env.storage().persistent().set(
+        &DataKey::Released,
+        &released.checked_add(amount).expect("release overflow"),
+    );
+}
+
+/// Adds a settled bet to the payout ledger. Call once per claim, with the
+/// amount `settlement_amount` returned for it.
+pub(crate) fn record_payout(env: &Env, bet: &Bet, amount: i128) {
+    let stake_claimed: i128 = env.storage().persistent().get(&DataKey::StakeClaimed).unwrap_or(0);
+    let paid_out: i128 = env.storage().persistent().get(&DataKey::PaidOut).unwrap_or(0);
+    env.storage().persistent().set(
+        &DataKey::StakeClaimed,
+        &stake_claimed.checked_add(bet.amount).expect("stake overflow"),
+    );
+    env.storage().persistent().set(
+        &DataKey::PaidOut,
+        &paid_out.checked_add(amount).expect("payout overflow"),
+    );
+    record_released(env, amount);
+}
+
+#[contractimpl]
+impl MarketContract {
+    /// Settles every winning or refundable bet and prop bet of `bettor` in one call.
+    ///
+    /// Visits at most `CLAIM_PAGE_SIZE` entries starting at `cursor`, counting
+    /// through the bettor's bets and then their prop bets. Losing and
+    /// already-claimed bets are skipped. Payouts are
+    /// summed into a single Treasury transfer and one `batch_claimed` event
+    /// carrying the aggregated receipt.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `bettor` - Address whose bets are claimed. Must authorize this call.
+    /// * `cursor` - Index to resume from; `0` on the first call, then the
+    ///   returned `next_cursor` until `done` is true.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `BatchClaim` with the claimed bet ids and their total payout.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the market is not `Resolved` or `Cancelled`, or with
+    /// `ContractError::ClaimExpired` once the claim deadline has passed.
+    pub fn claim_all(env: Env, bettor: Address, cursor: u32) -> BatchClaim {
+        bettor.require_auth();
+
+        let market = Self::read_market(&env);
+        match market.status {
+            MarketStatus::Resolved | MarketStatus::Cancelled => {}
+            _ => panic!("market not settled"),
+        }
+        require_final(&env, &market);
+        require_claim_open(&env);
+
+        let bet_ids: Vec<Bytes> = env.storage().persistent()
+            .get(&DataKey::BetsByAddr(bettor.clone()))
+            .unwrap_or(Vec::new(&env));
+        let prop_ids: Vec<Bytes> = env.storage().persistent()
+            .get(&DataKey::PropBetsByAddr(bettor.clone()))
+            .unwrap_or(Vec::new(&env));
+        let entries = bet_ids.len() + prop_ids.len();
+        let end = entries.min(cursor.saturating_add(CLAIM_PAGE_SIZE));
+
+        let mut claimed = Vec::new(&env);
+        let mut total_payout: i128 = 0;
+        for i in cursor..end {
+            let bet_id = if i < bet_ids.len() {
+                bet_ids.get(i).unwrap()
+            } else {
+                prop_ids.get(i - bet_ids.len()).unwrap()
+            };
+            let already_claimed: bool = env.storage().persistent()
+                .get(&DataKey::Claimed(bet_id.clone()))
+                .unwrap_or(false);
+            if already_claimed {
+                continue;
+            }
+            let amount = if i < bet_ids.len() {
+                let bet: Bet = match env.storage().persistent().get(&DataKey::Bet(bet_id.clone())) {
+                    Some(bet) => bet,
+                    None => continue,
+                };
+                let amount = settlement_amount(&env, &market, &bet);
+                if amount == 0 {
+                    continue;
+                }
+                record_payout(&env, &bet, amount);
+                // Mark claimed BEFORE the transfer (re-entrancy guard).
+                env.storage().persistent().set(&DataKey::Claimed(bet_id.clone()), &true);
+                amount
+            } else {
+                let bet: PropBet = match env.storage().persistent().get(&DataKey::PropBet(bet_id.clone())) {
+                    Some(bet) => bet,
+                    None => continue,
+                };
+                let amount = match crate::props::prop_settlement(&env, &market, &bet) {
+                    Some(amount) if amount > 0 => amount,
+                    _ => continue,
+                };
+                crate::props::record_prop_payout(&env, bet, amount);
+                amount
+            };
+            total_payout = total_payout.checked_add(amount).expect("payout overflow");
+            claimed.push_back(bet_id);
+        }
+
+        if total_payout > 0 {
+            Self::release_stake(&env, &market, &bettor, total_payout);
+        }
+
+        let receipt = BatchClaim {
+            bettor,
+            bet_ids: claimed,
+            total_payout,
+            next_cursor: end.max(cursor),
+            done: end >= entries,
+            claimed_at: env.ledger().timestamp(),
+        };
+        events::emit_batch_claimed(&env, BatchClaimedEvent {
+            market_id: market.numeric_id,
+            bettor: receipt.bettor.clone(),
+            bet_ids: receipt.bet_ids.clone(),
+            total_payout,
+            claimed_at: receipt.claimed_at,
+        });
+
+        receipt
+    }
+
+    /// Moves whatever escrow this market still holds to the treasury's fee
+    /// bucket once the claim deadline has passed: the payouts nobody claimed,
+    /// since the protocol fee already left escrow when claims opened. Permissionless; every later
+    /// claim fails with `ContractError::ClaimExpired`. Emits an
+    /// `unclaimed_swept` event with the amount.
+    ///
+    /// # Returns
+    ///
+    /// Returns the amount swept, in stroops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - The market has no claim deadline, or it has not passed yet.
+    /// - The market was already swept.
+    pub fn sweep_unclaimed(env: Env) -> i128 {
+        let deadline: u64 = env.storage().persistent()
+            .get(&DataKey::ClaimDeadline)
+            .expect("claims never expire");
+        if env.ledger().timestamp() <= deadline {
+            panic!("claim window still open");
+        }
+        let swept: bool = env.storage().persistent().get(&DataKey::Swept).unwrap_or(false);
+        if swept {
+            panic!("already swept");
+        }
+        env.storage().persistent().set(&DataKey::Swept, &true);
+
+        let market = Self::read_market(&env);
+        let amount: i128 = env.invoke_contract(
+            &market.treasury,
+            &Symbol::new(&env, "sweep_market"),
+            soroban_sdk::vec![
+                &env,
+                env.current_contract_address().into_val(&env),
+                market.market_id.clone().into_val(&env),
+            ],
+        );
+        record_released(&env, amount);
+
+        events::emit_unclaimed_swept(&env, UnclaimedSweptEvent {
+            market_id: market.numeric_id,
+            amount,
+        });
+
+        amount
+    }
+
+    /// Returns the payout ledger: what has been paid out against the pool so far.
+    ///
+    /// Once `complete` is true, `paid_out + fee + dust == total_pool` holds and
+    /// `dust` is zero, since the last claim absorbs every rounding remainder.
+    pub fn get_payout_ledger(env: Env) -> PayoutLedger {
+        let market = Self::read_market(&env);
+        let settled = matches!(market.status, MarketStatus::Resolved | MarketStatus::Cancelled);
+        let (payable, stake_basis, fee) = if settled {
+            settlement_split(&market)
+        } else {
+            (0, 0, 0)
+        };
+        let paid_out: i128 = env.storage().persistent().get(&DataKey::PaidOut).unwrap_or(0);
+        let stake_claimed: i128 = env.storage().persistent().get(&DataKey::StakeClaimed).unwrap_or(0);
+        let complete = settled && stake_basis > 0 && stake_claimed == stake_basis;
+
+        PayoutLedger {
+            total_pool: market.total_pool,
+            fee,
+            paid_out,
+            dust: if complete { market.total_pool - fee - paid_out } else { 0 },
+            outstanding: payable - paid_out,
+            stake_claimed,
+            complete,
+        }
+    }
+
+    /// True once claims are open: the market was cancelled, or its resolution
+    /// was finalized and can no longer be disputed.
+    pub fn is_finalized(env: Env) -> bool {
+        let market = Self::read_market(&env);
+        is_final(&env, &market)
+    }
+
+    /// Returns when claims on this market expire, or `0` if they never do
+    /// (unsettled market or `claim_expiry_sec` of zero).
+    pub fn get_claim_deadline(env: Env) -> u64 {
+        env.storage().persistent()
+            .get(&DataKey::ClaimDeadline)
+            .unwrap_or(0)
+    }
+
+    /// Returns up to `limit` of `bettor`'s bets starting at index `cursor`.
+    pub fn get_bets_by_address_page(env: Env, bettor: Address, cursor: u32, limit: u32) -> Vec<Bet> {
+        let bet_ids: Vec<Bytes> = env.storage().persistent()
+            .get(&DataKey::BetsByAddr(bettor))
+            .unwrap_or(Vec::new(&env));
+        let end = bet_ids.len().min(cursor.saturating_add(limit));
+        let mut bets = Vec::new(&env);
+        for i in cursor..end {
+            if let Some(bet) = env.storage().persistent().get(&DataKey::Bet(bet_ids.get(i).unwrap())) {
+                bets.push_back(bet);
+            }
+        }
+        bets
+    }
+}
@@ -0,0 +1,472 @@
+//! ============================================================
+//! BOXMEOUT — Dispute Bonds
+//! Disputes are a staked game played in the bet token. The
+//! disputer posts a bond to challenge the oracle's outcome;
+//! other participants answer with counter-bonds, each round
+//! doubling the required amount, and a repeat dispute picks up
+//! the doubling where the last one left off. When the admin settles the
+//! dispute, the side it agrees with gets its bonds back plus a
+//! pro-rata share of the losing side's bonds. Every dispute is
+//! kept in a log with its evidence and the arbiter's response.
+//! When the factory registers an arbiter panel, its members
+//! vote on the ruling; the admin only steps in on timeout. The
+//! panel is copied when a dispute opens, so a later change on
+//! the factory cannot swap voters mid-dispute. Every vote is
+//! also reported to the factory's arbiter registry.
+//! ============================================================
+
+use shared::events::{self, ArbiterVotedEvent, DisputeBondPostedEvent, DisputeBondsSettledEvent};
+use shared::types::ArbiterPanel;
+use soroban_sdk::{contractimpl, Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Vec};
+
+use crate::types::{
+    ArbiterBallot, Dispute, DisputeBond, DisputeStatus, Market, MarketStatus, Outcome,
+};
+use crate::{DataKey, MarketContract, MarketContractArgs, MarketContractClient};
+
+/// Bond for the opening challenge until the admin sets one (1 token at 7 decimals).
+pub const DEFAULT_DISPUTE_BOND: i128 = 10_000_000;
+
+/// Maximum number of bonds one dispute can collect. Every round doubles the
+/// bond, across disputes, so together with `MAX_DISPUTES` this caps the
+/// largest bond at `base << (MAX_DISPUTES * MAX_DISPUTE_ROUNDS - 1)`.
+pub const MAX_DISPUTE_ROUNDS: u32 = 10;
+
+/// Maximum number of disputes one market accepts over its lifetime.
+pub const MAX_DISPUTES: u32 = 3;
+
+/// Cap on dispute reasons, evidence URIs and arbiter responses, in bytes.
+pub const MAX_DISPUTE_TEXT_LEN: u32 = 256;
+
+fn read_disputes(env: &Env) -> Vec<Dispute> {
+    env.storage().persistent()
+        .get(&DataKey::Disputes)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Appends an `Open` dispute to the log and returns its id. Copies the
+/// factory's arbiter panel for the dispute to be voted on.
+///
+/// # Panics
+///
+/// Panics if a dispute is already open, `MAX_DISPUTES` were already raised,
+/// or `reason` / `evidence_uri` exceeds `MAX_DISPUTE_TEXT_LEN`.
+pub(crate) fn open_dispute(
+    env: &Env,
+    disputer: &Address,
+    reason: &Bytes,
+    evidence_uri: String,
+    evidence_hash: BytesN<32>,
+) -> u32 {
+    let mut disputes = read_disputes(env);
+    if disputes.last().is_some_and(|d| d.status == DisputeStatus::Open) {
+        panic!("dispute already raised");
+    }
+    if disputes.len() >= MAX_DISPUTES {
+        panic!("dispute limit reached");
+    }
+    if reason.len() > MAX_DISPUTE_TEXT_LEN {
+        panic!("dispute reason exceeds maximum length");
+    }
+    if evidence_uri.len() > MAX_DISPUTE_TEXT_LEN {
+        panic!("evidence uri exceeds maximum length");
+    }
+
+    let id = disputes.len();
+    disputes.push_back(Dispute {
+        id,
+        disputer: disputer.clone(),
+        raised_at: env.ledger().timestamp(),
+        reason: reason.clone(),
+        evidence_uri,
+        evidence_hash,
+        status: DisputeStatus::Open,
+        arbiter_response: Bytes::new(env),
+        resolved_at: 0,
+    });
+    env.storage().persistent().set(&DataKey::Disputes, &disputes);
+    snapshot_arbiter_panel(env);
+    id
+}
+
+/// Closes the open dispute with the arbiter's ruling and response.
+///
+/// # Panics
+///
+/// Panics if no dispute is open or `response` exceeds `MAX_DISPUTE_TEXT_LEN`.
+pub(crate) fn close_dispute(env: &Env, upheld: bool, response: Bytes) {
+    if response.len() > MAX_DISPUTE_TEXT_LEN {
+        panic!("arbiter response exceeds maximum length");
+    }
+    let mut disputes = read_disputes(env);
+    let last = disputes.len().checked_sub(1).expect("no open dispute");
+    let mut dispute = disputes.get(last).unwrap();
+    if dispute.status != DisputeStatus::Open {
+        panic!("no open dispute");
+    }
+    dispute.status = if upheld { DisputeStatus::Upheld } else { DisputeStatus::Rejected };
+    dispute.arbiter_response = response;
+    dispute.resolved_at = env.ledger().timestamp();
+    disputes.set(last, dispute);
+    env.storage().persistent().set(&DataKey::Disputes, &disputes);
+}
+
+fn read_factory(env: &Env) -> Address {
+    env.storage().persistent()
+        .get(&DataKey::Factory)
+        .expect("factory not set")
+}
+
+/// Stores the factory's current arbiter panel as the panel of the dispute
+/// being opened. A factory error aborts the dispute rather than quietly
+/// handing it to the admin.
+fn snapshot_arbiter_panel(env: &Env) {
+    let panel: ArbiterPanel = env.invoke_contract(
+        &read_factory(env),
+        &Symbol::new(env, "get_arbiter_panel"),
+        soroban_sdk::vec![env],
+    );
+    if panel.members.is_empty() {
+        env.storage().persistent().remove(&DataKey::ArbiterPanel);
+    } else {
+        env.storage().persistent().set(&DataKey::ArbiterPanel, &panel);
+    }
+}
+
+/// The panel copied when the latest dispute opened, or `None` if the factory
+/// had no members then.
+fn read_arbiter_panel(env: &Env) -> Option<ArbiterPanel> {
+    env.storage().persistent().get(&DataKey::ArbiterPanel)
+}
+
+/// When the panel's vote on the open dispute closes, or `None` if no dispute
+/// is open or there is no panel.
+fn arbitration_deadline(env: &Env, panel: &ArbiterPanel) -> Option<(Dispute, u64)> {
+    let dispute = read_disputes(env).last()?;
+    if dispute.status != DisputeStatus::Open {
+        return None;
+    }
+    let deadline = dispute.raised_at + panel.vote_window_sec;
+    Some((dispute, deadline))
+}
+
+/// Panics while an arbiter panel can still decide the open dispute, so the
+/// admin only rules as a fallback.
+pub(crate) fn require_admin_may_rule(env: &Env) {
+    let Some(panel) = read_arbiter_panel(env) else {
+        return;
+    };
+    if arbitration_deadline(env, &panel).is_some_and(|(_, deadline)| env.ledger().timestamp() <= deadline) {
+        panic!("arbiter panel still voting");
+    }
+}
+
+fn read_ballots(env: &Env) -> Vec<ArbiterBallot> {
+    env.storage().persistent()
+        .get(&DataKey::ArbiterBallots)
+        .unwrap_or(Vec::new(env))
+}
+
+fn read_bonds(env: &Env) -> Vec<DisputeBond> {
+    env.storage().persistent()
+        .get(&DataKey::DisputeBonds)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Sum of the bonds posted in the open dispute, all still in escrow.
+pub(crate) fn bonds_held(env: &Env) -> i128 {
+    read_bonds(env)
+        .iter()
+        .try_fold(0i128, |total, bond| total.checked_add(bond.amount))
+        .expect("bond overflow")
+}
+
+/// Bonds posted over the market's lifetime, including those of settled disputes.
+fn bonds_posted(env: &Env) -> u32 {
+    env.storage().persistent().get(&DataKey::BondsPosted).unwrap_or(0)
+}
+
+/// Bond the next round requires: the base bond doubled once per bond posted
+/// against this market, in any dispute.
+fn required_bond(env: &Env, round: u32) -> i128 {
+    let base: i128 = env.storage().persistent()
+        .get(&DataKey::DisputeBondBase)
+        .unwrap_or(DEFAULT_DISPUTE_BOND);
+    base.checked_mul(1i128 << round).expect("bond overflow")
+}
+
+/// Escrows the next round's bond from `bonder` via the Treasury and records it.
+/// The opening bond must challenge the oracle's outcome; after that, sides
+/// alternate so every round answers the previous one. Emits a
+/// `dispute_bond_posted` event.
+///
+/// # Panics
+///
+/// Panics if the side does not alternate or `MAX_DISPUTE_ROUNDS` is reached.
+pub(crate) fn post_bond(env: &Env, market: &Market, bonder: &Address, challenges: bool) -> i128 {
+    let mut bonds = read_bonds(env);
+    let round = bonds.len();
+    if round >= MAX_DISPUTE_ROUNDS {
+        panic!("dispute rounds exhausted");
+    }
+    let expected_side = match bonds.last() {
+        Some(last) => !last.challenges,
+        None => true,
+    };
+    if challenges != expected_side {
+        panic!("bond must answer the previous round");
+    }
+
+    let posted = bonds_posted(env);
+    let amount = required_bond(env, posted);
+    MarketContract::escrow_stake(env, market, bonder, amount);
+    env.storage().persistent().set(&DataKey::BondsPosted, &(posted + 1));
+
+    bonds.push_back(DisputeBond {
+        bonder: bonder.clone(),
+        amount,
+        challenges,
+        posted_at: env.ledger().timestamp(),
+    });
+    env.storage().persistent().set(&DataKey::DisputeBonds, &bonds);
+
+    events::emit_dispute_bond_posted(env, DisputeBondPostedEvent {
+        market_id: market.numeric_id,
+        bonder: bonder.clone(),
+        challenges,
+        amount,
+    });
+
+    amount
+}
+
+/// Pays out the bonds of a settled dispute. Bonders on the side the admin
+/// agreed with get their bond back plus their pro-rata share of the losing
+/// side's bonds; rounding dust, or everything if nobody backed the winning
+/// side, is booked as protocol fees. Emits a `dispute_bonds_settled` event.
+pub(crate) fn settle_bonds(env: &Env, market: &Market, challenge_upheld: bool) {
+    let bonds = read_bonds(env);
+    if bonds.is_empty() {
+        return;
+    }
+
+    let mut winning_total: i128 = 0;
+    let mut forfeited: i128 = 0;
+    for bond in bonds.iter() {
+        if bond.challenges == challenge_upheld {
+            winning_total = winning_total.checked_add(bond.amount).expect("bond overflow");
+        } else {
+            forfeited = forfeited.checked_add(bond.amount).expect("bond overflow");
+        }
+    }
+
+    let mut awarded: i128 = 0;
+    if winning_total > 0 {
+        for bond in bonds.iter() {
+            if bond.challenges != challenge_upheld {
+                continue;
+            }
+            let share = bond.amount
+                .checked_mul(forfeited)
+                .expect("bond overflow")
+                .checked_div(winning_total)
+                .expect("bond div zero");
+            awarded += share;
+            MarketContract::release_stake(env, market, &bond.bonder, bond.amount + share);
+        }
+    }
+
+    let dust = forfeited - awarded;
+    if dust > 0 {
+        MarketContract::collect_fee(env, market, dust);
+    }
+    env.storage().persistent().remove(&DataKey::DisputeBonds);
+
+    events::emit_dispute_bonds_settled(env, DisputeBondsSettledEvent {
+        market_id: market.numeric_id,
+        challenge_upheld,
+        forfeited,
+        awarded,
+    });
+}
+
+#[contractimpl]
+impl MarketContract {
+    /// Posts the next bond in an open dispute, either backing the challenge or
+    /// defending the oracle's outcome. Each bond must answer the previous one
+    /// and costs twice as much.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `bettor` - Participant posting the bond. Must authorize this call and
+    ///   hold a bet in this market.
+    /// * `challenges` - `true` to back the dispute, `false` to defend the outcome.
+    ///
+    /// # Returns
+    ///
+    /// Returns the bond amount escrowed, in stroops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - The market is not `Disputed`.
+    /// - `bettor` has no bet in this market.
+    /// - `challenges` takes the same side as the previous bond.
+    /// - `MAX_DISPUTE_ROUNDS` bonds were already posted.
+    pub fn post_dispute_bond(env: Env, bettor: Address, challenges: bool) -> i128 {
+        bettor.require_auth();
+
+        let market = Self::read_market(&env);
+        if market.status != MarketStatus::Disputed {
+            panic!("market not in disputed state");
+        }
+        let bettor_bets: Vec<Bytes> = env.storage().persistent()
+            .get(&DataKey::BetsByAddr(bettor.clone()))
+            .unwrap_or(Vec::new(&env));
+        if bettor_bets.is_empty() {
+            panic!("bettor has no bets in this market");
+        }
+
+        post_bond(&env, &market, &bettor, challenges)
+    }
+
+    /// Records an arbiter panel member's vote on the open dispute, here and in
+    /// the factory's per-arbiter history. Once one outcome holds a strict
+    /// majority of the panel, the dispute is settled with it, exactly as
+    /// `resolve_dispute` would. Emits an `arbiter_voted` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `arbiter` - Panel member casting the vote. Must authorize this call.
+    /// * `outcome` - The outcome the arbiter rules for.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if this vote decided the dispute.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - The market is not `Disputed`.
+    /// - The dispute was raised without an arbiter panel, or `arbiter` is not
+    ///   on the panel it was raised under.
+    /// - The panel's voting window has closed.
+    /// - `arbiter` already voted on this dispute.
+    pub fn cast_arbiter_vote(env: Env, arbiter: Address, outcome: Outcome) -> bool {
+        arbiter.require_auth();
+
+        let market = Self::read_market(&env);
+        if market.status != MarketStatus::Disputed {
+            panic!("market not in disputed state");
+        }
+        let panel = read_arbiter_panel(&env).expect("no arbiter panel");
+        if !panel.members.contains(&arbiter) {
+            panic!("not an arbiter");
+        }
+        let (dispute, deadline) = arbitration_deadline(&env, &panel).expect("no open dispute");
+        if env.ledger().timestamp() > deadline {
+            panic!("arbitration window closed");
+        }
+
+        let mut ballots = read_ballots(&env);
+        let mut votes_for_outcome: u32 = 1;
+        for ballot in ballots.iter() {
+            if ballot.dispute_id != dispute.id {
+                continue;
+            }
+            if ballot.arbiter == arbiter {
+                panic!("already voted");
+            }
+            if ballot.outcome == outcome {
+                votes_for_outcome += 1;
+            }
+        }
+        ballots.push_back(ArbiterBallot {
+            arbiter: arbiter.clone(),
+            dispute_id: dispute.id,
+            outcome: outcome.clone(),
+            voted_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&DataKey::ArbiterBallots, &ballots);
+        env.invoke_contract::<()>(
+            &read_factory(&env),
+            &Symbol::new(&env, "record_arbiter_vote"),
+            soroban_sdk::vec![
+                &env,
+                market.market_id.clone().into_val(&env),
+                arbiter.clone().into_val(&env),
+                dispute.id.into_val(&env),
+                outcome.clone().into_val(&env),
+            ],
+        );
+
+        events::emit_arbiter_voted(&env, ArbiterVotedEvent {
+            market_id: market.numeric_id,
+            arbiter,
+            dispute_id: dispute.id,
+            outcome: outcome.clone(),
+        });
+
+        let decided = votes_for_outcome * 2 > panel.members.len();
+        if decided {
+            Self::apply_dispute_ruling(
+                &env,
+                outcome,
+                Bytes::from_slice(&env, b"arbiter panel majority"),
+            );
+        }
+        decided
+    }
+
+    /// Returns every vote `arbiter` cast on this market, oldest first.
+    pub fn get_arbiter_votes(env: Env, arbiter: Address) -> Vec<ArbiterBallot> {
+        let mut votes = Vec::new(&env);
+        for ballot in read_ballots(&env).iter() {
+            if ballot.arbiter == arbiter {
+                votes.push_back(ballot);
+            }
+        }
+        votes
+    }
+
+    /// Returns when the arbiter panel's vote on the open dispute closes, or
+    /// `0` if no dispute is open or it was raised without a panel.
+    pub fn get_arbitration_deadline(env: Env) -> u64 {
+        read_arbiter_panel(&env)
+            .and_then(|panel| arbitration_deadline(&env, &panel))
+            .map_or(0, |(_, deadline)| deadline)
+    }
+
+    /// Sets the bond required to open a dispute. Later rounds double it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `admin` is not the factory admin or `amount` is not positive.
+    pub fn set_dispute_bond(env: Env, admin: Address, amount: i128) {
+        admin.require_auth();
+        if admin != Self::read_config(&env).admin {
+            panic!("not admin");
+        }
+        if amount <= 0 {
+            panic!("bond must be positive");
+        }
+        env.storage().persistent().set(&DataKey::DisputeBondBase, &amount);
+    }
+
+    /// Returns the bond the next dispute round requires, in stroops.
+    pub fn get_required_bond(env: Env) -> i128 {
+        required_bond(&env, bonds_posted(&env))
+    }
+
+    /// Returns every dispute raised against this market, oldest first.
+    pub fn get_disputes(env: Env) -> Vec<Dispute> {
+        read_disputes(&env)
+    }
+
+    /// Returns the bonds posted in the open dispute, oldest first.
+    pub fn get_dispute_bonds(env: Env) -> Vec<DisputeBond> {
+        read_bonds(&env)
+    }
+}
@@ -0,0 +1,102 @@
+//! ============================================================
+//! BOXMEOUT — Invariant Checker
+//! Read-only reconciliation of the market's pools against its
+//! bets, and of what bettors are still owed against the escrow
+//! the treasury holds for this market. The escrow must also
+//! conserve every stake and bond taken in, less what was paid out.
+//! ============================================================
+
+use soroban_sdk::{contractimpl, Env, IntoVal, Symbol};
+
+use crate::claims::settlement_split;
+use crate::types::{Bet, BetSide, MarketInvariantReport, MarketStatus};
+use crate::{DataKey, MarketContract, MarketContractArgs, MarketContractClient};
+
+#[contractimpl]
+impl MarketContract {
+    /// Checks that the market's books reconcile:
+    /// - `total_pool` equals `pool_a + pool_b + pool_draw`;
+    /// - the stakes of the bets still in the pools add up to each pool;
+    /// - the treasury escrow covers what bettors are still owed;
+    /// - the treasury escrow equals the winner and prop pools plus the open
+    ///   dispute's bonds, less what left escrow after settlement.
+    ///
+    /// Read-only — does not modify state, not even the lazy lock. Visits every
+    /// bet ever placed, so it is meant for simulation and monitoring rather
+    /// than for other contracts to call.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`MarketInvariantReport`] with the figures compared and
+    /// whether each invariant holds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the market has not been initialized.
+    pub fn check_invariants(env: Env) -> MarketInvariantReport {
+        let market = Self::read_stored_market(&env);
+
+        // Withdrawn and cashed-out bets are the only ones whose stored `claimed`
+        // flag is set; settled claims only set `DataKey::Claimed`.
+        let (mut bets_a, mut bets_b, mut bets_draw) = (0i128, 0i128, 0i128);
+        let bet_count: u64 = env.storage().persistent()
+            .get(&Symbol::new(&env, "BET_COUNT"))
+            .unwrap_or(0);
+        for n in 1..=bet_count {
+            let bet_id = Self::bet_id_at(&env, &market, n);
+            let bet: Bet = match env.storage().persistent().get(&DataKey::Bet(bet_id)) {
+                Some(bet) => bet,
+                // The id went to a prop bet.
+                None => continue,
+            };
+            if bet.claimed {
+                continue;
+            }
+            match bet.side {
+                BetSide::FighterA => bets_a += bet.amount,
+                BetSide::FighterB => bets_b += bet.amount,
+                BetSide::Draw => bets_draw += bet.amount,
+            }
+        }
+
+        let swept: bool = env.storage().persistent().get(&DataKey::Swept).unwrap_or(false);
+        let outstanding = match market.status {
+            _ if swept => 0,
+            MarketStatus::Resolved | MarketStatus::Cancelled => {
+                let (payable, _, _) = settlement_split(&market);
+                let paid_out: i128 = env.storage().persistent().get(&DataKey::PaidOut).unwrap_or(0);
+                payable - paid_out
+            }
+            _ => market.total_pool,
+        };
+        let escrow: i128 = env.invoke_contract(
+            &market.treasury,
+            &Symbol::new(&env, "get_market_escrow"),
+            soroban_sdk::vec![&env, market.market_id.clone().into_val(&env)],
+        );
+
+        let prop_pool: i128 = env.storage().persistent().get(&DataKey::PropTotalPool).unwrap_or(0);
+        let released: i128 = env.storage().persistent().get(&DataKey::Released).unwrap_or(0);
+        let expected_escrow = market.total_pool + prop_pool + crate::disputes::bonds_held(&env) - released;
+
+        let pool_sum = market.pool_a + market.pool_b + market.pool_draw;
+        let pools_balanced = market.total_pool == pool_sum;
+        let bets_match_pools = bets_a == market.pool_a
+            && bets_b == market.pool_b
+            && bets_draw == market.pool_draw;
+        let escrow_covers_claims = escrow >= outstanding;
+        let escrow_conserved = escrow == expected_escrow;
+        MarketInvariantReport {
+            pool_sum,
+            bet_sum: bets_a + bets_b + bets_draw,
+            outstanding,
+            escrow,
+            expected_escrow,
+            pools_balanced,
+            bets_match_pools,
+            escrow_covers_claims,
+            escrow_conserved,
+            ok: pools_balanced && bets_match_pools && escrow_covers_claims && escrow_conserved,
+        }
+    }
+}
@@ -1,12 +1,24 @@
 #![no_std]
 
+pub mod attestations;
+pub mod claims;
+pub mod disputes;
+pub mod invariants;
+pub mod props;
 pub mod types;
 
+use shared::errors::ContractError;
+use shared::events::{
+    self, BetCashedOutEvent, BetPlacedEvent, BetWithdrawnEvent, DisputeResolvedEvent,
+    FighterSubstitutedEvent, MarketCancelledEvent, MarketInitializedEvent, MarketLockedEvent,
+    MarketRescheduledEvent, MarketResolvedEvent, RefundClaimedEvent, ResolutionDisputedEvent,
+    ResolutionFinalizedEvent, ResolutionTimedOutEvent, WinningsClaimedEvent,
+};
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Bytes, Env, IntoVal, String,
-    Symbol, Vec,
+    contract, contractimpl, contracttype, panic_with_error, Address, Bytes, BytesN,
+    Env, IntoVal, String, Symbol, Vec,
 };
-use types::{Bet, BetSide, ClaimReceipt, Fighter, Market, MarketResolved, MarketStatus, Outcome, ProtocolConfig, WinningsClaimed};
+use types::{Bet, BetSide, Fighter, Market, MarketStatus, Outcome, PropSelection, ProtocolConfig, OptionalOutcome, Substitution};
 
 // ─── STORAGE KEYS ─────────────────────────────────────────────────────────────
 // DataKey::MarketInfo     -> Market
@@ -14,9 +26,33 @@ use types::{Bet, BetSide, ClaimReceipt, Fighter, Market, MarketResolved, MarketS
 // DataKey::Bet(id)        -> Bet
 // DataKey::BetsByAddr(a)  -> Vec<Bytes>  (all bet_ids for an address)
 // DataKey::Claimed(id)    -> bool
-// DataKey::DisputeRaised  -> bool
-// DataKey::DisputeReason  -> Bytes
-// "BET_COUNT"             -> u64
+// DataKey::Disputes       -> Vec<Dispute>  (every dispute raised, oldest first)
+// DataKey::ArbiterBallots -> Vec<ArbiterBallot>  (every arbiter vote, across disputes)
+// DataKey::ArbiterPanel   -> ArbiterPanel  (factory panel copied when the latest dispute opened)
+// DataKey::OracleKeys     -> Vec<BytesN<32>>  (ed25519 keys whose attestations resolve the market)
+// DataKey::AttestationNonce(n) -> bool  (nonce already consumed)
+// DataKey::ResolutionDeadline -> u64  (oracle must resolve by this; then anyone may cancel)
+// DataKey::OracleOutcome  -> Outcome  (the oracle's call, judged against the finalized outcome)
+// DataKey::DisputeBonds   -> Vec<DisputeBond>  (bonds of the open dispute)
+// DataKey::DisputeBondBase -> i128 (opening bond; DEFAULT_DISPUTE_BOND when unset)
+// DataKey::BondsPosted    -> u32  (bonds posted across every dispute; sets the next bond)
+// DataKey::PropBet(id)    -> PropBet
+// DataKey::PropBetsByAddr(a) -> Vec<Bytes>  (all prop bet_ids for an address)
+// DataKey::PropPool(sel)  -> i128  (total staked on one PropSelection)
+// DataKey::PropTotalPool  -> i128  (total staked across all prop selections)
+// DataKey::PropPaidOut    -> i128  (sum of every prop payout and refund claimed)
+// DataKey::PropStakeClaimed -> i128 (stake of the prop bets claimed so far)
+// DataKey::FightResult    -> FightResult
+// DataKey::WithdrawalWindowEnds -> u64  (penalty-free withdrawals after a reschedule or substitution)
+// DataKey::SideFrozenUntil(side) -> u64  (no new bets on a side whose fighter was swapped)
+// DataKey::Substitutions  -> Vec<Substitution>
+// DataKey::CashOutFeeBp   -> u32  (early-exit fee; DEFAULT_CASH_OUT_FEE_BP when unset)
+// DataKey::ClaimDeadline  -> u64  (claims rejected after this; absent = never expires)
+// DataKey::Swept          -> bool (unclaimed escrow moved to the treasury)
+// DataKey::PaidOut        -> i128 (sum of every payout and refund claimed)
+// DataKey::StakeClaimed   -> i128 (stake of the bets claimed so far)
+// DataKey::Released       -> i128 (escrow paid out since settlement: claims, prop claims, fee, sweep)
+// "BET_COUNT"             -> u64  (shared by winner and prop bets)
 
 #[contracttype]
 pub enum DataKey {
@@ -25,26 +61,226 @@ pub enum DataKey {
     Bet(Bytes),
     BetsByAddr(Address),
     Claimed(Bytes),
-    DisputeRaised,
-    DisputeReason,
+    Disputes,
+    PropBet(Bytes),
+    PropBetsByAddr(Address),
+    PropPool(PropSelection),
+    PropTotalPool,
+    PropPaidOut,
+    PropStakeClaimed,
+    FightResult,
+    WithdrawalWindowEnds,
+    SideFrozenUntil(BetSide),
+    Substitutions,
+    CashOutFeeBp,
+    ClaimDeadline,
+    Swept,
+    PaidOut,
+    StakeClaimed,
+    Released,
+    DisputeBonds,
+    DisputeBondBase,
+    BondsPosted,
+    ArbiterBallots,
+    ArbiterPanel,
+    OracleKeys,
+    AttestationNonce(u64),
+    ResolutionDeadline,
+    OracleOutcome,
+    Finalized,
 }
 
+/// How long bettors may withdraw their stake penalty-free after a reschedule.
+pub const RESCHEDULE_WITHDRAWAL_WINDOW_SEC: u64 = 48 * 60 * 60;
+
+/// How long bettors may opt out after a fighter is substituted. Bets on the
+/// affected side are frozen for the same period.
+pub const SUBSTITUTION_OPT_OUT_SEC: u64 = 24 * 60 * 60;
+
+/// How long after `scheduled_at` the oracle has to resolve the market. Past
+/// this deadline anyone may cancel it for full refunds.
+pub const RESOLUTION_WINDOW_SEC: u64 = 72 * 60 * 60;
+
+/// Early-exit fee charged by `cash_out` until the admin sets one (5%).
+pub const DEFAULT_CASH_OUT_FEE_BP: u32 = 500;
+
 #[contract]
 pub struct MarketContract;
 
 #[contractimpl]
 impl MarketContract {
+    /// Reads the market with its effective status. An `Open` market whose
+    /// `betting_ends_at` has passed is locked on this first touch: the
+    /// transition is stored and `market_locked` emitted, so nobody has to call
+    /// `lock_market`.
     fn read_market(env: &Env) -> Market {
+        let mut market = Self::read_stored_market(env);
+        let now = env.ledger().timestamp();
+        if market.status == MarketStatus::Open && now >= market.betting_ends_at {
+            market.status = MarketStatus::Locked;
+            Self::write_market(env, &market);
+            Self::emit_locked(env, &market, now);
+        }
+        market
+    }
+
+    /// Reads the market exactly as stored, without the lazy lock.
+    fn read_stored_market(env: &Env) -> Market {
         env.storage()
             .persistent()
             .get(&DataKey::MarketInfo)
             .expect("market not initialized")
     }
 
+    fn emit_locked(env: &Env, market: &Market, locked_at: u64) {
+        events::emit_market_locked(env, MarketLockedEvent {
+            market_id: market.numeric_id,
+            locked_at,
+        });
+    }
+
     fn write_market(env: &Env, market: &Market) {
         env.storage().persistent().set(&DataKey::MarketInfo, market);
     }
 
+    fn read_config(env: &Env) -> ProtocolConfig {
+        let factory: Address = env.storage().persistent()
+            .get(&DataKey::Factory)
+            .expect("factory not set");
+        env.invoke_contract(
+            &factory,
+            &Symbol::new(env, "get_config"),
+            soroban_sdk::vec![env],
+        )
+    }
+
+    /// Reports this market's oracle to the factory's reputation registry.
+    /// The factory only creates markets for staked oracles, so a failed
+    /// report aborts the settlement rather than leaving the record stale.
+    fn report_oracle(env: &Env, func: &str, market_id: &Bytes) {
+        let factory: Address = env.storage().persistent()
+            .get(&DataKey::Factory)
+            .expect("factory not set");
+        env.invoke_contract::<soroban_sdk::Val>(
+            &factory,
+            &Symbol::new(env, func),
+            soroban_sdk::vec![env, market_id.into_val(env)],
+        );
+    }
+
+    /// Reports the oracle overturned if the finalized outcome departs from its
+    /// call. Call once, when the resolution is finalized, so rulings a later
+    /// dispute round reverses never count against it.
+    fn settle_oracle(env: &Env, market: &Market) {
+        let oracle_outcome: Option<Outcome> = env.storage().persistent().get(&DataKey::OracleOutcome);
+        if oracle_outcome.is_some_and(|called| market.outcome.outcome() != Some(called)) {
+            Self::report_oracle(env, "report_oracle_overturned", &market.market_id);
+        }
+    }
+
+    /// Escrows `amount` from `bettor` via the Treasury. The cross-contract call
+    /// aborts the whole transaction on failure, so callers only record a bet
+    /// once this has returned.
+    fn escrow_stake(env: &Env, market: &Market, bettor: &Address, amount: i128) {
+        env.invoke_contract::<()>(
+            &market.treasury,
+            &Symbol::new(env, "deposit"),
+            soroban_sdk::vec![
+                env,
+                env.current_contract_address().into_val(env),
+                market.market_id.clone().into_val(env),
+                bettor.clone().into_val(env),
+                amount.into_val(env),
+            ],
+        );
+    }
+
+    /// Returns `amount` of escrowed stake to `recipient` via the Treasury.
+    fn release_stake(env: &Env, market: &Market, recipient: &Address, amount: i128) {
+        env.invoke_contract::<()>(
+            &market.treasury,
+            &Symbol::new(env, "release"),
+            soroban_sdk::vec![
+                env,
+                env.current_contract_address().into_val(env),
+                market.market_id.clone().into_val(env),
+                recipient.clone().into_val(env),
+                amount.into_val(env),
+            ],
+        );
+    }
+
+    /// Books `amount` of escrowed stake as protocol fees via the Treasury.
+    fn collect_fee(env: &Env, market: &Market, amount: i128) {
+        env.invoke_contract::<()>(
+            &market.treasury,
+            &Symbol::new(env, "collect_fee"),
+            soroban_sdk::vec![
+                env,
+                env.current_contract_address().into_val(env),
+                market.market_id.clone().into_val(env),
+                amount.into_val(env),
+            ],
+        );
+    }
+
+    /// Records the resolution deadline for a fight at `scheduled_at`.
+    fn set_resolution_deadline(env: &Env, scheduled_at: u64) {
+        let deadline = scheduled_at.saturating_add(RESOLUTION_WINDOW_SEC);
+        env.storage().persistent().set(&DataKey::ResolutionDeadline, &deadline);
+    }
+
+    /// Latest time the oracle may resolve the market.
+    fn resolution_deadline(env: &Env, market: &Market) -> u64 {
+        env.storage().persistent()
+            .get(&DataKey::ResolutionDeadline)
+            .unwrap_or(market.scheduled_at.saturating_add(RESOLUTION_WINDOW_SEC))
+    }
+
+    /// Opens (or extends) the penalty-free withdrawal window to at least
+    /// `duration` seconds from now. Never shortens a window already open.
+    fn open_withdrawal_window(env: &Env, duration: u64) -> u64 {
+        let current: u64 = env.storage().persistent()
+            .get(&DataKey::WithdrawalWindowEnds)
+            .unwrap_or(0);
+        let window_ends = current.max(env.ledger().timestamp() + duration);
+        env.storage().persistent().set(&DataKey::WithdrawalWindowEnds, &window_ends);
+        window_ends
+    }
+
+    /// Panics unless a withdrawal window is open and the
+    /// market has not yet settled.
+    fn require_withdrawal_window(env: &Env, market: &Market) {
+        match market.status {
+            MarketStatus::Open | MarketStatus::Locked => {}
+            _ => panic!("market already settled"),
+        }
+        let window_ends: Option<u64> = env.storage().persistent()
+            .get(&DataKey::WithdrawalWindowEnds);
+        if window_ends.is_none_or(|ends| env.ledger().timestamp() > ends) {
+            panic!("no withdrawal window open");
+        }
+    }
+
+    /// Allocates the next bet id: the market's numeric id then the
+    /// `BET_COUNT` counter, both big-endian, so ids are unique across markets.
+    fn next_bet_id(env: &Env, market: &Market) -> Bytes {
+        let bet_count: u64 = env.storage().persistent()
+            .get(&Symbol::new(env, "BET_COUNT"))
+            .unwrap_or(0u64);
+        let new_count = bet_count + 1;
+        env.storage().persistent().set(&Symbol::new(env, "BET_COUNT"), &new_count);
+        Self::bet_id_at(env, market, new_count)
+    }
+
+    /// The id `next_bet_id` allocated when `BET_COUNT` reached `count`.
+    fn bet_id_at(env: &Env, market: &Market, count: u64) -> Bytes {
+        let mut id_bytes = [0u8; 32];
+        id_bytes[..8].copy_from_slice(&market.numeric_id.to_be_bytes());
+        id_bytes[8..16].copy_from_slice(&count.to_be_bytes());
+        Bytes::from_array(env, &id_bytes)
+    }
+
     /// Called by MarketFactory immediately after contract deployment.
     /// Initializes a new boxing prediction market.
     ///
@@ -55,6 +291,8 @@ impl MarketContract {
     ///
     /// * `env` - The Soroban execution environment.
     /// * `market_id` - Unique identifier for this market (32-byte hash).
+    /// * `numeric_id` - The factory-assigned numeric id (`MarketInfo::numeric_id`),
+    ///   carried by every event this market emits.
     /// * `fighter_a` - Metadata for the first fighter.
     /// * `fighter_b` - Metadata for the second fighter.
     /// * `scheduled_at` - Unix timestamp (seconds) of the scheduled fight time.
@@ -66,15 +304,19 @@ impl MarketContract {
     /// * `dispute_window_sec` - Duration in seconds during which disputes can be raised after resolution.
     /// * `treasury` - Address of the `Treasury` contract used to escrow bet funds.
     /// * `bet_token` - Address of the token contract accepted for bets on this market.
+    /// * `draw_enabled` - If `true`, `BetSide::Draw` is a third pool and a `Draw`
+    ///   outcome pays its backers; otherwise a `Draw` cancels the market for refunds.
     ///
     /// # Panics
     ///
     /// Panics if:
     /// - The market has already been initialized.
     /// - `betting_ends_at` (lock time) is after `scheduled_at` (end time).
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize(
         env: Env,
         market_id: Bytes,
+        numeric_id: u64,
         fighter_a: Fighter,
         fighter_b: Fighter,
         scheduled_at: u64,
@@ -86,6 +328,7 @@ impl MarketContract {
         dispute_window_sec: u64,
         treasury: Address,
         bet_token: Address,
+        draw_enabled: bool,
     ) {
         if env.storage().persistent().has(&DataKey::MarketInfo) {
             panic!("already initialized");
@@ -94,7 +337,8 @@ impl MarketContract {
             panic!("lock time must be at or before end time");
         }
         let market = Market {
-            market_id: market_id.clone(),
+            market_id,
+            numeric_id,
             fighter_a,
             fighter_b,
             scheduled_at,
@@ -104,38 +348,46 @@ impl MarketContract {
             status: MarketStatus::Open,
             pool_a: 0,
             pool_b: 0,
+            pool_draw: 0,
             total_pool: 0,
             protocol_fee_bp,
             oracle_address: oracle,
-            outcome: None,
+            outcome: OptionalOutcome::None,
             fee_collector_address: fee_collector,
             resolved_at: 0,
             dispute_window_sec,
             treasury,
             bet_token,
+            draw_enabled,
         };
         env.storage().persistent().set(&DataKey::MarketInfo, &market);
         env.storage().persistent().set(&DataKey::Factory, &factory);
+        Self::set_resolution_deadline(&env, market.scheduled_at);
 
-        // Emit market_created event with contract address and market info
-        // Topics: (Symbol("market_created"), market_id)
-        // Data: Market struct (matches MarketInfo field-for-field)
-        env.events().publish(
-            (Symbol::new(&env, "market_created"), market_id),
-            market,
-        );
+        events::emit_market_initialized(&env, MarketInitializedEvent {
+            market_id: numeric_id,
+            fighter_a: market.fighter_a.name,
+            fighter_b: market.fighter_b.name,
+            oracle: market.oracle_address,
+            scheduled_at,
+            betting_ends_at,
+            protocol_fee_bp,
+            draw_enabled,
+            created_at: market.created_at,
+        });
     }
 
     /// Places a bet on a fighter in this market.
     ///
     /// Transfers XLM from `bettor` to this contract (escrow), records the bet,
-    /// updates the relevant pool, and emits a `BetPlaced` event.
+    /// updates the relevant pool, and emits a `bet_placed` event.
     ///
     /// # Arguments
     ///
     /// * `env` - The Soroban execution environment.
     /// * `bettor` - Address of the user placing the bet. Must authorize this call.
-    /// * `side` - Which fighter to bet on (`BetSide::FighterA` or `BetSide::FighterB`).
+    /// * `side` - Which fighter to bet on (`BetSide::FighterA` or `BetSide::FighterB`),
+    ///   or `BetSide::Draw` on a draw-enabled market.
     /// * `amount` - Bet amount in stroops. Must satisfy `min_bet_amount ≤ amount ≤ max_bet_amount`.
     ///
     /// # Returns
@@ -145,8 +397,9 @@ impl MarketContract {
     /// # Panics
     ///
     /// Panics if:
-    /// - The market status is not `Open`.
-    /// - The current ledger time is at or after `betting_ends_at`.
+    /// - The market status is not `Open`; it locks itself once `betting_ends_at`
+    ///   has passed.
+    /// - `side` is `Draw` and the market was not created with `draw_enabled`.
     /// - `amount` is below the configured `min_bet_amount`.
     /// - `amount` is above the configured `max_bet_amount`.
     /// - `bettor` has not authorized the call.
@@ -163,18 +416,21 @@ impl MarketContract {
         if market.status != MarketStatus::Open {
             panic!("market not open");
         }
-        if env.ledger().timestamp() >= market.betting_ends_at {
-            panic!("betting period has ended");
+        if side == BetSide::Draw && !market.draw_enabled {
+            panic!("draw betting not enabled");
+        }
+        let frozen_until: u64 = env.storage().persistent()
+            .get(&DataKey::SideFrozenUntil(side.clone()))
+            .unwrap_or(0);
+        if env.ledger().timestamp() < frozen_until {
+            panic!("side frozen after fighter substitution");
         }
 
-        let factory: Address = env.storage().persistent()
-            .get(&DataKey::Factory)
-            .expect("factory not set");
-        let config: ProtocolConfig = env.invoke_contract(
-            &factory,
-            &Symbol::new(&env, "get_config"),
-            soroban_sdk::vec![&env],
-        );
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+
+        let config = Self::read_config(&env);
 
         // Prevent dust bets that consume on-chain storage without contributing
         // meaningful opposing liquidity. The configured min_bet_amount is checked
@@ -186,40 +442,18 @@ impl MarketContract {
             panic!("above maximum bet");
         }
 
-        if amount <= 0 {
-            panic!("amount must be positive");
-        }
-
-        // Escrow the bet amount via the Treasury. This is a cross-contract call
-        // that aborts the whole transaction on failure, so the bet is only ever
-        // recorded below once the deposit has actually succeeded.
-        env.invoke_contract::<()>(
-            &market.treasury,
-            &Symbol::new(&env, "deposit"),
-            soroban_sdk::vec![
-                &env,
-                env.current_contract_address().into_val(&env),
-                market.market_id.clone().into_val(&env),
-                bettor.clone().into_val(&env),
-                amount.into_val(&env),
-            ],
-        );
+        // Escrow the bet amount via the Treasury. The bet is only ever recorded
+        // below once the deposit has actually succeeded.
+        Self::escrow_stake(&env, &market, &bettor, amount);
 
         match side {
             BetSide::FighterA => market.pool_a = market.pool_a.checked_add(amount).expect("pool_a overflow"),
             BetSide::FighterB => market.pool_b = market.pool_b.checked_add(amount).expect("pool_b overflow"),
+            BetSide::Draw => market.pool_draw = market.pool_draw.checked_add(amount).expect("pool_draw overflow"),
         }
         market.total_pool = market.total_pool.checked_add(amount).expect("total_pool overflow");
 
-        let bet_count: u64 = env.storage().persistent()
-            .get(&Symbol::new(&env, "BET_COUNT"))
-            .unwrap_or(0u64);
-        let new_count = bet_count + 1;
-        env.storage().persistent().set(&Symbol::new(&env, "BET_COUNT"), &new_count);
-
-        let mut id_bytes = [0u8; 32];
-        id_bytes[..8].copy_from_slice(&new_count.to_be_bytes());
-        let bet_id = Bytes::from_array(&env, &id_bytes);
+        let bet_id = Self::next_bet_id(&env, &market);
 
         let placed_at = env.ledger().timestamp();
         let bet = Bet {
@@ -245,17 +479,14 @@ impl MarketContract {
 
         Self::write_market(&env, &market);
 
-        env.events().publish(
-            (Symbol::new(&env, "bet_placed"),),
-            BetPlacedEvent {
-                bet_id: bet_id.clone(),
-                market_id: market.market_id.clone(),
-                bettor,
-                side,
-                amount,
-                placed_at: env.ledger().timestamp(),
-            },
-        );
+        events::emit_bet_placed(&env, BetPlacedEvent {
+            market_id: market.numeric_id,
+            bet_id: bet_id.clone(),
+            bettor,
+            side,
+            amount,
+            placed_at: env.ledger().timestamp(),
+        });
 
         bet_id
     }
@@ -263,7 +494,7 @@ impl MarketContract {
     /// Transitions market status from Open to Locked.
     /// Admin-only. Cancels a market (e.g. fight postponed).
     /// require_auth() is the first call. Verifies caller is the factory admin.
-    /// Valid only when status is Open or Locked. Emits MarketCancelled event.
+    /// Valid only when status is Open or Locked. Emits a `market_cancelled` event.
     pub fn cancel_market(env: Env, admin: Address) {
         admin.require_auth();
 
@@ -281,256 +512,680 @@ impl MarketContract {
             panic!("not factory admin");
         }
 
-        let mut market = Self::read_market(&env);
+        Self::cancel(&env, &config);
+    }
+
+    /// Cancels the market on the factory's behalf, for `cancel_event_markets`.
+    /// The factory passes its live config, since the market cannot call back
+    /// into the factory while the factory is calling it.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless the factory authorized the call, or if the market is
+    /// not `Open` or `Locked`.
+    pub fn cancel_by_factory(env: Env, config: ProtocolConfig) {
+        let factory: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Factory)
+            .expect("factory not set");
+        factory.require_auth();
+
+        Self::cancel(&env, &config);
+    }
+
+    /// Shared body of `cancel_market` and `cancel_by_factory`.
+    fn cancel(env: &Env, config: &ProtocolConfig) {
+        let mut market = Self::read_market(env);
         match market.status {
             MarketStatus::Open | MarketStatus::Locked => {}
             _ => panic!("cannot cancel: market already resolved or cancelled"),
         }
 
         market.status = MarketStatus::Cancelled;
-        Self::write_market(&env, &market);
+        Self::write_market(env, &market);
+        claims::start_claim_period(env, &market, config);
 
-        env.events().publish(
-            (Symbol::new(&env, "MarketCancelled"),),
-            market.market_id.clone(),
-        );
+        events::emit_market_cancelled(env, MarketCancelledEvent {
+            market_id: market.numeric_id,
+            cancelled_at: env.ledger().timestamp(),
+        });
     }
 
-    /// Transitions the market status from `Open` to `Locked`.
-    ///
-    /// After locking, no new bets are accepted. Can be called by the oracle address
-    /// at any time, or by anyone once `betting_ends_at` has passed.
-    /// Emits a `MarketLocked` event.
-    ///
-    /// # Arguments
-    ///
-    /// * `env` - The Soroban execution environment.
-    /// * `oracle` - Address of the oracle or any caller after the betting period ends.
+    /// Cancels a market the oracle failed to resolve by its resolution deadline,
+    /// opening full refunds. Permissionless. Emits a `resolution_timed_out` event.
     ///
     /// # Panics
     ///
-    /// Panics if the market status is not `Open`, or if `oracle` is not the
-    /// authorized oracle address and the betting period has not yet ended.
-    pub fn lock_market(env: Env, oracle: Address) {
+    /// Panics if the market is not `Open` or `Locked`, or the resolution
+    /// deadline has not passed yet.
+    pub fn cancel_unresolved(env: Env) {
         let mut market = Self::read_market(&env);
-
-        if market.status != MarketStatus::Open {
-            panic!("market already locked");
+        match market.status {
+            MarketStatus::Open | MarketStatus::Locked => {}
+            _ => panic!("market already settled"),
         }
-
-        let now = env.ledger().timestamp();
-        if now < market.betting_ends_at {
-            // Early lock: only the market's oracle may lock before lock_time passes.
-            oracle.require_auth();
-            if oracle != market.oracle_address {
-                panic!("not authorized oracle");
-            }
+        let deadline = Self::resolution_deadline(&env, &market);
+        if env.ledger().timestamp() <= deadline {
+            panic!("resolution window still open");
         }
-        // Once lock_time has passed, locking is permissionless — no auth required.
 
-        market.status = MarketStatus::Locked;
+        market.status = MarketStatus::Cancelled;
         Self::write_market(&env, &market);
+        claims::start_claim_period(&env, &market, &Self::read_config(&env));
 
-        env.events().publish(
-            (Symbol::new(&env, "MarketLocked"),),
-            (market.market_id.clone(), now),
-        );
+        events::emit_resolution_timed_out(&env, ResolutionTimedOutEvent {
+            market_id: market.numeric_id,
+            deadline,
+        });
     }
 
-    /// Called by oracle after fight concludes.
-    /// Draw outcome sets status to Cancelled so both sides can claim full refunds.
-    /// Records the fight outcome and resolves the market.
+    /// Returns the latest time the oracle may resolve this market:
+    /// `scheduled_at + RESOLUTION_WINDOW_SEC`, moved along by reschedules.
+    pub fn get_resolution_deadline(env: Env) -> u64 {
+        Self::resolution_deadline(&env, &Self::read_market(&env))
+    }
+
+    /// Moves the fight to a new date instead of cancelling the market.
     ///
-    /// Called by the oracle after the fight concludes. Sets the outcome and
-    /// transitions status to `Resolved`. If `outcome` is `NoContest`, status is
-    /// set to `Cancelled` instead, enabling full refunds. Emits a `MarketResolved` event.
+    /// Valid while `Open` or `Locked`. A locked market re-opens if the new lock
+    /// time is still in the future. Opens a `RESCHEDULE_WITHDRAWAL_WINDOW_SEC`
+    /// window in which any bettor may withdraw their stake penalty-free via
+    /// `withdraw_bet`. Emits a `market_rescheduled` event.
     ///
     /// # Arguments
     ///
     /// * `env` - The Soroban execution environment.
-    /// * `oracle` - Address of the authorized oracle. Must authorize this call.
-    /// * `outcome` - The fight result (`FighterA`, `FighterB`, `Draw`, or `NoContest`).
+    /// * `caller` - The market's oracle or the factory admin. Must authorize this call.
+    /// * `new_scheduled_at` - New fight time (Unix seconds). Must be in the future.
+    /// * `new_betting_ends_at` - New lock time. Must be at or before `new_scheduled_at`.
     ///
     /// # Panics
     ///
     /// Panics if:
-    /// - The caller is not the authorized oracle address.
-    /// - The market status is not `Locked`.
-    pub fn resolve_market(env: Env, oracle: Address, outcome: Outcome) {
-        oracle.require_auth();
+    /// - `caller` is neither the oracle nor the factory admin.
+    /// - The market status is not `Open` or `Locked`.
+    /// - `new_scheduled_at` is not in the future, or `new_betting_ends_at` is after it.
+    pub fn reschedule_market(
+        env: Env,
+        caller: Address,
+        new_scheduled_at: u64,
+        new_betting_ends_at: u64,
+    ) {
+        caller.require_auth();
 
-        let mut market: Market = env.storage().persistent()
-            .get(&DataKey::MarketInfo)
-            .expect("market not initialized");
+        let mut market = Self::read_market(&env);
+        if caller != market.oracle_address && caller != Self::read_config(&env).admin {
+            panic!("not authorized");
+        }
+        match market.status {
+            MarketStatus::Open | MarketStatus::Locked => {}
+            _ => panic!("cannot reschedule: market already resolved or cancelled"),
+        }
 
-        if market.status != MarketStatus::Locked {
-            panic!("market not locked");
+        let now = env.ledger().timestamp();
+        if new_scheduled_at <= now {
+            panic!("new fight time must be in the future");
+        }
+        if new_betting_ends_at > new_scheduled_at {
+            panic!("lock time must be at or before end time");
         }
 
-        if market.oracle_address != oracle {
-            panic!("not authorized oracle");
+        let old_scheduled_at = market.scheduled_at;
+        market.scheduled_at = new_scheduled_at;
+        market.betting_ends_at = new_betting_ends_at;
+        if market.status == MarketStatus::Locked && new_betting_ends_at > now {
+            market.status = MarketStatus::Open;
         }
+        Self::write_market(&env, &market);
+        Self::set_resolution_deadline(&env, new_scheduled_at);
 
-        // Set resolved_at timestamp for dispute window enforcement
-        market.resolved_at = env.ledger().timestamp();
+        let window_ends = Self::open_withdrawal_window(&env, RESCHEDULE_WITHDRAWAL_WINDOW_SEC);
 
-        // Draw reuses the Cancelled path so both sides receive full refunds with no fee.
-        market.status = match outcome {
-            Outcome::NoContest | Outcome::Draw => MarketStatus::Cancelled,
-            _ => MarketStatus::Resolved,
-        };
-        market.outcome = Some(outcome.clone());
-        let resolution_time = env.ledger().timestamp();
-        env.storage().persistent().set(&DataKey::MarketInfo, &market);
+        events::emit_market_rescheduled(&env, MarketRescheduledEvent {
+            market_id: market.numeric_id,
+            old_scheduled_at,
+            new_scheduled_at,
+            new_betting_ends_at,
+            withdrawals_end_at: window_ends,
+        });
+    }
 
-        // Emit market_resolved event with market_id, outcome, and resolution_time
-        let market_id_u64 = u64::from_le_bytes([
-            market.market_id.as_ref()[0],
-            market.market_id.as_ref()[1],
-            market.market_id.as_ref()[2],
-            market.market_id.as_ref()[3],
-            market.market_id.as_ref()[4],
-            market.market_id.as_ref()[5],
-            market.market_id.as_ref()[6],
-            market.market_id.as_ref()[7],
-        ]);
-        events::emit_market_resolved(&env, market_id_u64, outcome, resolution_time);
-    }
-
-    /// Allows a winning bettor to claim their proportional share of the pool.
-    /// Payout = bettor_stake / winning_pool * net_pool (fee already deducted).
-    /// Pays out winnings to a bettor whose bet matched the fight outcome.
+    /// Replaces one fighter after a short-notice opponent swap.
     ///
-    /// Payout formula: `(bettor_stake / winning_pool) * total_pool * (1 - fee_bp / 10_000)`.
-    /// The protocol fee portion is transferred to `fee_collector`.
-    /// The `CLAIMED` flag is set before any transfer to guard against re-entrancy.
-    /// Emits a `WinningsClaimed` event.
+    /// Valid while `Open` or `Locked`. New bets on the affected side are frozen
+    /// for `SUBSTITUTION_OPT_OUT_SEC`, and every bettor may opt out with a full
+    /// refund via `withdraw_bet` during that grace period. The swap is appended
+    /// to the on-chain substitution history. Emits a `fighter_substituted` event.
     ///
     /// # Arguments
     ///
     /// * `env` - The Soroban execution environment.
-    /// * `bettor` - Address of the bettor claiming winnings. Must authorize this call.
-    /// * `bet_id` - Unique identifier of the bet to claim.
-    ///
-    /// # Returns
-    ///
-    /// Returns the payout amount transferred to `bettor`, in stroops.
+    /// * `admin` - The factory admin. Must authorize this call.
+    /// * `side` - `FighterA` or `FighterB`; the corner being replaced.
+    /// * `new_fighter` - The replacement fighter's record.
     ///
     /// # Panics
     ///
     /// Panics if:
-    /// - `bettor` has not authorized the call.
-    /// - `bet_id` does not exist.
-    /// - `bettor` is not the owner of the bet.
-    /// - The market status is not `Resolved`.
-    /// - The bet's side does not match the winning outcome.
-    /// - The bet has already been claimed.
-    pub fn claim_winnings(env: Env, bettor: Address, bet_id: Bytes) -> i128 {
-        bettor.require_auth();
-
-        let bet: Bet = env.storage().persistent()
-            .get(&DataKey::Bet(bet_id.clone()))
-            .expect("bet not found");
-        if bet.bettor != bettor {
-            panic!("not your bet");
-        }
-
-        let market: Market = env.storage().persistent()
-            .get(&DataKey::MarketInfo)
-            .expect("market not initialized");
-        if market.status != MarketStatus::Resolved {
-            panic!("market not resolved");
-        }
-
-        let outcome = market.outcome.clone().expect("no outcome set");
-        let is_winner = match (&bet.side, &outcome) {
-            (BetSide::FighterA, Outcome::FighterA) => true,
-            (BetSide::FighterB, Outcome::FighterB) => true,
-            _ => false,
-        };
-        if !is_winner {
-            panic!("bet did not win");
+    /// - `admin` is not the factory admin.
+    /// - `side` is `Draw`.
+    /// - The market status is not `Open` or `Locked`.
+    pub fn substitute_fighter(env: Env, admin: Address, side: BetSide, new_fighter: Fighter) {
+        admin.require_auth();
+        if admin != Self::read_config(&env).admin {
+            panic!("not admin");
         }
 
-        let already_claimed: bool = env.storage().persistent()
-            .get(&DataKey::Claimed(bet_id.clone()))
-            .unwrap_or(false);
-        if already_claimed {
-            panic!("already claimed");
+        let mut market = Self::read_market(&env);
+        match market.status {
+            MarketStatus::Open | MarketStatus::Locked => {}
+            _ => panic!("cannot substitute: market already resolved or cancelled"),
         }
 
-        let winning_pool = match outcome {
-            Outcome::FighterA => market.pool_a,
-            Outcome::FighterB => market.pool_b,
-            _ => market.pool_a.checked_add(market.pool_b).expect("pool sum overflow"),
+        let previous = match side {
+            BetSide::FighterA => core::mem::replace(&mut market.fighter_a, new_fighter.clone()),
+            BetSide::FighterB => core::mem::replace(&mut market.fighter_b, new_fighter.clone()),
+            BetSide::Draw => panic!("a draw has no fighter"),
         };
+        Self::write_market(&env, &market);
 
-        let payout = if winning_pool > 0 {
-            let fee_amount = shared::types::calculate_fee(market.total_pool, market.protocol_fee_bp);
-            let net_pool = market.total_pool.checked_sub(fee_amount).expect("net pool underflow");
-            bet.amount
-                .checked_mul(net_pool)
-                .expect("payout overflow")
-                .checked_div(winning_pool)
-                .expect("payout div zero")
-        } else {
-            0
-        };
+        let now = env.ledger().timestamp();
+        let opt_out_ends_at = Self::open_withdrawal_window(&env, SUBSTITUTION_OPT_OUT_SEC);
+        env.storage().persistent().set(
+            &DataKey::SideFrozenUntil(side.clone()),
+            &(now + SUBSTITUTION_OPT_OUT_SEC),
+        );
 
-        // Mark claimed BEFORE any transfer (re-entrancy guard).
-        env.storage().persistent().set(&DataKey::Claimed(bet_id.clone()), &true);
+        let mut history: Vec<Substitution> = env.storage().persistent()
+            .get(&DataKey::Substitutions)
+            .unwrap_or(Vec::new(&env));
+        history.push_back(Substitution {
+            side: side.clone(),
+            previous,
+            replacement: new_fighter.clone(),
+            substituted_at: now,
+            opt_out_ends_at,
+        });
+        env.storage().persistent().set(&DataKey::Substitutions, &history);
 
-        // Emit winnings_claimed event with market_id, claimant, and amount (payout after fee)
-        let market_id_u64 = u64::from_le_bytes([
-            market.market_id.as_ref()[0],
-            market.market_id.as_ref()[1],
-            market.market_id.as_ref()[2],
-            market.market_id.as_ref()[3],
-            market.market_id.as_ref()[4],
-            market.market_id.as_ref()[5],
-            market.market_id.as_ref()[6],
-            market.market_id.as_ref()[7],
-        ]);
-
-        // Create ClaimReceipt for event emission
-        use shared::types::ClaimReceipt;
-        let receipt = ClaimReceipt {
-            bet_id: bet_id.clone(),
-            bettor: bettor.clone(),
-            payout,
-            claimed_at: env.ledger().timestamp(),
-        };
-        events::emit_winnings_claimed(&env, market_id_u64, receipt);
+        events::emit_fighter_substituted(&env, FighterSubstitutedEvent {
+            market_id: market.numeric_id,
+            side,
+            replacement: new_fighter.name,
+            opt_out_ends_at,
+        });
+    }
 
-        payout
+    /// Returns every fighter substitution made on this market, oldest first.
+    pub fn get_substitutions(env: Env) -> Vec<Substitution> {
+        env.storage().persistent()
+            .get(&DataKey::Substitutions)
+            .unwrap_or(Vec::new(&env))
     }
 
-    /// Issues a full refund when market is Cancelled (includes Draw and NoContest outcomes).
-    /// No protocol fee deducted on refunds.
+    /// Withdraws a bet's full stake during the window opened by `reschedule_market`
+    /// or `substitute_fighter`. Works for winner and prop bets alike.
     ///
-    /// Applicable when market status is `Cancelled` or outcome is `NoContest`.
-    /// No protocol fee is deducted on refunds. The `CLAIMED` flag is set before
-    /// any transfer to guard against re-entrancy. Emits a `RefundClaimed` event.
+    /// No fee is charged. The stake is removed from the pools, the bet is marked
+    /// claimed and dropped from `BetsByAddr` (or `PropBetsByAddr`), and the funds
+    /// are released from the Treasury. Emits a `bet_withdrawn` event.
     ///
     /// # Arguments
     ///
     /// * `env` - The Soroban execution environment.
-    /// * `bettor` - Address of the bettor claiming the refund. Must authorize this call.
-    /// * `bet_id` - Unique identifier of the bet to refund.
+    /// * `bettor` - Owner of the bet. Must authorize this call.
+    /// * `bet_id` - Identifier of the bet to withdraw.
     ///
     /// # Returns
     ///
-    /// Returns the refund amount (equal to the original `bet.amount`), in stroops.
+    /// Returns the amount returned to `bettor` (the full stake), in stroops.
     ///
     /// # Panics
     ///
     /// Panics if:
-    /// - `bettor` has not authorized the call.
-    /// - `bet_id` does not exist.
-    /// - `bettor` is not the owner of the bet.
-    /// - The market status is not `Cancelled` and outcome is not `NoContest`.
+    /// - `bet_id` does not exist or `bettor` is not its owner.
+    /// - The bet was already claimed or withdrawn.
+    /// - No withdrawal window is open, or the market has already settled.
+    pub fn withdraw_bet(env: Env, bettor: Address, bet_id: Bytes) -> i128 {
+        bettor.require_auth();
+
+        let mut market = Self::read_market(&env);
+        Self::require_withdrawal_window(&env, &market);
+
+        let amount = Self::unwind_stake(&env, &mut market, &bettor, &bet_id);
+        Self::release_stake(&env, &market, &bettor, amount);
+
+        events::emit_bet_withdrawn(&env, BetWithdrawnEvent {
+            market_id: market.numeric_id,
+            bet_id,
+            bettor,
+            amount,
+        });
+
+        amount
+    }
+
+    /// Exits a bet before the market locks, refunding the stake minus the
+    /// early-exit fee.
+    ///
+    /// The stake is removed from `pool_a`/`pool_b`/`pool_draw` and `total_pool`,
+    /// and the bet is dropped from `BetsByAddr`; a prop bet leaves its prop pool
+    /// and `PropBetsByAddr` instead. The fee stays in the Treasury and is booked
+    /// as protocol fees. Emits a `bet_cashed_out` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `bettor` - Owner of the bet. Must authorize this call.
+    /// * `bet_id` - Identifier of the bet to cash out.
+    ///
+    /// # Returns
+    ///
+    /// Returns the amount refunded to `bettor` (stake minus fee), in stroops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - The market is not `Open`; it locks itself once `betting_ends_at` has
+    ///   passed.
+    /// - `bet_id` does not exist or `bettor` is not its owner.
+    /// - The bet was already claimed, withdrawn or cashed out.
+    pub fn cash_out(env: Env, bettor: Address, bet_id: Bytes) -> i128 {
+        bettor.require_auth();
+
+        let mut market = Self::read_market(&env);
+        if market.status != MarketStatus::Open {
+            panic!("market not open");
+        }
+
+        let amount = Self::unwind_stake(&env, &mut market, &bettor, &bet_id);
+
+        let fee_bp = Self::get_cash_out_fee(env.clone());
+        let fee = amount * fee_bp as i128 / 10_000;
+        let refund = amount - fee;
+
+        if refund > 0 {
+            Self::release_stake(&env, &market, &bettor, refund);
+        }
+        if fee > 0 {
+            Self::collect_fee(&env, &market, fee);
+        }
+
+        events::emit_bet_cashed_out(&env, BetCashedOutEvent {
+            market_id: market.numeric_id,
+            bet_id,
+            bettor,
+            refund,
+            fee,
+        });
+
+        refund
+    }
+
+    /// Sets the early-exit fee charged by `cash_out`.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `admin` - The factory admin. Must authorize this call.
+    /// * `fee_bp` - Fee in basis points of the stake. Must not exceed 10 000.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `admin` is not the factory admin or `fee_bp` exceeds 10 000.
+    pub fn set_cash_out_fee(env: Env, admin: Address, fee_bp: u32) {
+        admin.require_auth();
+        if admin != Self::read_config(&env).admin {
+            panic!("not admin");
+        }
+        if fee_bp > 10_000 {
+            panic!("fee_bp exceeds ceiling");
+        }
+        env.storage().persistent().set(&DataKey::CashOutFeeBp, &fee_bp);
+    }
+
+    /// Returns the early-exit fee charged by `cash_out`, in basis points.
+    pub fn get_cash_out_fee(env: Env) -> u32 {
+        env.storage().persistent()
+            .get(&DataKey::CashOutFeeBp)
+            .unwrap_or(DEFAULT_CASH_OUT_FEE_BP)
+    }
+
+    /// Returns when the current withdrawal window closes, or `0` if the market
+    /// was never rescheduled and no fighter was substituted.
+    pub fn get_withdrawal_window_ends(env: Env) -> u64 {
+        env.storage().persistent()
+            .get(&DataKey::WithdrawalWindowEnds)
+            .unwrap_or(0)
+    }
+
+    /// Takes an unsettled winner or prop bet back out of the market, whichever
+    /// `bet_id` names, and returns its stake.
+    fn unwind_stake(env: &Env, market: &mut Market, bettor: &Address, bet_id: &Bytes) -> i128 {
+        match crate::props::unwind_prop_bet(env, bettor, bet_id) {
+            Some(bet) => bet.amount,
+            None => Self::unwind_bet(env, market, bettor, bet_id).amount,
+        }
+    }
+
+    /// Takes an unsettled bet back out of the market: removes its stake from the
+    /// pools, marks it claimed and drops it from `BetsByAddr`. Writes the market.
+    /// Callers validate market state first and move the funds afterwards.
+    fn unwind_bet(env: &Env, market: &mut Market, bettor: &Address, bet_id: &Bytes) -> Bet {
+        let mut bet: Bet = env.storage().persistent()
+            .get(&DataKey::Bet(bet_id.clone()))
+            .expect("bet not found");
+        if bet.bettor != *bettor {
+            panic!("not your bet");
+        }
+        let already_claimed: bool = env.storage().persistent()
+            .get(&DataKey::Claimed(bet_id.clone()))
+            .unwrap_or(false);
+        if already_claimed {
+            panic!("already claimed");
+        }
+
+        match bet.side {
+            BetSide::FighterA => market.pool_a = market.pool_a.checked_sub(bet.amount).expect("pool_a underflow"),
+            BetSide::FighterB => market.pool_b = market.pool_b.checked_sub(bet.amount).expect("pool_b underflow"),
+            BetSide::Draw => market.pool_draw = market.pool_draw.checked_sub(bet.amount).expect("pool_draw underflow"),
+        }
+        market.total_pool = market.total_pool.checked_sub(bet.amount).expect("total_pool underflow");
+        Self::write_market(env, market);
+
+        // Mark claimed BEFORE any transfer (re-entrancy guard).
+        env.storage().persistent().set(&DataKey::Claimed(bet_id.clone()), &true);
+        bet.claimed = true;
+        env.storage().persistent().set(&DataKey::Bet(bet_id.clone()), &bet);
+        Self::remove_from_bets_by_addr(env, bettor, bet_id);
+
+        bet
+    }
+
+    fn remove_from_bets_by_addr(env: &Env, bettor: &Address, bet_id: &Bytes) {
+        let bets: Vec<Bytes> = env.storage().persistent()
+            .get(&DataKey::BetsByAddr(bettor.clone()))
+            .unwrap_or(Vec::new(env));
+        let mut kept = Vec::new(env);
+        for id in bets.iter() {
+            if id != *bet_id {
+                kept.push_back(id);
+            }
+        }
+        env.storage().persistent().set(&DataKey::BetsByAddr(bettor.clone()), &kept);
+    }
+
+    /// Transitions the market status from `Open` to `Locked`.
+    ///
+    /// After locking, no new bets are accepted. Can be called by the oracle address
+    /// or the factory (bulk-locking a fight card) at any time, or by anyone once
+    /// `betting_ends_at` has passed.
+    /// Emits a `market_locked` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `oracle` - Address of the oracle or any caller after the betting period ends.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the market status is not `Open`, or if `oracle` is neither the
+    /// authorized oracle nor the factory and the betting period has not yet ended.
+    pub fn lock_market(env: Env, oracle: Address) {
+        let mut market = Self::read_stored_market(&env);
+
+        if market.status != MarketStatus::Open {
+            panic!("market already locked");
+        }
+
+        let now = env.ledger().timestamp();
+        if now < market.betting_ends_at {
+            // Early lock: only the market's oracle or its factory may lock before
+            // lock_time passes.
+            oracle.require_auth();
+            let factory: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Factory)
+                .expect("factory not set");
+            if oracle != market.oracle_address && oracle != factory {
+                panic!("not authorized oracle");
+            }
+        }
+        // Once lock_time has passed, locking is permissionless — no auth required.
+
+        market.status = MarketStatus::Locked;
+        Self::write_market(&env, &market);
+        Self::emit_locked(&env, &market, now);
+    }
+
+    /// Records the fight outcome and resolves the market.
+    ///
+    /// Called by the oracle after the fight concludes. Sets the outcome and
+    /// transitions status to `Resolved`. If `outcome` is `NoContest`, or `Draw` on a
+    /// market created without `draw_enabled`, status is set to `Cancelled` instead so
+    /// both sides can claim full refunds. Emits a `market_resolved` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `oracle` - Address of the authorized oracle. Must authorize this call.
+    /// * `outcome` - The fight result (`FighterA`, `FighterB`, `Draw`, or `NoContest`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - The caller is not the authorized oracle address.
+    /// - The market status is not `Locked`.
+    /// - The resolution deadline has passed (`ContractError::ResolutionWindowExpired`).
+    pub fn resolve_market(env: Env, oracle: Address, outcome: Outcome) {
+        oracle.require_auth();
+        Self::apply_resolution(&env, &oracle, outcome);
+    }
+
+    /// Shared body of `resolve_market` and `resolve_with_result`. The caller
+    /// must already have checked `oracle.require_auth()`.
+    fn apply_resolution(env: &Env, oracle: &Address, outcome: Outcome) {
+        let market = Self::read_market(env);
+
+        if market.status != MarketStatus::Locked {
+            panic!("market not locked");
+        }
+
+        if market.oracle_address != *oracle {
+            panic!("not authorized oracle");
+        }
+
+        Self::record_resolution(env, market, outcome);
+    }
+
+    /// Settles a `Locked` market with `outcome` once the caller has established
+    /// that the result comes from the oracle.
+    ///
+    /// Panics with `ContractError::ResolutionWindowExpired` past the
+    /// resolution deadline; the market can only be cancelled by then.
+    fn record_resolution(env: &Env, mut market: Market, outcome: Outcome) {
+        if env.ledger().timestamp() > Self::resolution_deadline(env, &market) {
+            panic_with_error!(env, ContractError::ResolutionWindowExpired);
+        }
+
+        // Set resolved_at timestamp for dispute window enforcement
+        market.resolved_at = env.ledger().timestamp();
+
+        market.status = Self::settled_status(&market, &outcome);
+        market.outcome = OptionalOutcome::Some(outcome.clone());
+        let resolution_time = env.ledger().timestamp();
+        env.storage().persistent().set(&DataKey::MarketInfo, &market);
+        env.storage().persistent().set(&DataKey::OracleOutcome, &outcome);
+        // A winner can still be disputed, so its claim period starts once the
+        // resolution is finalized.
+        if market.status == MarketStatus::Cancelled {
+            claims::start_claim_period(env, &market, &Self::read_config(env));
+        }
+        Self::report_oracle(env, "record_oracle_resolution", &market.market_id);
+
+        events::emit_market_resolved(env, MarketResolvedEvent {
+            market_id: market.numeric_id,
+            outcome,
+            resolved_at: resolution_time,
+        });
+    }
+
+    /// Pool backing a decisive `outcome`, or `None` when the outcome itself
+    /// voids the market (NoContest, or Draw unless draw is a bettable side).
+    fn winning_pool(market: &Market, outcome: &Outcome) -> Option<i128> {
+        match outcome {
+            Outcome::FighterA => Some(market.pool_a),
+            Outcome::FighterB => Some(market.pool_b),
+            Outcome::Draw if market.draw_enabled => Some(market.pool_draw),
+            _ => None,
+        }
+    }
+
+    /// Status a market settles into for `outcome`. A voiding outcome cancels for
+    /// full refunds with no fee. So does a winner nobody backed, rather than
+    /// leaving the whole pool orphaned with no one able to claim it.
+    fn settled_status(market: &Market, outcome: &Outcome) -> MarketStatus {
+        match Self::winning_pool(market, outcome) {
+            Some(pool) if pool > 0 => MarketStatus::Resolved,
+            _ => MarketStatus::Cancelled,
+        }
+    }
+
+    /// True once the market has settled into full refunds for every bettor.
+    fn refunds_all(market: &Market) -> bool {
+        match market.status {
+            MarketStatus::Cancelled => true,
+            // A dispute override lands in Resolved even for refund outcomes.
+            MarketStatus::Resolved => market
+                .outcome
+                .outcome()
+                .is_some_and(|o| Self::settled_status(market, &o) == MarketStatus::Cancelled),
+            _ => false,
+        }
+    }
+
+    /// True when the oracle named a winner but nobody had backed that side.
+    fn winner_unbacked(market: &Market) -> bool {
+        market
+            .outcome
+            .outcome()
+            .and_then(|o| Self::winning_pool(market, &o))
+            == Some(0)
+    }
+
+    /// Pays out winnings to a bettor whose bet matched the fight outcome.
+    ///
+    /// Payout formula: `(bettor_stake / winning_pool) * total_pool * (1 - fee_bp / 10_000)`.
+    /// Shares are truncated; the claim that settles the last winning stake also
+    /// receives the rounding remainder, so nothing is left undistributed.
+    /// The payout is released to `bettor` from the market's treasury escrow.
+    /// The `CLAIMED` flag is set before any transfer to guard against re-entrancy.
+    /// Emits a `winnings_claimed` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `bettor` - Address of the bettor claiming winnings. Must authorize this call.
+    /// * `bet_id` - Unique identifier of the bet to claim.
+    ///
+    /// # Returns
+    ///
+    /// Returns the payout amount transferred to `bettor`, in stroops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `bettor` has not authorized the call.
+    /// - `bet_id` does not exist.
+    /// - `bettor` is not the owner of the bet.
+    /// - The market status is not `Resolved`.
+    /// - The bet's side does not match the winning outcome.
     /// - The bet has already been claimed.
-    /// Full refund for a bet when market is Cancelled. No protocol fee.
+    /// - The claim deadline has passed (`ContractError::ClaimExpired`).
+    pub fn claim_winnings(env: Env, bettor: Address, bet_id: Bytes) -> i128 {
+        bettor.require_auth();
+
+        let bet: Bet = env.storage().persistent()
+            .get(&DataKey::Bet(bet_id.clone()))
+            .expect("bet not found");
+        if bet.bettor != bettor {
+            panic!("not your bet");
+        }
+
+        let market = Self::read_market(&env);
+        if market.status != MarketStatus::Resolved {
+            panic!("market not resolved");
+        }
+        claims::require_final(&env, &market);
+        claims::require_claim_open(&env);
+
+        let outcome = market.outcome.outcome().expect("no outcome set");
+        let is_winner = matches!(
+            (&bet.side, &outcome),
+            (BetSide::FighterA, Outcome::FighterA)
+                | (BetSide::FighterB, Outcome::FighterB)
+                | (BetSide::Draw, Outcome::Draw)
+        );
+        if !is_winner {
+            panic!("bet did not win");
+        }
+
+        let already_claimed: bool = env.storage().persistent()
+            .get(&DataKey::Claimed(bet_id.clone()))
+            .unwrap_or(false);
+        if already_claimed {
+            panic!("already claimed");
+        }
+
+        let payout = claims::settlement_amount(&env, &market, &bet);
+        claims::record_payout(&env, &bet, payout);
+
+        // Mark claimed BEFORE any transfer (re-entrancy guard).
+        env.storage().persistent().set(&DataKey::Claimed(bet_id.clone()), &true);
+        if payout > 0 {
+            Self::release_stake(&env, &market, &bettor, payout);
+        }
+
+        events::emit_winnings_claimed(&env, WinningsClaimedEvent {
+            market_id: market.numeric_id,
+            bet_id,
+            bettor,
+            payout,
+            claimed_at: env.ledger().timestamp(),
+        });
+
+        payout
+    }
+
+    /// Issues a full refund for a bet on a cancelled market.
+    ///
+    /// Applicable when market status is `Cancelled` or outcome is `NoContest`
+    /// (or `Draw` when the market is not `draw_enabled`).
+    /// No protocol fee is deducted on refunds; the stake is released to `bettor`
+    /// from the market's treasury escrow. The `CLAIMED` flag is set before any
+    /// transfer to guard against re-entrancy. Emits a `refund_claimed` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `bettor` - Address of the bettor claiming the refund. Must authorize this call.
+    /// * `bet_id` - Unique identifier of the bet to refund.
+    ///
+    /// # Returns
+    ///
+    /// Returns the refund amount (equal to the original `bet.amount`), in stroops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `bettor` has not authorized the call.
+    /// - `bet_id` does not exist.
+    /// - `bettor` is not the owner of the bet.
+    /// - The market status is not `Cancelled` and outcome is not `NoContest`.
+    /// - The bet has already been claimed.
+    /// - The claim deadline has passed (`ContractError::ClaimExpired`).
     pub fn claim_refund(env: Env, bettor: Address, bet_id: Bytes) -> i128 {
         bettor.require_auth();
 
@@ -544,17 +1199,11 @@ impl MarketContract {
         }
 
         let market = Self::read_market(&env);
-        // Check market is Cancelled or has NoContest outcome
-        let is_eligible = match market.status {
-            MarketStatus::Cancelled => true,
-            MarketStatus::Resolved => {
-                market.outcome.clone().map(|o| matches!(o, Outcome::NoContest)).unwrap_or(false)
-            }
-            _ => false,
-        };
-        if !is_eligible {
+        if !Self::refunds_all(&market) {
             panic!("market not eligible for refund");
         }
+        claims::require_final(&env, &market);
+        claims::require_claim_open(&env);
 
         let already_claimed: bool = env
             .storage()
@@ -568,12 +1217,16 @@ impl MarketContract {
         // Mark claimed BEFORE any transfer (re-entrancy guard)
         env.storage()
             .persistent()
-            .set(&DataKey::Claimed(bet_id.clone()), &true);
-
-        env.events().publish(
-            (Symbol::new(&env, "RefundClaimed"),),
-            (bettor.clone(), bet_id, bet.amount),
-        );
+            .set(&DataKey::Claimed(bet_id.clone()), &true);
+        claims::record_payout(&env, &bet, bet.amount);
+        Self::release_stake(&env, &market, &bettor, bet.amount);
+
+        events::emit_refund_claimed(&env, RefundClaimedEvent {
+            market_id: market.numeric_id,
+            bet_id,
+            bettor,
+            amount: bet.amount,
+        });
 
         bet.amount
     }
@@ -583,7 +1236,11 @@ impl MarketContract {
     ///
     /// Transitions status to `Disputed`, freezing all claim processing until an admin
     /// settles the dispute. Must be called within `dispute_window_sec` of `resolved_at`.
-    /// Only one active dispute is allowed per market. Emits a `resolution_disputed` event.
+    /// The disputer escrows the opening bond (`get_required_bond`) in the bet token;
+    /// other participants may answer with `post_dispute_bond`.
+    /// Only one dispute may be open at a time, and at most `MAX_DISPUTES` per market;
+    /// each is kept in the log returned by `get_disputes`. Emits a
+    /// `resolution_disputed` event.
     ///
     /// # Arguments
     ///
@@ -591,6 +1248,12 @@ impl MarketContract {
     /// * `bettor` - Address of the bettor raising the dispute. Must authorize this call
     ///   and must have an existing bet in this market.
     /// * `reason` - Free-form bytes describing the reason for the dispute.
+    /// * `evidence_uri` - Where the supporting evidence is published (may be empty).
+    /// * `evidence_hash` - SHA-256 of the evidence, so the arbiter can verify it.
+    ///
+    /// # Returns
+    ///
+    /// Returns the id of the new dispute (its index in `get_disputes`).
     ///
     /// # Panics
     ///
@@ -598,9 +1261,16 @@ impl MarketContract {
     /// - `bettor` has not authorized the call.
     /// - `bettor` has no bet in this market.
     /// - The dispute window has elapsed since resolution.
-    /// - A dispute is already active on this market.
+    /// - A dispute is already open, or `MAX_DISPUTES` were already raised.
+    /// - `reason` or `evidence_uri` exceeds 256 bytes.
     /// - The market status is not `Resolved`.
-    pub fn dispute_resolution(env: Env, bettor: Address, reason: Bytes) {
+    pub fn dispute_resolution(
+        env: Env,
+        bettor: Address,
+        reason: Bytes,
+        evidence_uri: String,
+        evidence_hash: BytesN<32>,
+    ) -> u32 {
         bettor.require_auth();
 
         let mut market = Self::read_market(&env);
@@ -609,14 +1279,6 @@ impl MarketContract {
             panic!("market not resolved");
         }
 
-        // Check if a dispute has already been raised
-        let already_disputed: bool = env.storage().persistent()
-            .get(&DataKey::DisputeRaised)
-            .unwrap_or(false);
-        if already_disputed {
-            panic!("dispute already raised");
-        }
-
         // Verify bettor has a bet in this market
         let bettor_bets: Vec<Bytes> = env.storage().persistent()
             .get(&DataKey::BetsByAddr(bettor.clone()))
@@ -636,40 +1298,47 @@ impl MarketContract {
         market.status = MarketStatus::Disputed;
         Self::write_market(&env, &market);
 
-        // Cap reason length to prevent storage abuse (max 256 bytes)
-        let max_reason_len = 256;
-        if reason.len() > max_reason_len {
-            panic!("dispute reason exceeds maximum length");
-        }
+        let dispute_id = disputes::open_dispute(&env, &bettor, &reason, evidence_uri, evidence_hash);
+        disputes::post_bond(&env, &market, &bettor, true);
 
-        // Store dispute reason
-        env.storage().persistent().set(&DataKey::DisputeRaised, &true);
-        env.storage().persistent().set(&DataKey::DisputeReason, &reason);
+        events::emit_resolution_disputed(&env, ResolutionDisputedEvent {
+            market_id: market.numeric_id,
+            dispute_id,
+            disputer: bettor,
+            reason,
+        });
 
-        env.events().publish(
-            (Symbol::new(&env, "resolution_disputed"),),
-            (market.market_id.clone(), bettor.clone(), reason),
-        );
+        dispute_id
     }
 
     /// Settles a disputed market with a final admin-override outcome.
     ///
+    /// When the factory has an arbiter panel, the panel decides by majority
+    /// (`cast_arbiter_vote`) and the admin only rules as a fallback once the
+    /// panel's voting window lapses.
     /// The override outcome may differ from the oracle's original outcome.
     /// Transitions status back to `Resolved`, re-opening claims with the new outcome.
-    /// Emits a `DisputeResolved` event.
+    /// Dispute bonds go to the challengers if the outcome changed, otherwise to
+    /// the defenders (see `disputes::settle_bonds`). The open dispute is closed
+    /// as `Upheld` or `Rejected` with `response`, and a fresh dispute window
+    /// starts so the ruling itself can be challenged.
+    /// Emits a `dispute_resolved` event.
     ///
     /// # Arguments
     ///
     /// * `env` - The Soroban execution environment.
     /// * `admin` - Address of the protocol admin. Must authorize this call.
     /// * `override_outcome` - The admin-determined final outcome for the market.
+    /// * `response` - The arbiter's reasoning, recorded on the dispute (max 256 bytes).
     ///
     /// # Panics
     ///
     /// Panics if:
     /// - `admin` has not authorized the call or is not the configured admin.
     /// - The market status is not `Disputed`.
-    pub fn resolve_dispute(env: Env, admin: Address, override_outcome: Outcome) {
+    /// - An arbiter panel is registered and its voting window is still open.
+    /// - `response` exceeds 256 bytes.
+    pub fn resolve_dispute(env: Env, admin: Address, override_outcome: Outcome, response: Bytes) {
         admin.require_auth();
 
         let factory: Address = env
@@ -686,19 +1355,31 @@ impl MarketContract {
             panic!("not factory admin");
         }
 
-        let mut market = Self::read_market(&env);
+        let market = Self::read_market(&env);
         if market.status != MarketStatus::Disputed {
             panic!("market not in disputed state");
         }
+        disputes::require_admin_may_rule(&env);
+
+        Self::apply_dispute_ruling(&env, override_outcome, response);
+    }
 
-        market.outcome = Some(override_outcome.clone());
+    /// Settles the open dispute with `outcome`: re-resolves the market, records
+    /// the ruling on the dispute and pays out the bonds.
+    fn apply_dispute_ruling(env: &Env, outcome: Outcome, response: Bytes) {
+        let mut market = Self::read_market(env);
+        let challenge_upheld = market.outcome.outcome().as_ref() != Some(&outcome);
+        market.outcome = OptionalOutcome::Some(outcome.clone());
         market.status = MarketStatus::Resolved;
-        Self::write_market(&env, &market);
+        market.resolved_at = env.ledger().timestamp();
+        Self::write_market(env, &market);
+        disputes::close_dispute(env, challenge_upheld, response);
+        disputes::settle_bonds(env, &market, challenge_upheld);
 
-        env.events().publish(
-            (Symbol::new(&env, "DisputeResolved"),),
-            (market.market_id.clone(), override_outcome),
-        );
+        events::emit_dispute_resolved(env, DisputeResolvedEvent {
+            market_id: market.numeric_id,
+            final_outcome: outcome,
+        });
     }
 
     /// Finalizes the market resolution after dispute window expires or admin override.
@@ -707,8 +1388,10 @@ impl MarketContract {
     /// 1. Permissionless finalization when market is Resolved and dispute window has elapsed
     /// 2. Admin-controlled finalization when market is Disputed (admin-only)
     ///
-    /// After finalization, the `claim_winnings` function becomes available.
-    /// Emits a `ResolutionFinalized` event.
+    /// Claims stay closed until the resolution is finalized; finalizing opens
+    /// them and starts the claim period. If the finalized outcome departs from
+    /// the oracle's call, the oracle is reported overturned to the factory.
+    /// Emits a `resolution_finalized` event.
     ///
     /// # Arguments
     ///
@@ -720,7 +1403,8 @@ impl MarketContract {
     ///
     /// Panics if:
     /// - Market status is Resolved but dispute window has not elapsed yet.
-    /// - Market status is Disputed but caller is not the admin.
+    /// - Market status is Disputed but caller is not the admin, or an arbiter
+    ///   panel is still voting on it.
     /// - Market is in any other status (Open, Locked, Cancelled).
     pub fn finalize_resolution(env: Env, admin: Option<Address>) {
         let mut market = Self::read_market(&env);
@@ -732,11 +1416,15 @@ impl MarketContract {
                 if current_time <= dispute_deadline {
                     panic!("dispute window still open");
                 }
-                market.status = MarketStatus::Resolved;
-                Self::write_market(&env, &market);
+                if claims::is_final(&env, &market) {
+                    panic!("resolution already finalized");
+                }
+                claims::mark_final(&env);
+                claims::start_claim_period(&env, &market, &Self::read_config(&env));
+                Self::settle_oracle(&env, &market);
             }
             MarketStatus::Disputed => {
-                if let Some(admin_addr) = admin {
+                let config: ProtocolConfig = if let Some(admin_addr) = admin {
                     admin_addr.require_auth();
                     let factory: Address = env
                         .storage()
@@ -751,25 +1439,34 @@ impl MarketContract {
                     if config.admin != admin_addr {
                         panic!("not factory admin");
                     }
+                    config
                 } else {
                     panic!("admin required for disputed market");
-                }
+                };
+                disputes::require_admin_may_rule(&env);
 
                 market.status = MarketStatus::Resolved;
                 Self::write_market(&env, &market);
+                claims::mark_final(&env);
+                claims::start_claim_period(&env, &market, &config);
+                // Finalizing keeps the standing outcome, so the open challenge failed.
+                disputes::close_dispute(&env, false, Bytes::new(&env));
+                disputes::settle_bonds(&env, &market, false);
+                Self::settle_oracle(&env, &market);
             }
             _ => panic!("market cannot be finalized in current state"),
         }
 
-        env.events().publish(
-            (Symbol::new(&env, "ResolutionFinalized"),),
-            (market.market_id.clone(), env.ledger().timestamp()),
-        );
+        events::emit_resolution_finalized(&env, ResolutionFinalizedEvent {
+            market_id: market.numeric_id,
+            finalized_at: env.ledger().timestamp(),
+        });
     }
 
     /// Returns the full [`Market`] struct for this contract.
     ///
-    /// Read-only — does not modify state.
+    /// `status` is the effective status: an `Open` market past `betting_ends_at`
+    /// reads as `Locked`, and that first read stores the lock.
     ///
     /// # Arguments
     ///
@@ -849,7 +1546,9 @@ impl MarketContract {
     ///
     /// # Returns
     ///
-    /// Returns the estimated payout in stroops, given current pool totals.
+    /// Returns the estimated payout in stroops, given current pool totals. Once the
+    /// market has settled into refunds (including a winner nobody backed), this is
+    /// the full stake.
     ///
     /// # Panics
     ///
@@ -859,20 +1558,26 @@ impl MarketContract {
             .get(&DataKey::Bet(bet_id))
             .expect("bet not found");
 
-        let market: Market = env.storage().persistent()
-            .get(&DataKey::MarketInfo)
-            .expect("market not initialized");
+        let market = Self::read_market(&env);
+
+        if Self::refunds_all(&market) {
+            return bet.amount;
+        }
+        if market.status == MarketStatus::Resolved {
+            return claims::settlement_amount(&env, &market, &bet);
+        }
 
-        let outcome = match market.outcome.clone() {
+        let outcome = match market.outcome.outcome() {
             Some(o) => o,
             None => return 0,
         };
 
-        let is_winner = match (&bet.side, &outcome) {
-            (BetSide::FighterA, Outcome::FighterA) => true,
-            (BetSide::FighterB, Outcome::FighterB) => true,
-            _ => false,
-        };
+        let is_winner = matches!(
+            (&bet.side, &outcome),
+            (BetSide::FighterA, Outcome::FighterA)
+                | (BetSide::FighterB, Outcome::FighterB)
+                | (BetSide::Draw, Outcome::Draw)
+        );
 
         if !is_winner {
             return 0;
@@ -881,6 +1586,7 @@ impl MarketContract {
         let winning_pool = match outcome {
             Outcome::FighterA => market.pool_a,
             Outcome::FighterB => market.pool_b,
+            Outcome::Draw => market.pool_draw,
             _ => market.pool_a.checked_add(market.pool_b).expect("pool sum overflow"),
         };
 
@@ -899,9 +1605,29 @@ impl MarketContract {
 
     /// Returns current pool sizes and implied odds for both fighters.
     ///
+    /// The fighter-only view of `get_pool_odds_with_draw`, kept in its original
+    /// shape for existing callers. On a draw-enabled market the two odds leave
+    /// out the draw's share, so they can sum to less than 10000.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple `(pool_a, pool_b, implied_odds_a, implied_odds_b)`.
+    pub fn get_pool_odds(env: Env) -> (i128, i128, u32, u32) {
+        let (pool_a, pool_b, _, odds_a, odds_b, _) = Self::get_pool_odds_with_draw(env);
+        (pool_a, pool_b, odds_a, odds_b)
+    }
+
+    /// Returns current pool sizes and implied odds for every side.
+    ///
     /// Implied odds are expressed in basis points (0–10000), where
     /// `implied_odds_a = pool_a / total_pool * 10000`. When `total_pool` is zero,
-    /// returns a 50/50 split (5000, 5000). Read-only — does not modify state.
+    /// returns an even split across the sides that can be backed: (5000, 5000, 0),
+    /// or (3333, 3334, 3333) on a draw-enabled market. `get_pool_odds` serves
+    /// the same figures without the draw for callers that predate draws.
     ///
     /// # Arguments
     ///
@@ -909,31 +1635,42 @@ impl MarketContract {
     ///
     /// # Returns
     ///
-    /// Returns a tuple `(pool_a, pool_b, implied_odds_a, implied_odds_b)` where:
-    /// - `pool_a` / `pool_b` are total XLM staked per side, in stroops.
-    /// - `implied_odds_a` / `implied_odds_b` are basis-point probabilities summing to 10000.
-    pub fn get_pool_odds(env: Env) -> (i128, i128, u32, u32) {
-        let market: Market = env.storage().persistent()
-            .get(&DataKey::MarketInfo)
-            .expect("market not initialized");
-        let total = market.pool_a.checked_add(market.pool_b).unwrap_or(0);
-        let (odds_a, odds_b) = if total == 0 {
-            (5_000u32, 5_000u32)
+    /// Returns a tuple `(pool_a, pool_b, pool_draw, implied_odds_a, implied_odds_b,
+    /// implied_odds_draw)` where:
+    /// - `pool_a` / `pool_b` / `pool_draw` are total XLM staked per side, in stroops.
+    /// - The implied odds are basis-point probabilities summing to 10000 (the
+    ///   truncation remainder goes to fighter B), or all `0` once the market has
+    ///   settled into refunds and no side pays out.
+    pub fn get_pool_odds_with_draw(env: Env) -> (i128, i128, i128, u32, u32, u32) {
+        let market = Self::read_market(&env);
+        if Self::refunds_all(&market) {
+            return (market.pool_a, market.pool_b, market.pool_draw, 0, 0, 0);
+        }
+        let (odds_a, odds_draw) = if market.total_pool == 0 {
+            if market.draw_enabled {
+                (3_333u32, 3_333u32)
+            } else {
+                (5_000u32, 0u32)
+            }
         } else {
-            let a = market.pool_a
-                .checked_mul(10_000)
-                .expect("odds multiplication overflow")
-                .checked_div(total)
-                .expect("odds division error") as u32;
-            (a, 10_000u32.checked_sub(a).expect("odds underflow"))
+            let share = |pool: i128| {
+                pool.checked_mul(10_000)
+                    .expect("odds multiplication overflow")
+                    .checked_div(market.total_pool)
+                    .expect("odds division error") as u32
+            };
+            (share(market.pool_a), share(market.pool_draw))
         };
-        (market.pool_a, market.pool_b, odds_a, odds_b)
+        let odds_b = 10_000u32
+            .checked_sub(odds_a + odds_draw)
+            .expect("odds underflow");
+        (market.pool_a, market.pool_b, market.pool_draw, odds_a, odds_b, odds_draw)
     }
 
     /// Returns complete market data including status, pools, and metadata.
     ///
-    /// Read-only — does not modify state. Returns the full Market struct containing
-    /// fighter information, pools, fees, status, and outcome.
+    /// Returns the full Market struct containing fighter information, pools,
+    /// fees, effective status (as in `get_market_info`), and outcome.
     ///
     /// # Arguments
     ///
@@ -947,9 +1684,7 @@ impl MarketContract {
     ///
     /// Panics if the market has not been initialized.
     pub fn get_market_data(env: Env) -> Market {
-        env.storage().persistent()
-            .get(&DataKey::MarketInfo)
-            .expect("market not initialized")
+        Self::read_market(&env)
     }
 
     /// Returns a specific bet placed by an address, or None if not found.
@@ -977,8 +1712,7 @@ impl MarketContract {
 
     /// Returns current pool totals for both fighters.
     ///
-    /// Read-only — does not modify state. Returns the amount staked on each fighter
-    /// and the total pool size.
+    /// Returns the amount staked on each fighter and the total pool size.
     ///
     /// # Arguments
     ///
@@ -991,9 +1725,7 @@ impl MarketContract {
     /// - `pool_b` - Total XLM staked on Fighter B, in stroops.
     /// - `total_pool` - Total XLM in all pools, in stroops.
     pub fn get_pool_totals(env: Env) -> (i128, i128, i128) {
-        let market: Market = env.storage().persistent()
-            .get(&DataKey::MarketInfo)
-            .expect("market not initialized");
+        let market = Self::read_market(&env);
         (market.pool_a, market.pool_b, market.total_pool)
     }
 }
@@ -1003,217 +1735,20 @@ impl MarketContract {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use shared::test_utils::{create_test_address, create_test_env};
-    use shared::types::{Fighter, MarketStatus};
-    use soroban_sdk::String;
-
-    fn make_fighter(env: &Env, name: &str) -> Fighter {
-        Fighter {
-            name:         String::from_str(env, name),
-            record:       String::from_str(env, "10-0"),
-            nationality:  String::from_str(env, "US"),
-            weight_class: String::from_str(env, "Heavyweight"),
-        }
-    }
-
-    fn initialize_market(env: &Env, client: &MarketContractClient) {
-        let oracle      = create_test_address(env);
-        let factory     = create_test_address(env);
-        let fee_col     = create_test_address(env);
-    fn addr_from_u8(env: &Env, v: u8) -> Address {
-        let b = BytesN::from_array(env, &[v; 32]);
-        Address::from_account_id(env, &b)
-    }
-
-    fn default_market(env: &Env, status: MarketStatus) -> Market {
-        Market {
-            market_id: Bytes::from_array(env, &[0u8; 32]),
-            fighter_a: Fighter {
-                name: "A".into_val(env),
-                record: "0-0-0".into_val(env),
-                nationality: "USA".into_val(env),
-                weight_class: "Heavy".into_val(env),
-            },
-            fighter_b: Fighter {
-                name: "B".into_val(env),
-                record: "0-0-0".into_val(env),
-                nationality: "BRA".into_val(env),
-                weight_class: "Heavy".into_val(env),
-            },
-            scheduled_at: 1,
-            betting_ends_at: 1,
-            created_at: 1,
-            created_by: addr_from_u8(env, 1),
-            status,
-            pool_a: 0,
-            pool_b: 0,
-            total_pool: 0,
-            protocol_fee_bp: 100,
-            oracle_address: addr_from_u8(env, 2),
-            outcome: None,
-            fee_collector_address: addr_from_u8(env, 3),
-        }
-    }
-
-    #[test]
-    fn test_resolve_market_emits_event() {
-        let env = Env::default();
-        let market = default_market(&env, MarketStatus::Locked);
-        env.storage().set(&Symbol::short("MARKET_INFO"), &market);
-
-        let outcome = Outcome::FighterA;
-        MarketContract::resolve_market(env.clone(), market.oracle_address.clone(), outcome.clone());
-
-        let events = env.events().all();
-        assert_eq!(events.len(), 1);
-        let (topic, data_raw) = events[0].clone();
-        let data: MarketResolved = data_raw.try_into().unwrap();
-        assert_eq!(topic, Symbol::short("MarketResolved"));
-        assert_eq!(data.market_id, market.market_id);
-        assert_eq!(data.outcome, outcome);
-        assert_eq!(data.resolved_at, env.ledger().timestamp());
-    }
-
-    #[test]
-    fn test_resolve_market_emits_event_for_nocontest() {
-        let env = Env::default();
-        let market = default_market(&env, MarketStatus::Locked);
-        env.storage().set(&Symbol::short("MARKET_INFO"), &market);
-
-        let outcome = Outcome::NoContest;
-        MarketContract::resolve_market(env.clone(), market.oracle_address.clone(), outcome.clone());
-
-        let events = env.events().all();
-        assert_eq!(events.len(), 1);
-        let (topic, data_raw) = events[0].clone();
-        let data: MarketResolved = data_raw.try_into().unwrap();
-        assert_eq!(topic, Symbol::short("MarketResolved"));
-        assert_eq!(data.market_id, market.market_id);
-        assert_eq!(data.outcome, outcome);
-        assert_eq!(data.resolved_at, env.ledger().timestamp());
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::Address as _;
-
-    #[test]
-    fn place_bet_emits_bet_placed_event() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, MarketContract);
-        let client = MarketContractClient::new(&env, &contract_id);
-
-        let bettor = Address::generate(&env);
-        let oracle = Address::generate(&env);
-        let fighter_a = Fighter {
-            name: String::from_str(&env, "A"),
-            record: String::from_str(&env, "10-0"),
-            nationality: String::from_str(&env, "US"),
-            weight_class: String::from_str(&env, "Heavyweight"),
-        };
-        let fighter_b = Fighter {
-            name: String::from_str(&env, "B"),
-            record: String::from_str(&env, "9-1"),
-            nationality: String::from_str(&env, "MX"),
-            weight_class: String::from_str(&env, "Heavyweight"),
-        };
-        let market_id = Bytes::from_array(&[1u8; 32]);
-        client.initialize(
-            &Bytes::from_array(env, &[1u8; 32]),
-            &make_fighter(env, "Alpha"),
-            &make_fighter(env, "Beta"),
-            &1_000_000u64,
-            &900_000u64,
-            &oracle,
-            &factory,
-            &200u32,
-            &fee_col,
-        );
-    }
-
-    /// Demonstrates the test harness: register contract, initialize, read back state.
-    #[test]
-    fn test_harness_initialize_and_read() {
-        let env = create_test_env();
-        env.mock_all_auths();
-
-        let contract_id = env.register_contract(None, MarketContract);
-        let client      = MarketContractClient::new(&env, &contract_id);
-
-        initialize_market(&env, &client);
-
-        let market = client.get_market_info();
-        assert_eq!(market.pool_a, 0);
-        assert_eq!(market.pool_b, 0);
-        assert_eq!(market.total_pool, 0);
-        assert!(matches!(market.status, MarketStatus::Open));
-    }
-
-    #[test]
-    fn test_harness_get_pool_odds_empty_market() {
-        let env = create_test_env();
-        env.mock_all_auths();
-
-        let contract_id = env.register_contract(None, MarketContract);
-        let client      = MarketContractClient::new(&env, &contract_id);
-        initialize_market(&env, &client);
-
-        let (pool_a, pool_b, odds_a, odds_b) = client.get_pool_odds();
-        assert_eq!(pool_a, 0);
-        assert_eq!(pool_b, 0);
-        assert_eq!(odds_a, 5_000);
-        assert_eq!(odds_b, 5_000);
-    }
-
-    #[test]
-    fn test_claim_refund_after_cancellation() {
-        let env = create_test_env();
-        env.mock_all_auths();
-
-        let contract_id = env.register_contract(None, MarketContract);
-        let client      = MarketContractClient::new(&env, &contract_id);
-        initialize_market(&env, &client);
-
-        let bettor = create_test_address(&env);
-        let bet_id = Bytes::from_array(&env, &[9u8; 32]);
-        let amount = 500_000i128;
-
-        // Directly seed a bet and a Cancelled market in storage for refund testing.
-        env.as_contract(&contract_id, || {
-            let mut market: Market = env.storage().persistent().get(&DataKey::MarketInfo).unwrap();
-            market.status  = MarketStatus::Cancelled;
-            market.pool_a  = amount;
-            market.total_pool = amount;
-            env.storage().persistent().set(&DataKey::MarketInfo, &market);
-
-            let bet = Bet {
-                bet_id:    bet_id.clone(),
-                market_id: market.market_id.clone(),
-                bettor:    bettor.clone(),
-                side:      BetSide::FighterA,
-                amount,
-                placed_at: 0,
-                claimed:   false,
-            };
-            env.storage().persistent().set(&DataKey::Bet(bet_id.clone()), &bet);
-        });
-
-        let refund = client.claim_refund(&bettor, &bet_id);
-        assert_eq!(refund, amount);
-
-        // Second call must panic (already claimed).
-        let result = client.try_claim_refund(&bettor, &bet_id);
-        assert!(result.is_err());
-    }
+    use shared::event_parser::{
+        parse_market_cancelled_event, parse_market_resolved_event, parse_refund_claimed_event,
+    };
+    use shared::test_utils::create_test_address;
     use soroban_sdk::{
-        contract, contractimpl,
-        testutils::{Address as _, Events, Ledger},
-        Env, String, Symbol,
+        contract,
+        testutils::{Events, Ledger},
+        xdr::{ContractEventBody, ScVal},
+        TryFromVal, Val,
     };
 
-    // ─── Mock factory ──────────────────────────────────────────────────────────
+    const TEST_MIN_BET: i128 = 1_000;
+
+    // ─── Mock factory / treasury ───────────────────────────────────────────────
 
     #[contract]
     struct MockFactory;
@@ -1221,15 +1756,11 @@ mod test {
     #[contractimpl]
     impl MockFactory {
         pub fn __constructor(env: Env, admin: Address) {
-            env.storage()
-                .persistent()
-                .set(&Symbol::new(&env, "admin"), &admin);
+            env.storage().persistent().set(&Symbol::new(&env, "admin"), &admin);
         }
 
         pub fn get_config(env: Env) -> ProtocolConfig {
-            let admin: Address = env
-                .storage()
-                .persistent()
+            let admin: Address = env.storage().persistent()
                 .get(&Symbol::new(&env, "admin"))
                 .unwrap();
             ProtocolConfig {
@@ -1237,411 +1768,321 @@ mod test {
                 fee_collector: admin,
                 default_fee_bp: 200,
                 min_bet_amount: 100,
-                max_bet_amount: 100_000,
+                max_bet_amount: i128::MAX,
                 dispute_window_sec: 86_400,
+                claim_expiry_sec: 0,
                 paused: false,
             }
         }
+
+        pub fn record_oracle_resolution(_env: Env, _market_id: Bytes) {}
+
+        pub fn report_oracle_overturned(_env: Env, _market_id: Bytes) -> i128 {
+            0
+        }
     }
 
-    // ─── Setup ────────────────────────────────────────────────────────────────
+    /// Accepts every escrow movement without holding tokens.
+    #[contract]
+    struct MockTreasury;
 
-    fn make_fighters(env: &Env) -> (Fighter, Fighter) {
-        (
-            Fighter {
-                name: String::from_str(env, "Alpha"),
-                record: String::from_str(env, "10-0"),
-                nationality: String::from_str(env, "US"),
-                weight_class: String::from_str(env, "Heavyweight"),
-            },
-            Fighter {
-                name: String::from_str(env, "Beta"),
-                record: String::from_str(env, "9-1"),
-                nationality: String::from_str(env, "MX"),
-                weight_class: String::from_str(env, "Heavyweight"),
-            },
-        )
+    #[contractimpl]
+    impl MockTreasury {
+        pub fn deposit(_env: Env, _from_market: Address, _market_id: Bytes, _bettor: Address, _amount: i128) {}
+
+        pub fn release(_env: Env, _from_market: Address, _market_id: Bytes, _recipient: Address, _amount: i128) {}
+
+        pub fn collect_fee(_env: Env, _from_market: Address, _market_id: Bytes, _amount: i128) {}
+    }
+
+    // ─── Helpers ───────────────────────────────────────────────────────────────
+
+    fn make_fighter(env: &Env, name: &str) -> Fighter {
+        Fighter {
+            name:         String::from_str(env, name),
+            record:       String::from_str(env, "10-0"),
+            nationality:  String::from_str(env, "US"),
+            weight_class: String::from_str(env, "Heavyweight"),
+        }
     }
 
-    /// Returns (env, client, bettor, admin, betting_ends_at).
+    /// Registers the mocks and an initialized market whose betting closes
+    /// `betting_ends_at_offset` seconds from now.
+    ///
+    /// Returns (env, client, oracle, admin, betting_ends_at).
     fn setup(betting_ends_at_offset: u64) -> (Env, MarketContractClient<'static>, Address, Address, u64) {
         let env = Env::default();
         env.mock_all_auths();
 
-        let admin = Address::generate(&env);
+        let admin = create_test_address(&env);
         let factory_id = env.register(MockFactory, (admin.clone(),));
+        let treasury_id = env.register(MockTreasury, ());
+        let oracle = create_test_address(&env);
 
-        let bettor = Address::generate(&env);
-        let oracle = Address::generate(&env);
-        let fee_collector = Address::generate(&env);
-
-        let now = env.ledger().timestamp();
-        let betting_ends_at = now + betting_ends_at_offset;
-
-        let market_cid = env.register(MarketContract, ());
-        let client = MarketContractClient::new(&env, &market_cid);
-
-        let (fa, fb) = make_fighters(&env);
+        let betting_ends_at = env.ledger().timestamp() + betting_ends_at_offset;
+        let client = MarketContractClient::new(&env, &env.register(MarketContract, ()));
         client.initialize(
-            &Bytes::from_array(&env, &[1u8; 32]),
-            &fa,
-            &fb,
-            &(betting_ends_at + 1000),
+            &Bytes::from_array(&env, &[2u8; 32]),
+            &1u64,
+            &make_fighter(&env, "Fighter A"),
+            &make_fighter(&env, "Fighter B"),
+            &(betting_ends_at + 1_000),
             &betting_ends_at,
             &oracle,
             &factory_id,
             &200u32,
-            &fee_collector,
+            &create_test_address(&env),
+            &86_400u64,
+            &treasury_id,
+            &create_test_address(&env),
+            &false,
         );
 
-        (env, client, bettor, admin, betting_ends_at)
+        (env, client, oracle, admin, betting_ends_at)
     }
 
-    // ─── Issue 1: betting deadline ────────────────────────────────────────────
-
-    /// Full Draw flow: resolve_market(Draw) sets status=Cancelled, both sides
-    /// can claim full refunds, and no fee is deducted from either bettor.
-    #[test]
-    fn test_draw_outcome_full_refund_both_sides() {
-        let env = create_test_env();
-        env.mock_all_auths();
-
-        let contract_id = env.register_contract(None, MarketContract);
-        let client      = MarketContractClient::new(&env, &contract_id);
-        initialize_market(&env, &client);
-
-        let bettor_a    = create_test_address(&env);
-        let bettor_b    = create_test_address(&env);
-        let bet_id_a    = Bytes::from_array(&env, &[0xaau8; 32]);
-        let bet_id_b    = Bytes::from_array(&env, &[0xbbu8; 32]);
-        let amount_a    = 300_000i128;
-        let amount_b    = 700_000i128;
-        let oracle      = create_test_address(&env);
-
-        // Seed two bets and a Locked market directly in storage.
-        env.as_contract(&contract_id, || {
-            let mut market: Market = env.storage().persistent().get(&DataKey::MarketInfo).unwrap();
-            market.status     = MarketStatus::Locked;
-            market.pool_a     = amount_a;
-            market.pool_b     = amount_b;
-            market.total_pool = amount_a.checked_add(amount_b).expect("total_pool overflow");
-            market.oracle_address = oracle.clone();
-            env.storage().persistent().set(&DataKey::MarketInfo, &market);
-
-            let bet_a = Bet {
-                bet_id:    bet_id_a.clone(),
-                market_id: market.market_id.clone(),
-                bettor:    bettor_a.clone(),
-                side:      BetSide::FighterA,
-                amount:    amount_a,
-                placed_at: 0,
-                claimed:   false,
-            };
-            env.storage().persistent().set(&DataKey::Bet(bet_id_a.clone()), &bet_a);
-
-            let bet_b = Bet {
-                bet_id:    bet_id_b.clone(),
-                market_id: market.market_id.clone(),
-                bettor:    bettor_b.clone(),
-                side:      BetSide::FighterB,
-                amount:    amount_b,
-                placed_at: 0,
-                claimed:   false,
-            };
-            env.storage().persistent().set(&DataKey::Bet(bet_id_b.clone()), &bet_b);
-        });
-
-        // Resolving with Draw must flip status to Cancelled.
-        client.resolve_market(&oracle, &Outcome::Draw);
+    fn lock(env: &Env, client: &MarketContractClient, betting_ends_at: u64) {
+        env.ledger().with_mut(|l| l.timestamp = betting_ends_at);
+        assert_eq!(client.get_market_info().status, MarketStatus::Locked);
+    }
 
+    /// Lets the dispute window lapse and finalizes the resolution.
+    fn finalize(env: &Env, client: &MarketContractClient) {
         let market = client.get_market_info();
-        assert!(
-            matches!(market.status, MarketStatus::Cancelled),
-            "Draw outcome must set status to Cancelled"
-        );
-        assert!(matches!(market.outcome, Some(Outcome::Draw)));
-
-        // Both sides must receive full refunds with no fee.
-        let refund_a = client.claim_refund(&bettor_a, &bet_id_a);
-        let refund_b = client.claim_refund(&bettor_b, &bet_id_b);
-
-        assert_eq!(refund_a, amount_a, "bettor_a should receive full refund");
-        assert_eq!(refund_b, amount_b, "bettor_b should receive full refund");
-
-        // Neither bettor can claim again.
-        assert!(client.try_claim_refund(&bettor_a, &bet_id_a).is_err());
-        assert!(client.try_claim_refund(&bettor_b, &bet_id_b).is_err());
+        env.ledger().with_mut(|l| l.timestamp = market.resolved_at + market.dispute_window_sec + 1);
+        client.finalize_resolution(&None);
     }
 
-    #[test]
-    fn test_draw_via_claim_winnings_rejected() {
-        // After a Draw, the market is Cancelled so claim_winnings must fail.
-        let env = create_test_env();
-        env.mock_all_auths();
-
-        let contract_id = env.register_contract(None, MarketContract);
-        let client      = MarketContractClient::new(&env, &contract_id);
-        initialize_market(&env, &client);
-
-        let bettor = create_test_address(&env);
-        let bet_id = Bytes::from_array(&env, &[0xddu8; 32]);
-        let oracle = create_test_address(&env);
-
-        env.as_contract(&contract_id, || {
-            let mut market: Market = env.storage().persistent().get(&DataKey::MarketInfo).unwrap();
-            market.status = MarketStatus::Locked;
-            market.oracle_address = oracle.clone();
-            env.storage().persistent().set(&DataKey::MarketInfo, &market);
-
-            let bet = Bet {
-                bet_id:    bet_id.clone(),
-                market_id: market.market_id.clone(),
-                bettor:    bettor.clone(),
-                side:      BetSide::FighterA,
-                amount:    100_000,
-                placed_at: 0,
-                claimed:   false,
-            };
-            env.storage().persistent().set(&DataKey::Bet(bet_id.clone()), &bet);
+    fn set_status(env: &Env, client: &MarketContractClient, status: MarketStatus) {
+        env.as_contract(&client.address, || {
+            let mut market = MarketContract::read_stored_market(env);
+            market.status = status;
+            MarketContract::write_market(env, &market);
         });
-
-        client.resolve_market(&oracle, &Outcome::Draw);
-
-        // claim_winnings requires status==Resolved; Draw→Cancelled so this must fail.
-        assert!(client.try_claim_winnings(&bettor, &bet_id).is_err());
-    fn test_bet_exactly_at_deadline_succeeds() {
-        let (env, client, bettor, _admin, betting_ends_at) = setup(1000);
-
-        env.ledger().with_mut(|l| l.timestamp = betting_ends_at);
-        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &500i128);
-
-        let bet = client.get_bet(&bet_id);
-        assert_eq!(bet.amount, 500);
-        assert_eq!(bet.bettor, bettor);
     }
 
-    #[test]
-    #[should_panic(expected = "betting period has ended")]
-    fn test_bet_one_second_after_deadline_panics() {
-        let (env, client, bettor, _admin, betting_ends_at) = setup(1000);
-
-        env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
-        client.place_bet(&bettor, &BetSide::FighterA, &500i128);
+    /// Topics and data of the last event named `name` the market emitted.
+    fn last_event(env: &Env, client: &MarketContractClient, name: &str) -> (Vec<Val>, Val) {
+        let name = ScVal::Symbol(name.try_into().unwrap());
+        let event = env.events().all().filter_by_contract(&client.address).events().iter()
+            .rev()
+            .find_map(|e| match &e.body {
+                ContractEventBody::V0(body) if body.topics.first() == Some(&name) => Some(body.clone()),
+                _ => None,
+            })
+            .expect("event not emitted");
+        let mut topics = Vec::new(env);
+        for topic in event.topics.iter() {
+            topics.push_back(Val::try_from_val(env, topic).unwrap());
+        }
+        (topics, Val::try_from_val(env, &event.data).unwrap())
     }
 
-    // ─── Issue 2: cancel_market ───────────────────────────────────────────────
+    // ─── Initialization and views ──────────────────────────────────────────────
 
     #[test]
-    fn test_cancel_open_market_succeeds() {
-        let (env, client, _bettor, admin, _ends_at) = setup(1000);
-        client.cancel_market(&admin);
+    fn test_harness_initialize_and_read() {
+        let (_env, client, _oracle, _admin, _ends_at) = setup(1_000);
 
         let market = client.get_market_info();
-        assert_eq!(market.status, MarketStatus::Cancelled);
+        assert_eq!(market.pool_a, 0);
+        assert_eq!(market.pool_b, 0);
+        assert_eq!(market.total_pool, 0);
+        assert_eq!(market.status, MarketStatus::Open);
+        assert_eq!(market.outcome, OptionalOutcome::None);
     }
 
     #[test]
-    fn test_cancel_locked_market_succeeds() {
-        let (env, client, _bettor, admin, _ends_at) = setup(1000);
+    fn test_harness_get_pool_odds_empty_market() {
+        let (_env, client, _oracle, _admin, _ends_at) = setup(1_000);
 
-        // Force status to Locked directly in storage
-        env.as_contract(&client.address, || {
-            let mut m: Market = env
-                .storage()
-                .persistent()
-                .get(&DataKey::MarketInfo)
-                .unwrap();
-            m.status = MarketStatus::Locked;
-            env.storage().persistent().set(&DataKey::MarketInfo, &m);
-        });
+        let (pool_a, pool_b, pool_draw, odds_a, odds_b, odds_draw) = client.get_pool_odds_with_draw();
+        assert_eq!(pool_a, 0);
+        assert_eq!(pool_b, 0);
+        assert_eq!(pool_draw, 0);
+        assert_eq!(odds_a, 5_000);
+        assert_eq!(odds_b, 5_000);
+        assert_eq!(odds_draw, 0);
+    }
+
+    #[test]
+    fn test_get_market_info_returns_market() {
+        let (env, client, _oracle, _admin, _ends_at) = setup(1_000);
 
-        client.cancel_market(&admin);
         let market = client.get_market_info();
-        assert_eq!(market.status, MarketStatus::Cancelled);
+        assert_eq!(market.market_id, Bytes::from_array(&env, &[2u8; 32]));
+        assert_eq!(market.fighter_a.name, String::from_str(&env, "Fighter A"));
+        assert_eq!(market.fighter_b.name, String::from_str(&env, "Fighter B"));
+        assert_eq!(market.status, MarketStatus::Open);
+        assert_eq!(market.total_pool, 0);
     }
 
     #[test]
-    #[should_panic(expected = "cannot cancel: market already resolved or cancelled")]
-    fn test_cancel_resolved_market_panics() {
-        let (env, client, _bettor, admin, _ends_at) = setup(1000);
+    fn test_get_bet_returns_bet() {
+        let (_env, client, _oracle, _admin, _ends_at) = setup(1_000);
+        let bettor = create_test_address(&client.env);
 
-        env.as_contract(&client.address, || {
-            let mut m: Market = env
-                .storage()
-                .persistent()
-                .get(&DataKey::MarketInfo)
-                .unwrap();
-            m.status = MarketStatus::Resolved;
-            env.storage().persistent().set(&DataKey::MarketInfo, &m);
-        });
+        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &TEST_MIN_BET);
+        let bet = client.get_bet(&bet_id);
+        assert_eq!(bet.bet_id, bet_id);
+        assert_eq!(bet.bettor, bettor);
+        assert_eq!(bet.side, BetSide::FighterA);
+        assert_eq!(bet.amount, TEST_MIN_BET);
+    }
 
-        client.cancel_market(&admin);
+    #[test]
+    #[should_panic(expected = "bet not found")]
+    fn test_get_bet_panics_if_not_found() {
+        let (env, client, _oracle, _admin, _ends_at) = setup(1_000);
+        client.get_bet(&Bytes::from_array(&env, &[0u8; 32]));
     }
 
+    // ─── place_bet ─────────────────────────────────────────────────────────────
+
     #[test]
-    #[should_panic(expected = "cannot cancel: market already resolved or cancelled")]
-    fn test_cancel_already_cancelled_market_panics() {
-        let (env, client, _bettor, admin, _ends_at) = setup(1000);
+    fn place_bet_emits_bet_placed_event() {
+        let (env, client, _oracle, _admin, _ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
 
-        env.as_contract(&client.address, || {
-            let mut m: Market = env
-                .storage()
-                .persistent()
-                .get(&DataKey::MarketInfo)
-                .unwrap();
-            m.status = MarketStatus::Cancelled;
-            env.storage().persistent().set(&DataKey::MarketInfo, &m);
-        });
+        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &TEST_MIN_BET);
 
-        client.cancel_market(&admin);
+        let (topics, data) = last_event(&env, &client, events::BET_PLACED);
+        let event = shared::event_parser::parse_bet_placed_event(&env, &topics, &data).unwrap();
+        assert_eq!(event.market_id, 1);
+        assert_eq!(event.bet_id, bet_id);
+        assert_eq!(event.bettor, bettor);
+        assert_eq!(event.amount, TEST_MIN_BET);
     }
 
     #[test]
-    fn test_cancel_market_emits_event() {
-        let (env, client, _bettor, admin, _ends_at) = setup(1000);
-        client.cancel_market(&admin);
+    fn test_bet_one_second_before_deadline_succeeds() {
+        let (env, client, _oracle, _admin, betting_ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
+
+        env.ledger().with_mut(|l| l.timestamp = betting_ends_at - 1);
+        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &500i128);
 
-        // At least one event must be emitted (the MarketCancelled event).
-        let events = env.events().all();
-        assert!(!events.events().is_empty(), "MarketCancelled event not emitted");
+        let bet = client.get_bet(&bet_id);
+        assert_eq!(bet.amount, 500);
+        assert_eq!(bet.bettor, bettor);
     }
 
+    /// Betting closes at `betting_ends_at` itself: the lazy lock flips the
+    /// market before `place_bet` looks at it.
     #[test]
-    fn test_all_bettors_can_claim_refund_after_cancel() {
-        let (env, client, bettor, admin, _ends_at) = setup(1000);
+    #[should_panic(expected = "market not open")]
+    fn test_bet_exactly_at_deadline_panics() {
+        let (env, client, _oracle, _admin, betting_ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
 
-        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &500i128);
-        client.cancel_market(&admin);
-        let refund = client.claim_refund(&bettor, &bet_id);
-        assert_eq!(refund, 500);
+        env.ledger().with_mut(|l| l.timestamp = betting_ends_at);
+        client.place_bet(&bettor, &BetSide::FighterA, &500i128);
     }
 
     #[test]
-    #[should_panic(expected = "already claimed")]
-    fn test_claim_refund_twice_panics() {
-        let (env, client, bettor, admin, _ends_at) = setup(1000);
+    fn test_multiple_bets_same_bettor_tracks_correctly() {
+        let (env, client, _oracle, _admin, _ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
 
-        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &500i128);
-        client.cancel_market(&admin);
-        client.claim_refund(&bettor, &bet_id);
-        client.claim_refund(&bettor, &bet_id);
-    }
+        let id1 = client.place_bet(&bettor, &BetSide::FighterA, &TEST_MIN_BET);
+        let id2 = client.place_bet(&bettor, &BetSide::FighterB, &(TEST_MIN_BET * 2));
+        let id3 = client.place_bet(&bettor, &BetSide::FighterA, &(TEST_MIN_BET * 3));
 
-    // ─── Issue 3: auth — unauthorized calls must panic ─────────────────────────
+        let bets = client.get_bets_by_address(&bettor);
+        assert_eq!(bets.len(), 3);
+        assert_eq!(bets.get(0).unwrap().bet_id, id1);
+        assert_eq!(bets.get(1).unwrap().bet_id, id2);
+        assert_eq!(bets.get(2).unwrap().bet_id, id3);
 
-    #[test]
-    #[should_panic]
-    fn test_resolve_dispute_unauthorized_panics() {
-        let env = Env::default();
-        // Do NOT call env.mock_all_auths() — auth check must fire
+        let market = client.get_market_info();
+        assert_eq!(market.pool_a, TEST_MIN_BET * 4);
+        assert_eq!(market.pool_b, TEST_MIN_BET * 2);
+    }
 
-        let admin = Address::generate(&env);
-        let factory_id = env.register(MockFactory, (admin.clone(),));
-        let oracle = Address::generate(&env);
-        let fee_collector = Address::generate(&env);
-        let (fa, fb) = make_fighters(&env);
+    #[test]
+    fn test_overflow_safe_arithmetic_large_values() {
+        let (env, client, _oracle, _admin, _ends_at) = setup(1_000);
+        let large_amount_a: i128 = 50_000_000_000_000;
+        let large_amount_b: i128 = 75_000_000_000_000;
 
-        let market_cid = env.register(MarketContract, ());
-        let client = MarketContractClient::new(&env, &market_cid);
+        client.place_bet(&create_test_address(&env), &BetSide::FighterA, &large_amount_a);
+        client.place_bet(&create_test_address(&env), &BetSide::FighterB, &large_amount_b);
 
-        // initialize has no require_auth, so no mocking needed.
-        client.initialize(
-            &Bytes::from_array(&env, &[1u8; 32]),
-            &fa,
-            &fb,
-            &2000u64,
-            &1000u64,
-            &oracle,
-            &factory_id,
-            &200u32,
-            &fee_collector,
-        );
+        let market = client.get_market_info();
+        assert_eq!(market.pool_a, large_amount_a);
+        assert_eq!(market.pool_b, large_amount_b);
+        assert_eq!(market.total_pool, large_amount_a + large_amount_b);
+    }
 
-        // Force Disputed status directly in storage (no auth needed).
-        env.as_contract(&market_cid, || {
-            let mut m: Market = env
-                .storage()
-                .persistent()
-                .get(&DataKey::MarketInfo)
-                .unwrap();
-            m.status = MarketStatus::Disputed;
-            env.storage().persistent().set(&DataKey::MarketInfo, &m);
-        });
+    #[test]
+    #[should_panic(expected = "pool_a overflow")]
+    fn test_pool_overflow_protection() {
+        let (env, client, _oracle, _admin, _ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
 
-        // Attacker never received authorization — require_auth() panics.
-        let attacker = Address::generate(&env);
-        client.resolve_dispute(&attacker, &Outcome::FighterA);
+        client.place_bet(&bettor, &BetSide::FighterA, &i128::MAX);
+        client.place_bet(&bettor, &BetSide::FighterA, &TEST_MIN_BET);
     }
 
-    // ── get_pool_odds ───────────────────────────────────────────────────────
+    // ─── get_pool_odds ─────────────────────────────────────────────────────────
 
     #[test]
     fn test_get_pool_odds_zero_pool_returns_even_split() {
-        let (env, _, _) = setup_test_env();
-        let (pool_a, pool_b, odds_a, odds_b) = MarketContract::get_pool_odds(env.clone());
-        assert_eq!(pool_a, 0);
-        assert_eq!(pool_b, 0);
-        assert_eq!(odds_a, 5000);
-        assert_eq!(odds_b, 5000);
+        let (_env, client, _oracle, _admin, _ends_at) = setup(1_000);
+        assert_eq!(client.get_pool_odds(), (0, 0, 5_000, 5_000));
     }
 
     #[test]
     fn test_get_pool_odds_with_uneven_pools() {
-        let (env, bettor, _) = setup_test_env();
+        let (env, client, _oracle, _admin, _ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
 
-        // Place bets: 300 on A, 700 on B
-        MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterA, 300);
-        MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterB, 700);
+        client.place_bet(&bettor, &BetSide::FighterA, &300i128);
+        client.place_bet(&bettor, &BetSide::FighterB, &700i128);
 
-        let (pool_a, pool_b, odds_a, odds_b) = MarketContract::get_pool_odds(env.clone());
-        assert_eq!(pool_a, 300);
-        assert_eq!(pool_b, 700);
-        // odds_a = (300 * 10000) / 1000 = 3000
-        assert_eq!(odds_a, 3000);
-        assert_eq!(odds_b, 7000);
+        // odds_a = 300 * 10_000 / 1_000
+        assert_eq!(client.get_pool_odds(), (300, 700, 3_000, 7_000));
     }
 
     #[test]
     fn test_get_pool_odds_equal_pools() {
-        let (env, bettor, _) = setup_test_env();
+        let (env, client, _oracle, _admin, _ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
 
-        MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterA, 500);
-        MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterB, 500);
+        client.place_bet(&bettor, &BetSide::FighterA, &500i128);
+        client.place_bet(&bettor, &BetSide::FighterB, &500i128);
 
-        let (_, _, odds_a, odds_b) = MarketContract::get_pool_odds(env.clone());
-        assert_eq!(odds_a, 5000);
-        assert_eq!(odds_b, 5000);
+        let (_, _, odds_a, odds_b) = client.get_pool_odds();
+        assert_eq!((odds_a, odds_b), (5_000, 5_000));
     }
 
     #[test]
     fn test_get_pool_odds_one_side_only() {
-        let (env, bettor, _) = setup_test_env();
+        let (env, client, _oracle, _admin, _ends_at) = setup(1_000);
 
-        MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterA, 1000);
+        client.place_bet(&create_test_address(&env), &BetSide::FighterA, &TEST_MIN_BET);
 
-        let (_, _, odds_a, odds_b) = MarketContract::get_pool_odds(env.clone());
-        assert_eq!(odds_a, 10000);
-        assert_eq!(odds_b, 0);
+        let (_, _, odds_a, odds_b) = client.get_pool_odds();
+        assert_eq!((odds_a, odds_b), (10_000, 0));
     }
 
-    // ── get_bets_by_address ─────────────────────────────────────────────────
+    // ─── get_bets_by_address ───────────────────────────────────────────────────
 
     #[test]
     fn test_get_bets_by_address_empty_when_no_bets() {
-        let (env, _, _) = setup_test_env();
-        let bettor = Address::new(&env, &[99u8; 32]);
-        let bets = MarketContract::get_bets_by_address(env.clone(), bettor);
-        assert_eq!(bets.len(), 0);
+        let (env, client, _oracle, _admin, _ends_at) = setup(1_000);
+        assert_eq!(client.get_bets_by_address(&create_test_address(&env)).len(), 0);
     }
 
     #[test]
     fn test_get_bets_by_address_returns_placed_bets() {
-        let (env, bettor, _) = setup_test_env();
+        let (env, client, _oracle, _admin, _ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
 
-        let bet_id_1 = MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterA, TEST_MIN_BET);
-        let bet_id_2 = MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterB, TEST_MIN_BET * 2);
+        let bet_id_1 = client.place_bet(&bettor, &BetSide::FighterA, &TEST_MIN_BET);
+        let bet_id_2 = client.place_bet(&bettor, &BetSide::FighterB, &(TEST_MIN_BET * 2));
 
-        let bets = MarketContract::get_bets_by_address(env.clone(), bettor.clone());
+        let bets = client.get_bets_by_address(&bettor);
         assert_eq!(bets.len(), 2);
 
         let bet_1 = bets.get(0).unwrap();
@@ -1657,412 +2098,346 @@ mod test {
 
     #[test]
     fn test_get_bets_by_address_returns_bets_for_specific_address() {
-        let (env, bettor_a, _) = setup_test_env();
-        let bettor_b = Address::new(&env, &[5u8; 32]);
+        let (env, client, _oracle, _admin, _ends_at) = setup(1_000);
+        let bettor_a = create_test_address(&env);
+        let bettor_b = create_test_address(&env);
 
-        MarketContract::place_bet(env.clone(), bettor_a.clone(), BetSide::FighterA, TEST_MIN_BET);
-        MarketContract::place_bet(env.clone(), bettor_b.clone(), BetSide::FighterB, TEST_MIN_BET);
+        client.place_bet(&bettor_a, &BetSide::FighterA, &TEST_MIN_BET);
+        client.place_bet(&bettor_b, &BetSide::FighterB, &TEST_MIN_BET);
 
-        let bets_a = MarketContract::get_bets_by_address(env.clone(), bettor_a);
+        let bets_a = client.get_bets_by_address(&bettor_a);
         assert_eq!(bets_a.len(), 1);
         assert_eq!(bets_a.get(0).unwrap().side, BetSide::FighterA);
 
-        let bets_b = MarketContract::get_bets_by_address(env.clone(), bettor_b);
+        let bets_b = client.get_bets_by_address(&bettor_b);
         assert_eq!(bets_b.len(), 1);
         assert_eq!(bets_b.get(0).unwrap().side, BetSide::FighterB);
     }
 
-    // ── Full lifecycle ──────────────────────────────────────────────────────
+    // ─── resolve_market ────────────────────────────────────────────────────────
 
-    fn resolve_market_via_storage(env: &Env, outcome: Outcome) {
-        let mut market: Market = env.storage().persistent().get(&DataKey::MarketInfo)
-            .expect("market not initialized");
-        market.status = MarketStatus::Resolved;
-        market.outcome = Some(outcome);
-        market.resolved_at = env.ledger().timestamp();
-        env.storage().persistent().set(&DataKey::MarketInfo, &market);
-    }
+    #[test]
+    fn test_resolve_market_emits_event() {
+        let (env, client, oracle, _admin, betting_ends_at) = setup(1_000);
+        client.place_bet(&create_test_address(&env), &BetSide::FighterA, &TEST_MIN_BET);
+        lock(&env, &client, betting_ends_at);
 
-    fn lock_market_via_storage(env: &Env) {
-        let mut market: Market = env.storage().persistent().get(&DataKey::MarketInfo)
-            .expect("market not initialized");
-        market.status = MarketStatus::Locked;
-        env.storage().persistent().set(&DataKey::MarketInfo, &market);
+        client.resolve_market(&oracle, &Outcome::FighterA);
+
+        let (topics, data) = last_event(&env, &client, events::MARKET_RESOLVED);
+        let event = parse_market_resolved_event(&env, &topics, &data).unwrap();
+        assert_eq!(event.market_id, 1);
+        assert_eq!(event.outcome, Outcome::FighterA);
+        assert_eq!(event.resolved_at, env.ledger().timestamp());
+        assert_eq!(client.get_market_info().outcome, OptionalOutcome::Some(Outcome::FighterA));
     }
 
     #[test]
-    fn test_full_lifecycle_single_bettor_wins() {
-        let (env, bettor, _) = setup_test_env();
-
-        // Place bet on FighterA
-        let bet_id = MarketContract::place_bet(
-            env.clone(),
-            bettor.clone(),
-            BetSide::FighterA,
-            TEST_MIN_BET,
-        );
+    fn test_resolve_market_emits_event_for_nocontest() {
+        let (env, client, oracle, _admin, betting_ends_at) = setup(1_000);
+        lock(&env, &client, betting_ends_at);
 
-        // Verify pools
-        let (pool_a, pool_b, _, _) = MarketContract::get_pool_odds(env.clone());
-        assert_eq!(pool_a, TEST_MIN_BET);
-        assert_eq!(pool_b, 0);
+        client.resolve_market(&oracle, &Outcome::NoContest);
 
-        // Lock market (simulate)
-        lock_market_via_storage(&env);
+        let (topics, data) = last_event(&env, &client, events::MARKET_RESOLVED);
+        let event = parse_market_resolved_event(&env, &topics, &data).unwrap();
+        assert_eq!(event.outcome, Outcome::NoContest);
+        assert_eq!(client.get_market_info().status, MarketStatus::Cancelled);
+    }
 
-        // Resolve with FighterA winning
-        resolve_market_via_storage(&env, Outcome::FighterA);
+    /// Full Draw flow: resolve_market(Draw) cancels a market without draw
+    /// betting, and both sides claim their full stake with no fee.
+    #[test]
+    fn test_draw_outcome_full_refund_both_sides() {
+        let (env, client, oracle, _admin, betting_ends_at) = setup(1_000);
+        let bettor_a = create_test_address(&env);
+        let bettor_b = create_test_address(&env);
+        let bet_id_a = client.place_bet(&bettor_a, &BetSide::FighterA, &300_000i128);
+        let bet_id_b = client.place_bet(&bettor_b, &BetSide::FighterB, &700_000i128);
+        lock(&env, &client, betting_ends_at);
 
-        // Claim winnings
-        let payout = MarketContract::claim_winnings(
-            env.clone(),
-            bettor.clone(),
-            bet_id.clone(),
-        );
+        client.resolve_market(&oracle, &Outcome::Draw);
 
-        // Since bettor has 100% of winning pool, payout should be (100 * total_pool * (10000-fee)) / (100 * 10000)
-        let expected_payout = TEST_MIN_BET * (10000 - 200) / 10000;
-        assert_eq!(payout, expected_payout, "Payout should be total minus fee");
+        let market = client.get_market_info();
+        assert_eq!(market.status, MarketStatus::Cancelled);
+        assert_eq!(market.outcome, OptionalOutcome::Some(Outcome::Draw));
 
-        // Verify bet is tracked in address index
-        let bets = MarketContract::get_bets_by_address(env.clone(), bettor);
-        assert_eq!(bets.len(), 1);
+        assert_eq!(client.claim_refund(&bettor_a, &bet_id_a), 300_000);
+        assert_eq!(client.claim_refund(&bettor_b, &bet_id_b), 700_000);
+        assert!(client.try_claim_refund(&bettor_a, &bet_id_a).is_err());
+        assert!(client.try_claim_refund(&bettor_b, &bet_id_b).is_err());
     }
 
     #[test]
-    fn test_full_lifecycle_two_bettors_different_sides() {
-        let (env, bettor_a, _) = setup_test_env();
-        let bettor_b = Address::new(&env, &[5u8; 32]);
-
-        // Bettor A bets 300 on FighterA
-        MarketContract::place_bet(env.clone(), bettor_a.clone(), BetSide::FighterA, 300);
-        // Bettor B bets 700 on FighterB
-        MarketContract::place_bet(env.clone(), bettor_b.clone(), BetSide::FighterB, 700);
-
-        // Verify odds
-        let (_, _, odds_a, odds_b) = MarketContract::get_pool_odds(env.clone());
-        assert_eq!(odds_a, 3000);
-        assert_eq!(odds_b, 7000);
-
-        // Lock and resolve with FighterA winning
-        lock_market_via_storage(&env);
-        resolve_market_via_storage(&env, Outcome::FighterA);
-
-        // Bettor A claims (winning pool = 300, bettor has all of it)
-        let payout_a = MarketContract::claim_winnings(
-            env.clone(),
-            bettor_a.clone(),
-            MarketContract::get_bets_by_address(env.clone(), bettor_a.clone()).get(0).unwrap().bet_id,
-        );
-        // Bettor A gets: (300 * 1000 * 9800) / (300 * 10000) = 980
-        assert_eq!(payout_a, 300 * 1000 * (10000 - 200) / (300 * 10000)); // = 980
+    fn test_draw_via_claim_winnings_rejected() {
+        let (env, client, oracle, _admin, betting_ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
+        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &100_000i128);
+        lock(&env, &client, betting_ends_at);
 
-        // Bettor B tries to claim — should panic (losing side)
-        let bet_id_b = MarketContract::get_bets_by_address(env.clone(), bettor_b.clone()).get(0).unwrap().bet_id;
-        let result = std::panic::catch_unwind(|| {
-            MarketContract::claim_winnings(env.clone(), bettor_b.clone(), bet_id_b);
-        });
-        assert!(result.is_err(), "Losing bettor should not be able to claim");
+        client.resolve_market(&oracle, &Outcome::Draw);
+
+        // A Draw cancels the market, so there are no winnings to claim.
+        assert!(client.try_claim_winnings(&bettor, &bet_id).is_err());
     }
 
+    // ─── cancel_market ─────────────────────────────────────────────────────────
+
     #[test]
-    fn test_full_lifecycle_cancelled_market_refund() {
-        let (env, bettor, _) = setup_test_env();
+    fn test_cancel_open_market_succeeds() {
+        let (_env, client, _oracle, admin, _ends_at) = setup(1_000);
+        client.cancel_market(&admin);
+        assert_eq!(client.get_market_info().status, MarketStatus::Cancelled);
+    }
 
-        let bet_id = MarketContract::place_bet(
-            env.clone(),
-            bettor.clone(),
-            BetSide::FighterA,
-            TEST_MIN_BET,
-        );
+    #[test]
+    fn test_cancel_locked_market_succeeds() {
+        let (env, client, _oracle, admin, betting_ends_at) = setup(1_000);
+        lock(&env, &client, betting_ends_at);
 
-        // Cancel market
-        let mut market: Market = env.storage().persistent().get(&DataKey::MarketInfo)
-            .expect("market not initialized");
-        market.status = MarketStatus::Cancelled;
-        market.outcome = Some(Outcome::NoContest);
-        env.storage().persistent().set(&DataKey::MarketInfo, &market);
+        client.cancel_market(&admin);
+        assert_eq!(client.get_market_info().status, MarketStatus::Cancelled);
+    }
 
-        // Claim refund
-        let refund = MarketContract::claim_refund(
-            env.clone(),
-            bettor.clone(),
-            bet_id.clone(),
-        );
-        assert_eq!(refund, TEST_MIN_BET, "Full refund on cancellation");
+    #[test]
+    #[should_panic(expected = "cannot cancel: market already resolved or cancelled")]
+    fn test_cancel_resolved_market_panics() {
+        let (env, client, oracle, admin, betting_ends_at) = setup(1_000);
+        client.place_bet(&create_test_address(&env), &BetSide::FighterA, &TEST_MIN_BET);
+        lock(&env, &client, betting_ends_at);
+        client.resolve_market(&oracle, &Outcome::FighterA);
 
-        // Verify bets index still works
-        let bets = MarketContract::get_bets_by_address(env.clone(), bettor);
-        assert_eq!(bets.len(), 1);
+        client.cancel_market(&admin);
     }
 
     #[test]
-    fn test_get_market_info_returns_market() {
-        let (env, _, _) = setup_test_env();
-        let market: Market = MarketContract::get_market_info(env.clone());
-        assert_eq!(market.market_id, Bytes::from_slice(&env, &[2u8; 32]));
-        assert_eq!(market.fighter_a.name, String::from_str(&env, "Fighter A"));
-        assert_eq!(market.fighter_b.name, String::from_str(&env, "Fighter B"));
-        assert_eq!(market.status, MarketStatus::Open);
-        assert_eq!(market.pool_a, 0);
-        assert_eq!(market.pool_b, 0);
-        assert_eq!(market.total_pool, 0);
+    #[should_panic(expected = "cannot cancel: market already resolved or cancelled")]
+    fn test_cancel_already_cancelled_market_panics() {
+        let (_env, client, _oracle, admin, _ends_at) = setup(1_000);
+        client.cancel_market(&admin);
+        client.cancel_market(&admin);
     }
 
     #[test]
-    fn test_get_bet_returns_bet() {
-        let (env, bettor, _) = setup_test_env();
-        let bet_id = MarketContract::place_bet(
-            env.clone(),
-            bettor.clone(),
-            BetSide::FighterA,
-            TEST_MIN_BET,
-        );
-        let bet: Bet = MarketContract::get_bet(env.clone(), bet_id.clone());
-        assert_eq!(bet.bet_id, bet_id);
-        assert_eq!(bet.bettor, bettor);
-        assert_eq!(bet.side, BetSide::FighterA);
-        assert_eq!(bet.amount, TEST_MIN_BET);
+    fn test_cancel_market_emits_event() {
+        let (env, client, _oracle, admin, _ends_at) = setup(1_000);
+        client.cancel_market(&admin);
+
+        let (topics, data) = last_event(&env, &client, events::MARKET_CANCELLED);
+        let event = parse_market_cancelled_event(&env, &topics, &data).unwrap();
+        assert_eq!(event.market_id, 1);
+        assert_eq!(event.cancelled_at, env.ledger().timestamp());
     }
 
     #[test]
-    fn test_get_bet_panics_if_not_found() {
-        let (env, _, _) = setup_test_env();
-        let fake_id = Bytes::from_array(&env, &[0u8; 32]);
-        let result = std::panic::catch_unwind(|| {
-            MarketContract::get_bet(env.clone(), fake_id);
+    fn test_cancel_by_factory_needs_factory_auth_and_uses_given_config() {
+        let (env, client, _oracle, admin, _ends_at) = setup(1_000);
+        let factory: Address = env.as_contract(&client.address, || {
+            env.storage().persistent().get(&DataKey::Factory).unwrap()
+        });
+        let mut config = MockFactoryClient::new(&env, &factory).get_config();
+        config.admin = admin;
+        config.claim_expiry_sec = 3_600;
+
+        client.cancel_by_factory(&config);
+        assert_eq!(env.auths()[0].0, factory);
+        assert_eq!(client.get_market_info().status, MarketStatus::Cancelled);
+        let deadline: u64 = env.as_contract(&client.address, || {
+            env.storage().persistent().get(&DataKey::ClaimDeadline).unwrap()
         });
-        assert!(result.is_err(), "get_bet should panic for non-existent bet");
+        assert_eq!(deadline, env.ledger().timestamp() + 3_600);
     }
 
-    #[test]
-    fn test_multiple_bets_same_bettor_tracks_correctly() {
-        let (env, bettor, _) = setup_test_env();
-
-        let id1 = MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterA, TEST_MIN_BET);
-        let id2 = MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterB, TEST_MIN_BET * 2);
-        let id3 = MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterA, TEST_MIN_BET * 3);
+    // ─── claim_refund ──────────────────────────────────────────────────────────
 
-        let bets = MarketContract::get_bets_by_address(env.clone(), bettor);
-        assert_eq!(bets.len(), 3);
-        assert_eq!(bets.get(0).unwrap().bet_id, id1);
-        assert_eq!(bets.get(1).unwrap().bet_id, id2);
-        assert_eq!(bets.get(2).unwrap().bet_id, id3);
+    #[test]
+    fn test_claim_refund_after_cancellation() {
+        let (env, client, _oracle, admin, _ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
+        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &500_000i128);
+        client.cancel_market(&admin);
 
-        // Verify total pools
-        let market: Market = env.storage().persistent().get(&DataKey::MarketInfo).unwrap();
-        assert_eq!(market.pool_a, TEST_MIN_BET + TEST_MIN_BET * 3);
-        assert_eq!(market.pool_b, TEST_MIN_BET * 2);
+        assert_eq!(client.claim_refund(&bettor, &bet_id), 500_000);
+        assert!(client.try_claim_refund(&bettor, &bet_id).is_err());
     }
 
-    // ─── claim_refund() Tests (Issue #859) ─────────────────────────────────────
-
     #[test]
-    fn test_claim_refund_cancelled_market_returns_full_amount() {
-        let (env, bettor, _) = setup_test_env();
+    fn test_all_bettors_can_claim_refund_after_cancel() {
+        let (env, client, _oracle, admin, _ends_at) = setup(1_000);
+        let bettors = [create_test_address(&env), create_test_address(&env)];
+        let bet_a = client.place_bet(&bettors[0], &BetSide::FighterA, &500i128);
+        let bet_b = client.place_bet(&bettors[1], &BetSide::FighterB, &800i128);
+        client.cancel_market(&admin);
 
-        // Place a bet
-        let bet_id = MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterA, TEST_MIN_BET);
+        assert_eq!(client.claim_refund(&bettors[0], &bet_a), 500);
+        assert_eq!(client.claim_refund(&bettors[1], &bet_b), 800);
+    }
 
-        // Cancel the market
-        let mut market: Market = env.storage().persistent().get(&DataKey::MarketInfo).unwrap();
-        market.status = MarketStatus::Cancelled;
-        env.storage().persistent().set(&DataKey::MarketInfo, &market);
+    #[test]
+    #[should_panic(expected = "already claimed")]
+    fn test_claim_refund_twice_panics() {
+        let (env, client, _oracle, admin, _ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
+        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &500i128);
+        client.cancel_market(&admin);
 
-        // Claim refund
-        let refund = MarketContract::claim_refund(env.clone(), bettor.clone(), bet_id.clone());
+        client.claim_refund(&bettor, &bet_id);
+        client.claim_refund(&bettor, &bet_id);
+    }
 
-        // Verify full amount returned
-        assert_eq!(refund, TEST_MIN_BET);
+    #[test]
+    fn test_claim_refund_cancelled_market_returns_full_amount() {
+        let (env, client, _oracle, admin, _ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
+        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &TEST_MIN_BET);
+        client.cancel_market(&admin);
 
-        // Verify claimed flag is set
-        let claimed: bool = env.storage().persistent()
-            .get(&DataKey::Claimed(bet_id))
-            .unwrap_or(false);
+        assert_eq!(client.claim_refund(&bettor, &bet_id), TEST_MIN_BET);
+        let claimed: bool = env.as_contract(&client.address, || {
+            env.storage().persistent().get(&DataKey::Claimed(bet_id)).unwrap_or(false)
+        });
         assert!(claimed);
     }
 
     #[test]
     fn test_claim_refund_nocontest_outcome_returns_full_amount() {
-        let (env, bettor, _) = setup_test_env();
-
-        // Place a bet
-        let bet_id = MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterB, TEST_MIN_BET * 2);
-
-        // Resolve market with NoContest (which transitions to Cancelled)
-        let mut market: Market = env.storage().persistent().get(&DataKey::MarketInfo).unwrap();
-        market.status = MarketStatus::Resolved;
-        market.outcome = Some(Outcome::NoContest);
-        env.storage().persistent().set(&DataKey::MarketInfo, &market);
-
-        // Claim refund
-        let refund = MarketContract::claim_refund(env.clone(), bettor.clone(), bet_id.clone());
+        let (env, client, oracle, _admin, betting_ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
+        let bet_id = client.place_bet(&bettor, &BetSide::FighterB, &(TEST_MIN_BET * 2));
+        lock(&env, &client, betting_ends_at);
+        client.resolve_market(&oracle, &Outcome::NoContest);
 
-        // Verify full amount returned (no fee deduction)
-        assert_eq!(refund, TEST_MIN_BET * 2);
+        assert_eq!(client.claim_refund(&bettor, &bet_id), TEST_MIN_BET * 2);
     }
 
     #[test]
+    #[should_panic(expected = "already claimed")]
     fn test_claim_refund_panic_on_duplicate_claim() {
-        let (env, bettor, _) = setup_test_env();
-
-        // Place a bet and cancel market
-        let bet_id = MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterA, TEST_MIN_BET);
-        let mut market: Market = env.storage().persistent().get(&DataKey::MarketInfo).unwrap();
-        market.status = MarketStatus::Cancelled;
-        env.storage().persistent().set(&DataKey::MarketInfo, &market);
-
-        // First claim succeeds
-        let _ = MarketContract::claim_refund(env.clone(), bettor.clone(), bet_id.clone());
+        let (env, client, _oracle, _admin, _ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
+        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &TEST_MIN_BET);
+        set_status(&env, &client, MarketStatus::Cancelled);
 
-        // Second claim should panic
-        let result = std::panic::catch_unwind(|| {
-            MarketContract::claim_refund(env.clone(), bettor.clone(), bet_id);
-        });
-        assert!(result.is_err(), "second claim should panic");
+        client.claim_refund(&bettor, &bet_id);
+        client.claim_refund(&bettor, &bet_id);
     }
 
     #[test]
+    #[should_panic(expected = "market not eligible for refund")]
     fn test_claim_refund_panic_if_market_resolved() {
-        let (env, bettor, _) = setup_test_env();
-
-        // Place a bet
-        let bet_id = MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterA, TEST_MIN_BET);
-
-        // Resolve market (not Cancelled, not NoContest)
-        let mut market: Market = env.storage().persistent().get(&DataKey::MarketInfo).unwrap();
-        market.status = MarketStatus::Resolved;
-        market.outcome = Some(Outcome::FighterA);
-        env.storage().persistent().set(&DataKey::MarketInfo, &market);
+        let (env, client, oracle, _admin, betting_ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
+        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &TEST_MIN_BET);
+        lock(&env, &client, betting_ends_at);
+        client.resolve_market(&oracle, &Outcome::FighterA);
 
-        // Claim refund should panic
-        let result = std::panic::catch_unwind(|| {
-            MarketContract::claim_refund(env.clone(), bettor.clone(), bet_id);
-        });
-        assert!(result.is_err(), "claim_refund should panic for resolved market without NoContest");
+        client.claim_refund(&bettor, &bet_id);
     }
 
     #[test]
+    #[should_panic(expected = "not your bet")]
     fn test_claim_refund_panic_if_not_bettor() {
-        let (env, bettor, _) = setup_test_env();
-        let other = Address::generate(&env);
-
-        // Place a bet
-        let bet_id = MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterA, TEST_MIN_BET);
-
-        // Cancel market
-        let mut market: Market = env.storage().persistent().get(&DataKey::MarketInfo).unwrap();
-        market.status = MarketStatus::Cancelled;
-        env.storage().persistent().set(&DataKey::MarketInfo, &market);
+        let (env, client, _oracle, admin, _ends_at) = setup(1_000);
+        let bet_id = client.place_bet(&create_test_address(&env), &BetSide::FighterA, &TEST_MIN_BET);
+        client.cancel_market(&admin);
 
-        // Claim refund as different address should panic
-        let result = std::panic::catch_unwind(|| {
-            MarketContract::claim_refund(env.clone(), other, bet_id);
-        });
-        assert!(result.is_err(), "claim_refund should panic if not bet owner");
+        client.claim_refund(&create_test_address(&env), &bet_id);
     }
 
     #[test]
     fn test_claim_refund_emits_event() {
-        let (env, bettor, _) = setup_test_env();
-
-        // Place a bet
-        let bet_id = MarketContract::place_bet(env.clone(), bettor.clone(), BetSide::FighterA, TEST_MIN_BET);
+        let (env, client, _oracle, admin, _ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
+        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &TEST_MIN_BET);
+        client.cancel_market(&admin);
 
-        // Cancel market
-        let mut market: Market = env.storage().persistent().get(&DataKey::MarketInfo).unwrap();
-        market.status = MarketStatus::Cancelled;
-        env.storage().persistent().set(&DataKey::MarketInfo, &market);
+        client.claim_refund(&bettor, &bet_id);
 
-        // Clear previous events
-        let _ = env.events().all();
+        let (topics, data) = last_event(&env, &client, events::REFUND_CLAIMED);
+        let event = parse_refund_claimed_event(&env, &topics, &data).unwrap();
+        assert_eq!(event.bet_id, bet_id);
+        assert_eq!(event.bettor, bettor);
+        assert_eq!(event.amount, TEST_MIN_BET);
+    }
 
-        // Claim refund
-        MarketContract::claim_refund(env.clone(), bettor.clone(), bet_id.clone());
+    // ─── Auth ──────────────────────────────────────────────────────────────────
 
-        // Verify event was emitted
-        let events = env.events().all();
-        assert!(events.len() > 0, "RefundClaimed event should be emitted");
-        let (topic, _) = &events[events.len() - 1];
-        assert_eq!(*topic, vec![&env, &Symbol::new(&env, "RefundClaimed")]);
     #[test]
-    fn test_overflow_safe_arithmetic_large_values() {
-        let env = Env::default();
-        env.mock_all_auths();
+    #[should_panic(expected = "not factory admin")]
+    fn test_resolve_dispute_unauthorized_panics() {
+        let (env, client, _oracle, _admin, _ends_at) = setup(1_000);
+        set_status(&env, &client, MarketStatus::Disputed);
 
-        let contract_id = env.register_contract(None, MarketContract);
-        let client = MarketContractClient::new(&env, &contract_id);
+        let attacker = create_test_address(&env);
+        client.resolve_dispute(&attacker, &Outcome::FighterA, &Bytes::new(&env));
+    }
 
-        let oracle = create_test_address(&env);
-        let factory = create_test_address(&env);
-        let fee_col = create_test_address(&env);
-        let fighter_a = make_fighter(&env, "Fury");
-        let fighter_b = make_fighter(&env, "Usyk");
-        let market_id = Bytes::from_array(&env, &[1u8; 32]);
+    // ─── Full lifecycle ────────────────────────────────────────────────────────
 
-        client.initialize(
-            &market_id,
-            &fighter_a,
-            &fighter_b,
-            &1000u64,
-            &500u64,
-            &oracle,
-            &factory,
-            &200u32,
-            &fee_col,
-        );
+    #[test]
+    fn test_full_lifecycle_single_bettor_wins() {
+        let (env, client, oracle, _admin, betting_ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
+        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &TEST_MIN_BET);
+        assert_eq!(client.get_pool_totals(), (TEST_MIN_BET, 0, TEST_MIN_BET));
 
-        let bettor_a = create_test_address(&env);
-        let bettor_b = create_test_address(&env);
+        lock(&env, &client, betting_ends_at);
+        client.resolve_market(&oracle, &Outcome::FighterA);
+        finalize(&env, &client);
 
-        let large_amount_a: i128 = 50_000_000_000_000i128;
-        let large_amount_b: i128 = 75_000_000_000_000i128;
+        // Sole winner: the whole pool less the 2% fee.
+        let payout = client.claim_winnings(&bettor, &bet_id);
+        assert_eq!(payout, TEST_MIN_BET * (10_000 - 200) / 10_000);
+        assert_eq!(client.get_bets_by_address(&bettor).len(), 1);
+    }
 
-        let bet_id_a = client.place_bet(&bettor_a, &BetSide::FighterA, &large_amount_a);
-        assert!(bet_id_a.len() > 0);
+    #[test]
+    fn test_claims_wait_for_finalized_resolution() {
+        let (env, client, oracle, _admin, betting_ends_at) = setup(1_000);
+        let bettor = create_test_address(&env);
+        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &TEST_MIN_BET);
+        lock(&env, &client, betting_ends_at);
+        client.resolve_market(&oracle, &Outcome::FighterA);
 
-        let bet_id_b = client.place_bet(&bettor_b, &BetSide::FighterB, &large_amount_b);
-        assert!(bet_id_b.len() > 0);
+        assert!(!client.is_finalized());
+        assert!(client.try_claim_winnings(&bettor, &bet_id).is_err());
+        assert!(client.try_claim_all(&bettor, &0).is_err());
 
-        let market = client.get_market_info();
-        assert_eq!(market.pool_a, large_amount_a);
-        assert_eq!(market.pool_b, large_amount_b);
-        let expected_total = large_amount_a.checked_add(large_amount_b).unwrap();
-        assert_eq!(market.total_pool, expected_total);
+        finalize(&env, &client);
+        assert!(client.is_finalized());
+        assert!(client.try_finalize_resolution(&None).is_err());
+        assert_eq!(client.claim_winnings(&bettor, &bet_id), TEST_MIN_BET * (10_000 - 200) / 10_000);
     }
 
     #[test]
-    #[should_panic(expected = "pool_a overflow")]
-    fn test_pool_overflow_protection() {
-        let env = Env::default();
-        env.mock_all_auths();
+    fn test_full_lifecycle_two_bettors_different_sides() {
+        let (env, client, oracle, _admin, betting_ends_at) = setup(1_000);
+        let bettor_a = create_test_address(&env);
+        let bettor_b = create_test_address(&env);
+        let bet_id_a = client.place_bet(&bettor_a, &BetSide::FighterA, &300i128);
+        let bet_id_b = client.place_bet(&bettor_b, &BetSide::FighterB, &700i128);
 
-        let contract_id = env.register_contract(None, MarketContract);
-        let client = MarketContractClient::new(&env, &contract_id);
+        let (_, _, odds_a, odds_b) = client.get_pool_odds();
+        assert_eq!((odds_a, odds_b), (3_000, 7_000));
 
-        let oracle = create_test_address(&env);
-        let factory = create_test_address(&env);
-        let fee_col = create_test_address(&env);
-        let fighter_a = make_fighter(&env, "Fury");
-        let fighter_b = make_fighter(&env, "Usyk");
-        let market_id = Bytes::from_array(&env, &[1u8; 32]);
+        lock(&env, &client, betting_ends_at);
+        client.resolve_market(&oracle, &Outcome::FighterA);
+        finalize(&env, &client);
 
-        client.initialize(
-            &market_id,
-            &fighter_a,
-            &fighter_b,
-            &1000u64,
-            &500u64,
-            &oracle,
-            &factory,
-            &200u32,
-            &fee_col,
-        );
+        // Bettor A holds the whole winning pool: 1_000 less the 2% fee.
+        assert_eq!(client.claim_winnings(&bettor_a, &bet_id_a), 980);
+        assert!(client.try_claim_winnings(&bettor_b, &bet_id_b).is_err());
+    }
 
+    #[test]
+    fn test_full_lifecycle_cancelled_market_refund() {
+        let (env, client, _oracle, admin, _ends_at) = setup(1_000);
         let bettor = create_test_address(&env);
-        let overflow_amount = i128::MAX;
+        let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &TEST_MIN_BET);
+
+        client.cancel_market(&admin);
 
-        client.place_bet(&bettor, &BetSide::FighterA, &overflow_amount);
-        client.place_bet(&bettor, &BetSide::FighterA, &1i128);
+        assert_eq!(client.claim_refund(&bettor, &bet_id), TEST_MIN_BET);
+        assert_eq!(client.get_bets_by_address(&bettor).len(), 1);
     }
 }
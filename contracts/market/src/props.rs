@@ -0,0 +1,464 @@
+//! ============================================================
+//! BOXMEOUT — Method-of-Victory and Round Prop Bets
+//! A second parimutuel pool on the same market, graded against
+//! the structured `FightResult` the oracle reports. Prop bets
+//! share the winner bets' exits: `withdraw_bet`, `cash_out` and
+//! `claim_all` all accept them.
+//! ============================================================
+
+use shared::events::{self, FightResultReportedEvent, PropBetPlacedEvent, PropClaimedEvent};
+use soroban_sdk::{contractimpl, Address, Bytes, Env, Vec};
+
+use crate::types::{
+    BetSide, FightResult, Market, MarketStatus, Outcome, PropBet, PropSelection, VictoryMethod,
+};
+use crate::{DataKey, MarketContract, MarketContractArgs, MarketContractClient};
+
+/// Returns true if `selection` wins under `result`.
+pub fn prop_wins(selection: &PropSelection, result: &FightResult) -> bool {
+    let went_distance = result.method == VictoryMethod::Decision;
+    match selection {
+        PropSelection::EndsInRound(round) => !went_distance && *round == result.round,
+        PropSelection::GoesTheDistance => went_distance,
+        PropSelection::WinsByMethod(side, method) => {
+            let side_won = matches!(
+                (side, &result.winner),
+                (BetSide::FighterA, Outcome::FighterA) | (BetSide::FighterB, Outcome::FighterB)
+            );
+            side_won && *method == result.method
+        }
+    }
+}
+
+/// Every selection that wins under `result`. At most two selections can win:
+/// the round (or distance) selection and the winner-by-method selection.
+fn winning_selections(env: &Env, result: &FightResult) -> Vec<PropSelection> {
+    let mut winners = Vec::new(env);
+    if result.method == VictoryMethod::Decision {
+        winners.push_back(PropSelection::GoesTheDistance);
+    } else {
+        winners.push_back(PropSelection::EndsInRound(result.round));
+    }
+    let side = match result.winner {
+        Outcome::FighterA => Some(BetSide::FighterA),
+        Outcome::FighterB => Some(BetSide::FighterB),
+        _ => None,
+    };
+    if let Some(side) = side {
+        winners.push_back(PropSelection::WinsByMethod(side, result.method.clone()));
+    }
+    winners
+}
+
+fn prop_pool(env: &Env, selection: &PropSelection) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PropPool(selection.clone()))
+        .unwrap_or(0)
+}
+
+/// Returns the result props are graded against, or `None` when props are void:
+/// no structured result was reported, or a dispute changed the winner away
+/// from the one in the reported result.
+fn gradable_result(env: &Env, market: &Market) -> Option<FightResult> {
+    let result: FightResult = env.storage().persistent().get(&DataKey::FightResult)?;
+    if market.outcome.outcome() != Some(result.winner.clone()) {
+        return None;
+    }
+    Some(result)
+}
+
+/// Combined pool of every selection that wins under `result`.
+fn winning_stake(env: &Env, result: &FightResult) -> i128 {
+    let mut stake: i128 = 0;
+    for selection in winning_selections(env, result).iter() {
+        stake = stake
+            .checked_add(prop_pool(env, &selection))
+            .expect("winning stake overflow");
+    }
+    stake
+}
+
+/// Amount owed on a prop bet once the market has settled: its payout or refund,
+/// or `None` if it lost. The caller checks the market is settled.
+pub(crate) fn prop_settlement(env: &Env, market: &Market, bet: &PropBet) -> Option<i128> {
+    // The fight still had a result when only the winner pool was empty.
+    if market.status == MarketStatus::Cancelled && !MarketContract::winner_unbacked(market) {
+        return Some(bet.amount);
+    }
+    MarketContract::prop_payout(env, market, bet)
+}
+
+/// Marks a settled prop bet claimed and adds it to the prop payout ledger.
+/// Call once per claim, with the amount `prop_settlement` returned for it.
+pub(crate) fn record_prop_payout(env: &Env, mut bet: PropBet, amount: i128) {
+    // Mark claimed BEFORE any transfer (re-entrancy guard).
+    env.storage().persistent().set(&DataKey::Claimed(bet.bet_id.clone()), &true);
+    bet.claimed = true;
+    env.storage().persistent().set(&DataKey::PropBet(bet.bet_id.clone()), &bet);
+
+    let stake_claimed: i128 = env.storage().persistent().get(&DataKey::PropStakeClaimed).unwrap_or(0);
+    let paid_out: i128 = env.storage().persistent().get(&DataKey::PropPaidOut).unwrap_or(0);
+    env.storage().persistent().set(
+        &DataKey::PropStakeClaimed,
+        &stake_claimed.checked_add(bet.amount).expect("stake overflow"),
+    );
+    env.storage().persistent().set(
+        &DataKey::PropPaidOut,
+        &paid_out.checked_add(amount).expect("payout overflow"),
+    );
+    crate::claims::record_released(env, amount);
+}
+
+/// Takes an unsettled prop bet back out of the market, like `unwind_bet` does
+/// for winner bets: removes its stake from the prop pools, marks it claimed and
+/// drops it from `PropBetsByAddr`. Returns `None` if `bet_id` is not a prop bet.
+pub(crate) fn unwind_prop_bet(env: &Env, bettor: &Address, bet_id: &Bytes) -> Option<PropBet> {
+    let mut bet: PropBet = env.storage().persistent().get(&DataKey::PropBet(bet_id.clone()))?;
+    if bet.bettor != *bettor {
+        panic!("not your bet");
+    }
+    let already_claimed: bool = env.storage().persistent()
+        .get(&DataKey::Claimed(bet_id.clone()))
+        .unwrap_or(false);
+    if already_claimed {
+        panic!("already claimed");
+    }
+
+    let pool = prop_pool(env, &bet.selection)
+        .checked_sub(bet.amount)
+        .expect("prop pool underflow");
+    env.storage()
+        .persistent()
+        .set(&DataKey::PropPool(bet.selection.clone()), &pool);
+    let total: i128 = env.storage().persistent()
+        .get(&DataKey::PropTotalPool)
+        .unwrap_or(0);
+    let total = total.checked_sub(bet.amount).expect("prop total underflow");
+    env.storage().persistent().set(&DataKey::PropTotalPool, &total);
+
+    // Mark claimed BEFORE any transfer (re-entrancy guard).
+    env.storage().persistent().set(&DataKey::Claimed(bet_id.clone()), &true);
+    bet.claimed = true;
+    env.storage().persistent().set(&DataKey::PropBet(bet_id.clone()), &bet);
+
+    let bets: Vec<Bytes> = env.storage().persistent()
+        .get(&DataKey::PropBetsByAddr(bettor.clone()))
+        .unwrap_or(Vec::new(env));
+    let mut kept = Vec::new(env);
+    for id in bets.iter() {
+        if id != *bet_id {
+            kept.push_back(id);
+        }
+    }
+    env.storage().persistent().set(&DataKey::PropBetsByAddr(bettor.clone()), &kept);
+
+    Some(bet)
+}
+
+/// Protocol fee on the prop pool of a settled market. Zero when props are
+/// void, since every prop bet is then refunded in full.
+pub(crate) fn prop_fee(env: &Env, market: &Market) -> i128 {
+    if market.status == MarketStatus::Cancelled && !MarketContract::winner_unbacked(market) {
+        return 0;
+    }
+    let result = match gradable_result(env, market) {
+        Some(r) => r,
+        None => return 0,
+    };
+    if winning_stake(env, &result) == 0 {
+        return 0;
+    }
+    let total: i128 = env.storage().persistent()
+        .get(&DataKey::PropTotalPool)
+        .unwrap_or(0);
+    shared::types::calculate_fee(total, market.protocol_fee_bp)
+}
+
+#[contractimpl]
+impl MarketContract {
+    /// Places a prop bet on how or when the fight ends.
+    ///
+    /// Prop stakes go into a pool separate from `pool_a`/`pool_b` and are
+    /// escrowed through the Treasury exactly like `place_bet`.
+    /// Emits a `prop_bet_placed` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `bettor` - Address of the user placing the bet. Must authorize this call.
+    /// * `selection` - The prop being backed (round, distance, or winner by method).
+    /// * `amount` - Bet amount in stroops. Must satisfy `min_bet_amount ≤ amount ≤ max_bet_amount`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the unique `bet_id` assigned to this prop bet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - The market status is not `Open`, or betting has ended.
+    /// - `selection` is `EndsInRound(0)` or `WinsByMethod(Draw, _)`.
+    /// - `amount` is outside the configured bet limits.
+    pub fn place_prop_bet(
+        env: Env,
+        bettor: Address,
+        selection: PropSelection,
+        amount: i128,
+    ) -> Bytes {
+        bettor.require_auth();
+
+        let market = Self::read_market(&env);
+        if market.status != MarketStatus::Open {
+            panic!("market not open");
+        }
+        match selection {
+            PropSelection::EndsInRound(0) => panic!("round must be at least 1"),
+            PropSelection::WinsByMethod(BetSide::Draw, _) => panic!("a draw has no winner"),
+            _ => {}
+        }
+
+        let config = Self::read_config(&env);
+        if amount < config.min_bet_amount {
+            panic!("below minimum bet");
+        }
+        if amount > config.max_bet_amount {
+            panic!("above maximum bet");
+        }
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+
+        Self::escrow_stake(&env, &market, &bettor, amount);
+
+        let pool = prop_pool(&env, &selection)
+            .checked_add(amount)
+            .expect("prop pool overflow");
+        env.storage()
+            .persistent()
+            .set(&DataKey::PropPool(selection.clone()), &pool);
+        let total: i128 = env.storage().persistent()
+            .get(&DataKey::PropTotalPool)
+            .unwrap_or(0);
+        let total = total.checked_add(amount).expect("prop total overflow");
+        env.storage().persistent().set(&DataKey::PropTotalPool, &total);
+
+        let bet_id = Self::next_bet_id(&env, &market);
+        let bet = PropBet {
+            bet_id: bet_id.clone(),
+            market_id: market.market_id.clone(),
+            bettor: bettor.clone(),
+            selection: selection.clone(),
+            amount,
+            placed_at: env.ledger().timestamp(),
+            claimed: false,
+        };
+        env.storage().persistent().set(&DataKey::PropBet(bet_id.clone()), &bet);
+
+        let mut bets: Vec<Bytes> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PropBetsByAddr(bettor.clone()))
+            .unwrap_or(Vec::new(&env));
+        bets.push_back(bet_id.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::PropBetsByAddr(bettor.clone()), &bets);
+
+        events::emit_prop_bet_placed(&env, PropBetPlacedEvent {
+            market_id: market.numeric_id,
+            bet_id: bet_id.clone(),
+            bettor,
+            selection,
+            amount,
+        });
+
+        bet_id
+    }
+
+    /// Resolves the market from a structured fight result.
+    ///
+    /// Behaves exactly like `resolve_market(oracle, result.winner)` and also
+    /// stores `result` so prop bets can be graded. Emits a `fight_result_reported`
+    /// event in addition to `market_resolved`.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `oracle` - Address of the authorized oracle. Must authorize this call.
+    /// * `result` - Winner, method and round the fight ended in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `result.round` is 0.
+    /// - `result.winner` is `NoContest` (use `resolve_market` instead).
+    /// - `result.winner` is `Draw` but the method is not `Decision`.
+    /// - Any of the `resolve_market` checks fail.
+    pub fn resolve_with_result(env: Env, oracle: Address, result: FightResult) {
+        oracle.require_auth();
+
+        if result.round == 0 {
+            panic!("round must be at least 1");
+        }
+        match result.winner {
+            Outcome::NoContest => panic!("no contest has no fight result"),
+            Outcome::Draw if result.method != VictoryMethod::Decision => {
+                panic!("a draw can only be a decision")
+            }
+            _ => {}
+        }
+
+        // Stored first: a settlement that is final straight away books the
+        // prop fee, which is graded against this result.
+        env.storage().persistent().set(&DataKey::FightResult, &result);
+        Self::apply_resolution(&env, &oracle, result.winner.clone());
+
+        let market = Self::read_market(&env);
+        events::emit_fight_result_reported(&env, FightResultReportedEvent {
+            market_id: market.numeric_id,
+            result,
+        });
+    }
+
+    /// Settles a prop bet: pays a winning prop, or refunds a void one.
+    ///
+    /// Props are void (full refund, no fee) when the market was cancelled, when
+    /// no structured result was reported, when a dispute changed the winner, or
+    /// when no stake backed any winning selection. Otherwise a winning bet gets
+    /// `amount * (prop_total - fee) / winning_stake`, where `winning_stake` is
+    /// the combined pool of every winning selection; shares are truncated and
+    /// the claim that settles the last winning stake receives the remainder.
+    /// The amount is released to `bettor` from the market's treasury escrow.
+    /// Emits a `prop_claimed` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `bettor` - Owner of the prop bet. Must authorize this call.
+    /// * `bet_id` - Identifier returned by `place_prop_bet`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the payout or refund amount, in stroops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `bet_id` does not exist or `bettor` is not its owner.
+    /// - The market is not `Resolved` or `Cancelled`.
+    /// - The prop lost, or has already been claimed.
+    pub fn claim_prop_winnings(env: Env, bettor: Address, bet_id: Bytes) -> i128 {
+        bettor.require_auth();
+
+        let bet: PropBet = env.storage().persistent()
+            .get(&DataKey::PropBet(bet_id.clone()))
+            .expect("bet not found");
+        if bet.bettor != bettor {
+            panic!("not your bet");
+        }
+
+        let already_claimed: bool = env.storage().persistent()
+            .get(&DataKey::Claimed(bet_id.clone()))
+            .unwrap_or(false);
+        if already_claimed {
+            panic!("already claimed");
+        }
+
+        let market = Self::read_market(&env);
+        let payout = match market.status {
+            MarketStatus::Resolved | MarketStatus::Cancelled => prop_settlement(&env, &market, &bet)
+                .expect("bet did not win"),
+            _ => panic!("market not settled"),
+        };
+        crate::claims::require_final(&env, &market);
+        crate::claims::require_claim_open(&env);
+
+        record_prop_payout(&env, bet, payout);
+        if payout > 0 {
+            Self::release_stake(&env, &market, &bettor, payout);
+        }
+
+        events::emit_prop_claimed(&env, PropClaimedEvent {
+            market_id: market.numeric_id,
+            bet_id,
+            bettor,
+            payout,
+        });
+
+        payout
+    }
+
+    /// Returns the [`PropBet`] identified by `bet_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bet_id` does not correspond to any prop bet.
+    pub fn get_prop_bet(env: Env, bet_id: Bytes) -> PropBet {
+        env.storage().persistent().get(&DataKey::PropBet(bet_id))
+            .expect("bet not found")
+    }
+
+    /// Returns all prop bets placed by `bettor` in this market.
+    pub fn get_prop_bets_by_address(env: Env, bettor: Address) -> Vec<PropBet> {
+        let bet_ids: Vec<Bytes> = env.storage().persistent()
+            .get(&DataKey::PropBetsByAddr(bettor))
+            .unwrap_or(Vec::new(&env));
+        let mut bets = Vec::new(&env);
+        for id in bet_ids.iter() {
+            if let Some(bet) = env.storage().persistent().get(&DataKey::PropBet(id)) {
+                bets.push_back(bet);
+            }
+        }
+        bets
+    }
+
+    /// Returns `(selection_pool, prop_total_pool)` for one prop selection.
+    pub fn get_prop_pool(env: Env, selection: PropSelection) -> (i128, i128) {
+        let total: i128 = env.storage().persistent()
+            .get(&DataKey::PropTotalPool)
+            .unwrap_or(0);
+        (prop_pool(&env, &selection), total)
+    }
+
+    /// Returns the structured result reported via `resolve_with_result`, if any.
+    pub fn get_fight_result(env: Env) -> Option<FightResult> {
+        env.storage().persistent().get(&DataKey::FightResult)
+    }
+
+    /// Payout for a prop bet on a resolved market. `Some(stake)` for a void prop,
+    /// `None` if the bet lost. The claim that settles the last winning stake
+    /// receives whatever the truncated shares before it left behind.
+    fn prop_payout(env: &Env, market: &Market, bet: &PropBet) -> Option<i128> {
+        let result = match gradable_result(env, market) {
+            Some(r) => r,
+            None => return Some(bet.amount),
+        };
+
+        let winning_stake = winning_stake(env, &result);
+        if winning_stake == 0 {
+            return Some(bet.amount);
+        }
+        if !prop_wins(&bet.selection, &result) {
+            return None;
+        }
+
+        let total: i128 = env.storage().persistent()
+            .get(&DataKey::PropTotalPool)
+            .unwrap_or(0);
+        let fee_amount = shared::types::calculate_fee(total, market.protocol_fee_bp);
+        let net_pool = total.checked_sub(fee_amount).expect("net pool underflow");
+        let stake_claimed: i128 = env.storage().persistent().get(&DataKey::PropStakeClaimed).unwrap_or(0);
+        if stake_claimed.checked_add(bet.amount).expect("stake overflow") == winning_stake {
+            let paid_out: i128 = env.storage().persistent().get(&DataKey::PropPaidOut).unwrap_or(0);
+            return Some(net_pool.checked_sub(paid_out).expect("paid out exceeds prop pool"));
+        }
+        Some(
+            bet.amount
+                .checked_mul(net_pool)
+                .expect("payout overflow")
+                .checked_div(winning_stake)
+                .expect("payout div zero"),
+        )
+    }
+}
@@ -1,4 +1,8 @@
-use soroban_sdk::{contracttype, Address, Bytes, String};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, String, Vec};
+
+// Cross-contract enums live in `shared` so event payloads and other
+// contracts decode them without depending on this crate.
+pub use shared::types::{BetSide, FightResult, OptionalOutcome, Outcome, PropSelection, VictoryMethod};
 
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
@@ -10,22 +14,6 @@ pub enum MarketStatus {
     Disputed,
 }
 
-#[contracttype]
-#[derive(Clone, Debug, PartialEq)]
-pub enum Outcome {
-    FighterA,
-    FighterB,
-    Draw,
-    NoContest,
-}
-
-#[contracttype]
-#[derive(Clone, Debug, PartialEq)]
-pub enum BetSide {
-    FighterA,
-    FighterB,
-}
-
 /// Post-resolution outcome stored in Market. Pending until the market resolves.
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
@@ -50,6 +38,8 @@ pub struct Fighter {
 #[derive(Clone, Debug)]
 pub struct Market {
     pub market_id: Bytes,
+    /// Factory-assigned sequential id; used in events.
+    pub numeric_id: u64,
     pub fighter_a: Fighter,
     pub fighter_b: Fighter,
     pub scheduled_at: u64,
@@ -59,15 +49,29 @@ pub struct Market {
     pub status: MarketStatus,
     pub pool_a: i128,
     pub pool_b: i128,
+    pub pool_draw: i128,
     pub total_pool: i128,
     pub protocol_fee_bp: u32,
     pub oracle_address: Address,
-    pub outcome: Option<Outcome>,
+    /// `OptionalOutcome::None` until the market is resolved.
+    pub outcome: OptionalOutcome,
     pub fee_collector_address: Address,
     pub resolved_at: u64,
     pub dispute_window_sec: u64,
     pub treasury: Address,
     pub bet_token: Address,
+    pub draw_enabled: bool,
+}
+
+/// One fighter swap recorded by `substitute_fighter`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Substitution {
+    pub side: BetSide,
+    pub previous: Fighter,
+    pub replacement: Fighter,
+    pub substituted_at: u64,
+    pub opt_out_ends_at: u64,
 }
 
 #[contracttype]
@@ -82,6 +86,18 @@ pub struct Bet {
     pub claimed: bool,
 }
 
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PropBet {
+    pub bet_id: Bytes,
+    pub market_id: Bytes,
+    pub bettor: Address,
+    pub selection: PropSelection,
+    pub amount: i128,
+    pub placed_at: u64,
+    pub claimed: bool,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct ClaimReceipt {
@@ -91,32 +107,141 @@ pub struct ClaimReceipt {
     pub claimed_at: u64,
 }
 
+/// Aggregated receipt for one page of `claim_all`.
 #[contracttype]
 #[derive(Clone, Debug)]
-pub struct ProtocolConfig {
-    pub admin: Address,
-    pub fee_collector: Address,
-    pub default_fee_bp: u32,
-    pub min_bet_amount: i128,
-    pub max_bet_amount: i128,
-    pub dispute_window_sec: u64,
-    pub paused: bool,
+pub struct BatchClaim {
+    pub bettor: Address,
+    pub bet_ids: Vec<Bytes>,
+    pub total_payout: i128,
+    /// Index to pass to the next `claim_all` call, counting the bettor's bets
+    /// and then their prop bets.
+    pub next_cursor: u32,
+    /// True once every bet and prop bet of the bettor has been visited.
+    pub done: bool,
+    pub claimed_at: u64,
 }
 
+/// A fight result signed off-chain by a registered oracle key. The signature
+/// covers the XDR encoding of this struct (an `ScVal::Map` keyed by field name).
 #[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct MarketResolved {
+#[derive(Clone, Debug)]
+pub struct OracleAttestation {
     pub market_id: Bytes,
     pub outcome: Outcome,
+    /// When the oracle signed the result.
+    pub timestamp: u64,
+    /// Single-use value so one signed result cannot be replayed.
+    pub nonce: u64,
+}
+
+/// Where a dispute stands. `Upheld` means the arbiter changed the outcome.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DisputeStatus {
+    Open,
+    Upheld,
+    Rejected,
+}
+
+/// One dispute raised against a market's outcome.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Dispute {
+    pub id: u32,
+    pub disputer: Address,
+    pub raised_at: u64,
+    pub reason: Bytes,
+    pub evidence_uri: String,
+    pub evidence_hash: BytesN<32>,
+    pub status: DisputeStatus,
+    /// The arbiter's reasoning, filled in by `resolve_dispute`.
+    pub arbiter_response: Bytes,
+    /// `0` while the dispute is open.
     pub resolved_at: u64,
 }
 
+/// One arbiter's vote on a dispute.
 #[contracttype]
 #[derive(Clone, Debug)]
-pub struct WinningsClaimed {
-    pub bet_id: Bytes,
-    pub bettor: Address,
-    pub payout: i128,
-    pub fee_paid: i128,
-    pub claimed_at: u64,
+pub struct ArbiterBallot {
+    pub arbiter: Address,
+    pub dispute_id: u32,
+    pub outcome: Outcome,
+    pub voted_at: u64,
+}
+
+/// One bond posted in a dispute.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct DisputeBond {
+    pub bonder: Address,
+    pub amount: i128,
+    /// `true` if the bond backs the challenge, `false` if it defends the
+    /// oracle's outcome.
+    pub challenges: bool,
+    pub posted_at: u64,
+}
+
+/// Running account of what a settled market has paid out against its pool.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutLedger {
+    pub total_pool: i128,
+    /// Protocol fee retained from the pool; zero when the market refunds.
+    pub fee: i128,
+    /// Sum of every winning payout and refund claimed so far.
+    pub paid_out: i128,
+    /// Stroops left undistributed once every claim is in; always zero.
+    pub dust: i128,
+    /// Amount still owed to unclaimed bets.
+    pub outstanding: i128,
+    /// Stake of the bets claimed so far.
+    pub stake_claimed: i128,
+    /// True once every winning (or refundable) stake has been claimed.
+    pub complete: bool,
+}
+
+/// What `check_invariants` found. Each flag compares the figures reported
+/// alongside it; `ok` is true when every flag is.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MarketInvariantReport {
+    /// `pool_a + pool_b + pool_draw`.
+    pub pool_sum: i128,
+    /// Stake of every bet still in the pools; withdrawn and cashed-out bets
+    /// are excluded.
+    pub bet_sum: i128,
+    /// What the bettors are still owed: every stake until the market settles,
+    /// then the payout ledger's `outstanding`, and nothing once swept.
+    pub outstanding: i128,
+    /// The market's escrow as recorded by the treasury.
+    pub escrow: i128,
+    /// What the escrow should hold: the winner and prop pools plus the open
+    /// dispute's bonds, less what left escrow after settlement (claims, prop
+    /// claims, the settlement fee and the sweep).
+    pub expected_escrow: i128,
+    /// `total_pool == pool_sum`.
+    pub pools_balanced: bool,
+    /// On every side, the bets add up to that side's pool.
+    pub bets_match_pools: bool,
+    /// `escrow >= outstanding`.
+    pub escrow_covers_claims: bool,
+    /// `escrow == expected_escrow`.
+    pub escrow_conserved: bool,
+    pub ok: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ProtocolConfig {
+    pub admin: Address,
+    pub fee_collector: Address,
+    pub default_fee_bp: u32,
+    pub min_bet_amount: i128,
+    pub max_bet_amount: i128,
+    pub dispute_window_sec: u64,
+    /// Seconds after settlement during which claims are accepted. `0` = never expires.
+    pub claim_expiry_sec: u64,
+    pub paused: bool,
 }
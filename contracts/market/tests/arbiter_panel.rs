@@ -0,0 +1,272 @@
+//! =============================================================================
+//! BOXMEOUT — Arbiter Panel
+//! =============================================================================
+//!
+//! Covers panel-decided disputes:
+//!   - A strict majority of the factory's arbiter panel settles the dispute
+//!   - The admin may only rule once the panel's voting window lapses
+//!   - Non-members, repeat votes and late votes are rejected
+//!   - Each arbiter's vote history is kept across dispute rounds
+//!   - A dispute keeps the panel it was raised under
+
+mod common;
+
+use common::{lock, setup_market, TestMarket};
+use market::types::{BetSide, DisputeStatus, MarketStatus, OptionalOutcome, Outcome};
+use market::MarketContractClient;
+use shared::types::ArbiterPanel;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Bytes, BytesN, Env, String, Symbol, Vec,
+};
+
+const VOTE_WINDOW: u64 = 3_600;
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+/// Sets up a market whose factory seats `panel`.
+fn setup_with_panel<'a>(env: &'a Env, panel: &ArbiterPanel) -> TestMarket<'a> {
+    let market = setup_market(env);
+    market.factory.set_arbiter_panel(panel);
+    market
+}
+
+/// Resolves the market for fighter A with bettors on both sides, returning
+/// (challenger, defender).
+fn resolve_with_bettors(
+    env: &Env,
+    client: &MarketContractClient,
+    oracle: &Address,
+    betting_ends_at: u64,
+) -> (Address, Address) {
+    let challenger = Address::generate(env);
+    let defender = Address::generate(env);
+    client.place_bet(&challenger, &BetSide::FighterB, &1_000i128);
+    client.place_bet(&defender, &BetSide::FighterA, &1_000i128);
+    lock(env, client, oracle, betting_ends_at);
+    client.resolve_market(oracle, &Outcome::FighterA);
+    (challenger, defender)
+}
+
+fn raise_dispute(env: &Env, client: &MarketContractClient, bettor: &Address) -> u32 {
+    client.dispute_resolution(
+        bettor,
+        &Bytes::from_array(env, b"judges scored it wrong"),
+        &String::from_str(env, "ipfs://bafy-scorecards"),
+        &BytesN::from_array(env, &[0xabu8; 32]),
+    )
+}
+
+fn panel_of(env: &Env, size: u32) -> ArbiterPanel {
+    let mut members = Vec::new(env);
+    for _ in 0..size {
+        members.push_back(Address::generate(env));
+    }
+    ArbiterPanel {
+        members,
+        vote_window_sec: VOTE_WINDOW,
+    }
+}
+
+// ─── Arbiter panel ────────────────────────────────────────────────────────────
+
+#[test]
+fn majority_vote_settles_dispute() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let panel = panel_of(&env, 3);
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_with_panel(&env, &panel);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+    raise_dispute(&env, &client, &challenger);
+    assert_eq!(
+        client.get_arbitration_deadline(),
+        env.ledger().timestamp() + VOTE_WINDOW
+    );
+
+    let first = panel.members.get(0).unwrap();
+    let second = panel.members.get(1).unwrap();
+    assert!(!client.cast_arbiter_vote(&first, &Outcome::FighterB));
+    assert_eq!(client.get_market_info().status, MarketStatus::Disputed);
+    assert!(client.cast_arbiter_vote(&second, &Outcome::FighterB));
+
+    let market = client.get_market_info();
+    assert_eq!(market.status, MarketStatus::Resolved);
+    assert_eq!(market.outcome, OptionalOutcome::Some(Outcome::FighterB));
+    let dispute = client.get_disputes().get(0).unwrap();
+    assert_eq!(dispute.status, DisputeStatus::Upheld);
+    assert_eq!(
+        dispute.arbiter_response,
+        Bytes::from_array(&env, b"arbiter panel majority")
+    );
+    assert_eq!(client.get_arbitration_deadline(), 0);
+}
+
+#[test]
+fn split_vote_leaves_dispute_open() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let panel = panel_of(&env, 4);
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_with_panel(&env, &panel);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+    raise_dispute(&env, &client, &challenger);
+
+    client.cast_arbiter_vote(&panel.members.get(0).unwrap(), &Outcome::FighterA);
+    client.cast_arbiter_vote(&panel.members.get(1).unwrap(), &Outcome::FighterA);
+    client.cast_arbiter_vote(&panel.members.get(2).unwrap(), &Outcome::FighterB);
+    // Two of four is not a strict majority.
+    assert_eq!(client.get_market_info().status, MarketStatus::Disputed);
+}
+
+#[test]
+#[should_panic(expected = "arbiter panel still voting")]
+fn admin_cannot_rule_while_panel_votes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let panel = panel_of(&env, 3);
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_with_panel(&env, &panel);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+    raise_dispute(&env, &client, &challenger);
+
+    client.resolve_dispute(&admin, &Outcome::FighterB, &Bytes::new(&env));
+}
+
+#[test]
+fn admin_rules_after_panel_times_out() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let panel = panel_of(&env, 3);
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_with_panel(&env, &panel);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+    raise_dispute(&env, &client, &challenger);
+    client.cast_arbiter_vote(&panel.members.get(0).unwrap(), &Outcome::FighterB);
+
+    let deadline = client.get_arbitration_deadline();
+    env.ledger().with_mut(|l| l.timestamp = deadline + 1);
+    assert!(client
+        .try_cast_arbiter_vote(&panel.members.get(1).unwrap(), &Outcome::FighterB)
+        .is_err());
+
+    client.resolve_dispute(&admin, &Outcome::FighterA, &Bytes::new(&env));
+    assert_eq!(client.get_market_info().status, MarketStatus::Resolved);
+    assert_eq!(
+        client.get_disputes().get(0).unwrap().status,
+        DisputeStatus::Rejected
+    );
+}
+
+#[test]
+#[should_panic(expected = "not an arbiter")]
+fn non_member_vote_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let panel = panel_of(&env, 3);
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_with_panel(&env, &panel);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+    raise_dispute(&env, &client, &challenger);
+
+    client.cast_arbiter_vote(&challenger, &Outcome::FighterB);
+}
+
+#[test]
+#[should_panic(expected = "already voted")]
+fn repeat_vote_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let panel = panel_of(&env, 3);
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_with_panel(&env, &panel);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+    raise_dispute(&env, &client, &challenger);
+
+    let arbiter = panel.members.get(0).unwrap();
+    client.cast_arbiter_vote(&arbiter, &Outcome::FighterB);
+    client.cast_arbiter_vote(&arbiter, &Outcome::FighterA);
+}
+
+#[test]
+fn vote_history_spans_dispute_rounds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let panel = panel_of(&env, 1);
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_with_panel(&env, &panel);
+    let (challenger, defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+    let arbiter = panel.members.get(0).unwrap();
+
+    raise_dispute(&env, &client, &challenger);
+    assert!(client.cast_arbiter_vote(&arbiter, &Outcome::FighterB));
+    raise_dispute(&env, &client, &defender);
+    assert!(client.cast_arbiter_vote(&arbiter, &Outcome::FighterB));
+
+    let votes = client.get_arbiter_votes(&arbiter);
+    assert_eq!(votes.len(), 2);
+    assert_eq!(votes.get(0).unwrap().dispute_id, 0);
+    assert_eq!(votes.get(1).unwrap().dispute_id, 1);
+    assert_eq!(votes.get(1).unwrap().outcome, Outcome::FighterB);
+    assert_eq!(
+        client.get_disputes().get(1).unwrap().status,
+        DisputeStatus::Rejected
+    );
+}
+
+#[test]
+fn dispute_keeps_the_panel_it_was_raised_under() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let panel = panel_of(&env, 1);
+    let TestMarket {
+        client,
+        factory,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_with_panel(&env, &panel);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    raise_dispute(&env, &client, &challenger);
+    let replacement = panel_of(&env, 3);
+    factory.set_arbiter_panel(&replacement);
+
+    let newcomer = replacement.members.get(0).unwrap();
+    assert!(client
+        .try_cast_arbiter_vote(&newcomer, &Outcome::FighterB)
+        .is_err());
+    let arbiter = panel.members.get(0).unwrap();
+    assert!(client.cast_arbiter_vote(&arbiter, &Outcome::FighterB));
+    assert_eq!(factory.reports(&Symbol::new(&env, "arbiter_votes")), 1);
+}
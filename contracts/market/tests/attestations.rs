@@ -0,0 +1,218 @@
+//! =============================================================================
+//! BOXMEOUT — Signed Oracle Attestations
+//! =============================================================================
+//!
+//! Covers `resolve_with_attestation`:
+//!   - A relayed result signed by a registered oracle key resolves the market
+//!   - Unregistered keys fail with `ContractError::InvalidOracleSignature`
+//!   - Tampered payloads, replayed nonces and out-of-range timestamps are rejected
+
+mod common;
+
+use common::{lock, setup_market, TestMarket};
+use ed25519_dalek::{Signer, SigningKey};
+use market::types::{BetSide, MarketStatus, OptionalOutcome, OracleAttestation, Outcome};
+use market::MarketContractClient;
+use shared::errors::ContractError;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env,
+};
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+fn oracle_key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+fn public_key(env: &Env, key: &SigningKey) -> BytesN<32> {
+    BytesN::from_array(env, &key.verifying_key().to_bytes())
+}
+
+fn sign(env: &Env, key: &SigningKey, attestation: &OracleAttestation) -> BytesN<64> {
+    let message: std::vec::Vec<u8> = attestation.clone().to_xdr(env).iter().collect();
+    BytesN::from_array(env, &key.sign(&message).to_bytes())
+}
+
+/// Locks the market, moves past the fight and returns a fresh attestation
+/// for fighter A.
+fn locked_with_attestation(
+    env: &Env,
+    client: &MarketContractClient,
+    oracle: &Address,
+    betting_ends_at: u64,
+) -> OracleAttestation {
+    client.place_bet(&Address::generate(env), &BetSide::FighterA, &1_000i128);
+    client.place_bet(&Address::generate(env), &BetSide::FighterB, &1_000i128);
+    lock(env, client, oracle, betting_ends_at);
+
+    let scheduled_at = client.get_market_info().scheduled_at;
+    env.ledger()
+        .with_mut(|l| l.timestamp = scheduled_at + 3_600);
+    OracleAttestation {
+        market_id: client.get_market_info().market_id,
+        outcome: Outcome::FighterA,
+        timestamp: scheduled_at + 3_000,
+        nonce: 1,
+    }
+}
+
+// ─── resolve_with_attestation ─────────────────────────────────────────────────
+
+#[test]
+fn relayed_attestation_resolves_market() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let key = oracle_key();
+    client.add_oracle_key(&admin, &public_key(&env, &key));
+
+    let attestation = locked_with_attestation(&env, &client, &oracle, betting_ends_at);
+    let signature = sign(&env, &key, &attestation);
+    client.resolve_with_attestation(&attestation, &public_key(&env, &key), &signature);
+
+    let market = client.get_market_info();
+    assert_eq!(market.status, MarketStatus::Resolved);
+    assert_eq!(market.outcome, OptionalOutcome::Some(Outcome::FighterA));
+}
+
+#[test]
+fn unregistered_key_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let key = oracle_key();
+
+    let attestation = locked_with_attestation(&env, &client, &oracle, betting_ends_at);
+    let signature = sign(&env, &key, &attestation);
+    let err = client
+        .try_resolve_with_attestation(&attestation, &public_key(&env, &key), &signature)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Ok(soroban_sdk::Error::from_contract_error(
+            ContractError::InvalidOracleSignature as u32
+        ))
+    );
+}
+
+#[test]
+fn tampered_outcome_fails_verification() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let key = oracle_key();
+    client.add_oracle_key(&admin, &public_key(&env, &key));
+
+    let mut attestation = locked_with_attestation(&env, &client, &oracle, betting_ends_at);
+    let signature = sign(&env, &key, &attestation);
+    attestation.outcome = Outcome::FighterB;
+    assert!(client
+        .try_resolve_with_attestation(&attestation, &public_key(&env, &key), &signature)
+        .is_err());
+    assert_eq!(client.get_market_info().status, MarketStatus::Locked);
+}
+
+#[test]
+#[should_panic(expected = "nonce already used")]
+fn replayed_nonce_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let key = oracle_key();
+    client.add_oracle_key(&admin, &public_key(&env, &key));
+
+    let attestation = locked_with_attestation(&env, &client, &oracle, betting_ends_at);
+    let signature = sign(&env, &key, &attestation);
+    client.resolve_with_attestation(&attestation, &public_key(&env, &key), &signature);
+
+    // Put the market back in Locked to show the nonce, not the status, stops a replay.
+    let mut market = client.get_market_info();
+    market.status = MarketStatus::Locked;
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&market::DataKey::MarketInfo, &market);
+    });
+    client.resolve_with_attestation(&attestation, &public_key(&env, &key), &signature);
+}
+
+#[test]
+#[should_panic(expected = "attestation predates the fight")]
+fn attestation_before_fight_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let key = oracle_key();
+    client.add_oracle_key(&admin, &public_key(&env, &key));
+
+    let mut attestation = locked_with_attestation(&env, &client, &oracle, betting_ends_at);
+    attestation.timestamp = betting_ends_at;
+    let signature = sign(&env, &key, &attestation);
+    client.resolve_with_attestation(&attestation, &public_key(&env, &key), &signature);
+}
+
+#[test]
+#[should_panic(expected = "attestation for another market")]
+fn attestation_for_other_market_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let key = oracle_key();
+    client.add_oracle_key(&admin, &public_key(&env, &key));
+
+    let mut attestation = locked_with_attestation(&env, &client, &oracle, betting_ends_at);
+    attestation.market_id = Bytes::from_array(&env, &[0x99u8; 32]);
+    let signature = sign(&env, &key, &attestation);
+    client.resolve_with_attestation(&attestation, &public_key(&env, &key), &signature);
+}
+
+#[test]
+fn oracle_keys_can_be_revoked() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, admin, .. } = setup_market(&env);
+    let key = public_key(&env, &oracle_key());
+
+    client.add_oracle_key(&admin, &key);
+    assert!(client.try_add_oracle_key(&admin, &key).is_err());
+    assert_eq!(client.get_oracle_keys().len(), 1);
+    client.remove_oracle_key(&admin, &key);
+    assert_eq!(client.get_oracle_keys().len(), 0);
+}
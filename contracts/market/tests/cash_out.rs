@@ -0,0 +1,145 @@
+//! =============================================================================
+//! BOXMEOUT — Early Cash-Out
+//! =============================================================================
+//!
+//! Covers `cash_out` while the market is Open:
+//!   - Stake minus the early-exit fee is released back to the bettor
+//!   - Pools and `BetsByAddr` no longer include the bet
+//!   - The fee is booked with the Treasury
+//!   - Admin-configurable fee, rejected once the market locks
+
+mod common;
+
+use common::{finalize, lock, setup_market, TestMarket};
+use market::types::{BetSide, Outcome};
+use market::DEFAULT_CASH_OUT_FEE_BP;
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
+
+// ─── cash_out ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn cash_out_refunds_stake_minus_default_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client, treasury, ..
+    } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &10_000i128);
+    client.place_bet(&b, &BetSide::FighterB, &4_000i128);
+
+    let fee = 10_000 * DEFAULT_CASH_OUT_FEE_BP as i128 / 10_000;
+    assert_eq!(client.cash_out(&a, &bet_a), 10_000 - fee);
+
+    let m = client.get_market_info();
+    assert_eq!(m.pool_a, 0);
+    assert_eq!(m.pool_b, 4_000);
+    assert_eq!(m.total_pool, 4_000);
+    assert_eq!(client.get_bets_by_address(&a).len(), 0);
+
+    assert_eq!(treasury.total(&Symbol::new(&env, "released")), 10_000 - fee);
+    assert_eq!(treasury.total(&Symbol::new(&env, "fees")), fee);
+}
+
+#[test]
+fn cash_out_uses_admin_configured_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        admin,
+        ..
+    } = setup_market(&env);
+
+    client.set_cash_out_fee(&admin, &1_000u32);
+    assert_eq!(client.get_cash_out_fee(), 1_000);
+
+    let a = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &5_000i128);
+    assert_eq!(client.cash_out(&a, &bet_a), 4_500);
+    assert_eq!(treasury.total(&Symbol::new(&env, "fees")), 500);
+}
+
+#[test]
+fn remaining_bettors_settle_against_reduced_pools() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let a1 = Address::generate(&env);
+    let a2 = Address::generate(&env);
+    let b = Address::generate(&env);
+    let bet_a1 = client.place_bet(&a1, &BetSide::FighterA, &1_000i128);
+    let bet_a2 = client.place_bet(&a2, &BetSide::FighterA, &1_000i128);
+    client.place_bet(&b, &BetSide::FighterB, &2_000i128);
+
+    client.cash_out(&a1, &bet_a1);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
+    assert_eq!(client.claim_winnings(&a2, &bet_a2), 3_000 * 9_800 / 10_000);
+    assert!(client.try_claim_winnings(&a1, &bet_a1).is_err());
+}
+
+#[test]
+#[should_panic(expected = "market not open")]
+fn cash_out_after_lock_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    lock(&env, &client, &oracle, betting_ends_at);
+
+    client.cash_out(&a, &bet_a);
+}
+
+#[test]
+#[should_panic(expected = "already claimed")]
+fn cash_out_twice_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, .. } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    client.cash_out(&a, &bet_a);
+    client.cash_out(&a, &bet_a);
+}
+
+#[test]
+#[should_panic(expected = "not your bet")]
+fn cash_out_someone_elses_bet_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, .. } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    client.cash_out(&Address::generate(&env), &bet_a);
+}
+
+#[test]
+#[should_panic(expected = "fee_bp exceeds ceiling")]
+fn cash_out_fee_above_ceiling_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, admin, .. } = setup_market(&env);
+
+    client.set_cash_out_fee(&admin, &10_001u32);
+}
@@ -0,0 +1,160 @@
+//! =============================================================================
+//! BOXMEOUT — Batch Claims
+//! =============================================================================
+//!
+//! Covers `claim_all`:
+//!   - Winning and refundable bets settle in one call with one transfer
+//!   - Losing and already-claimed bets are skipped
+//!   - Large bet lists are paged with a continuation cursor
+
+mod common;
+
+use common::{finalize, lock, setup_market, TestMarket};
+use market::claims::CLAIM_PAGE_SIZE;
+use market::types::{BetSide, Outcome};
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
+
+// ─── claim_all ────────────────────────────────────────────────────────────────
+
+#[test]
+fn claim_all_sums_winning_bets_into_one_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let bet1 = client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    let bet2 = client.place_bet(&a, &BetSide::FighterA, &3_000i128);
+    client.place_bet(&a, &BetSide::FighterB, &500i128);
+    client.place_bet(&b, &BetSide::FighterB, &5_500i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
+
+    let receipt = client.claim_all(&a, &0u32);
+    let expected = 1_000 * 9_800 / 4_000 + 3_000 * 9_800 / 4_000;
+    assert_eq!(receipt.total_payout, expected);
+    assert_eq!(receipt.bet_ids.len(), 2);
+    assert_eq!(receipt.bet_ids.get(0).unwrap(), bet1);
+    assert_eq!(receipt.bet_ids.get(1).unwrap(), bet2);
+    assert!(receipt.done);
+    assert_eq!(treasury.total(&Symbol::new(&env, "released")), expected);
+
+    // Every bet is now settled.
+    assert!(client.try_claim_winnings(&a, &bet1).is_err());
+    let again = client.claim_all(&a, &0u32);
+    assert_eq!(again.total_payout, 0);
+    assert_eq!(again.bet_ids.len(), 0);
+}
+
+#[test]
+fn claim_all_refunds_cancelled_market() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    client.place_bet(&a, &BetSide::FighterB, &2_000i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::NoContest);
+
+    let receipt = client.claim_all(&a, &0u32);
+    assert_eq!(receipt.total_payout, 3_000);
+    assert_eq!(receipt.bet_ids.len(), 2);
+}
+
+#[test]
+fn claim_all_skips_bets_claimed_individually() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let bet1 = client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    client.place_bet(&b, &BetSide::FighterB, &2_000i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
+
+    client.claim_winnings(&a, &bet1);
+    let receipt = client.claim_all(&a, &0u32);
+    assert_eq!(receipt.bet_ids.len(), 1);
+    assert_eq!(receipt.total_payout, 1_000 * 3_920 / 2_000);
+}
+
+#[test]
+fn claim_all_pages_through_many_bets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let count = CLAIM_PAGE_SIZE + 5;
+    for _ in 0..count {
+        client.place_bet(&a, &BetSide::FighterA, &100i128);
+    }
+    client.place_bet(&b, &BetSide::FighterB, &3_000i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
+
+    let page = client.get_bets_by_address_page(&a, &0u32, &10u32);
+    assert_eq!(page.len(), 10);
+
+    let first = client.claim_all(&a, &0u32);
+    assert_eq!(first.bet_ids.len(), CLAIM_PAGE_SIZE);
+    assert_eq!(first.next_cursor, CLAIM_PAGE_SIZE);
+    assert!(!first.done);
+
+    let second = client.claim_all(&a, &first.next_cursor);
+    assert_eq!(second.bet_ids.len(), 5);
+    assert!(second.done);
+
+    let per_bet = 100 * 9_800 * (count as i128 * 100 + 3_000) / 10_000 / (count as i128 * 100);
+    assert_eq!(
+        first.total_payout + second.total_payout,
+        per_bet * count as i128
+    );
+}
+
+#[test]
+#[should_panic(expected = "market not settled")]
+fn claim_all_before_resolution_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, .. } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    client.claim_all(&a, &0u32);
+}
@@ -0,0 +1,233 @@
+//! =============================================================================
+//! BOXMEOUT — Claim Deadline and Sweeping
+//! =============================================================================
+//!
+//! Covers `claim_expiry_sec` and `sweep_unclaimed`:
+//!   - A final settlement starts the claim window; claims inside it succeed
+//!   - After the deadline every claim fails with `ContractError::ClaimExpired`
+//!   - Anyone may sweep the remaining escrow to the treasury, exactly once
+//!   - A zero `claim_expiry_sec` keeps claims open forever
+//!   - The protocol fee is booked once the settlement is final, sweep or not
+
+mod common;
+
+use common::{finalize, lock, setup_market, update_config, TestMarket};
+use market::types::{BetSide, Outcome};
+use shared::errors::ContractError;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, Symbol,
+};
+
+const CLAIM_EXPIRY_SEC: u64 = 30 * 86_400;
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+fn setup_with_expiry(env: &Env, claim_expiry_sec: u64) -> TestMarket<'_> {
+    let market = setup_market(env);
+    update_config(&market.factory, |c| c.claim_expiry_sec = claim_expiry_sec);
+    market
+}
+
+fn expired() -> Result<soroban_sdk::Error, soroban_sdk::InvokeError> {
+    Ok(soroban_sdk::Error::from_contract_error(
+        ContractError::ClaimExpired as u32,
+    ))
+}
+
+// ─── Claim deadline ───────────────────────────────────────────────────────────
+
+#[test]
+fn claims_succeed_until_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_with_expiry(&env, CLAIM_EXPIRY_SEC);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    client.place_bet(&b, &BetSide::FighterB, &1_000i128);
+
+    assert_eq!(client.get_claim_deadline(), 0);
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    // The claim period starts once the resolution is final.
+    assert_eq!(client.get_claim_deadline(), 0);
+    finalize(&env, &client);
+
+    let deadline = client.get_claim_deadline();
+    assert_eq!(deadline, env.ledger().timestamp() + CLAIM_EXPIRY_SEC);
+
+    env.ledger().with_mut(|l| l.timestamp = deadline);
+    assert_eq!(client.claim_winnings(&a, &bet_a), 2_000 * 9_800 / 10_000);
+}
+
+#[test]
+fn claims_after_deadline_fail_with_typed_error() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_with_expiry(&env, CLAIM_EXPIRY_SEC);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    client.place_bet(&b, &BetSide::FighterB, &1_000i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
+
+    let deadline = client.get_claim_deadline();
+    env.ledger().with_mut(|l| l.timestamp = deadline + 1);
+
+    assert_eq!(
+        client.try_claim_winnings(&a, &bet_a).unwrap_err(),
+        expired()
+    );
+    assert_eq!(client.try_claim_all(&a, &0u32).unwrap_err(), expired());
+}
+
+#[test]
+fn refunds_expire_too() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_with_expiry(&env, CLAIM_EXPIRY_SEC);
+
+    let a = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::NoContest);
+
+    let deadline = client.get_claim_deadline();
+    env.ledger().with_mut(|l| l.timestamp = deadline + 1);
+    assert_eq!(client.try_claim_refund(&a, &bet_a).unwrap_err(), expired());
+}
+
+// ─── sweep_unclaimed ──────────────────────────────────────────────────────────
+
+#[test]
+fn sweep_moves_remaining_escrow_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_with_expiry(&env, CLAIM_EXPIRY_SEC);
+
+    client.place_bet(&Address::generate(&env), &BetSide::FighterA, &1_000i128);
+    client.place_bet(&Address::generate(&env), &BetSide::FighterB, &2_500i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
+
+    assert!(client.try_sweep_unclaimed().is_err());
+
+    // The 2% fee was booked at finality; only the unclaimed payout is swept.
+    let deadline = client.get_claim_deadline();
+    env.ledger().with_mut(|l| l.timestamp = deadline + 1);
+    assert_eq!(client.sweep_unclaimed(), 3_430);
+    assert_eq!(treasury.total(&Symbol::new(&env, "fees")), 70);
+    assert_eq!(treasury.total(&Symbol::new(&env, "swept")), 3_430);
+    assert!(client.try_sweep_unclaimed().is_err());
+}
+
+#[test]
+#[should_panic(expected = "claims never expire")]
+fn zero_expiry_keeps_claims_open_and_blocks_sweep() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_with_expiry(&env, 0);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    client.place_bet(&b, &BetSide::FighterB, &1_000i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
+    assert_eq!(client.get_claim_deadline(), 0);
+
+    env.ledger()
+        .with_mut(|l| l.timestamp += 10 * CLAIM_EXPIRY_SEC);
+    client.claim_winnings(&a, &bet_a);
+    client.sweep_unclaimed();
+}
+
+// ─── Fee collection ───────────────────────────────────────────────────────────
+
+#[test]
+fn fee_is_collected_at_finality_without_claim_expiry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        market_id,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_with_expiry(&env, 0);
+
+    let a = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    client.place_bet(&Address::generate(&env), &BetSide::FighterB, &1_500i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    // A dispute could still overturn the outcome, so nothing is booked yet.
+    assert_eq!(treasury.total(&Symbol::new(&env, "fees")), 0);
+
+    finalize(&env, &client);
+    assert_eq!(treasury.total(&Symbol::new(&env, "fees")), 50);
+    assert_eq!(treasury.get_market_escrow(&market_id), 2_450);
+
+    // The sole winner takes the rest of the escrow.
+    assert_eq!(client.claim_winnings(&a, &bet_a), 2_450);
+    assert_eq!(treasury.get_market_escrow(&market_id), 0);
+}
+
+#[test]
+fn refunds_collect_no_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_with_expiry(&env, 0);
+
+    client.place_bet(&Address::generate(&env), &BetSide::FighterA, &1_000i128);
+    client.place_bet(&Address::generate(&env), &BetSide::FighterB, &1_500i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::NoContest);
+    assert_eq!(treasury.total(&Symbol::new(&env, "fees")), 0);
+}
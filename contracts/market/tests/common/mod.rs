@@ -0,0 +1,321 @@
+//! =============================================================================
+//! BOXMEOUT — Shared Market Test Fixtures
+//! =============================================================================
+//!
+//! Mock factory and treasury contracts plus the market setup every
+//! integration test in `tests/` starts from. Each test binary pulls this in
+//! with `mod common;` and uses only part of it.
+
+#![allow(dead_code)]
+
+use market::types::{Fighter, Outcome, ProtocolConfig};
+use market::{MarketContract, MarketContractClient};
+use shared::types::ArbiterPanel;
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger},
+    Address, Bytes, Env, String, Symbol, Vec,
+};
+
+// ─── Mock Factory ─────────────────────────────────────────────────────────────
+
+/// Serves a mutable `ProtocolConfig` and arbiter panel, and counts the oracle
+/// reputation and arbiter vote reports markets send it.
+#[contract]
+pub struct MockFactory;
+
+#[contractimpl]
+impl MockFactory {
+    pub fn __constructor(env: Env, admin: Address) {
+        let config = ProtocolConfig {
+            admin: admin.clone(),
+            fee_collector: admin.clone(),
+            default_fee_bp: 200,
+            min_bet_amount: 100,
+            max_bet_amount: 100_000_000_000,
+            dispute_window_sec: 86_400,
+            claim_expiry_sec: 0,
+            paused: false,
+        };
+        Self::set_config(env.clone(), config);
+        Self::set_arbiter_panel(
+            env.clone(),
+            ArbiterPanel {
+                members: Vec::new(&env),
+                vote_window_sec: 0,
+            },
+        );
+    }
+
+    pub fn get_config(env: Env) -> ProtocolConfig {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, "config"))
+            .unwrap()
+    }
+
+    /// Test hook: replaces the config every market reads.
+    pub fn set_config(env: Env, config: ProtocolConfig) {
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, "config"), &config);
+    }
+
+    pub fn get_arbiter_panel(env: Env) -> ArbiterPanel {
+        env.storage()
+            .persistent()
+            .get(&Symbol::new(&env, "panel"))
+            .unwrap()
+    }
+
+    /// Test hook: seats an arbiter panel. The default panel is empty, which
+    /// leaves disputes to the admin.
+    pub fn set_arbiter_panel(env: Env, panel: ArbiterPanel) {
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, "panel"), &panel);
+    }
+
+    pub fn record_oracle_resolution(env: Env, _market_id: Bytes) {
+        let rejecting: bool = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, "reject"))
+            .unwrap_or(false);
+        assert!(!rejecting, "oracle not staked");
+        Self::bump(&env, "resolutions");
+    }
+
+    /// Test hook: fails every later resolution report, as the real factory
+    /// does for an oracle it has no stake for.
+    pub fn reject_reports(env: Env) {
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, "reject"), &true);
+    }
+
+    pub fn report_oracle_overturned(env: Env, _market_id: Bytes) -> i128 {
+        Self::bump(&env, "overturned");
+        0
+    }
+
+    pub fn record_arbiter_vote(
+        env: Env,
+        _market_id: Bytes,
+        _arbiter: Address,
+        _dispute_id: u32,
+        _outcome: Outcome,
+    ) {
+        Self::bump(&env, "arbiter_votes");
+    }
+
+    /// Number of `resolutions`, `overturned` or `arbiter_votes` reports received.
+    pub fn reports(env: Env, key: Symbol) -> u32 {
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+}
+
+impl MockFactory {
+    fn bump(env: &Env, name: &str) {
+        let key = Symbol::new(env, name);
+        let count: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(count + 1));
+    }
+}
+
+// ─── Mock Treasury ────────────────────────────────────────────────────────────
+
+/// Tracks escrow per market like the real Treasury, without moving tokens.
+/// Also keeps running totals (`deposited`, `released`, `fees`, `swept`) and
+/// what each recipient has been released.
+#[contract]
+pub struct MockTreasury;
+
+#[contractimpl]
+impl MockTreasury {
+    pub fn deposit(
+        env: Env,
+        _from_market: Address,
+        market_id: Bytes,
+        _bettor: Address,
+        amount: i128,
+    ) {
+        Self::adjust(&env, market_id, amount);
+        Self::add(&env, Symbol::new(&env, "deposited"), amount);
+    }
+
+    pub fn release(
+        env: Env,
+        _from_market: Address,
+        market_id: Bytes,
+        recipient: Address,
+        amount: i128,
+    ) {
+        Self::adjust(&env, market_id, -amount);
+        Self::add(&env, Symbol::new(&env, "released"), amount);
+        Self::add(&env, recipient, amount);
+    }
+
+    pub fn collect_fee(env: Env, _from_market: Address, market_id: Bytes, amount: i128) {
+        Self::adjust(&env, market_id, -amount);
+        Self::add(&env, Symbol::new(&env, "fees"), amount);
+    }
+
+    pub fn sweep_market(env: Env, _from_market: Address, market_id: Bytes) -> i128 {
+        let amount = Self::get_market_escrow(env.clone(), market_id.clone());
+        Self::adjust(&env, market_id, -amount);
+        Self::add(&env, Symbol::new(&env, "swept"), amount);
+        amount
+    }
+
+    pub fn get_market_escrow(env: Env, market_id: Bytes) -> i128 {
+        env.storage().persistent().get(&market_id).unwrap_or(0)
+    }
+
+    /// Test hook standing in for escrow that went missing.
+    pub fn set_escrow(env: Env, market_id: Bytes, amount: i128) {
+        env.storage().persistent().set(&market_id, &amount);
+    }
+
+    pub fn total(env: Env, key: Symbol) -> i128 {
+        env.storage().persistent().get(&key).unwrap_or(0)
+    }
+
+    pub fn released_to(env: Env, recipient: Address) -> i128 {
+        env.storage().persistent().get(&recipient).unwrap_or(0)
+    }
+}
+
+impl MockTreasury {
+    fn adjust(env: &Env, market_id: Bytes, delta: i128) {
+        let escrow = Self::get_market_escrow(env.clone(), market_id.clone()) + delta;
+        assert!(escrow >= 0, "amount exceeds market escrow");
+        env.storage().persistent().set(&market_id, &escrow);
+    }
+
+    fn add<K>(env: &Env, key: K, amount: i128)
+    where
+        K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>,
+    {
+        let key = key.into_val(env);
+        let total: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(total + amount));
+    }
+}
+
+// ─── Market setup ─────────────────────────────────────────────────────────────
+
+/// What `setup_market_with` initializes the market with.
+pub struct MarketParams {
+    pub market_id: [u8; 32],
+    pub fighter_a: &'static str,
+    pub fighter_b: &'static str,
+    pub fee_bp: u32,
+    pub draw_enabled: bool,
+    /// Betting closes this long after the current ledger time; the fight is
+    /// scheduled twice as far out.
+    pub betting_window_sec: u64,
+}
+
+impl Default for MarketParams {
+    fn default() -> Self {
+        MarketParams {
+            market_id: [0x33; 32],
+            fighter_a: "Tyson",
+            fighter_b: "Holyfield",
+            fee_bp: 200,
+            draw_enabled: false,
+            betting_window_sec: 1_000_000,
+        }
+    }
+}
+
+/// An initialized market wired to a fresh mock factory and treasury.
+pub struct TestMarket<'a> {
+    pub client: MarketContractClient<'a>,
+    pub factory: MockFactoryClient<'a>,
+    pub treasury: MockTreasuryClient<'a>,
+    pub market_id: Bytes,
+    pub oracle: Address,
+    pub admin: Address,
+    pub betting_ends_at: u64,
+}
+
+pub fn make_fighter(env: &Env, name: &str) -> Fighter {
+    Fighter {
+        name: String::from_str(env, name),
+        record: String::from_str(env, "10-0"),
+        nationality: String::from_str(env, "US"),
+        weight_class: String::from_str(env, "Heavyweight"),
+    }
+}
+
+/// Sets up a two-way Tyson vs Holyfield market with a 2% fee.
+pub fn setup_market(env: &Env) -> TestMarket<'_> {
+    setup_market_with(env, MarketParams::default())
+}
+
+pub fn setup_market_with(env: &Env, params: MarketParams) -> TestMarket<'_> {
+    let admin = Address::generate(env);
+    let factory_id = env.register(MockFactory, (admin.clone(),));
+    let treasury_id = env.register(MockTreasury, ());
+    let oracle = Address::generate(env);
+    let fee_collector = Address::generate(env);
+    let bet_token = Address::generate(env);
+    let market_id = Bytes::from_array(env, &params.market_id);
+
+    let now = env.ledger().timestamp();
+    let betting_ends_at = now + params.betting_window_sec;
+    let scheduled_at = now + 2 * params.betting_window_sec;
+
+    let market_cid = env.register(MarketContract, ());
+    let client = MarketContractClient::new(env, &market_cid);
+
+    client.initialize(
+        &market_id,
+        &1u64,
+        &make_fighter(env, params.fighter_a),
+        &make_fighter(env, params.fighter_b),
+        &scheduled_at,
+        &betting_ends_at,
+        &oracle,
+        &factory_id,
+        &params.fee_bp,
+        &fee_collector,
+        &86_400u64,
+        &treasury_id,
+        &bet_token,
+        &params.draw_enabled,
+    );
+
+    TestMarket {
+        client,
+        factory: MockFactoryClient::new(env, &factory_id),
+        treasury: MockTreasuryClient::new(env, &treasury_id),
+        market_id,
+        oracle,
+        admin,
+        betting_ends_at,
+    }
+}
+
+/// Applies `edit` to the factory's live config.
+pub fn update_config(factory: &MockFactoryClient, edit: impl FnOnce(&mut ProtocolConfig)) {
+    let mut config = factory.get_config();
+    edit(&mut config);
+    factory.set_config(&config);
+}
+
+/// Moves past the betting deadline and locks the market.
+pub fn lock(env: &Env, client: &MarketContractClient, oracle: &Address, betting_ends_at: u64) {
+    env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
+    client.lock_market(oracle);
+}
+
+/// Lets the dispute window lapse and finalizes the resolution, opening claims.
+pub fn finalize(env: &Env, client: &MarketContractClient) {
+    let market = client.get_market_info();
+    env.ledger()
+        .with_mut(|l| l.timestamp = market.resolved_at + market.dispute_window_sec + 1);
+    client.finalize_resolution(&None);
+}
@@ -0,0 +1,280 @@
+//! =============================================================================
+//! BOXMEOUT — Dispute Bonds
+//! =============================================================================
+//!
+//! Covers the staked dispute game:
+//!   - Opening a dispute escrows the base bond
+//!   - Counter-bonds alternate sides and double each round
+//!   - A repeat dispute keeps doubling from where the last one stopped
+//!   - `resolve_dispute` returns and awards bonds to the side it agrees with
+//!   - Unanswered losing bonds are booked as protocol fees
+
+mod common;
+
+use common::{lock, setup_market, TestMarket};
+use market::disputes::{DEFAULT_DISPUTE_BOND, MAX_DISPUTE_ROUNDS};
+use market::types::{BetSide, MarketStatus, Outcome};
+use market::MarketContractClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Bytes, BytesN, Env, String, Symbol,
+};
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+/// Resolves the market for fighter A with bettors on both sides, returning
+/// (challenger, defender).
+fn resolve_with_bettors(
+    env: &Env,
+    client: &MarketContractClient,
+    oracle: &Address,
+    betting_ends_at: u64,
+) -> (Address, Address) {
+    let challenger = Address::generate(env);
+    let defender = Address::generate(env);
+    client.place_bet(&challenger, &BetSide::FighterB, &1_000i128);
+    client.place_bet(&defender, &BetSide::FighterA, &1_000i128);
+    lock(env, client, oracle, betting_ends_at);
+    client.resolve_market(oracle, &Outcome::FighterA);
+    (challenger, defender)
+}
+
+fn raise_dispute(env: &Env, client: &MarketContractClient, bettor: &Address) -> u32 {
+    client.dispute_resolution(
+        bettor,
+        &Bytes::from_array(env, b"wrong"),
+        &String::from_str(env, ""),
+        &BytesN::from_array(env, &[0u8; 32]),
+    )
+}
+
+// ─── Dispute bonds ────────────────────────────────────────────────────────────
+
+#[test]
+fn opening_dispute_escrows_base_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    assert_eq!(client.get_required_bond(), DEFAULT_DISPUTE_BOND);
+    raise_dispute(&env, &client, &challenger);
+
+    let bonds = client.get_dispute_bonds();
+    assert_eq!(bonds.len(), 1);
+    let bond = bonds.get(0).unwrap();
+    assert_eq!(bond.bonder, challenger);
+    assert_eq!(bond.amount, DEFAULT_DISPUTE_BOND);
+    assert!(bond.challenges);
+    assert_eq!(
+        treasury.total(&Symbol::new(&env, "deposited")),
+        2_000 + DEFAULT_DISPUTE_BOND
+    );
+    assert_eq!(client.get_required_bond(), DEFAULT_DISPUTE_BOND * 2);
+}
+
+#[test]
+fn counter_bonds_double_each_round() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    raise_dispute(&env, &client, &challenger);
+    assert_eq!(
+        client.post_dispute_bond(&defender, &false),
+        DEFAULT_DISPUTE_BOND * 2
+    );
+    assert_eq!(
+        client.post_dispute_bond(&challenger, &true),
+        DEFAULT_DISPUTE_BOND * 4
+    );
+    assert_eq!(client.get_dispute_bonds().len(), 3);
+}
+
+#[test]
+fn repeat_dispute_keeps_doubling_the_bond() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    client.set_dispute_bond(&admin, &100i128);
+    raise_dispute(&env, &client, &challenger);
+    client.post_dispute_bond(&defender, &false);
+    client.resolve_dispute(&admin, &Outcome::FighterB, &Bytes::new(&env));
+
+    // Two bonds were posted in the first round, so the next dispute opens at 400.
+    assert_eq!(client.get_required_bond(), 400);
+    env.ledger().with_mut(|l| l.timestamp += 3_600);
+    raise_dispute(&env, &client, &defender);
+    assert_eq!(client.get_dispute_bonds().get(0).unwrap().amount, 400);
+    assert_eq!(client.post_dispute_bond(&challenger, &false), 800);
+    assert_eq!(
+        treasury.total(&Symbol::new(&env, "deposited")),
+        2_000 + 100 + 200 + 400 + 800
+    );
+}
+
+#[test]
+#[should_panic(expected = "bond must answer the previous round")]
+fn counter_bond_on_same_side_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    raise_dispute(&env, &client, &challenger);
+    client.post_dispute_bond(&defender, &true);
+}
+
+#[test]
+#[should_panic(expected = "market not in disputed state")]
+fn counter_bond_without_dispute_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (_challenger, defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    client.post_dispute_bond(&defender, &false);
+}
+
+#[test]
+fn rounds_are_capped() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    client.set_dispute_bond(&admin, &10i128);
+    raise_dispute(&env, &client, &challenger);
+    for round in 1..MAX_DISPUTE_ROUNDS {
+        let challenges = round % 2 == 0;
+        let bonder = if challenges { &challenger } else { &defender };
+        client.post_dispute_bond(bonder, &challenges);
+    }
+    assert!(client.try_post_dispute_bond(&defender, &false).is_err());
+}
+
+#[test]
+fn upheld_challenge_awards_defender_bonds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    client.set_dispute_bond(&admin, &100i128);
+    raise_dispute(&env, &client, &challenger);
+    client.post_dispute_bond(&defender, &false);
+    client.resolve_dispute(&admin, &Outcome::FighterB, &Bytes::new(&env));
+
+    // Challenger's 100 back plus the defender's forfeited 200.
+    assert_eq!(treasury.released_to(&challenger), 300);
+    assert_eq!(treasury.released_to(&defender), 0);
+    assert_eq!(client.get_dispute_bonds().len(), 0);
+    assert_eq!(client.get_market_info().status, MarketStatus::Resolved);
+}
+
+#[test]
+fn rejected_challenge_awards_defenders_pro_rata() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+    let second_defender = Address::generate(&env);
+
+    client.set_dispute_bond(&admin, &100i128);
+    raise_dispute(&env, &client, &challenger);
+    client.post_dispute_bond(&defender, &false);
+    client.post_dispute_bond(&challenger, &true);
+    // Without a bet the second defender cannot join.
+    assert!(client
+        .try_post_dispute_bond(&second_defender, &false)
+        .is_err());
+    client.post_dispute_bond(&defender, &false);
+
+    // Bonds: challenger 100 + 400, defender 200 + 800.
+    client.resolve_dispute(&admin, &Outcome::FighterA, &Bytes::new(&env));
+    assert_eq!(treasury.released_to(&defender), 1_000 + 500);
+    assert_eq!(treasury.released_to(&challenger), 0);
+    assert_eq!(treasury.total(&Symbol::new(&env, "fees")), 0);
+}
+
+#[test]
+fn unanswered_losing_bond_becomes_fees() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    client.set_dispute_bond(&admin, &100i128);
+    raise_dispute(&env, &client, &challenger);
+    client.resolve_dispute(&admin, &Outcome::FighterA, &Bytes::new(&env));
+
+    assert_eq!(treasury.released_to(&challenger), 0);
+    assert_eq!(treasury.total(&Symbol::new(&env, "fees")), 100);
+}
+
+#[test]
+#[should_panic(expected = "bond must be positive")]
+fn zero_bond_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, admin, .. } = setup_market(&env);
+    client.set_dispute_bond(&admin, &0i128);
+}
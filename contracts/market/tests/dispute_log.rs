@@ -0,0 +1,275 @@
+//! =============================================================================
+//! BOXMEOUT — Dispute Log
+//! =============================================================================
+//!
+//! Covers dispute records:
+//!   - Each dispute stores disputer, timestamp, reason and evidence
+//!   - `resolve_dispute` records the ruling and the arbiter's response
+//!   - A ruling can itself be disputed, up to `MAX_DISPUTES` per market
+//!   - Claims wait until the ruling is finalized, which starts the claim period
+
+mod common;
+
+use common::{finalize, lock, setup_market, update_config, TestMarket};
+use market::disputes::MAX_DISPUTES;
+use market::types::{BetSide, DisputeStatus, MarketStatus, Outcome};
+use market::MarketContractClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Bytes, BytesN, Env, String, Symbol,
+};
+
+const CLAIM_EXPIRY_SEC: u64 = 30 * 86_400;
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+/// Resolves the market for fighter A with bettors on both sides, returning
+/// (challenger, defender).
+fn resolve_with_bettors(
+    env: &Env,
+    client: &MarketContractClient,
+    oracle: &Address,
+    betting_ends_at: u64,
+) -> (Address, Address) {
+    let challenger = Address::generate(env);
+    let defender = Address::generate(env);
+    client.place_bet(&challenger, &BetSide::FighterB, &1_000i128);
+    client.place_bet(&defender, &BetSide::FighterA, &1_000i128);
+    lock(env, client, oracle, betting_ends_at);
+    client.resolve_market(oracle, &Outcome::FighterA);
+    (challenger, defender)
+}
+
+fn raise_dispute(env: &Env, client: &MarketContractClient, bettor: &Address) -> u32 {
+    client.dispute_resolution(
+        bettor,
+        &Bytes::from_array(env, b"judges scored it wrong"),
+        &String::from_str(env, "ipfs://bafy-scorecards"),
+        &BytesN::from_array(env, &[0xabu8; 32]),
+    )
+}
+
+// ─── Dispute log ──────────────────────────────────────────────────────────────
+
+#[test]
+fn dispute_record_stores_evidence() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    assert_eq!(raise_dispute(&env, &client, &challenger), 0);
+
+    let disputes = client.get_disputes();
+    assert_eq!(disputes.len(), 1);
+    let dispute = disputes.get(0).unwrap();
+    assert_eq!(dispute.id, 0);
+    assert_eq!(dispute.disputer, challenger);
+    assert_eq!(dispute.raised_at, env.ledger().timestamp());
+    assert_eq!(
+        dispute.reason,
+        Bytes::from_array(&env, b"judges scored it wrong")
+    );
+    assert_eq!(
+        dispute.evidence_uri,
+        String::from_str(&env, "ipfs://bafy-scorecards")
+    );
+    assert_eq!(
+        dispute.evidence_hash,
+        BytesN::from_array(&env, &[0xabu8; 32])
+    );
+    assert_eq!(dispute.status, DisputeStatus::Open);
+    assert_eq!(dispute.resolved_at, 0);
+}
+
+#[test]
+fn resolve_dispute_records_ruling_and_response() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    raise_dispute(&env, &client, &challenger);
+    let response = Bytes::from_array(&env, b"scorecards confirm B");
+    client.resolve_dispute(&admin, &Outcome::FighterB, &response);
+
+    let dispute = client.get_disputes().get(0).unwrap();
+    assert_eq!(dispute.status, DisputeStatus::Upheld);
+    assert_eq!(dispute.arbiter_response, response);
+    assert_eq!(dispute.resolved_at, env.ledger().timestamp());
+}
+
+#[test]
+fn ruling_can_be_disputed_again() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    raise_dispute(&env, &client, &challenger);
+    client.resolve_dispute(&admin, &Outcome::FighterB, &Bytes::new(&env));
+
+    // The ruling restarts the dispute window; the other side now disputes it.
+    env.ledger().with_mut(|l| l.timestamp += 3_600);
+    assert_eq!(raise_dispute(&env, &client, &defender), 1);
+    client.resolve_dispute(&admin, &Outcome::FighterB, &Bytes::new(&env));
+
+    let disputes = client.get_disputes();
+    assert_eq!(disputes.len(), 2);
+    assert_eq!(disputes.get(0).unwrap().status, DisputeStatus::Upheld);
+    assert_eq!(disputes.get(1).unwrap().status, DisputeStatus::Rejected);
+    assert_eq!(disputes.get(1).unwrap().disputer, defender);
+    assert_eq!(client.get_market_info().status, MarketStatus::Resolved);
+}
+
+#[test]
+fn disputes_are_capped_per_market() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    for _ in 0..MAX_DISPUTES {
+        raise_dispute(&env, &client, &challenger);
+        client.resolve_dispute(&admin, &Outcome::FighterA, &Bytes::new(&env));
+    }
+    assert!(client
+        .try_dispute_resolution(
+            &challenger,
+            &Bytes::new(&env),
+            &String::from_str(&env, ""),
+            &BytesN::from_array(&env, &[0u8; 32]),
+        )
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "market not resolved")]
+fn second_dispute_while_open_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    raise_dispute(&env, &client, &challenger);
+    raise_dispute(&env, &client, &defender);
+}
+
+#[test]
+#[should_panic(expected = "evidence uri exceeds maximum length")]
+fn oversized_evidence_uri_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    let long_uri = [b'a'; 300];
+    client.dispute_resolution(
+        &challenger,
+        &Bytes::new(&env),
+        &String::from_bytes(&env, &long_uri),
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+}
+
+#[test]
+fn claims_wait_for_the_ruling_to_be_finalized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+    let defender_bet = client.get_bets_by_address(&defender).get(0).unwrap().bet_id;
+    let challenger_bet = client
+        .get_bets_by_address(&challenger)
+        .get(0)
+        .unwrap()
+        .bet_id;
+
+    // The oracle's winner cannot collect while the call can still be challenged.
+    assert!(client.try_claim_winnings(&defender, &defender_bet).is_err());
+    raise_dispute(&env, &client, &challenger);
+    client.resolve_dispute(&admin, &Outcome::FighterB, &Bytes::new(&env));
+    assert!(client
+        .try_claim_winnings(&challenger, &challenger_bet)
+        .is_err());
+
+    finalize(&env, &client);
+    assert!(client.try_claim_winnings(&defender, &defender_bet).is_err());
+    assert_eq!(
+        client.claim_winnings(&challenger, &challenger_bet),
+        2_000 * 9_800 / 10_000
+    );
+    assert!(client.get_payout_ledger().complete);
+}
+
+#[test]
+fn finalizing_a_dispute_starts_the_claim_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        factory,
+        treasury,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    update_config(&factory, |c| c.claim_expiry_sec = CLAIM_EXPIRY_SEC);
+    let (challenger, defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+    let defender_bet = client.get_bets_by_address(&defender).get(0).unwrap().bet_id;
+
+    let bond = client.get_required_bond();
+    raise_dispute(&env, &client, &challenger);
+    assert_eq!(client.get_claim_deadline(), 0);
+
+    // The admin upholds the oracle by finalizing the disputed market.
+    client.finalize_resolution(&Some(admin));
+    assert!(client.is_finalized());
+    assert_eq!(
+        client.get_claim_deadline(),
+        env.ledger().timestamp() + CLAIM_EXPIRY_SEC
+    );
+    // The failed challenger's bond plus the 2% fee.
+    assert_eq!(treasury.total(&Symbol::new(&env, "fees")), bond + 40);
+    assert_eq!(client.claim_winnings(&defender, &defender_bet), 1_960);
+}
@@ -0,0 +1,203 @@
+//! =============================================================================
+//! BOXMEOUT — Draw as a Bettable Side
+//! =============================================================================
+//!
+//! Covers markets created with `draw_enabled`:
+//!   - Draw is a third pool paid parimutuel from all three pools
+//!   - `get_pool_odds_with_draw` prices the draw pool alongside both fighters,
+//!     while `get_pool_odds` keeps its fighter-only shape
+//!   - NoContest still cancels and refunds every side
+//!   - Markets without `draw_enabled` reject Draw bets and refund on a Draw
+
+mod common;
+
+use common::{finalize, lock, setup_market_with, MarketParams, TestMarket};
+use market::types::{
+    BetSide, MarketStatus, OptionalOutcome, Outcome, PropSelection, VictoryMethod,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+fn setup_draw_market(env: &Env, draw_enabled: bool) -> TestMarket<'_> {
+    setup_market_with(
+        env,
+        MarketParams {
+            draw_enabled,
+            ..MarketParams::default()
+        },
+    )
+}
+
+// ─── Draw-enabled markets ─────────────────────────────────────────────────────
+
+#[test]
+fn draw_backers_paid_from_all_pools() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_draw_market(&env, true);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let d1 = Address::generate(&env);
+    let d2 = Address::generate(&env);
+
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &5_000i128);
+    let bet_b = client.place_bet(&b, &BetSide::FighterB, &3_000i128);
+    let bet_d1 = client.place_bet(&d1, &BetSide::Draw, &1_500i128);
+    let bet_d2 = client.place_bet(&d2, &BetSide::Draw, &500i128);
+
+    let m = client.get_market_info();
+    assert!(m.draw_enabled);
+    assert_eq!(m.pool_draw, 2_000);
+    assert_eq!(m.total_pool, m.pool_a + m.pool_b + m.pool_draw);
+    assert_eq!(
+        client.get_pool_odds_with_draw(),
+        (5_000, 3_000, 2_000, 5_000, 3_000, 2_000)
+    );
+    // The fighter-only view leaves the draw's share out.
+    assert_eq!(client.get_pool_odds(), (5_000, 3_000, 5_000, 3_000));
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::Draw);
+    finalize(&env, &client);
+
+    let m = client.get_market_info();
+    assert_eq!(m.status, MarketStatus::Resolved);
+    assert_eq!(m.outcome, OptionalOutcome::Some(Outcome::Draw));
+
+    // net = 10_000 - 2% = 9_800, split over the 2_000 draw pool.
+    assert_eq!(client.calculate_payout(&bet_d1), 1_500 * 9_800 / 2_000);
+    assert_eq!(client.claim_winnings(&d1, &bet_d1), 1_500 * 9_800 / 2_000);
+    assert_eq!(client.claim_winnings(&d2, &bet_d2), 500 * 9_800 / 2_000);
+
+    // Fighter backers lost, and cannot fall back to a refund.
+    assert!(client.try_claim_winnings(&a, &bet_a).is_err());
+    assert!(client.try_claim_winnings(&b, &bet_b).is_err());
+    assert!(client.try_claim_refund(&a, &bet_a).is_err());
+}
+
+#[test]
+fn empty_draw_market_splits_odds_three_ways() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, .. } = setup_draw_market(&env, true);
+
+    // Fighter B takes the truncation remainder so the odds sum to 10_000.
+    assert_eq!(
+        client.get_pool_odds_with_draw(),
+        (0, 0, 0, 3_333, 3_334, 3_333)
+    );
+
+    client.place_bet(&Address::generate(&env), &BetSide::Draw, &1_000i128);
+    assert_eq!(
+        client.get_pool_odds_with_draw(),
+        (0, 0, 1_000, 0, 0, 10_000)
+    );
+}
+
+#[test]
+fn draw_backers_lose_when_a_fighter_wins() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_draw_market(&env, true);
+
+    let a = Address::generate(&env);
+    let d = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    let bet_d = client.place_bet(&d, &BetSide::Draw, &1_000i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
+
+    assert_eq!(client.calculate_payout(&bet_d), 0);
+    assert!(client.try_claim_winnings(&d, &bet_d).is_err());
+    assert_eq!(client.claim_winnings(&a, &bet_a), 2_000 * 9_800 / 10_000);
+}
+
+#[test]
+fn no_contest_still_refunds_every_side() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_draw_market(&env, true);
+
+    let a = Address::generate(&env);
+    let d = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &700i128);
+    let bet_d = client.place_bet(&d, &BetSide::Draw, &300i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::NoContest);
+
+    assert_eq!(client.get_market_info().status, MarketStatus::Cancelled);
+    assert_eq!(client.claim_refund(&a, &bet_a), 700);
+    assert_eq!(client.claim_refund(&d, &bet_d), 300);
+}
+
+#[test]
+#[should_panic(expected = "a draw has no winner")]
+fn draw_by_method_prop_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, .. } = setup_draw_market(&env, true);
+
+    let bettor = Address::generate(&env);
+    client.place_prop_bet(
+        &bettor,
+        &PropSelection::WinsByMethod(BetSide::Draw, VictoryMethod::Decision),
+        &100i128,
+    );
+}
+
+// ─── Markets without draw betting ─────────────────────────────────────────────
+
+#[test]
+#[should_panic(expected = "draw betting not enabled")]
+fn draw_bet_rejected_when_not_enabled() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, .. } = setup_draw_market(&env, false);
+
+    let bettor = Address::generate(&env);
+    client.place_bet(&bettor, &BetSide::Draw, &1_000i128);
+}
+
+#[test]
+fn draw_outcome_refunds_when_not_enabled() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_draw_market(&env, false);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &5_000i128);
+    let bet_b = client.place_bet(&b, &BetSide::FighterB, &3_000i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::Draw);
+
+    assert_eq!(client.get_market_info().status, MarketStatus::Cancelled);
+    assert_eq!(client.claim_refund(&a, &bet_a), 5_000);
+    assert_eq!(client.claim_refund(&b, &bet_b), 3_000);
+}
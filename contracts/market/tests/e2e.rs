@@ -5,65 +5,25 @@
 //! Exercises the full Market contract lifecycle using the Soroban test
 //! environment (`soroban-sdk` testutils):
 //!
-//!   1. Deploy a mock factory + market
+//!   1. Deploy a market against the shared mock factory
 //!   2. Multiple users place bets on both sides
 //!   3. Lock the market (post betting window)
 //!   4. Oracle resolves with an outcome
 //!   5. Winners claim their proportional payouts
 //!   6. Assert final balances match expected payouts *exactly*
 
-use market::types::{
-    BetSide, Fighter, Market, MarketStatus, Outcome, ProtocolConfig,
-};
-use market::{DataKey, MarketContract, MarketContractClient};
+mod common;
+
+use common::{finalize, setup_market_with, MarketParams, TestMarket};
+use market::types::{BetSide, Market, MarketStatus, OptionalOutcome, Outcome};
+use market::DataKey;
 use soroban_sdk::{
-    contract, contractimpl,
     testutils::{Address as _, Ledger},
-    Address, Bytes, Env, String, Symbol,
+    Address, Env,
 };
 
-// ─── Mock Factory ─────────────────────────────────────────────────────────────
-
-#[contract]
-struct MockFactory;
-
-#[contractimpl]
-impl MockFactory {
-    pub fn __constructor(env: Env, admin: Address) {
-        env.storage()
-            .persistent()
-            .set(&Symbol::new(&env, "admin"), &admin);
-    }
-
-    pub fn get_config(env: Env) -> ProtocolConfig {
-        let admin: Address = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, "admin"))
-            .unwrap();
-        ProtocolConfig {
-            admin: admin.clone(),
-            fee_collector: admin,
-            default_fee_bp: 200,
-            min_bet_amount: 100,
-            max_bet_amount: 100_000_000_000,
-            dispute_window_sec: 86_400,
-            paused: false,
-        }
-    }
-}
-
 // ─── Helpers ──────────────────────────────────────────────────────────────────
 
-fn make_fighter(env: &Env, name: &str) -> Fighter {
-    Fighter {
-        name: String::from_str(env, name),
-        record: String::from_str(env, "10-0"),
-        nationality: String::from_str(env, "US"),
-        weight_class: String::from_str(env, "Heavyweight"),
-    }
-}
-
 /// Compute expected payout using the same formula as `claim_winnings`:
 ///   payout = bet_amount * (total_pool - fee) / winning_pool
 fn expected_payout(bet_amount: i128, winning_pool: i128, total_pool: i128, fee_bp: u32) -> i128 {
@@ -90,30 +50,22 @@ fn e2e_create_bet_lock_resolve_claim() {
     let env = Env::default();
     env.mock_all_auths();
 
-    // ── 1. Deploy factory ─────────────────────────────────────────────────
-    let admin = Address::generate(&env);
-    let factory_id = env.register(MockFactory, (admin.clone(),));
-
-    let oracle = Address::generate(&env);
-    let fee_collector = Address::generate(&env);
-    let market_cid = env.register(MarketContract, ());
-    let client = MarketContractClient::new(&env, &market_cid);
-
-    let now = env.ledger().timestamp();
-    let scheduled_at = now + 2_000_000;
-    let betting_ends_at = now + 1_000_000;
-
-    client.initialize(
-        &Bytes::from_array(&env, &[0xAAu8; 32]),
-        &make_fighter(&env, "Canelo"),
-        &make_fighter(&env, "GGG"),
-        &scheduled_at,
-        &betting_ends_at,
-        &oracle,
-        &factory_id,
-        &200u32, // 2% protocol fee
-        &fee_collector,
+    // ── 1. Deploy factory and market ──────────────────────────────────────
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market_with(
+        &env,
+        MarketParams {
+            market_id: [0xAA; 32],
+            fighter_a: "Canelo",
+            fighter_b: "GGG",
+            ..MarketParams::default()
+        },
     );
+    let market_cid = client.address.clone();
 
     // Verify market is initialized and Open
     let m = client.get_market_info();
@@ -147,7 +99,7 @@ fn e2e_create_bet_lock_resolve_claim() {
     assert_eq!(pa, 800);
     assert_eq!(pb, 900);
     assert_eq!(odds_a, 800 * 10_000 / 1700);
-    assert_eq!(odds_b, 900 * 10_000 / 1700);
+    assert_eq!(odds_b, 10_000 - odds_a);
     assert_eq!(odds_a + odds_b, 10_000);
 
     // Verify bet retrieval
@@ -179,19 +131,21 @@ fn e2e_create_bet_lock_resolve_claim() {
 
     // ── 4. Resolve market (FighterA wins) ─────────────────────────────────
     client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
 
     let m = client.get_market_info();
     assert!(matches!(m.status, MarketStatus::Resolved));
-    assert_eq!(m.outcome, Some(Outcome::FighterA));
+    assert_eq!(m.outcome, OptionalOutcome::Some(Outcome::FighterA));
 
     // ── 5. Claim winnings ─────────────────────────────────────────────────
     let payout_1 = client.claim_winnings(&bettor_1, &bet1_id);
     let expected_1 = expected_payout(300, 800, 1700, 200);
     assert_eq!(payout_1, expected_1, "bettor 1 payout mismatch");
 
+    // The last winning claim also takes the rounding remainder.
     let payout_2 = client.claim_winnings(&bettor_2, &bet2_id);
     let expected_2 = expected_payout(500, 800, 1700, 200);
-    assert_eq!(payout_2, expected_2, "bettor 2 payout mismatch");
+    assert_eq!(payout_2, expected_2 + 1, "bettor 2 payout mismatch");
 
     // Losing bettors must NOT be able to claim
     assert!(client.try_claim_winnings(&bettor_3, &bet3_id).is_err());
@@ -204,15 +158,10 @@ fn e2e_create_bet_lock_resolve_claim() {
         .expect("fee mul")
         .checked_div(10_000)
         .expect("fee div");
-    assert!(
-        total_claimed + fee >= 1700 - 1,
-        "claimed({}) + fee({}) should approximate total_pool(1700)",
-        total_claimed,
-        fee
-    );
-    assert!(
-        total_claimed + fee <= 1700,
-        "claimed + fee must not exceed total_pool"
+    assert_eq!(
+        total_claimed + fee,
+        1700,
+        "claimed + fee must add up to total_pool"
     );
 
     // ── 7. Double-claim guard ─────────────────────────────────────────────
@@ -226,29 +175,21 @@ fn e2e_draw_both_sides_refunded() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let admin = Address::generate(&env);
-    let factory_id = env.register(MockFactory, (admin.clone(),));
-
-    let oracle = Address::generate(&env);
-    let fee_collector = Address::generate(&env);
-    let market_cid = env.register(MarketContract, ());
-    let client = MarketContractClient::new(&env, &market_cid);
-
-    let now = env.ledger().timestamp();
-    let scheduled_at = now + 2_000_000;
-    let betting_ends_at = now + 1_000_000;
-
-    client.initialize(
-        &Bytes::from_array(&env, &[0xBBu8; 32]),
-        &make_fighter(&env, "Fury"),
-        &make_fighter(&env, "Usyk"),
-        &scheduled_at,
-        &betting_ends_at,
-        &oracle,
-        &factory_id,
-        &200u32,
-        &fee_collector,
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market_with(
+        &env,
+        MarketParams {
+            market_id: [0xBB; 32],
+            fighter_a: "Fury",
+            fighter_b: "Usyk",
+            ..MarketParams::default()
+        },
     );
+    let market_cid = client.address.clone();
 
     let bettor_a = Address::generate(&env);
     let bettor_b = Address::generate(&env);
@@ -294,28 +235,14 @@ fn e2e_cancelled_market_full_refund() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let admin = Address::generate(&env);
-    let factory_id = env.register(MockFactory, (admin.clone(),));
-
-    let oracle = Address::generate(&env);
-    let fee_collector = Address::generate(&env);
-    let market_cid = env.register(MarketContract, ());
-    let client = MarketContractClient::new(&env, &market_cid);
-
-    let now = env.ledger().timestamp();
-    let scheduled_at = now + 2_000_000;
-    let betting_ends_at = now + 1_000_000;
-
-    client.initialize(
-        &Bytes::from_array(&env, &[0xCCu8; 32]),
-        &make_fighter(&env, "Bivol"),
-        &make_fighter(&env, "Beterbiev"),
-        &scheduled_at,
-        &betting_ends_at,
-        &oracle,
-        &factory_id,
-        &200u32,
-        &fee_collector,
+    let TestMarket { client, admin, .. } = setup_market_with(
+        &env,
+        MarketParams {
+            market_id: [0xCC; 32],
+            fighter_a: "Bivol",
+            fighter_b: "Beterbiev",
+            ..MarketParams::default()
+        },
     );
 
     let bettor = Address::generate(&env);
@@ -11,87 +11,33 @@
 //!   - Empty market query edge cases
 //!   - Double claim detection
 //!   - Total pool invariant across many bets
+//!   - One-sided markets: nobody backed the winner, so every bettor is refunded
 //!
 //! ≥10 distinct test cases, all must pass without panic/overflow.
 
+mod common;
+
+use common::{finalize, setup_market, setup_market_with, update_config, MarketParams, TestMarket};
 use market::types::{
-    BetSide, Fighter, Market, MarketStatus, Outcome, ProtocolConfig,
+    BetSide, FightResult, Market, MarketStatus, OptionalOutcome, Outcome, PropSelection,
+    VictoryMethod,
 };
-use market::{DataKey, MarketContract, MarketContractClient};
+use market::DataKey;
 use soroban_sdk::{
-    contract, contractimpl,
     testutils::{Address as _, Ledger},
-    Address, Bytes, Env, String, Symbol,
+    Address, Env,
 };
 
-// ─── Mock Factory ─────────────────────────────────────────────────────────────
-
-#[contract]
-struct MockFactory;
-
-#[contractimpl]
-impl MockFactory {
-    pub fn __constructor(env: Env, admin: Address) {
-        env.storage()
-            .persistent()
-            .set(&Symbol::new(&env, "admin"), &admin);
-    }
-
-    pub fn get_config(env: Env) -> ProtocolConfig {
-        let admin: Address = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, "admin"))
-            .unwrap();
-        ProtocolConfig {
-            admin: admin.clone(),
-            fee_collector: admin,
-            default_fee_bp: 200,
-            min_bet_amount: 100,
-            max_bet_amount: i128::MAX / 2,
-            dispute_window_sec: 86_400,
-            paused: false,
-        }
-    }
-}
-
 // ─── Helpers ──────────────────────────────────────────────────────────────────
 
-fn make_fighter(env: &Env, name: &str) -> Fighter {
-    Fighter {
-        name: String::from_str(env, name),
-        record: String::from_str(env, "10-0"),
-        nationality: String::from_str(env, "US"),
-        weight_class: String::from_str(env, "Heavyweight"),
-    }
+/// A default market whose bet cap leaves room for the stress tests.
+fn setup_uncapped(env: &Env) -> TestMarket<'_> {
+    uncap(setup_market(env))
 }
 
-fn setup_market(env: &Env) -> (MarketContractClient, Address, Address, u64) {
-    let admin = Address::generate(env);
-    let factory_id = env.register(MockFactory, (admin.clone(),));
-    let oracle = Address::generate(env);
-    let fee_collector = Address::generate(env);
-
-    let now = env.ledger().timestamp();
-    let scheduled_at = now + 2_000_000;
-    let betting_ends_at = now + 1_000_000;
-
-    let market_cid = env.register(MarketContract, ());
-    let client = MarketContractClient::new(env, &market_cid);
-
-    client.initialize(
-        &Bytes::from_array(env, &[0xEEu8; 32]),
-        &make_fighter(env, "Ali"),
-        &make_fighter(env, "Frazier"),
-        &scheduled_at,
-        &betting_ends_at,
-        &oracle,
-        &factory_id,
-        &200u32,
-        &fee_collector,
-    );
-
-    (client, oracle, admin, betting_ends_at)
+fn uncap(market: TestMarket<'_>) -> TestMarket<'_> {
+    update_config(&market.factory, |c| c.max_bet_amount = i128::MAX / 2);
+    market
 }
 
 fn lock_market_via_storage(env: &Env, contract_id: &Address) {
@@ -113,7 +59,12 @@ fn edge_zero_losing_pool_all_on_winner() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, oracle, _admin, betting_ends_at) = setup_market(&env);
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_uncapped(&env);
     let market_cid = client.address.clone();
 
     let b1 = Address::generate(&env);
@@ -136,6 +87,7 @@ fn edge_zero_losing_pool_all_on_winner() {
     env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
     lock_market_via_storage(&env, &market_cid);
     client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
 
     let p1 = client.claim_winnings(&b1, &bet1);
     let p2 = client.claim_winnings(&b2, &bet2);
@@ -155,7 +107,12 @@ fn edge_single_bettor_wins() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, oracle, _admin, betting_ends_at) = setup_market(&env);
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_uncapped(&env);
     let market_cid = client.address.clone();
 
     let bettor = Address::generate(&env);
@@ -167,6 +124,7 @@ fn edge_single_bettor_wins() {
     env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
     lock_market_via_storage(&env, &market_cid);
     client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
 
     let payout = client.claim_winnings(&bettor, &bet_id);
     let expected = 1_000_000 - (1_000_000 * 200 / 10_000);
@@ -180,29 +138,13 @@ fn edge_max_i128_pool_totals() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let admin = Address::generate(&env);
-    let factory_id = env.register(MockFactory, (admin.clone(),));
-    let oracle = Address::generate(&env);
-    let fee_collector = Address::generate(&env);
-
-    let now = env.ledger().timestamp();
-    let scheduled_at = now + 2_000_000;
-    let betting_ends_at = now + 1_000_000;
-
-    let market_cid = env.register(MarketContract, ());
-    let client = MarketContractClient::new(&env, &market_cid);
-
-    client.initialize(
-        &Bytes::from_array(&env, &[0xFFu8; 32]),
-        &make_fighter(&env, "Tyson"),
-        &make_fighter(&env, "Holyfield"),
-        &scheduled_at,
-        &betting_ends_at,
-        &oracle,
-        &factory_id,
-        &200u32,
-        &fee_collector,
-    );
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_uncapped(&env);
+    let market_cid = client.address.clone();
 
     let max_bet = 1_000_000_000_000_000_000i128; // 10^18
     let b1 = Address::generate(&env);
@@ -221,6 +163,7 @@ fn edge_max_i128_pool_totals() {
     env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
     lock_market_via_storage(&env, &market_cid);
     client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
 
     let payout = client.claim_winnings(&b1, &bet1);
     assert!(payout > 0, "large pool payout must be > 0");
@@ -234,7 +177,7 @@ fn edge_zero_bet_amount_panics() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _oracle, _admin, _betting_ends_at) = setup_market(&env);
+    let TestMarket { client, .. } = setup_uncapped(&env);
     let bettor = Address::generate(&env);
     client.place_bet(&bettor, &BetSide::FighterA, &0i128);
 }
@@ -247,7 +190,7 @@ fn edge_below_minimum_bet_panics() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _oracle, _admin, _betting_ends_at) = setup_market(&env);
+    let TestMarket { client, .. } = setup_uncapped(&env);
     let bettor = Address::generate(&env);
     client.place_bet(&bettor, &BetSide::FighterA, &1i128);
 }
@@ -257,11 +200,11 @@ fn edge_minimum_bet_amount_succeeds() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _oracle, _admin, _betting_ends_at) = setup_market(&env);
+    let TestMarket { client, .. } = setup_uncapped(&env);
     let bettor = Address::generate(&env);
 
     let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &100i128);
-    assert!(!bet_id.to_array().is_empty());
+    assert!(!bet_id.is_empty());
 }
 
 #[test]
@@ -269,11 +212,11 @@ fn edge_maximum_bet_amount_succeeds() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _oracle, _admin, _betting_ends_at) = setup_market(&env);
+    let TestMarket { client, .. } = setup_uncapped(&env);
     let bettor = Address::generate(&env);
 
     let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &(i128::MAX / 2));
-    assert!(!bet_id.to_array().is_empty());
+    assert!(!bet_id.is_empty());
 }
 
 // ─── Test 6: Multiple claims on same bet must panic ───────────────────────────
@@ -284,7 +227,12 @@ fn edge_double_claim_panics() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, oracle, _admin, betting_ends_at) = setup_market(&env);
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_uncapped(&env);
     let market_cid = client.address.clone();
 
     let bettor = Address::generate(&env);
@@ -293,6 +241,7 @@ fn edge_double_claim_panics() {
     env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
     lock_market_via_storage(&env, &market_cid);
     client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
 
     let _ = client.claim_winnings(&bettor, &bet_id);
     let _ = client.claim_winnings(&bettor, &bet_id); // panics
@@ -305,13 +254,15 @@ fn edge_empty_market_pool_odds() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _oracle, _admin, _betting_ends_at) = setup_market(&env);
+    let TestMarket { client, .. } = setup_uncapped(&env);
 
-    let (pool_a, pool_b, odds_a, odds_b) = client.get_pool_odds();
+    let (pool_a, pool_b, pool_draw, odds_a, odds_b, odds_draw) = client.get_pool_odds_with_draw();
     assert_eq!(pool_a, 0);
     assert_eq!(pool_b, 0);
+    assert_eq!(pool_draw, 0);
     assert_eq!(odds_a, 5_000);
     assert_eq!(odds_b, 5_000);
+    assert_eq!(odds_draw, 0);
 }
 
 // ─── Test 8: claim_winnings panics when market not resolved ───────────────────
@@ -322,7 +273,7 @@ fn edge_claim_before_resolution_panics() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _oracle, _admin, _betting_ends_at) = setup_market(&env);
+    let TestMarket { client, .. } = setup_uncapped(&env);
 
     let bettor = Address::generate(&env);
     let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &500i128);
@@ -337,7 +288,7 @@ fn edge_total_pool_invariant_many_bets() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let (client, _oracle, _admin, _betting_ends_at) = setup_market(&env);
+    let TestMarket { client, .. } = setup_uncapped(&env);
 
     let mut expected_pool_a: i128 = 0;
     let mut expected_pool_b: i128 = 0;
@@ -356,13 +307,18 @@ fn edge_total_pool_invariant_many_bets() {
         match side {
             BetSide::FighterA => expected_pool_a = expected_pool_a.checked_add(amount).unwrap(),
             BetSide::FighterB => expected_pool_b = expected_pool_b.checked_add(amount).unwrap(),
+            BetSide::Draw => unreachable!(),
         }
         expected_total = expected_total.checked_add(amount).unwrap();
 
         let m = client.get_market_info();
         assert_eq!(m.pool_a, expected_pool_a, "pool_a mismatch at bet {}", i);
         assert_eq!(m.pool_b, expected_pool_b, "pool_b mismatch at bet {}", i);
-        assert_eq!(m.total_pool, expected_total, "total_pool mismatch at bet {}", i);
+        assert_eq!(
+            m.total_pool, expected_total,
+            "total_pool mismatch at bet {}",
+            i
+        );
         assert_eq!(
             m.total_pool,
             m.pool_a + m.pool_b,
@@ -378,29 +334,19 @@ fn edge_zero_fee_full_payout() {
     let env = Env::default();
     env.mock_all_auths();
 
-    let admin = Address::generate(&env);
-    let factory_id = env.register(MockFactory, (admin.clone(),));
-    let oracle = Address::generate(&env);
-    let fee_collector = Address::generate(&env);
-
-    let now = env.ledger().timestamp();
-    let scheduled_at = now + 2_000_000;
-    let betting_ends_at = now + 1_000_000;
-
-    let market_cid = env.register(MarketContract, ());
-    let client = MarketContractClient::new(&env, &market_cid);
-
-    client.initialize(
-        &Bytes::from_array(&env, &[0x11u8; 32]),
-        &make_fighter(&env, "Lomachenko"),
-        &make_fighter(&env, "Lopez"),
-        &scheduled_at,
-        &betting_ends_at,
-        &oracle,
-        &factory_id,
-        &0u32,
-        &fee_collector,
-    );
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = uncap(setup_market_with(
+        &env,
+        MarketParams {
+            fee_bp: 0,
+            ..MarketParams::default()
+        },
+    ));
+    let market_cid = client.address.clone();
 
     let b1 = Address::generate(&env);
     let b2 = Address::generate(&env);
@@ -411,8 +357,111 @@ fn edge_zero_fee_full_payout() {
     env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
     lock_market_via_storage(&env, &market_cid);
     client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
 
     // With 0 fee, winner gets 100% of total pool
     let payout = client.claim_winnings(&b1, &bet1);
     assert_eq!(payout, 1000);
 }
+
+// ─── Test 11: Winning side has no bets — everyone is refunded ─────────────────
+
+#[test]
+fn edge_unbacked_winner_refunds_every_bettor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_uncapped(&env);
+    let market_cid = client.address.clone();
+
+    let b1 = Address::generate(&env);
+    let b2 = Address::generate(&env);
+    let bet1 = client.place_bet(&b1, &BetSide::FighterB, &400_000i128);
+    let bet2 = client.place_bet(&b2, &BetSide::FighterB, &100_000i128);
+
+    env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
+    lock_market_via_storage(&env, &market_cid);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+
+    let m = client.get_market_info();
+    assert_eq!(m.status, MarketStatus::Cancelled);
+    assert_eq!(m.outcome, OptionalOutcome::Some(Outcome::FighterA));
+
+    assert_eq!(client.calculate_payout(&bet1), 400_000);
+    let (_, _, odds_a, odds_b) = client.get_pool_odds();
+    assert_eq!((odds_a, odds_b), (0, 0));
+
+    // Full stake back, no fee taken.
+    assert!(client.try_claim_winnings(&b1, &bet1).is_err());
+    assert_eq!(client.claim_refund(&b1, &bet1), 400_000);
+    assert_eq!(client.claim_refund(&b2, &bet2), 100_000);
+}
+
+// ─── Test 12: Empty market resolved — nothing orphaned, nothing to claim ──────
+
+#[test]
+fn edge_unbacked_winner_on_empty_market_cancels() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_uncapped(&env);
+    let market_cid = client.address.clone();
+
+    env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
+    lock_market_via_storage(&env, &market_cid);
+    client.resolve_market(&oracle, &Outcome::FighterB);
+
+    assert_eq!(client.get_market_info().status, MarketStatus::Cancelled);
+}
+
+// ─── Test 13: One-sided main pool still grades prop bets ──────────────────────
+
+#[test]
+fn edge_unbacked_winner_still_grades_props() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_uncapped(&env);
+    let market_cid = client.address.clone();
+
+    let loser = Address::generate(&env);
+    let prop_winner = Address::generate(&env);
+    let prop_loser = Address::generate(&env);
+    let bet = client.place_bet(&loser, &BetSide::FighterB, &1_000i128);
+    let won = client.place_prop_bet(&prop_winner, &PropSelection::EndsInRound(3), &1_000i128);
+    let lost = client.place_prop_bet(&prop_loser, &PropSelection::GoesTheDistance, &1_000i128);
+
+    env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
+    lock_market_via_storage(&env, &market_cid);
+    client.resolve_with_result(
+        &oracle,
+        &FightResult {
+            winner: Outcome::FighterA,
+            method: VictoryMethod::Knockout,
+            round: 3,
+        },
+    );
+
+    assert_eq!(client.get_market_info().status, MarketStatus::Cancelled);
+    assert_eq!(client.claim_refund(&loser, &bet), 1_000);
+    assert_eq!(
+        client.claim_prop_winnings(&prop_winner, &won),
+        2_000 * 9_800 / 10_000
+    );
+    assert!(client.try_claim_prop_winnings(&prop_loser, &lost).is_err());
+}
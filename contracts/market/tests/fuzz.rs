@@ -4,52 +4,22 @@
 //!
 //! Feeds randomized bet sequences into the Market contract to confirm no
 //! overflow, panic, or invariant violations under realistic and adversarial
-//! inputs.
+//! inputs. The lifecycle suite interleaves bets, cash-outs, withdrawals,
+//! settlement, claims and refunds, and checks `check_invariants` after
+//! every step.
 //!
 //! Runs in CI with a bounded number of test cases (configurable via
 //! `PROPTEST_CASES` env var, default 256).
 
-use market::types::{
-    BetSide, Fighter, Market, MarketStatus, Outcome, ProtocolConfig,
-};
-use market::{DataKey, MarketContract, MarketContractClient};
+mod common;
+
+use common::{finalize, setup_market_with, update_config, MarketParams, TestMarket};
+use market::types::{BetSide, MarketStatus, Outcome};
 use soroban_sdk::{
-    contract, contractimpl,
     testutils::{Address as _, Ledger},
-    Address, Bytes, Env, String, Symbol, Vec,
+    Address, Bytes, Env, Vec,
 };
 
-// ─── Mock Factory ─────────────────────────────────────────────────────────────
-
-#[contract]
-struct MockFactory;
-
-#[contractimpl]
-impl MockFactory {
-    pub fn __constructor(env: Env, admin: Address) {
-        env.storage()
-            .persistent()
-            .set(&Symbol::new(&env, "admin"), &admin);
-    }
-
-    pub fn get_config(env: Env) -> ProtocolConfig {
-        let admin: Address = env
-            .storage()
-            .persistent()
-            .get(&Symbol::new(&env, "admin"))
-            .unwrap();
-        ProtocolConfig {
-            admin: admin.clone(),
-            fee_collector: admin,
-            default_fee_bp: 200,
-            min_bet_amount: 1,
-            max_bet_amount: 100_000_000_000,
-            dispute_window_sec: 86_400,
-            paused: false,
-        }
-    }
-}
-
 // ─── Proptest strategies ──────────────────────────────────────────────────────
 
 mod proptest_helpers {
@@ -69,19 +39,67 @@ mod proptest_helpers {
     pub fn bet_sequence(max_len: usize) -> impl Strategy<Value = Vec<(i128, bool)>> {
         proptest::collection::vec((bet_amount(), bet_side()), 1..max_len)
     }
-}
 
-// ─── Helper ───────────────────────────────────────────────────────────────────
+    /// One step while the market is open. `bettor` and `pick` are reduced
+    /// modulo the bettors and bets that exist at that point.
+    #[derive(Clone, Debug)]
+    pub enum OpenOp {
+        Bet { bettor: u8, side: u8, amount: i128 },
+        CashOut { pick: usize },
+        Withdraw { pick: usize },
+        Reschedule,
+    }
 
-fn make_fighter(env: &Env, name: &str) -> Fighter {
-    Fighter {
-        name: String::from_str(env, name),
-        record: String::from_str(env, "10-0"),
-        nationality: String::from_str(env, "US"),
-        weight_class: String::from_str(env, "Heavyweight"),
+    /// How the market settles.
+    #[derive(Clone, Debug)]
+    pub enum Settlement {
+        Resolve(u8),
+        Cancel,
+    }
+
+    pub fn open_op() -> impl Strategy<Value = OpenOp> {
+        prop_oneof![
+            6 => (any::<u8>(), 0u8..3, bet_amount())
+                .prop_map(|(bettor, side, amount)| OpenOp::Bet { bettor, side, amount }),
+            1 => any::<usize>().prop_map(|pick| OpenOp::CashOut { pick }),
+            1 => any::<usize>().prop_map(|pick| OpenOp::Withdraw { pick }),
+            1 => Just(OpenOp::Reschedule),
+        ]
+    }
+
+    pub fn settlement() -> impl Strategy<Value = Settlement> {
+        prop_oneof![
+            4 => (0u8..4).prop_map(Settlement::Resolve),
+            1 => Just(Settlement::Cancel),
+        ]
+    }
+
+    /// Claims after settlement: (bettor, use `claim_all`).
+    pub fn claim_sequence(max_len: usize) -> impl Strategy<Value = Vec<(u8, bool)>> {
+        proptest::collection::vec((any::<u8>(), any::<bool>()), 0..max_len)
     }
 }
 
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+/// A fresh market with draw betting enabled and a betting window no
+/// generated sequence runs past.
+fn setup_fuzz_market(env: &Env, seed: u8) -> TestMarket<'_> {
+    let market = setup_market_with(
+        env,
+        MarketParams {
+            market_id: [seed; 32],
+            fighter_a: "Alpha",
+            fighter_b: "Beta",
+            draw_enabled: true,
+            betting_window_sec: 10_000_000,
+            ..MarketParams::default()
+        },
+    );
+    update_config(&market.factory, |c| c.min_bet_amount = 1);
+    market
+}
+
 // ─── Fuzz: place bets only ────────────────────────────────────────────────────
 
 proptest::proptest! {
@@ -94,33 +112,12 @@ proptest::proptest! {
         let env = Env::default();
         env.mock_all_auths();
 
-        let admin = Address::generate(&env);
-        let factory_id = env.register(MockFactory, (admin.clone(),));
-        let oracle = Address::generate(&env);
-        let fee_collector = Address::generate(&env);
-
-        let now = env.ledger().timestamp();
-        let betting_ends_at = now + 10_000_000;
-
-        let market_cid = env.register(MarketContract, ());
-        let client = MarketContractClient::new(&env, &market_cid);
-
-        client.initialize(
-            &Bytes::from_array(&env, &[1u8; 32]),
-            &make_fighter(&env, "Alpha"),
-            &make_fighter(&env, "Beta"),
-            &(betting_ends_at + 1_000_000),
-            &betting_ends_at,
-            &oracle,
-            &factory_id,
-            &200u32,
-            &fee_collector,
-        );
+        let TestMarket { client, .. } = setup_fuzz_market(&env, 1);
 
         for (amount, is_a) in &bets {
             let side = if *is_a { BetSide::FighterA } else { BetSide::FighterB };
             let bettor = Address::generate(&env);
-            let _bet_id = client.place_bet(&bettor, &side, &amount);
+            let _bet_id = client.place_bet(&bettor, &side, amount);
 
             // Basic invariant: total_pool >= pool_a + pool_b
             let m = client.get_market_info();
@@ -129,6 +126,8 @@ proptest::proptest! {
                 "total_pool must be >= pool_a + pool_b"
             );
         }
+        let report = client.check_invariants();
+        proptest::prop_assert!(report.ok, "{:?}", report);
     }
 }
 
@@ -142,28 +141,7 @@ proptest::proptest! {
         let env = Env::default();
         env.mock_all_auths();
 
-        let admin = Address::generate(&env);
-        let factory_id = env.register(MockFactory, (admin.clone(),));
-        let oracle = Address::generate(&env);
-        let fee_collector = Address::generate(&env);
-
-        let now = env.ledger().timestamp();
-        let betting_ends_at = now + 10_000_000;
-
-        let market_cid = env.register(MarketContract, ());
-        let client = MarketContractClient::new(&env, &market_cid);
-
-        client.initialize(
-            &Bytes::from_array(&env, &[2u8; 32]),
-            &make_fighter(&env, "Alpha"),
-            &make_fighter(&env, "Beta"),
-            &(betting_ends_at + 1_000_000),
-            &betting_ends_at,
-            &oracle,
-            &factory_id,
-            &200u32,
-            &fee_collector,
-        );
+        let TestMarket { client, oracle, betting_ends_at, .. } = setup_fuzz_market(&env, 2);
 
         // Track (bettor, bet_id, amount) for claims later
         let mut bets_on_a: Vec<(Address, Bytes, i128)> = Vec::new(&env);
@@ -172,11 +150,12 @@ proptest::proptest! {
         for (amount, is_a) in &bets {
             let side = if *is_a { BetSide::FighterA } else { BetSide::FighterB };
             let bettor = Address::generate(&env);
-            let bet_id = client.place_bet(&bettor, &side, &amount);
+            let bet_id = client.place_bet(&bettor, &side, amount);
 
             match side {
                 BetSide::FighterA => bets_on_a.push_back((bettor, bet_id, *amount)),
                 BetSide::FighterB => bets_on_b.push_back((bettor, bet_id, *amount)),
+                BetSide::Draw => unreachable!(),
             }
         }
 
@@ -186,19 +165,16 @@ proptest::proptest! {
         // Fast-forward past betting deadline, then lock and resolve
         env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
 
-        // Manually lock storage (lock_market is a todo!() currently)
-        env.as_contract(&market_cid, || {
-            let mut m: Market = env
-                .storage()
-                .persistent()
-                .get(&DataKey::MarketInfo)
-                .unwrap();
-            m.status = MarketStatus::Locked;
-            env.storage().persistent().set(&DataKey::MarketInfo, &m);
-        });
+        client.lock_market(&oracle);
 
         // Resolve with FighterA winning
         client.resolve_market(&oracle, &Outcome::FighterA);
+        if bets_on_a.is_empty() {
+            // Nobody backed the winner, so the market refunds instead.
+            proptest::prop_assert_eq!(client.get_market_info().status, MarketStatus::Cancelled);
+            return Ok(());
+        }
+        finalize(&env, &client);
 
         // Claim all winning bets
         let mut total_claimed = 0i128;
@@ -221,6 +197,8 @@ proptest::proptest! {
             total_claimed + expected_fee >= total_pool - (bets_on_a.len() as i128),
             "claimed + fee should be close to total pool"
         );
+        let report = client.check_invariants();
+        proptest::prop_assert!(report.ok, "{:?}", report);
     }
 }
 
@@ -233,41 +211,129 @@ proptest::proptest! {
         let env = Env::default();
         env.mock_all_auths();
 
-        let admin = Address::generate(&env);
-        let factory_id = env.register(MockFactory, (admin.clone(),));
-        let oracle = Address::generate(&env);
-        let fee_collector = Address::generate(&env);
-
-        let now = env.ledger().timestamp();
-        let betting_ends_at = now + 10_000_000;
-
-        let market_cid = env.register(MarketContract, ());
-        let client = MarketContractClient::new(&env, &market_cid);
-
-        client.initialize(
-            &Bytes::from_array(&env, &[3u8; 32]),
-            &make_fighter(&env, "Alpha"),
-            &make_fighter(&env, "Beta"),
-            &(betting_ends_at + 1_000_000),
-            &betting_ends_at,
-            &oracle,
-            &factory_id,
-            &200u32,
-            &fee_collector,
-        );
+        let TestMarket { client, .. } = setup_fuzz_market(&env, 3);
 
         for (amount, is_a) in &bets {
             let side = if *is_a { BetSide::FighterA } else { BetSide::FighterB };
             let bettor = Address::generate(&env);
-            let _ = client.place_bet(&bettor, &side, &amount);
+            let _ = client.place_bet(&bettor, &side, amount);
         }
 
-        let (pa, pb, odds_a, odds_b) = client.get_pool_odds();
+        let (pa, pb, _, odds_a, odds_b, odds_draw) = client.get_pool_odds_with_draw();
 
         assert!(odds_a <= 10_000, "odds_a out of range: {}", odds_a);
         assert!(odds_b <= 10_000, "odds_b out of range: {}", odds_b);
+        assert_eq!(odds_draw, 0, "draw odds on a two-way market: {}", odds_draw);
         if pa + pb > 0 {
             assert_eq!(odds_a + odds_b, 10_000, "odds must sum to 10000");
         }
     }
 }
+
+// ─── Fuzz: invariants across the lifecycle ────────────────────────────────────
+
+/// Runs `check_invariants` and fails the case unless every invariant holds.
+macro_rules! assert_invariants {
+    ($client:expr, $step:expr) => {{
+        let report = $client.check_invariants();
+        proptest::prop_assert!(report.ok, "after {:?}: {:?}", $step, report);
+        report
+    }};
+}
+
+proptest::proptest! {
+    /// Random bets, cash-outs, withdrawals and reschedules, then a random
+    /// settlement and random single or batch claims. `check_invariants` must
+    /// hold after every step, and once everything is claimed the escrow still
+    /// covers the ledger.
+    #[test]
+    fn fuzz_invariants_hold_through_lifecycle(
+        ops in proptest::collection::vec(proptest_helpers::open_op(), 1..24),
+        settlement in proptest_helpers::settlement(),
+        claims in proptest_helpers::claim_sequence(12),
+    ) {
+        use proptest_helpers::{OpenOp, Settlement};
+
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let TestMarket { client, oracle, admin, betting_ends_at, .. } = setup_fuzz_market(&env, 4);
+        let bettors: std::vec::Vec<Address> = (0..4).map(|_| Address::generate(&env)).collect();
+        let mut bets: std::vec::Vec<(Address, Bytes)> = std::vec::Vec::new();
+        assert_invariants!(client, "initialize");
+
+        // Rejected operations (an unwound bet, no withdrawal window) must
+        // leave the books untouched just like accepted ones.
+        for op in &ops {
+            match op {
+                OpenOp::Bet { bettor, side, amount } => {
+                    let bettor = &bettors[*bettor as usize % bettors.len()];
+                    let side = match side {
+                        0 => BetSide::FighterA,
+                        1 => BetSide::FighterB,
+                        _ => BetSide::Draw,
+                    };
+                    if let Ok(Ok(bet_id)) = client.try_place_bet(bettor, &side, amount) {
+                        bets.push((bettor.clone(), bet_id));
+                    }
+                }
+                OpenOp::CashOut { pick } if !bets.is_empty() => {
+                    let (bettor, bet_id) = &bets[pick % bets.len()];
+                    let _ = client.try_cash_out(bettor, bet_id);
+                }
+                OpenOp::Withdraw { pick } if !bets.is_empty() => {
+                    let (bettor, bet_id) = &bets[pick % bets.len()];
+                    let _ = client.try_withdraw_bet(bettor, bet_id);
+                }
+                OpenOp::Reschedule => {
+                    client.reschedule_market(&oracle, &(betting_ends_at + 1_000_000), &betting_ends_at);
+                }
+                _ => {}
+            }
+            assert_invariants!(client, op);
+        }
+
+        env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
+        match &settlement {
+            Settlement::Resolve(outcome) => {
+                let outcome = match outcome {
+                    0 => Outcome::FighterA,
+                    1 => Outcome::FighterB,
+                    2 => Outcome::Draw,
+                    _ => Outcome::NoContest,
+                };
+                client.lock_market(&oracle);
+                client.resolve_market(&oracle, &outcome);
+                if client.get_market_info().status == MarketStatus::Resolved {
+                    finalize(&env, &client);
+                }
+            }
+            Settlement::Cancel => client.cancel_market(&admin),
+        }
+        let settled = assert_invariants!(client, settlement);
+
+        for (bettor, use_claim_all) in &claims {
+            let bettor = &bettors[*bettor as usize % bettors.len()];
+            if *use_claim_all {
+                let _ = client.try_claim_all(bettor, &0u32);
+            } else {
+                for (owner, bet_id) in bets.iter().filter(|(owner, _)| owner == bettor) {
+                    if client.try_claim_winnings(owner, bet_id).is_err() {
+                        let _ = client.try_claim_refund(owner, bet_id);
+                    }
+                }
+            }
+            let report = assert_invariants!(client, (bettor, use_claim_all));
+            proptest::prop_assert!(report.outstanding <= settled.outstanding);
+        }
+
+        // Claiming never moves stake between pools, and the ledger never pays
+        // out more than the pool holds.
+        let report = assert_invariants!(client, "claims");
+        proptest::prop_assert_eq!(report.bet_sum, settled.bet_sum);
+        let ledger = client.get_payout_ledger();
+        proptest::prop_assert!(ledger.paid_out + ledger.fee <= ledger.total_pool);
+        let status = client.get_market_info().status;
+        proptest::prop_assert!(matches!(status, MarketStatus::Resolved | MarketStatus::Cancelled));
+    }
+}
@@ -0,0 +1,229 @@
+//! =============================================================================
+//! BOXMEOUT — Invariant Checker
+//! =============================================================================
+//!
+//! Covers `check_invariants`:
+//!   - Reports the pool, bet, escrow and outstanding figures it compares
+//!   - Unwound bets drop out of `bet_sum`; claimed bets stay in it
+//!   - Flags pools that disagree with the bets or with `total_pool`
+//!   - Flags escrow that no longer covers what bettors are owed
+//!   - Escrow conserves prop stakes and dispute bonds through settlement, and
+//!     any surplus or shortfall against them is flagged
+
+mod common;
+
+use common::{finalize, setup_market, TestMarket};
+use market::types::{
+    Bet, BetSide, FightResult, Market, MarketInvariantReport, Outcome, PropSelection, VictoryMethod,
+};
+use market::DataKey;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Bytes, BytesN, Env, String,
+};
+
+// ─── Tests ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_report_tracks_bets_through_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let bet_a = client.place_bet(&alice, &BetSide::FighterA, &3_000);
+    client.place_bet(&bob, &BetSide::FighterB, &1_000);
+    let bet_c = client.place_bet(&carol, &BetSide::FighterB, &2_000);
+
+    // Carol exits: her stake leaves the pool, the 5% fee leaves the escrow.
+    client.cash_out(&carol, &bet_c);
+    assert_eq!(
+        client.check_invariants(),
+        MarketInvariantReport {
+            pool_sum: 4_000,
+            bet_sum: 4_000,
+            outstanding: 4_000,
+            escrow: 4_000,
+            expected_escrow: 4_000,
+            pools_balanced: true,
+            bets_match_pools: true,
+            escrow_covers_claims: true,
+            escrow_conserved: true,
+            ok: true,
+        }
+    );
+
+    env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
+    let settled = client.check_invariants();
+    assert!(settled.ok);
+    // Net pool after the 2% fee.
+    assert_eq!(settled.outstanding, 3_920);
+
+    client.claim_winnings(&alice, &bet_a);
+    let claimed = client.check_invariants();
+    assert!(claimed.ok);
+    assert_eq!(claimed.bet_sum, 4_000);
+    assert_eq!(claimed.outstanding, 0);
+}
+
+#[test]
+fn test_report_flags_pools_out_of_line_with_bets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, .. } = setup_market(&env);
+
+    let alice = Address::generate(&env);
+    let bet_id = client.place_bet(&alice, &BetSide::FighterA, &1_000);
+
+    // Shrink the stored bet without touching the pools.
+    env.as_contract(&client.address, || {
+        let mut bet: Bet = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Bet(bet_id.clone()))
+            .unwrap();
+        bet.amount = 900;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Bet(bet_id.clone()), &bet);
+    });
+    let report = client.check_invariants();
+    assert_eq!((report.pool_sum, report.bet_sum), (1_000, 900));
+    assert!(report.pools_balanced);
+    assert!(!report.bets_match_pools);
+    assert!(!report.ok);
+
+    // Inflate `total_pool` past the side pools.
+    env.as_contract(&client.address, || {
+        let mut market: Market = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MarketInfo)
+            .unwrap();
+        market.total_pool += 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::MarketInfo, &market);
+    });
+    assert!(!client.check_invariants().pools_balanced);
+}
+
+#[test]
+fn test_report_flags_escrow_shortfall() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        market_id,
+        ..
+    } = setup_market(&env);
+
+    client.place_bet(&Address::generate(&env), &BetSide::FighterA, &1_000);
+    treasury.set_escrow(&market_id, &999);
+
+    let report = client.check_invariants();
+    assert_eq!((report.outstanding, report.escrow), (1_000, 999));
+    assert!(!report.escrow_covers_claims);
+    assert!(!report.escrow_conserved);
+    assert!(!report.ok);
+}
+
+#[test]
+fn test_escrow_conserves_props_and_bonds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let dave = Address::generate(&env);
+    let bet_a = client.place_bet(&alice, &BetSide::FighterA, &3_000);
+    client.place_bet(&bob, &BetSide::FighterB, &1_000);
+    let prop_c = client.place_prop_bet(&carol, &PropSelection::EndsInRound(3), &1_000);
+    client.place_prop_bet(&dave, &PropSelection::GoesTheDistance, &1_000);
+
+    env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
+    client.resolve_with_result(
+        &oracle,
+        &FightResult {
+            winner: Outcome::FighterA,
+            method: VictoryMethod::Knockout,
+            round: 3,
+        },
+    );
+    let resolved = client.check_invariants();
+    assert!(resolved.ok);
+    assert_eq!(resolved.expected_escrow, 6_000);
+
+    // Bonds sit in escrow until the dispute is settled.
+    client.set_dispute_bond(&admin, &100);
+    client.dispute_resolution(
+        &bob,
+        &Bytes::from_array(&env, b"wrong"),
+        &String::from_str(&env, ""),
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.post_dispute_bond(&alice, &false);
+    let disputed = client.check_invariants();
+    assert!(disputed.ok);
+    assert_eq!(disputed.expected_escrow, 6_300);
+
+    client.resolve_dispute(&admin, &Outcome::FighterA, &Bytes::new(&env));
+    assert!(client.check_invariants().ok);
+
+    // The 2% fee on both pools leaves escrow once the ruling is final.
+    finalize(&env, &client);
+    let settled = client.check_invariants();
+    assert!(settled.ok);
+    assert_eq!(settled.expected_escrow, 6_000 - 80 - 40);
+
+    client.claim_winnings(&alice, &bet_a);
+    client.claim_prop_winnings(&carol, &prop_c);
+    let claimed = client.check_invariants();
+    assert!(claimed.ok);
+    assert_eq!((claimed.escrow, claimed.expected_escrow), (0, 0));
+}
+
+#[test]
+fn test_report_flags_escrow_surplus() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        market_id,
+        ..
+    } = setup_market(&env);
+
+    client.place_bet(&Address::generate(&env), &BetSide::FighterA, &1_000);
+    client.place_prop_bet(
+        &Address::generate(&env),
+        &PropSelection::GoesTheDistance,
+        &500,
+    );
+    treasury.set_escrow(&market_id, &1_600);
+
+    // More than enough to pay everyone, but not what the market took in.
+    let report = client.check_invariants();
+    assert_eq!((report.escrow, report.expected_escrow), (1_600, 1_500));
+    assert!(report.escrow_covers_claims);
+    assert!(!report.escrow_conserved);
+    assert!(!report.ok);
+}
@@ -0,0 +1,120 @@
+//! =============================================================================
+//! BOXMEOUT — Lazy Lock
+//! =============================================================================
+//!
+//! Covers the effective market status:
+//!   - An Open market reads as Locked once `betting_ends_at` passes
+//!   - The first touch stores the lock and emits `market_locked` exactly once
+//!   - The oracle can resolve without anyone calling `lock_market`
+
+mod common;
+
+use common::{setup_market, TestMarket};
+use market::types::{BetSide, MarketStatus, Outcome};
+use market::MarketContractClient;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    xdr::{ContractEventBody, ScSymbol, ScVal},
+    Address, Env,
+};
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+/// Number of `market_locked` events the last invocation emitted.
+fn locked_events(env: &Env, client: &MarketContractClient) -> usize {
+    let topic = ScVal::Symbol(ScSymbol("market_locked".try_into().unwrap()));
+    env.events()
+        .all()
+        .filter_by_contract(&client.address)
+        .events()
+        .iter()
+        .filter(|e| match &e.body {
+            ContractEventBody::V0(body) => body.topics.first() == Some(&topic),
+        })
+        .count()
+}
+
+// ─── Lazy lock ────────────────────────────────────────────────────────────────
+
+#[test]
+fn market_stays_open_before_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    env.ledger().with_mut(|l| l.timestamp = betting_ends_at - 1);
+    assert_eq!(client.get_market_info().status, MarketStatus::Open);
+    assert_eq!(locked_events(&env, &client), 0);
+}
+
+#[test]
+fn first_read_after_deadline_locks_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    env.ledger().with_mut(|l| l.timestamp = betting_ends_at);
+    assert_eq!(client.get_market_info().status, MarketStatus::Locked);
+    assert_eq!(locked_events(&env, &client), 1);
+
+    // The lock is stored, so later touches do not announce it again.
+    assert_eq!(client.get_market_data().status, MarketStatus::Locked);
+    assert_eq!(locked_events(&env, &client), 0);
+}
+
+#[test]
+fn oracle_resolves_without_explicit_lock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    client.place_bet(&Address::generate(&env), &BetSide::FighterA, &1_000i128);
+
+    env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    assert_eq!(client.get_market_info().status, MarketStatus::Resolved);
+}
+
+#[test]
+#[should_panic(expected = "market not open")]
+fn bet_after_deadline_sees_locked_market() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    env.ledger().with_mut(|l| l.timestamp = betting_ends_at);
+    client.place_bet(&Address::generate(&env), &BetSide::FighterA, &1_000i128);
+}
+
+#[test]
+#[should_panic(expected = "market already locked")]
+fn explicit_lock_after_lazy_lock_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    env.ledger().with_mut(|l| l.timestamp = betting_ends_at);
+    client.get_market_info();
+    client.lock_market(&oracle);
+}
@@ -0,0 +1,186 @@
+//! =============================================================================
+//! BOXMEOUT — Oracle Reputation
+//! =============================================================================
+//!
+//! Covers what the market reports to the factory's oracle registry:
+//!   - Every oracle resolution is recorded, and a failed report aborts it
+//!   - The oracle is reported overturned at finalization, only if the final
+//!     outcome departs from its call
+//!   - Rejected disputes leave the oracle's record alone
+
+mod common;
+
+use common::{finalize, lock, setup_market, TestMarket};
+use market::types::{BetSide, MarketStatus, Outcome};
+use market::MarketContractClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Bytes, BytesN, Env, String, Symbol,
+};
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+/// Resolves the market for fighter A with bettors on both sides, returning
+/// (challenger, defender).
+fn resolve_with_bettors(
+    env: &Env,
+    client: &MarketContractClient,
+    oracle: &Address,
+    betting_ends_at: u64,
+) -> (Address, Address) {
+    let challenger = Address::generate(env);
+    let defender = Address::generate(env);
+    client.place_bet(&challenger, &BetSide::FighterB, &1_000i128);
+    client.place_bet(&defender, &BetSide::FighterA, &1_000i128);
+    lock(env, client, oracle, betting_ends_at);
+    client.resolve_market(oracle, &Outcome::FighterA);
+    (challenger, defender)
+}
+
+fn raise_dispute(env: &Env, client: &MarketContractClient, bettor: &Address) -> u32 {
+    client.dispute_resolution(
+        bettor,
+        &Bytes::from_array(env, b"judges scored it wrong"),
+        &String::from_str(env, "ipfs://bafy-scorecards"),
+        &BytesN::from_array(env, &[0xabu8; 32]),
+    )
+}
+
+// ─── Oracle reports ───────────────────────────────────────────────────────────
+
+#[test]
+fn oracle_resolution_is_reported_to_factory() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        factory,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    assert_eq!(factory.reports(&Symbol::new(&env, "resolutions")), 1);
+    assert_eq!(factory.reports(&Symbol::new(&env, "overturned")), 0);
+}
+
+#[test]
+fn failed_report_aborts_the_resolution() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        factory,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    client.place_bet(&Address::generate(&env), &BetSide::FighterA, &1_000i128);
+    lock(&env, &client, &oracle, betting_ends_at);
+
+    factory.reject_reports();
+    assert!(client
+        .try_resolve_market(&oracle, &Outcome::FighterA)
+        .is_err());
+    assert_eq!(client.get_market_info().status, MarketStatus::Locked);
+    assert_eq!(factory.reports(&Symbol::new(&env, "resolutions")), 0);
+}
+
+#[test]
+fn overriding_ruling_reports_oracle_overturned_when_final() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        factory,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    raise_dispute(&env, &client, &challenger);
+    client.resolve_dispute(&admin, &Outcome::FighterB, &Bytes::new(&env));
+    // A later round could still restore the oracle's call.
+    assert_eq!(factory.reports(&Symbol::new(&env, "overturned")), 0);
+
+    finalize(&env, &client);
+    assert_eq!(factory.reports(&Symbol::new(&env, "overturned")), 1);
+}
+
+#[test]
+fn ruling_reversed_by_a_later_round_leaves_oracle_record_alone() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        factory,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    raise_dispute(&env, &client, &challenger);
+    client.resolve_dispute(&admin, &Outcome::FighterB, &Bytes::new(&env));
+
+    // The next round restores the oracle's call, and that is what is finalized.
+    env.ledger().with_mut(|l| l.timestamp += 3_600);
+    raise_dispute(&env, &client, &defender);
+    client.resolve_dispute(&admin, &Outcome::FighterA, &Bytes::new(&env));
+    finalize(&env, &client);
+    assert_eq!(factory.reports(&Symbol::new(&env, "overturned")), 0);
+}
+
+#[test]
+fn admin_finalizing_a_dispute_judges_the_standing_outcome() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        factory,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    raise_dispute(&env, &client, &challenger);
+    client.resolve_dispute(&admin, &Outcome::FighterB, &Bytes::new(&env));
+    env.ledger().with_mut(|l| l.timestamp += 3_600);
+    raise_dispute(&env, &client, &defender);
+
+    // Finalizing the second dispute keeps the first ruling, which overrode the oracle.
+    client.finalize_resolution(&Some(admin));
+    assert_eq!(
+        client.get_market_info().outcome.outcome(),
+        Some(Outcome::FighterB)
+    );
+    assert_eq!(factory.reports(&Symbol::new(&env, "overturned")), 1);
+}
+
+#[test]
+fn rejected_dispute_leaves_oracle_record_alone() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        factory,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (challenger, _defender) = resolve_with_bettors(&env, &client, &oracle, betting_ends_at);
+
+    raise_dispute(&env, &client, &challenger);
+    client.resolve_dispute(&admin, &Outcome::FighterA, &Bytes::new(&env));
+    finalize(&env, &client);
+
+    assert_eq!(factory.reports(&Symbol::new(&env, "overturned")), 0);
+    assert_eq!(factory.reports(&Symbol::new(&env, "resolutions")), 1);
+}
@@ -0,0 +1,161 @@
+//! =============================================================================
+//! BOXMEOUT — Payout Rounding
+//! =============================================================================
+//!
+//! Covers the payout ledger:
+//!   - Truncated shares leave a remainder that the last claimer receives
+//!   - `paid_out + fee + dust == total_pool` once every claim is in
+//!   - `calculate_payout` quotes the remainder to the last unclaimed bet
+//!   - Refunds balance the ledger without a fee
+
+mod common;
+
+use common::{finalize, lock, setup_market, TestMarket};
+use market::types::{BetSide, Outcome};
+use market::MarketContractClient;
+use soroban_sdk::{testutils::Address as _, Address, Bytes, Env, Symbol};
+
+// ─── Payout ledger ────────────────────────────────────────────────────────────
+
+/// Three 100-stroop bets on A against 1_000 on B: net pool 1_274 over a
+/// 300-stroop winning pool truncates each share to 424, leaving 2 stroops.
+fn place_uneven_bets(
+    env: &Env,
+    client: &MarketContractClient,
+) -> (Address, Address, Address, Bytes, Bytes, Bytes) {
+    let a = Address::generate(env);
+    let b = Address::generate(env);
+    let c = Address::generate(env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &100i128);
+    let bet_b = client.place_bet(&b, &BetSide::FighterA, &100i128);
+    let bet_c = client.place_bet(&c, &BetSide::FighterA, &100i128);
+    client.place_bet(&Address::generate(env), &BetSide::FighterB, &1_000i128);
+    (a, b, c, bet_a, bet_b, bet_c)
+}
+
+#[test]
+fn last_claimer_receives_rounding_remainder() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (a, b, c, bet_a, bet_b, bet_c) = place_uneven_bets(&env, &client);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
+
+    assert_eq!(client.claim_winnings(&a, &bet_a), 424);
+    assert_eq!(client.claim_winnings(&b, &bet_b), 424);
+    assert_eq!(client.calculate_payout(&bet_c), 426);
+    assert_eq!(client.claim_winnings(&c, &bet_c), 426);
+
+    let ledger = client.get_payout_ledger();
+    assert!(ledger.complete);
+    assert_eq!(ledger.fee, 26);
+    assert_eq!(ledger.paid_out, 1_274);
+    assert_eq!(ledger.dust, 0);
+    assert_eq!(ledger.outstanding, 0);
+    assert_eq!(
+        ledger.paid_out + ledger.fee + ledger.dust,
+        ledger.total_pool
+    );
+}
+
+#[test]
+fn ledger_is_incomplete_until_every_winner_claims() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (a, _b, _c, bet_a, _bet_b, bet_c) = place_uneven_bets(&env, &client);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
+    client.claim_winnings(&a, &bet_a);
+
+    let ledger = client.get_payout_ledger();
+    assert!(!ledger.complete);
+    assert_eq!(ledger.stake_claimed, 100);
+    assert_eq!(ledger.paid_out, 424);
+    assert_eq!(ledger.outstanding, 1_274 - 424);
+    // Not the last stake yet, so the quote is the plain truncated share.
+    assert_eq!(client.calculate_payout(&bet_c), 424);
+}
+
+#[test]
+fn claim_all_balances_ledger_to_the_stroop() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (a, b, c, _bet_a, _bet_b, _bet_c) = place_uneven_bets(&env, &client);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
+
+    client.claim_all(&a, &0u32);
+    client.claim_all(&b, &0u32);
+    let last = client.claim_all(&c, &0u32);
+    assert_eq!(last.total_payout, 426);
+    assert_eq!(treasury.total(&Symbol::new(&env, "released")), 1_274);
+
+    let ledger = client.get_payout_ledger();
+    assert!(ledger.complete);
+    assert_eq!(ledger.paid_out + ledger.fee, ledger.total_pool);
+}
+
+#[test]
+fn refunds_balance_ledger_without_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let (a, b, c, bet_a, bet_b, bet_c) = place_uneven_bets(&env, &client);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::NoContest);
+
+    client.claim_refund(&a, &bet_a);
+    client.claim_refund(&b, &bet_b);
+    client.claim_refund(&c, &bet_c);
+
+    let ledger = client.get_payout_ledger();
+    assert_eq!(ledger.fee, 0);
+    assert_eq!(ledger.paid_out, 300);
+    // The B-side stake is still unclaimed.
+    assert!(!ledger.complete);
+    assert_eq!(ledger.outstanding, 1_000);
+}
+
+#[test]
+fn ledger_is_empty_before_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, .. } = setup_market(&env);
+    place_uneven_bets(&env, &client);
+
+    let ledger = client.get_payout_ledger();
+    assert_eq!(ledger.total_pool, 1_300);
+    assert_eq!(ledger.paid_out, 0);
+    assert!(!ledger.complete);
+}
@@ -0,0 +1,406 @@
+//! =============================================================================
+//! BOXMEOUT — Method-of-Victory & Round Prop Tests
+//! =============================================================================
+//!
+//! Covers the prop pool that settles from the oracle's structured result:
+//!   - "ends in round N", "goes the distance" and "A by KO" grading
+//!   - Payouts split across every winning selection
+//!   - Void props (no winning stake, cancelled market) refund in full
+//!   - The last winning claim takes the rounding remainder
+//!   - Prop bets can be withdrawn, cashed out and batch-claimed
+//!   - Structured result validation
+
+mod common;
+
+use common::{finalize, lock, setup_market, TestMarket};
+use market::props::prop_wins;
+use market::types::{
+    BetSide, FightResult, MarketStatus, OptionalOutcome, Outcome, PropSelection, VictoryMethod,
+};
+use soroban_sdk::{testutils::Address as _, Address, Env, Symbol};
+
+const STAKE: i128 = 1_000;
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+fn result(winner: Outcome, method: VictoryMethod, round: u32) -> FightResult {
+    FightResult {
+        winner,
+        method,
+        round,
+    }
+}
+
+// ─── Grading ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn prop_grading_for_stoppage() {
+    let ko_r3 = result(Outcome::FighterA, VictoryMethod::Knockout, 3);
+
+    assert!(prop_wins(&PropSelection::EndsInRound(3), &ko_r3));
+    assert!(!prop_wins(&PropSelection::EndsInRound(4), &ko_r3));
+    assert!(!prop_wins(&PropSelection::GoesTheDistance, &ko_r3));
+    assert!(prop_wins(
+        &PropSelection::WinsByMethod(BetSide::FighterA, VictoryMethod::Knockout),
+        &ko_r3
+    ));
+    assert!(!prop_wins(
+        &PropSelection::WinsByMethod(BetSide::FighterA, VictoryMethod::TechnicalKnockout),
+        &ko_r3
+    ));
+    assert!(!prop_wins(
+        &PropSelection::WinsByMethod(BetSide::FighterB, VictoryMethod::Knockout),
+        &ko_r3
+    ));
+}
+
+#[test]
+fn prop_grading_for_decision() {
+    let decision = result(Outcome::FighterB, VictoryMethod::Decision, 12);
+
+    assert!(prop_wins(&PropSelection::GoesTheDistance, &decision));
+    // A decision is not "ends in round 12" — the fight was not stopped.
+    assert!(!prop_wins(&PropSelection::EndsInRound(12), &decision));
+    assert!(prop_wins(
+        &PropSelection::WinsByMethod(BetSide::FighterB, VictoryMethod::Decision),
+        &decision
+    ));
+
+    let draw = result(Outcome::Draw, VictoryMethod::Decision, 12);
+    assert!(prop_wins(&PropSelection::GoesTheDistance, &draw));
+    assert!(!prop_wins(
+        &PropSelection::WinsByMethod(BetSide::FighterA, VictoryMethod::Decision),
+        &draw
+    ));
+}
+
+// ─── Settlement ───────────────────────────────────────────────────────────────
+
+#[test]
+fn prop_payouts_split_across_winning_selections() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let round_backer = Address::generate(&env);
+    let ko_backer = Address::generate(&env);
+    let distance_backer = Address::generate(&env);
+
+    let round_bet =
+        client.place_prop_bet(&round_backer, &PropSelection::EndsInRound(3), &1_000i128);
+    let ko_bet = client.place_prop_bet(
+        &ko_backer,
+        &PropSelection::WinsByMethod(BetSide::FighterA, VictoryMethod::Knockout),
+        &3_000i128,
+    );
+    let distance_bet = client.place_prop_bet(
+        &distance_backer,
+        &PropSelection::GoesTheDistance,
+        &6_000i128,
+    );
+
+    assert_eq!(
+        client.get_prop_pool(&PropSelection::GoesTheDistance),
+        (6_000, 10_000)
+    );
+
+    // Prop stakes never touch the winner pools.
+    let m = client.get_market_info();
+    assert_eq!(m.total_pool, 0);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_with_result(
+        &oracle,
+        &result(Outcome::FighterA, VictoryMethod::Knockout, 3),
+    );
+
+    // Nobody backed the winner in the main pool, so it refunds; props still settle.
+    let m = client.get_market_info();
+    assert_eq!(m.status, MarketStatus::Cancelled);
+    assert_eq!(m.outcome, OptionalOutcome::Some(Outcome::FighterA));
+    assert_eq!(
+        client.get_fight_result(),
+        Some(result(Outcome::FighterA, VictoryMethod::Knockout, 3))
+    );
+
+    // The prop fee is booked when the settlement is final.
+    assert_eq!(treasury.total(&Symbol::new(&env, "fees")), 200);
+
+    // net = 10_000 - 2% = 9_800, split over 4_000 of winning stake.
+    assert_eq!(
+        client.claim_prop_winnings(&round_backer, &round_bet),
+        1_000 * 9_800 / 4_000
+    );
+    assert_eq!(
+        client.claim_prop_winnings(&ko_backer, &ko_bet),
+        3_000 * 9_800 / 4_000
+    );
+    assert!(client
+        .try_claim_prop_winnings(&distance_backer, &distance_bet)
+        .is_err());
+
+    // Double claim is rejected.
+    assert!(client
+        .try_claim_prop_winnings(&round_backer, &round_bet)
+        .is_err());
+    assert!(client.get_prop_bet(&round_bet).claimed);
+}
+
+#[test]
+fn prop_void_when_no_stake_on_winning_selections() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let bettor = Address::generate(&env);
+    let bet_id = client.place_prop_bet(&bettor, &PropSelection::EndsInRound(1), &2_500i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_with_result(
+        &oracle,
+        &result(Outcome::FighterB, VictoryMethod::Decision, 12),
+    );
+
+    // Nobody backed the distance or B by decision — every prop is refunded.
+    assert_eq!(client.claim_prop_winnings(&bettor, &bet_id), 2_500);
+}
+
+#[test]
+fn prop_void_when_resolved_without_structured_result() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let bettor = Address::generate(&env);
+    let bet_id = client.place_prop_bet(&bettor, &PropSelection::GoesTheDistance, &900i128);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+
+    assert_eq!(client.get_fight_result(), None);
+    assert_eq!(client.claim_prop_winnings(&bettor, &bet_id), 900);
+}
+
+#[test]
+fn prop_refund_on_cancelled_market() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, admin, .. } = setup_market(&env);
+
+    let bettor = Address::generate(&env);
+    let bet_id = client.place_prop_bet(
+        &bettor,
+        &PropSelection::WinsByMethod(BetSide::FighterB, VictoryMethod::Submission),
+        &700i128,
+    );
+
+    client.cancel_market(&admin);
+    assert_eq!(client.claim_prop_winnings(&bettor, &bet_id), 700);
+}
+
+#[test]
+fn prop_bets_indexed_by_address() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, .. } = setup_market(&env);
+
+    let bettor = Address::generate(&env);
+    let first = client.place_prop_bet(&bettor, &PropSelection::EndsInRound(2), &100i128);
+    let second = client.place_prop_bet(&bettor, &PropSelection::GoesTheDistance, &200i128);
+    // Winner bets share the id counter, so ids never collide.
+    let winner_bet = client.place_bet(&bettor, &BetSide::FighterA, &300i128);
+
+    let props = client.get_prop_bets_by_address(&bettor);
+    assert_eq!(props.len(), 2);
+    assert_eq!(props.get(0).unwrap().bet_id, first);
+    assert_eq!(props.get(1).unwrap().bet_id, second);
+    assert_ne!(winner_bet, first);
+    assert_ne!(winner_bet, second);
+}
+
+#[test]
+fn last_prop_winner_takes_the_rounding_remainder() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let winners = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let mut bet_ids = std::vec::Vec::new();
+    for winner in winners.iter() {
+        bet_ids.push(client.place_prop_bet(winner, &PropSelection::GoesTheDistance, &STAKE));
+    }
+    client.place_prop_bet(
+        &Address::generate(&env),
+        &PropSelection::EndsInRound(1),
+        &STAKE,
+    );
+    client.place_bet(&winners[0], &BetSide::FighterB, &STAKE);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_with_result(
+        &oracle,
+        &result(Outcome::FighterB, VictoryMethod::Decision, 12),
+    );
+    finalize(&env, &client);
+
+    // net = 4_000 - 2% = 3_920 over 3_000 of winning stake: 1_306 each, 2 left over.
+    let payouts: std::vec::Vec<i128> = winners
+        .iter()
+        .zip(bet_ids.iter())
+        .map(|(winner, bet_id)| client.claim_prop_winnings(winner, bet_id))
+        .collect();
+    assert_eq!(payouts, [1_306, 1_306, 1_308]);
+    assert!(client.check_invariants().ok);
+}
+
+// ─── Exits ────────────────────────────────────────────────────────────────────
+
+#[test]
+fn prop_bet_withdrawn_after_reschedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        treasury,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let bettor = Address::generate(&env);
+    let bet_id = client.place_prop_bet(&bettor, &PropSelection::GoesTheDistance, &STAKE);
+    client.reschedule_market(&oracle, &(betting_ends_at + 7_200), &betting_ends_at);
+
+    assert_eq!(client.withdraw_bet(&bettor, &bet_id), STAKE);
+    assert_eq!(treasury.released_to(&bettor), STAKE);
+    assert_eq!(
+        client.get_prop_pool(&PropSelection::GoesTheDistance),
+        (0, 0)
+    );
+    assert_eq!(client.get_prop_bets_by_address(&bettor).len(), 0);
+    assert!(client.get_prop_bet(&bet_id).claimed);
+    assert!(client.try_withdraw_bet(&bettor, &bet_id).is_err());
+    assert!(client.check_invariants().ok);
+}
+
+#[test]
+fn prop_bet_cashed_out_before_lock() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client, treasury, ..
+    } = setup_market(&env);
+
+    let bettor = Address::generate(&env);
+    let bet_id = client.place_prop_bet(&bettor, &PropSelection::EndsInRound(2), &STAKE);
+
+    // Default 5% early-exit fee.
+    assert_eq!(client.cash_out(&bettor, &bet_id), 950);
+    assert_eq!(treasury.total(&Symbol::new(&env, "fees")), 50);
+    assert_eq!(client.get_prop_pool(&PropSelection::EndsInRound(2)), (0, 0));
+    assert!(client.check_invariants().ok);
+}
+
+#[test]
+fn claim_all_settles_prop_bets_after_winner_bets() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let bettor = Address::generate(&env);
+    let rival = Address::generate(&env);
+    let prop_bet = client.place_prop_bet(&bettor, &PropSelection::EndsInRound(3), &STAKE);
+    let bet = client.place_bet(&bettor, &BetSide::FighterA, &STAKE);
+    client.place_bet(&rival, &BetSide::FighterB, &STAKE);
+    client.place_prop_bet(&rival, &PropSelection::GoesTheDistance, &STAKE);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_with_result(
+        &oracle,
+        &result(Outcome::FighterA, VictoryMethod::Knockout, 3),
+    );
+    finalize(&env, &client);
+
+    let receipt = client.claim_all(&bettor, &0);
+    assert_eq!(receipt.bet_ids.len(), 2);
+    assert_eq!(receipt.bet_ids.get(0).unwrap(), bet);
+    assert_eq!(receipt.bet_ids.get(1).unwrap(), prop_bet);
+    // Both pools are 2_000 less 2%, each taken by a single winner.
+    assert_eq!(receipt.total_payout, 1_960 + 1_960);
+    assert_eq!(receipt.next_cursor, 2);
+    assert!(receipt.done);
+    assert!(client.get_prop_bet(&prop_bet).claimed);
+    assert!(client.try_claim_prop_winnings(&bettor, &prop_bet).is_err());
+    assert!(client.check_invariants().ok);
+}
+
+// ─── Validation ───────────────────────────────────────────────────────────────
+
+#[test]
+#[should_panic(expected = "round must be at least 1")]
+fn prop_round_zero_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, .. } = setup_market(&env);
+
+    let bettor = Address::generate(&env);
+    client.place_prop_bet(&bettor, &PropSelection::EndsInRound(0), &100i128);
+}
+
+#[test]
+#[should_panic(expected = "a draw can only be a decision")]
+fn prop_draw_by_stoppage_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_with_result(&oracle, &result(Outcome::Draw, VictoryMethod::Knockout, 5));
+}
+
+#[test]
+#[should_panic(expected = "market not settled")]
+fn prop_claim_before_resolution_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, .. } = setup_market(&env);
+
+    let bettor = Address::generate(&env);
+    let bet_id = client.place_prop_bet(&bettor, &PropSelection::GoesTheDistance, &100i128);
+    client.claim_prop_winnings(&bettor, &bet_id);
+}
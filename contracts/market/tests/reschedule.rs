@@ -0,0 +1,213 @@
+//! =============================================================================
+//! BOXMEOUT — Fight Postponement and Rescheduling
+//! =============================================================================
+//!
+//! Covers `reschedule_market` and the withdrawal window it opens:
+//!   - Oracle or factory admin may move the fight while Open or Locked
+//!   - A locked market re-opens when the new lock time is in the future
+//!   - Bettors withdraw their full stake until the window closes
+
+mod common;
+
+use common::{lock, setup_market, TestMarket};
+use market::types::{BetSide, MarketStatus, Outcome};
+use market::RESCHEDULE_WITHDRAWAL_WINDOW_SEC;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+// ─── reschedule_market ────────────────────────────────────────────────────────
+
+#[test]
+fn oracle_reschedules_open_market() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let new_lock = betting_ends_at + 604_800;
+    let new_fight = new_lock + 3_600;
+    client.reschedule_market(&oracle, &new_fight, &new_lock);
+
+    let m = client.get_market_info();
+    assert_eq!(m.status, MarketStatus::Open);
+    assert_eq!(m.scheduled_at, new_fight);
+    assert_eq!(m.betting_ends_at, new_lock);
+    assert_eq!(
+        client.get_withdrawal_window_ends(),
+        env.ledger().timestamp() + RESCHEDULE_WITHDRAWAL_WINDOW_SEC
+    );
+
+    // Betting stays open until the new lock time.
+    env.ledger().with_mut(|l| l.timestamp = betting_ends_at + 1);
+    client.place_bet(&Address::generate(&env), &BetSide::FighterA, &1_000i128);
+}
+
+#[test]
+fn admin_reopens_locked_market() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    assert_eq!(client.get_market_info().status, MarketStatus::Locked);
+
+    let now = env.ledger().timestamp();
+    client.reschedule_market(&admin, &(now + 700_000), &(now + 600_000));
+    assert_eq!(client.get_market_info().status, MarketStatus::Open);
+}
+
+#[test]
+fn locked_market_stays_locked_when_new_lock_time_passed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    let now = env.ledger().timestamp();
+    client.reschedule_market(&oracle, &(now + 3_600), &now);
+    assert_eq!(client.get_market_info().status, MarketStatus::Locked);
+}
+
+#[test]
+#[should_panic(expected = "not authorized")]
+fn stranger_cannot_reschedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let stranger = Address::generate(&env);
+    client.reschedule_market(&stranger, &(betting_ends_at + 10), &betting_ends_at);
+}
+
+#[test]
+#[should_panic(expected = "cannot reschedule: market already resolved or cancelled")]
+fn resolved_market_cannot_be_rescheduled() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+
+    let now = env.ledger().timestamp();
+    client.reschedule_market(&oracle, &(now + 3_600), &(now + 1_800));
+}
+
+#[test]
+#[should_panic(expected = "lock time must be at or before end time")]
+fn reschedule_rejects_lock_after_fight() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    client.reschedule_market(&oracle, &(betting_ends_at + 10), &(betting_ends_at + 20));
+}
+
+// ─── withdraw_bet ─────────────────────────────────────────────────────────────
+
+#[test]
+fn bettor_withdraws_full_stake_in_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &4_000i128);
+    client.place_bet(&b, &BetSide::FighterB, &1_000i128);
+
+    client.reschedule_market(&oracle, &(betting_ends_at + 20), &(betting_ends_at + 10));
+    assert_eq!(client.withdraw_bet(&a, &bet_a), 4_000);
+
+    let m = client.get_market_info();
+    assert_eq!(m.pool_a, 0);
+    assert_eq!(m.total_pool, 1_000);
+    assert_eq!(client.get_bets_by_address(&a).len(), 0);
+    assert!(client.try_withdraw_bet(&a, &bet_a).is_err());
+}
+
+#[test]
+#[should_panic(expected = "no withdrawal window open")]
+fn withdraw_without_reschedule_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, .. } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    client.withdraw_bet(&a, &bet_a);
+}
+
+#[test]
+#[should_panic(expected = "no withdrawal window open")]
+fn withdraw_after_window_closes_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, oracle, .. } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+
+    let now = env.ledger().timestamp();
+    let far = now + 10 * RESCHEDULE_WITHDRAWAL_WINDOW_SEC;
+    client.reschedule_market(&oracle, &far, &far);
+
+    let window_ends = client.get_withdrawal_window_ends();
+    env.ledger().with_mut(|l| l.timestamp = window_ends + 1);
+    client.withdraw_bet(&a, &bet_a);
+}
+
+#[test]
+#[should_panic(expected = "not your bet")]
+fn withdraw_someone_elses_bet_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &1_000i128);
+    client.reschedule_market(&oracle, &(betting_ends_at + 20), &(betting_ends_at + 10));
+
+    client.withdraw_bet(&Address::generate(&env), &bet_a);
+}
@@ -0,0 +1,155 @@
+//! =============================================================================
+//! BOXMEOUT — Resolution Deadline
+//! =============================================================================
+//!
+//! Covers the oracle timeout:
+//!   - Each market records `scheduled_at + RESOLUTION_WINDOW_SEC` as its deadline
+//!   - Late resolutions fail with `ContractError::ResolutionWindowExpired`
+//!   - Anyone may cancel an unresolved market past the deadline for refunds
+//!   - Reschedules move the deadline along with the fight
+
+mod common;
+
+use common::{lock, setup_market, TestMarket};
+use market::types::{BetSide, MarketStatus, Outcome};
+use market::RESOLUTION_WINDOW_SEC;
+use shared::errors::ContractError;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+// ─── Resolution deadline ──────────────────────────────────────────────────────
+
+#[test]
+fn deadline_follows_scheduled_at() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, .. } = setup_market(&env);
+
+    let scheduled_at = client.get_market_info().scheduled_at;
+    assert_eq!(
+        client.get_resolution_deadline(),
+        scheduled_at + RESOLUTION_WINDOW_SEC
+    );
+}
+
+#[test]
+fn late_resolution_fails_with_typed_error() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    lock(&env, &client, &oracle, betting_ends_at);
+
+    let deadline = client.get_resolution_deadline();
+    env.ledger().with_mut(|l| l.timestamp = deadline + 1);
+    let err = client
+        .try_resolve_market(&oracle, &Outcome::FighterA)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        Ok(soroban_sdk::Error::from_contract_error(
+            ContractError::ResolutionWindowExpired as u32
+        ))
+    );
+}
+
+#[test]
+fn resolution_on_deadline_succeeds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    client.place_bet(&Address::generate(&env), &BetSide::FighterA, &1_000i128);
+    lock(&env, &client, &oracle, betting_ends_at);
+
+    let deadline = client.get_resolution_deadline();
+    env.ledger().with_mut(|l| l.timestamp = deadline);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    assert_eq!(client.get_market_info().status, MarketStatus::Resolved);
+}
+
+#[test]
+fn anyone_cancels_unresolved_market_for_refunds() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    let bettor = Address::generate(&env);
+    let bet_id = client.place_bet(&bettor, &BetSide::FighterA, &1_000i128);
+    lock(&env, &client, &oracle, betting_ends_at);
+
+    let deadline = client.get_resolution_deadline();
+    env.ledger().with_mut(|l| l.timestamp = deadline + 1);
+    client.cancel_unresolved();
+    assert_eq!(client.get_market_info().status, MarketStatus::Cancelled);
+
+    assert_eq!(client.claim_refund(&bettor, &bet_id), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "resolution window still open")]
+fn cancel_before_deadline_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    lock(&env, &client, &oracle, betting_ends_at);
+
+    client.cancel_unresolved();
+}
+
+#[test]
+#[should_panic(expected = "market already settled")]
+fn cancel_after_resolution_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+
+    let deadline = client.get_resolution_deadline();
+    env.ledger().with_mut(|l| l.timestamp = deadline + 1);
+    client.cancel_unresolved();
+}
+
+#[test]
+fn reschedule_moves_deadline() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let new_scheduled_at = betting_ends_at + 5_000_000;
+    client.reschedule_market(&oracle, &new_scheduled_at, &(new_scheduled_at - 3_600));
+    assert_eq!(
+        client.get_resolution_deadline(),
+        new_scheduled_at + RESOLUTION_WINDOW_SEC
+    );
+}
@@ -0,0 +1,147 @@
+//! =============================================================================
+//! BOXMEOUT — Fighter Substitution
+//! =============================================================================
+//!
+//! Covers `substitute_fighter`:
+//!   - Admin-only, while Open or Locked
+//!   - Bets on the affected side are frozen during the opt-out grace period
+//!   - Every bettor may withdraw their full stake until the grace period ends
+//!   - Each swap is recorded in the substitution history
+
+mod common;
+
+use common::{finalize, lock, make_fighter, setup_market, TestMarket};
+use market::types::{BetSide, Outcome};
+use market::SUBSTITUTION_OPT_OUT_SEC;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+
+// ─── substitute_fighter ───────────────────────────────────────────────────────
+
+#[test]
+fn admin_substitutes_and_history_is_recorded() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, admin, .. } = setup_market(&env);
+
+    client.substitute_fighter(&admin, &BetSide::FighterB, &make_fighter(&env, "Douglas"));
+
+    let m = client.get_market_info();
+    assert_eq!(m.fighter_b.name, String::from_str(&env, "Douglas"));
+    assert_eq!(m.fighter_a.name, String::from_str(&env, "Tyson"));
+
+    let history = client.get_substitutions();
+    assert_eq!(history.len(), 1);
+    let sub = history.get(0).unwrap();
+    assert_eq!(sub.side, BetSide::FighterB);
+    assert_eq!(sub.previous.name, String::from_str(&env, "Holyfield"));
+    assert_eq!(sub.replacement.name, String::from_str(&env, "Douglas"));
+    assert_eq!(
+        sub.opt_out_ends_at,
+        env.ledger().timestamp() + SUBSTITUTION_OPT_OUT_SEC
+    );
+
+    client.substitute_fighter(&admin, &BetSide::FighterA, &make_fighter(&env, "Lewis"));
+    assert_eq!(client.get_substitutions().len(), 2);
+}
+
+#[test]
+fn affected_side_frozen_until_grace_period_ends() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, admin, .. } = setup_market(&env);
+
+    client.substitute_fighter(&admin, &BetSide::FighterB, &make_fighter(&env, "Douglas"));
+
+    let bettor = Address::generate(&env);
+    assert!(client
+        .try_place_bet(&bettor, &BetSide::FighterB, &1_000i128)
+        .is_err());
+    // The other corner is unaffected.
+    client.place_bet(&bettor, &BetSide::FighterA, &1_000i128);
+
+    env.ledger()
+        .with_mut(|l| l.timestamp += SUBSTITUTION_OPT_OUT_SEC);
+    client.place_bet(&bettor, &BetSide::FighterB, &1_000i128);
+}
+
+#[test]
+fn every_bettor_may_opt_out_during_grace_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, admin, .. } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &2_000i128);
+    let bet_b = client.place_bet(&b, &BetSide::FighterB, &3_000i128);
+
+    client.substitute_fighter(&admin, &BetSide::FighterB, &make_fighter(&env, "Douglas"));
+
+    assert_eq!(client.withdraw_bet(&a, &bet_a), 2_000);
+    assert_eq!(client.withdraw_bet(&b, &bet_b), 3_000);
+    assert_eq!(client.get_market_info().total_pool, 0);
+}
+
+#[test]
+fn bettors_who_stay_are_settled_normally() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket {
+        client,
+        oracle,
+        admin,
+        betting_ends_at,
+        ..
+    } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &2_000i128);
+    client.place_bet(&b, &BetSide::FighterB, &3_000i128);
+
+    client.substitute_fighter(&admin, &BetSide::FighterB, &make_fighter(&env, "Douglas"));
+
+    lock(&env, &client, &oracle, betting_ends_at);
+    client.resolve_market(&oracle, &Outcome::FighterA);
+    finalize(&env, &client);
+    assert_eq!(client.claim_winnings(&a, &bet_a), 5_000 * 9_800 / 10_000);
+}
+
+#[test]
+#[should_panic(expected = "no withdrawal window open")]
+fn opt_out_after_grace_period_panics() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, admin, .. } = setup_market(&env);
+
+    let a = Address::generate(&env);
+    let bet_a = client.place_bet(&a, &BetSide::FighterA, &2_000i128);
+    client.substitute_fighter(&admin, &BetSide::FighterB, &make_fighter(&env, "Douglas"));
+
+    env.ledger()
+        .with_mut(|l| l.timestamp += SUBSTITUTION_OPT_OUT_SEC + 1);
+    client.withdraw_bet(&a, &bet_a);
+}
+
+#[test]
+#[should_panic(expected = "not admin")]
+fn oracle_cannot_substitute() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, oracle, .. } = setup_market(&env);
+
+    client.substitute_fighter(&oracle, &BetSide::FighterA, &make_fighter(&env, "Lewis"));
+}
+
+#[test]
+#[should_panic(expected = "a draw has no fighter")]
+fn draw_side_cannot_be_substituted() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let TestMarket { client, admin, .. } = setup_market(&env);
+
+    client.substitute_fighter(&admin, &BetSide::Draw, &make_fighter(&env, "Lewis"));
+}
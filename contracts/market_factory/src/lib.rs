@@ -3,30 +3,94 @@
 //! BOXMEOUT — MarketFactory Contract
 //! Deploys and tracks Market contract instances.
 //! ============================================================
-use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Map, String, Vec};
-
-use shared::{errors::ContractError, types::MarketInfo};
+use soroban_sdk::{
+    contract, contractimpl, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol,
+    TryFromVal, Val, Vec,
+};
+
+use shared::{
+    errors::ContractError,
+    events::{
+        self, ClaimsAggregatedEvent, EventMarketAddedEvent, EventMarketsUpdatedEvent,
+        OracleResolutionRecordedEvent, OracleSlashedEvent, OracleStakeWithdrawnEvent,
+        OracleStakedEvent, OracleUnbondingEvent,
+    },
+    types::{
+        ArbiterPanel, ArbiterVote, Event, Fighter, MarketClaim, MarketInfo, OracleRecord, Outcome,
+        ProtocolConfig,
+    },
+};
 
 // Storage keys for persistent state
 const ADMIN: &str = "ADMIN";
 const MARKET_WASM_HASH: &str = "MARKET_WASM_HASH";
 const TREASURY: &str = "TREASURY";
 const PAUSED: &str = "PAUSED";
+/// ProtocolConfig served to markets, the treasury and parlays via `get_config`
+const CONFIG_KEY: &str = "CONFIG";
 const MARKET_COUNT_KEY: &str = "MARKET_COUNT";
 const MARKET_MAP: &str = "MARKET_MAP";
+/// numeric_id -> market_id (hash), for lookups by the factory-assigned number.
+const MARKET_NUM_MAP: &str = "MARKET_NUM_MAP";
 const ALL_MARKETS_KEY: &str = "ALL_MARKETS";
+const EVENT_COUNT_KEY: &str = "EVENT_COUNT";
+const EVENT_MAP: &str = "EVENT_MAP";
+/// market_id -> event_id, so a market can only ever sit on one card.
+const MARKET_EVENT_MAP: &str = "MARKET_EVENT_MAP";
+const ARBITER_PANEL: &str = "ARBITER_PANEL";
+/// arbiter -> Vec<ArbiterVote> (every vote it cast, across markets, oldest first)
+const ARBITER_VOTES: &str = "ARBITER_VOTES";
+/// oracle -> OracleRecord (bonded stake and resolution track record)
+const ORACLE_MAP: &str = "ORACLE_MAP";
+const ORACLE_SLASH_BPS: &str = "ORACLE_SLASH_BPS";
+/// oracle -> Vec<market_id> (markets it was given, until it withdraws its stake)
+const ORACLE_MARKETS: &str = "ORACLE_MARKETS";
+
+/// Smallest stake an oracle may bond (10 XLM).
+pub const MIN_ORACLE_STAKE: i128 = 100_000_000;
+
+/// Share of an oracle's stake slashed per overturned resolution until the
+/// admin sets one (10%).
+pub const DEFAULT_ORACLE_SLASH_BPS: u32 = 1_000;
+
+/// How long a deregistered oracle's stake stays slashable before it can be
+/// withdrawn, so late rulings on its markets still bite.
+pub const ORACLE_UNBONDING_SEC: u64 = 7 * 24 * 60 * 60;
+
+/// Protocol fee new markets charge until the admin sets one (2%).
+pub const DEFAULT_FEE_BP: u32 = 200;
+
+/// Bet limits until the admin sets them (0.1 XLM to 10 000 XLM).
+pub const DEFAULT_MIN_BET: i128 = 1_000_000;
+pub const DEFAULT_MAX_BET: i128 = 100_000_000_000;
+
+/// Dispute window new markets get until the admin sets one (24 hours).
+pub const DEFAULT_DISPUTE_WINDOW_SEC: u64 = 24 * 60 * 60;
 
 /// Maximum number of markets that may be returned in a single `list_markets` /
 /// `list_active_markets` page, regardless of the caller-requested `limit`.
 const MAX_PAGE_SIZE: u32 = 50;
 
+/// Maximum number of markets a single `claim_many` call may touch.
+const MAX_CLAIM_MARKETS: u32 = 20;
+
+/// One page of a Market's `claim_all` receipt, as `claim_many` reads it.
+struct ClaimPage {
+    payout: i128,
+    bets_claimed: u32,
+    next_cursor: u32,
+    done: bool,
+}
+
 #[contract]
 pub struct MarketFactory;
 
 #[contractimpl]
 impl MarketFactory {
     /// One-time setup. Stores the admin, the Market contract's wasm hash
-    /// (used for all future deployments), and the treasury address.
+    /// (used for all future deployments), and the treasury address, and
+    /// seeds the protocol config with the defaults above, collecting fees to
+    /// the treasury.
     ///
     /// # Errors
     /// Returns `ContractError::AlreadyInitialized` if called more than once.
@@ -44,6 +108,19 @@ impl MarketFactory {
         env.storage().persistent().set(&MARKET_WASM_HASH, &market_wasm_hash);
         env.storage().persistent().set(&TREASURY, &treasury);
         env.storage().persistent().set(&PAUSED, &false);
+        env.storage().persistent().set(
+            &CONFIG_KEY,
+            &ProtocolConfig {
+                admin: admin.clone(),
+                fee_collector: treasury.clone(),
+                default_fee_bp: DEFAULT_FEE_BP,
+                min_bet_amount: DEFAULT_MIN_BET,
+                max_bet_amount: DEFAULT_MAX_BET,
+                dispute_window_sec: DEFAULT_DISPUTE_WINDOW_SEC,
+                claim_expiry_sec: 0,
+                paused: false,
+            },
+        );
         env.storage().persistent().set(&MARKET_COUNT_KEY, &0u64);
         env.storage()
             .persistent()
@@ -67,9 +144,45 @@ impl MarketFactory {
     ) {
         admin.require_auth();
 
-        let config: ProtocolConfig = env.storage().persistent()
+        let config = Self::get_config(env.clone());
+        if config.admin != admin {
+            panic!("not admin");
+        }
+        env.storage().persistent().set(&MARKET_WASM_HASH, &new_wasm_hash);
+    }
+
+    /// Returns the protocol config markets, the treasury and parlays read.
+    /// `admin` and `paused` always reflect `set_admin` and `pause_factory`.
+    pub fn get_config(env: Env) -> ProtocolConfig {
+        let mut config: ProtocolConfig = env
+            .storage()
+            .persistent()
             .get(&CONFIG_KEY)
             .expect("not initialized");
+        config.admin = env.storage().persistent().get(&ADMIN).expect("not initialized");
+        config.paused = Self::is_paused(env);
+        config
+    }
+
+    /// Replaces the protocol config. Takes effect on the next read, so it
+    /// also applies to markets already deployed. `admin` and `paused` in
+    /// `new_config` are ignored; use `set_admin` and `pause_factory`.
+    ///
+    /// # Errors
+    /// - `ContractError::Unauthorized` if `admin` is not the protocol admin
+    /// - `ContractError::InvalidConfig` if `default_fee_bp` exceeds 10_000
+    ///   or `min_bet_amount` is not positive or exceeds `max_bet_amount`
+    pub fn update_config(env: Env, admin: Address, new_config: ProtocolConfig) -> Result<(), ContractError> {
+        Self::require_admin(&env, &admin)?;
+        if new_config.default_fee_bp > 10_000
+            || new_config.min_bet_amount <= 0
+            || new_config.min_bet_amount > new_config.max_bet_amount
+        {
+            return Err(ContractError::InvalidConfig);
+        }
+        env.storage().persistent().set(&CONFIG_KEY, &new_config);
+        Ok(())
+    }
 
     /// Returns the stored Market contract wasm hash.
     pub fn get_market_wasm_hash(env: Env) -> BytesN<32> {
@@ -84,12 +197,35 @@ impl MarketFactory {
         env.storage().persistent().get(&TREASURY).expect("not initialized")
     }
 
-    /// Deploys a new Market contract instance and registers its `MarketInfo`.
+    /// Returns the protocol admin.
+    pub fn get_admin(env: Env) -> Address {
+        env.storage().persistent().get(&ADMIN).expect("not initialized")
+    }
+
+    /// Deploys a new Market contract instance, initializes it and registers
+    /// its `MarketInfo`.
+    ///
+    /// The market gets two ids: a sequential `numeric_id` from the market
+    /// counter (starting at 1), used in events, and a 32-byte `market_id`,
+    /// the sha256 of the numeric id, both fighter names and `end_time`, used
+    /// as the registry key and deployment salt.
+    ///
+    /// The market is initialized in the same call that deploys it, so nobody
+    /// can initialize it first with their own oracle or treasury. It takes the
+    /// fee, fee collector and dispute window from the live config, the bet
+    /// token from the treasury, `end_time` as the fight time and `lock_time`
+    /// as the betting deadline. Only the fighters' names are known here; the
+    /// rest of their metadata starts empty.
     ///
     /// # Errors
     /// - `ContractError::FactoryPaused` if the factory is paused
     /// - `ContractError::InvalidTimestamp` if `end_time` is in the past, or
     ///   `lock_time` is after `end_time`
+    /// - `ContractError::OracleNotWhitelisted` if `oracle` never staked
+    /// - `ContractError::OracleStakeTooLow` if `oracle` bonds less than
+    ///   `MIN_ORACLE_STAKE` or is unbonding
+    /// - `ContractError::MarketIdCollision` if either id is already registered
+    #[allow(clippy::too_many_arguments)]
     pub fn create_market(
         env: Env,
         caller: Address,
@@ -98,6 +234,7 @@ impl MarketFactory {
         oracle: Address,
         lock_time: u64,
         end_time: u64,
+        draw_enabled: bool,
     ) -> Result<Bytes, ContractError> {
         caller.require_auth();
 
@@ -111,20 +248,35 @@ impl MarketFactory {
             return Err(ContractError::InvalidTimestamp);
         }
 
+        // The oracle's stake is what a dispute ruling slashes, so it must be
+        // bonded, and not on its way out, before it is trusted with a market.
+        let record = Self::read_oracles(&env)
+            .get(oracle.clone())
+            .ok_or(ContractError::OracleNotWhitelisted)?;
+        if record.stake < MIN_ORACLE_STAKE || record.unbonds_at != 0 {
+            return Err(ContractError::OracleStakeTooLow);
+        }
+
         let count: u64 = env.storage().persistent().get(&MARKET_COUNT_KEY).unwrap_or(0);
+        let numeric_id = count + 1;
 
-        // Generate a collision-resistant market_id from the creation nonce,
-        // both fighter names, and the scheduled end_time.
-        let mut id_bytes = [0u8; 32];
-        id_bytes[0..8].copy_from_slice(&count.to_le_bytes());
-        for (i, byte) in fighter_a.to_bytes().iter().take(8).enumerate() {
-            id_bytes[8 + i] ^= byte;
-        }
-        for (i, byte) in fighter_b.to_bytes().iter().take(8).enumerate() {
-            id_bytes[16 + i] ^= byte;
+        let preimage = (numeric_id, fighter_a.clone(), fighter_b.clone(), end_time).to_xdr(&env);
+        let salt: BytesN<32> = env.crypto().sha256(&preimage).into();
+        let market_id = Bytes::from(salt.clone());
+
+        let mut market_map: Map<Bytes, MarketInfo> = env
+            .storage()
+            .persistent()
+            .get(&MARKET_MAP)
+            .unwrap_or_else(|| Map::new(&env));
+        let mut num_map: Map<u64, Bytes> = env
+            .storage()
+            .persistent()
+            .get(&MARKET_NUM_MAP)
+            .unwrap_or_else(|| Map::new(&env));
+        if market_map.contains_key(market_id.clone()) || num_map.contains_key(numeric_id) {
+            return Err(ContractError::MarketIdCollision);
         }
-        id_bytes[24..32].copy_from_slice(&end_time.to_le_bytes());
-        let market_id = Bytes::from_array(&env, &id_bytes);
 
         let wasm_hash: BytesN<32> = env
             .storage()
@@ -132,7 +284,6 @@ impl MarketFactory {
             .get(&MARKET_WASM_HASH)
             .expect("not initialized");
 
-        let salt = BytesN::from_array(&env, &id_bytes);
         let market_address = env
             .deployer()
             .with_address(env.current_contract_address(), salt)
@@ -140,6 +291,7 @@ impl MarketFactory {
 
         let info = MarketInfo {
             market_id: market_id.clone(),
+            numeric_id,
             market_address,
             creator: caller,
             fighter_a: fighter_a.clone(),
@@ -149,14 +301,18 @@ impl MarketFactory {
             end_time,
             created_at: now,
         };
+        Self::initialize_market(&env, &info, draw_enabled);
 
-        let mut market_map: Map<Bytes, MarketInfo> = env
-            .storage()
-            .persistent()
-            .get(&MARKET_MAP)
-            .unwrap_or_else(|| Map::new(&env));
         market_map.set(market_id.clone(), info.clone());
         env.storage().persistent().set(&MARKET_MAP, &market_map);
+        num_map.set(numeric_id, market_id.clone());
+        env.storage().persistent().set(&MARKET_NUM_MAP, &num_map);
+
+        let mut oracle_markets = Self::read_oracle_markets(&env);
+        let mut assigned = oracle_markets.get(oracle.clone()).unwrap_or_else(|| Vec::new(&env));
+        assigned.push_back(market_id.clone());
+        oracle_markets.set(oracle, assigned);
+        env.storage().persistent().set(&ORACLE_MARKETS, &oracle_markets);
 
         let mut all_markets: Vec<Bytes> = env
             .storage()
@@ -168,9 +324,9 @@ impl MarketFactory {
 
         env.storage()
             .persistent()
-            .set(&MARKET_COUNT_KEY, &(count + 1));
+            .set(&MARKET_COUNT_KEY, &numeric_id);
 
-        env.events().publish(("market_created",), info);
+        events::emit_market_created(&env, info);
 
         Ok(market_id)
     }
@@ -185,6 +341,17 @@ impl MarketFactory {
         map.get(market_id)
     }
 
+    /// Read-only lookup of a single market by its factory-assigned numeric id.
+    pub fn get_market_by_numeric_id(env: Env, numeric_id: u64) -> Option<MarketInfo> {
+        let num_map: Map<u64, Bytes> = env
+            .storage()
+            .persistent()
+            .get(&MARKET_NUM_MAP)
+            .unwrap_or_else(|| Map::new(&env));
+        let market_id = num_map.get(numeric_id)?;
+        Self::get_market(env, market_id)
+    }
+
     /// Returns a bounded, stably-ordered (creation order) page of all markets
     /// ever created. `limit` is capped at `MAX_PAGE_SIZE` regardless of the
     /// value requested, to bound gas.
@@ -287,6 +454,660 @@ impl MarketFactory {
         Ok(())
     }
 
+    /// Creates an empty fight card. Bouts are attached with `add_market_to_event`.
+    ///
+    /// # Errors
+    /// Returns `ContractError::Unauthorized` if `admin` does not match the
+    /// stored admin.
+    pub fn create_event(
+        env: Env,
+        admin: Address,
+        name: String,
+        venue: String,
+        date: u64,
+    ) -> Result<u64, ContractError> {
+        Self::require_admin(&env, &admin)?;
+
+        let count: u64 = env.storage().persistent().get(&EVENT_COUNT_KEY).unwrap_or(0);
+        let event_id = count + 1;
+
+        let event = Event {
+            event_id,
+            name,
+            venue,
+            date,
+            market_ids: Vec::new(&env),
+            main_event_id: Bytes::new(&env),
+        };
+
+        let mut event_map: Map<u64, Event> = env
+            .storage()
+            .persistent()
+            .get(&EVENT_MAP)
+            .unwrap_or_else(|| Map::new(&env));
+        event_map.set(event_id, event.clone());
+        env.storage().persistent().set(&EVENT_MAP, &event_map);
+        env.storage().persistent().set(&EVENT_COUNT_KEY, &event_id);
+
+        events::emit_event_created(&env, event);
+
+        Ok(event_id)
+    }
+
+    /// Attaches a market to a fight card. If `main_event` is set, the market
+    /// becomes the card's main event, replacing any previous one.
+    ///
+    /// # Errors
+    /// - `ContractError::Unauthorized` if `admin` does not match the stored admin
+    /// - `ContractError::EventNotFound` if `event_id` does not exist
+    /// - `ContractError::MarketNotFound` if `market_id` was not created by this factory
+    /// - `ContractError::MarketAlreadyInEvent` if the market is already on a card
+    pub fn add_market_to_event(
+        env: Env,
+        admin: Address,
+        event_id: u64,
+        market_id: Bytes,
+        main_event: bool,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &admin)?;
+
+        let mut event_map: Map<u64, Event> = env
+            .storage()
+            .persistent()
+            .get(&EVENT_MAP)
+            .unwrap_or_else(|| Map::new(&env));
+        let mut event = event_map.get(event_id).ok_or(ContractError::EventNotFound)?;
+
+        let market_map: Map<Bytes, MarketInfo> = env
+            .storage()
+            .persistent()
+            .get(&MARKET_MAP)
+            .unwrap_or_else(|| Map::new(&env));
+        let info = market_map.get(market_id.clone()).ok_or(ContractError::MarketNotFound)?;
+
+        let mut market_events: Map<Bytes, u64> = env
+            .storage()
+            .persistent()
+            .get(&MARKET_EVENT_MAP)
+            .unwrap_or_else(|| Map::new(&env));
+        if market_events.contains_key(market_id.clone()) {
+            return Err(ContractError::MarketAlreadyInEvent);
+        }
+        market_events.set(market_id.clone(), event_id);
+        env.storage().persistent().set(&MARKET_EVENT_MAP, &market_events);
+
+        event.market_ids.push_back(market_id.clone());
+        if main_event {
+            event.main_event_id = market_id.clone();
+        }
+        event_map.set(event_id, event);
+        env.storage().persistent().set(&EVENT_MAP, &event_map);
+
+        events::emit_event_market_added(&env, EventMarketAddedEvent {
+            event_id,
+            market_id: info.numeric_id,
+            main_event,
+        });
+
+        Ok(())
+    }
+
+    /// Read-only lookup of a single fight card by ID.
+    pub fn get_event(env: Env, event_id: u64) -> Option<Event> {
+        let map: Map<u64, Event> = env
+            .storage()
+            .persistent()
+            .get(&EVENT_MAP)
+            .unwrap_or_else(|| Map::new(&env));
+        map.get(event_id)
+    }
+
+    /// Returns the event a market belongs to, if any.
+    pub fn get_market_event(env: Env, market_id: Bytes) -> Option<u64> {
+        let map: Map<Bytes, u64> = env
+            .storage()
+            .persistent()
+            .get(&MARKET_EVENT_MAP)
+            .unwrap_or_else(|| Map::new(&env));
+        map.get(market_id)
+    }
+
+    /// Returns every market on a fight card, in the order they were added.
+    ///
+    /// # Errors
+    /// Returns `ContractError::EventNotFound` if `event_id` does not exist.
+    pub fn list_event_markets(env: Env, event_id: u64) -> Result<Vec<MarketInfo>, ContractError> {
+        let event = Self::get_event(env.clone(), event_id).ok_or(ContractError::EventNotFound)?;
+
+        let market_map: Map<Bytes, MarketInfo> = env
+            .storage()
+            .persistent()
+            .get(&MARKET_MAP)
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut result: Vec<MarketInfo> = Vec::new(&env);
+        for id in event.market_ids.iter() {
+            result.push_back(market_map.get(id).unwrap());
+        }
+        Ok(result)
+    }
+
+    /// Locks every market on a card, e.g. when the whole event starts early or
+    /// is postponed. Each market accepts the factory as an early locker.
+    ///
+    /// Markets that refuse (already locked, resolved or cancelled) are skipped
+    /// rather than aborting the batch.
+    ///
+    /// # Returns
+    /// The number of markets that were locked by this call.
+    ///
+    /// # Errors
+    /// - `ContractError::Unauthorized` if `admin` does not match the stored admin
+    /// - `ContractError::EventNotFound` if `event_id` does not exist
+    pub fn lock_event_markets(env: Env, admin: Address, event_id: u64) -> Result<u32, ContractError> {
+        Self::require_admin(&env, &admin)?;
+
+        let factory = env.current_contract_address();
+        let mut locked = 0u32;
+        for info in Self::list_event_markets(env.clone(), event_id)?.iter() {
+            let result = env.try_invoke_contract::<(), soroban_sdk::Error>(
+                &info.market_address,
+                &Symbol::new(&env, "lock_market"),
+                soroban_sdk::vec![&env, factory.clone().into_val(&env)],
+            );
+            if matches!(result, Ok(Ok(()))) {
+                locked += 1;
+            }
+        }
+
+        events::emit_event_markets_locked(&env, EventMarketsUpdatedEvent { event_id, count: locked });
+
+        Ok(locked)
+    }
+
+    /// Cancels every market on a card when the whole event is called off, so
+    /// every bettor on the card can claim a full refund.
+    ///
+    /// Markets that refuse (already resolved or cancelled) are skipped rather
+    /// than aborting the batch.
+    ///
+    /// # Returns
+    /// The number of markets that were cancelled by this call.
+    ///
+    /// # Errors
+    /// - `ContractError::Unauthorized` if `admin` does not match the stored admin
+    /// - `ContractError::EventNotFound` if `event_id` does not exist
+    pub fn cancel_event_markets(env: Env, admin: Address, event_id: u64) -> Result<u32, ContractError> {
+        Self::require_admin(&env, &admin)?;
+
+        // Markets cannot call back into the factory for the config while it
+        // is calling them, so it is handed over with the call.
+        let config = Self::get_config(env.clone());
+        let mut cancelled = 0u32;
+        for info in Self::list_event_markets(env.clone(), event_id)?.iter() {
+            let result = env.try_invoke_contract::<(), soroban_sdk::Error>(
+                &info.market_address,
+                &Symbol::new(&env, "cancel_by_factory"),
+                soroban_sdk::vec![&env, config.clone().into_val(&env)],
+            );
+            if matches!(result, Ok(Ok(()))) {
+                cancelled += 1;
+            }
+        }
+
+        events::emit_event_markets_cancelled(&env, EventMarketsUpdatedEvent {
+            event_id,
+            count: cancelled,
+        });
+
+        Ok(cancelled)
+    }
+
+    /// Claims a bettor's winnings and refunds across several markets in one call.
+    ///
+    /// Calls each market's `claim_all` (which settles both winning and refundable
+    /// bets), following its cursor page by page until every bet of the bettor has
+    /// been visited. A market that refuses, e.g. because it is not settled yet, is
+    /// reported with `succeeded = false` instead of reverting the batch. If a later
+    /// page is refused, the pages already claimed are reported with `done = false`.
+    ///
+    /// # Returns
+    /// A per-market breakdown, in the order of `market_ids`.
+    ///
+    /// # Errors
+    /// - `ContractError::TooManyMarkets` if more than 20 market IDs are passed
+    /// - `ContractError::MarketNotFound` if any ID is not a factory market
+    pub fn claim_many(
+        env: Env,
+        bettor: Address,
+        market_ids: Vec<Bytes>,
+    ) -> Result<Vec<MarketClaim>, ContractError> {
+        bettor.require_auth();
+
+        if market_ids.len() > MAX_CLAIM_MARKETS {
+            return Err(ContractError::TooManyMarkets);
+        }
+
+        let market_map: Map<Bytes, MarketInfo> = env
+            .storage()
+            .persistent()
+            .get(&MARKET_MAP)
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut breakdown: Vec<MarketClaim> = Vec::new(&env);
+        let mut total_payout: i128 = 0;
+        for market_id in market_ids.iter() {
+            let info = market_map.get(market_id.clone()).ok_or(ContractError::MarketNotFound)?;
+
+            let mut claim = MarketClaim {
+                market_id: market_id.clone(),
+                payout: 0,
+                bets_claimed: 0,
+                done: false,
+                succeeded: false,
+            };
+            let mut cursor: u32 = 0;
+            while !claim.done {
+                let result = env.try_invoke_contract::<Map<Symbol, Val>, soroban_sdk::Error>(
+                    &info.market_address,
+                    &Symbol::new(&env, "claim_all"),
+                    soroban_sdk::vec![&env, bettor.clone().into_val(&env), cursor.into_val(&env)],
+                );
+                let page = match result {
+                    Ok(Ok(receipt)) => Self::read_batch_claim(&env, &receipt),
+                    _ => None,
+                };
+                let Some(page) = page else { break };
+                claim.payout += page.payout;
+                claim.bets_claimed += page.bets_claimed;
+                claim.done = page.done;
+                claim.succeeded = true;
+                if page.next_cursor <= cursor && !page.done {
+                    break;
+                }
+                cursor = page.next_cursor;
+            }
+
+            total_payout += claim.payout;
+            breakdown.push_back(claim);
+        }
+
+        events::emit_claims_aggregated(&env, ClaimsAggregatedEvent { bettor, total_payout });
+
+        Ok(breakdown)
+    }
+
+    /// Registers the committee that votes on disputed markets.
+    ///
+    /// Markets read the panel when a dispute is raised against them: members
+    /// vote on the final outcome within `vote_window_sec`, and the admin only
+    /// rules once that window lapses without a majority. Passing no members
+    /// hands disputes back to the admin. Disputes already open keep the panel
+    /// they were raised under.
+    ///
+    /// # Errors
+    /// - `ContractError::Unauthorized` if `admin` is not the protocol admin
+    /// - `ContractError::InvalidArbiterPanel` if a member is listed twice, or
+    ///   members are given with a zero `vote_window_sec`
+    pub fn set_arbiter_panel(
+        env: Env,
+        admin: Address,
+        members: Vec<Address>,
+        vote_window_sec: u64,
+    ) -> Result<(), ContractError> {
+        Self::require_admin(&env, &admin)?;
+
+        for (i, member) in members.iter().enumerate() {
+            if members.first_index_of(&member) != Some(i as u32) {
+                return Err(ContractError::InvalidArbiterPanel);
+            }
+        }
+        if !members.is_empty() && vote_window_sec == 0 {
+            return Err(ContractError::InvalidArbiterPanel);
+        }
+
+        let panel = ArbiterPanel { members, vote_window_sec };
+        env.storage().persistent().set(&ARBITER_PANEL, &panel);
+        events::emit_arbiter_panel_set(&env, panel);
+        Ok(())
+    }
+
+    /// Returns the registered arbiter panel; empty if none was set.
+    pub fn get_arbiter_panel(env: Env) -> ArbiterPanel {
+        env.storage().persistent().get(&ARBITER_PANEL).unwrap_or(ArbiterPanel {
+            members: Vec::new(&env),
+            vote_window_sec: 0,
+        })
+    }
+
+    /// Appends a vote to `arbiter`'s history. Called by the market when a
+    /// member of its dispute's panel votes.
+    ///
+    /// # Errors
+    /// - `ContractError::MarketNotFound` if `market_id` is not registered
+    pub fn record_arbiter_vote(
+        env: Env,
+        market_id: Bytes,
+        arbiter: Address,
+        dispute_id: u32,
+        outcome: Outcome,
+    ) -> Result<(), ContractError> {
+        Self::require_registered_market(&env, &market_id)?;
+
+        let mut votes: Map<Address, Vec<ArbiterVote>> = env
+            .storage()
+            .persistent()
+            .get(&ARBITER_VOTES)
+            .unwrap_or(Map::new(&env));
+        let mut history = votes.get(arbiter.clone()).unwrap_or(Vec::new(&env));
+        history.push_back(ArbiterVote {
+            market_id,
+            dispute_id,
+            outcome,
+            voted_at: env.ledger().timestamp(),
+        });
+        votes.set(arbiter, history);
+        env.storage().persistent().set(&ARBITER_VOTES, &votes);
+        Ok(())
+    }
+
+    /// Returns every vote `arbiter` cast, across markets, oldest first.
+    pub fn get_arbiter_votes(env: Env, arbiter: Address) -> Vec<ArbiterVote> {
+        env.storage()
+            .persistent()
+            .get::<_, Map<Address, Vec<ArbiterVote>>>(&ARBITER_VOTES)
+            .and_then(|votes| votes.get(arbiter))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Bonds `amount` of the bet token as `oracle`'s stake, registering the
+    /// oracle on first call and topping it up afterwards. The tokens are held
+    /// by the treasury. Staking again cancels a pending unbonding.
+    ///
+    /// # Errors
+    /// - `ContractError::OracleStakeTooLow` if `amount` is not positive, or a
+    ///   new oracle bonds less than `MIN_ORACLE_STAKE`
+    pub fn stake_oracle(env: Env, oracle: Address, amount: i128) -> Result<OracleRecord, ContractError> {
+        oracle.require_auth();
+
+        let mut oracles = Self::read_oracles(&env);
+        let mut record = oracles.get(oracle.clone()).unwrap_or(OracleRecord {
+            oracle: oracle.clone(),
+            stake: 0,
+            resolutions: 0,
+            overturned: 0,
+            total_slashed: 0,
+            registered_at: env.ledger().timestamp(),
+            unbonds_at: 0,
+        });
+        if amount <= 0 || record.stake + amount < MIN_ORACLE_STAKE {
+            return Err(ContractError::OracleStakeTooLow);
+        }
+
+        Self::call_treasury(&env, "stake_oracle", &oracle, amount);
+        record.stake += amount;
+        record.unbonds_at = 0;
+        oracles.set(oracle.clone(), record.clone());
+        env.storage().persistent().set(&ORACLE_MAP, &oracles);
+
+        events::emit_oracle_staked(&env, OracleStakedEvent { oracle, amount, stake: record.stake });
+        Ok(record)
+    }
+
+    /// Starts unbonding `oracle`'s stake. It stays slashable for
+    /// `ORACLE_UNBONDING_SEC`, after which `withdraw_oracle_stake` returns it.
+    ///
+    /// # Returns
+    /// The timestamp from which the stake can be withdrawn.
+    ///
+    /// # Errors
+    /// - `ContractError::OracleNotWhitelisted` if `oracle` never staked
+    /// - `ContractError::OracleStakeLocked` if it is already unbonding
+    pub fn deregister_oracle(env: Env, oracle: Address) -> Result<u64, ContractError> {
+        oracle.require_auth();
+
+        let mut oracles = Self::read_oracles(&env);
+        let mut record = oracles.get(oracle.clone()).ok_or(ContractError::OracleNotWhitelisted)?;
+        if record.unbonds_at != 0 {
+            return Err(ContractError::OracleStakeLocked);
+        }
+        record.unbonds_at = env.ledger().timestamp() + ORACLE_UNBONDING_SEC;
+        oracles.set(oracle.clone(), record.clone());
+        env.storage().persistent().set(&ORACLE_MAP, &oracles);
+
+        events::emit_oracle_unbonding(&env, OracleUnbondingEvent { oracle, unbonds_at: record.unbonds_at });
+        Ok(record.unbonds_at)
+    }
+
+    /// Returns an unbonded oracle's remaining stake. Its record, and so its
+    /// reputation, is kept.
+    ///
+    /// The stake stays slashable until every market the oracle was given is
+    /// final (cancelled, or its resolution finalized), however long that takes.
+    ///
+    /// # Returns
+    /// The amount returned, in stroops.
+    ///
+    /// # Errors
+    /// - `ContractError::OracleNotWhitelisted` if `oracle` never staked
+    /// - `ContractError::OracleStakeLocked` if it has not deregistered, its
+    ///   unbonding period has not elapsed, or one of its markets is not final
+    pub fn withdraw_oracle_stake(env: Env, oracle: Address) -> Result<i128, ContractError> {
+        oracle.require_auth();
+
+        let mut oracles = Self::read_oracles(&env);
+        let mut record = oracles.get(oracle.clone()).ok_or(ContractError::OracleNotWhitelisted)?;
+        if record.unbonds_at == 0 || env.ledger().timestamp() < record.unbonds_at {
+            return Err(ContractError::OracleStakeLocked);
+        }
+
+        let mut oracle_markets = Self::read_oracle_markets(&env);
+        if let Some(assigned) = oracle_markets.get(oracle.clone()) {
+            let market_map: Map<Bytes, MarketInfo> = env
+                .storage()
+                .persistent()
+                .get(&MARKET_MAP)
+                .unwrap_or_else(|| Map::new(&env));
+            for market_id in assigned.iter() {
+                let info = market_map.get(market_id).ok_or(ContractError::MarketNotFound)?;
+                let finalized: bool = env.invoke_contract(
+                    &info.market_address,
+                    &Symbol::new(&env, "is_finalized"),
+                    soroban_sdk::vec![&env],
+                );
+                if !finalized {
+                    return Err(ContractError::OracleStakeLocked);
+                }
+            }
+            oracle_markets.remove(oracle.clone());
+            env.storage().persistent().set(&ORACLE_MARKETS, &oracle_markets);
+        }
+
+        let amount = record.stake;
+        if amount > 0 {
+            Self::call_treasury(&env, "release_oracle_stake", &oracle, amount);
+        }
+        record.stake = 0;
+        oracles.set(oracle.clone(), record);
+        env.storage().persistent().set(&ORACLE_MAP, &oracles);
+
+        events::emit_oracle_stake_withdrawn(&env, OracleStakeWithdrawnEvent { oracle, amount });
+        Ok(amount)
+    }
+
+    /// Sets the share of an oracle's stake slashed per overturned resolution.
+    ///
+    /// # Errors
+    /// - `ContractError::Unauthorized` if `admin` is not the protocol admin
+    /// - `ContractError::InvalidSlashRate` if `bps` exceeds 10_000
+    pub fn set_oracle_slash_bps(env: Env, admin: Address, bps: u32) -> Result<(), ContractError> {
+        Self::require_admin(&env, &admin)?;
+        if bps > 10_000 {
+            return Err(ContractError::InvalidSlashRate);
+        }
+        env.storage().persistent().set(&ORACLE_SLASH_BPS, &bps);
+        Ok(())
+    }
+
+    /// Returns the slash rate in basis points; `DEFAULT_ORACLE_SLASH_BPS` if unset.
+    pub fn get_oracle_slash_bps(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&ORACLE_SLASH_BPS)
+            .unwrap_or(DEFAULT_ORACLE_SLASH_BPS)
+    }
+
+    /// Credits the oracle of `market_id` with a resolution. Called by the
+    /// market itself when its oracle settles it.
+    ///
+    /// # Errors
+    /// - `ContractError::MarketNotFound` if `market_id` is not registered
+    /// - `ContractError::OracleNotWhitelisted` if the market's oracle never staked
+    pub fn record_oracle_resolution(env: Env, market_id: Bytes) -> Result<(), ContractError> {
+        let info = Self::require_registered_market(&env, &market_id)?;
+
+        let mut oracles = Self::read_oracles(&env);
+        let mut record = oracles.get(info.oracle.clone()).ok_or(ContractError::OracleNotWhitelisted)?;
+        record.resolutions += 1;
+        oracles.set(info.oracle.clone(), record);
+        env.storage().persistent().set(&ORACLE_MAP, &oracles);
+
+        events::emit_oracle_resolution_recorded(&env, OracleResolutionRecordedEvent {
+            oracle: info.oracle,
+            market_id: info.numeric_id,
+        });
+        Ok(())
+    }
+
+    /// Marks the resolution of `market_id` as overturned and slashes
+    /// `get_oracle_slash_bps` of its oracle's remaining stake into protocol
+    /// fees. Called by the market when it finalizes an outcome that overrides
+    /// the oracle's call.
+    ///
+    /// # Returns
+    /// The amount slashed, in stroops.
+    ///
+    /// # Errors
+    /// - `ContractError::MarketNotFound` if `market_id` is not registered
+    /// - `ContractError::OracleNotWhitelisted` if the market's oracle never staked
+    pub fn report_oracle_overturned(env: Env, market_id: Bytes) -> Result<i128, ContractError> {
+        let info = Self::require_registered_market(&env, &market_id)?;
+
+        let mut oracles = Self::read_oracles(&env);
+        let mut record = oracles.get(info.oracle.clone()).ok_or(ContractError::OracleNotWhitelisted)?;
+        let slashed = record.stake * Self::get_oracle_slash_bps(env.clone()) as i128 / 10_000;
+        if slashed > 0 {
+            Self::call_treasury(&env, "slash_oracle", &info.oracle, slashed);
+        }
+        record.overturned += 1;
+        record.stake -= slashed;
+        record.total_slashed += slashed;
+        oracles.set(info.oracle.clone(), record);
+        env.storage().persistent().set(&ORACLE_MAP, &oracles);
+
+        events::emit_oracle_slashed(&env, OracleSlashedEvent {
+            oracle: info.oracle,
+            market_id: info.numeric_id,
+            amount: slashed,
+        });
+        Ok(slashed)
+    }
+
+    /// Returns an oracle's stake and track record, or `None` if it never staked.
+    pub fn get_oracle_reputation(env: Env, oracle: Address) -> Option<OracleRecord> {
+        Self::read_oracles(&env).get(oracle)
+    }
+
+    /// Returns every oracle that has ever staked, active or not.
+    pub fn list_oracles(env: Env) -> Vec<OracleRecord> {
+        Self::read_oracles(&env).values()
+    }
+
+    fn read_oracles(env: &Env) -> Map<Address, OracleRecord> {
+        env.storage()
+            .persistent()
+            .get(&ORACLE_MAP)
+            .unwrap_or(Map::new(env))
+    }
+
+    fn read_oracle_markets(env: &Env) -> Map<Address, Vec<Bytes>> {
+        env.storage()
+            .persistent()
+            .get(&ORACLE_MARKETS)
+            .unwrap_or(Map::new(env))
+    }
+
+    /// Looks up `market_id` and requires its deployed contract to authorize the call.
+    fn require_registered_market(env: &Env, market_id: &Bytes) -> Result<MarketInfo, ContractError> {
+        let info = Self::get_market(env.clone(), market_id.clone()).ok_or(ContractError::MarketNotFound)?;
+        info.market_address.require_auth();
+        Ok(info)
+    }
+
+    /// Invokes one of the treasury's oracle stake entry points as the factory.
+    fn call_treasury(env: &Env, func: &str, oracle: &Address, amount: i128) {
+        let treasury = Self::get_treasury(env.clone());
+        env.invoke_contract::<()>(
+            &treasury,
+            &Symbol::new(env, func),
+            soroban_sdk::vec![
+                env,
+                env.current_contract_address().into_val(env),
+                oracle.into_val(env),
+                amount.into_val(env),
+            ],
+        );
+    }
+
+    /// Calls `initialize` on the market just deployed for `info`.
+    fn initialize_market(env: &Env, info: &MarketInfo, draw_enabled: bool) {
+        let config = Self::get_config(env.clone());
+        let treasury = Self::get_treasury(env.clone());
+        let bet_token: Address =
+            env.invoke_contract(&treasury, &Symbol::new(env, "get_token"), Vec::new(env));
+        let fighter = |name: &String| Fighter {
+            name: name.clone(),
+            record: String::from_str(env, ""),
+            nationality: String::from_str(env, ""),
+            weight_class: String::from_str(env, ""),
+        };
+        env.invoke_contract::<()>(
+            &info.market_address,
+            &Symbol::new(env, "initialize"),
+            soroban_sdk::vec![
+                env,
+                info.market_id.into_val(env),
+                info.numeric_id.into_val(env),
+                fighter(&info.fighter_a).into_val(env),
+                fighter(&info.fighter_b).into_val(env),
+                info.end_time.into_val(env),
+                info.lock_time.into_val(env),
+                info.oracle.into_val(env),
+                env.current_contract_address().into_val(env),
+                config.default_fee_bp.into_val(env),
+                config.fee_collector.into_val(env),
+                config.dispute_window_sec.into_val(env),
+                treasury.into_val(env),
+                bet_token.into_val(env),
+                draw_enabled.into_val(env),
+            ],
+        );
+    }
+
+    /// Reads the fields of a Market's `BatchClaim` receipt that `claim_many` follows.
+    fn read_batch_claim(env: &Env, receipt: &Map<Symbol, Val>) -> Option<ClaimPage> {
+        let payout = i128::try_from_val(env, &receipt.get(Symbol::new(env, "total_payout"))?).ok()?;
+        let bet_ids = Vec::<Bytes>::try_from_val(env, &receipt.get(Symbol::new(env, "bet_ids"))?).ok()?;
+        let next_cursor = u32::try_from_val(env, &receipt.get(Symbol::new(env, "next_cursor"))?).ok()?;
+        let done = bool::try_from_val(env, &receipt.get(Symbol::new(env, "done"))?).ok()?;
+        Some(ClaimPage {
+            payout,
+            bets_claimed: bet_ids.len(),
+            next_cursor,
+            done,
+        })
+    }
+
     fn require_admin(env: &Env, admin: &Address) -> Result<(), ContractError> {
         admin.require_auth();
         let stored: Address = env.storage().persistent().get(&ADMIN).expect("not initialized");
@@ -302,31 +1123,71 @@ mod tests {
     use super::*;
     use soroban_sdk::{testutils::{Address as _, Ledger as _}, Env};
 
-    // A minimal, dependency-free contract wasm used only to exercise the real
-    // `env.deployer()...deploy_v2()` path in tests. It is not the production
-    // Market contract; it just needs to be *some* valid, deployable wasm.
-    const DUMMY_WASM: &[u8] = include_bytes!("../test_fixtures/dummy.wasm");
+    // The Market contract built for wasm32v1-none by
+    // `scripts/build_market_fixture.sh`. `create_market` deploys and
+    // initializes it, so it has to be the real contract.
+    const MARKET_WASM: &[u8] = include_bytes!("../test_fixtures/market.wasm");
+
+    /// Treasury stand-in that tallies slashed stake instead of moving tokens.
+    #[soroban_sdk::contract]
+    struct MockTreasury;
+
+    #[contractimpl]
+    impl MockTreasury {
+        /// Markets are initialized with the treasury's token; the mock hands
+        /// out its own address, which nothing in these tests transfers.
+        pub fn get_token(env: Env) -> Address {
+            env.current_contract_address()
+        }
+
+        pub fn stake_oracle(_env: Env, _from_factory: Address, _oracle: Address, _amount: i128) {}
+
+        pub fn release_oracle_stake(_env: Env, _from_factory: Address, _oracle: Address, _amount: i128) {}
+
+        pub fn slash_oracle(env: Env, _from_factory: Address, _oracle: Address, amount: i128) {
+            let total: i128 = env.storage().persistent().get(&"SLASHED").unwrap_or(0);
+            env.storage().persistent().set(&"SLASHED", &(total + amount));
+        }
+
+        pub fn slashed(env: Env) -> i128 {
+            env.storage().persistent().get(&"SLASHED").unwrap_or(0)
+        }
+    }
+
 
     fn setup() -> (Env, MarketFactoryClient<'static>, Address, Address) {
         let env = Env::default();
         env.mock_all_auths();
+        // The fixture is the unoptimized Market build: uploading and
+        // instantiating it runs past the default budget, and its code entry
+        // is over the network size limit until `soroban contract optimize`
+        // shrinks it at deploy time.
+        env.cost_estimate().budget().reset_unlimited();
+        env.cost_estimate().disable_resource_limits();
 
         let contract_id = env.register(MarketFactory, ());
         let client = MarketFactoryClient::new(&env, &contract_id);
         let admin = Address::generate(&env);
-        let treasury = Address::generate(&env);
+        let treasury = env.register(MockTreasury, ());
 
         (env, client, admin, treasury)
     }
 
     fn init(env: &Env, client: &MarketFactoryClient<'static>, admin: &Address, treasury: &Address) {
-        let wasm_hash = env.deployer().upload_contract_wasm(DUMMY_WASM);
+        let wasm_hash = env.deployer().upload_contract_wasm(MARKET_WASM);
         client.initialize(admin, &wasm_hash, treasury);
     }
 
+    /// Registers a new oracle bonding exactly `MIN_ORACLE_STAKE`.
+    fn staked_oracle(env: &Env, client: &MarketFactoryClient<'static>) -> Address {
+        let oracle = Address::generate(env);
+        client.stake_oracle(&oracle, &MIN_ORACLE_STAKE);
+        oracle
+    }
+
     fn create_default_market(env: &Env, client: &MarketFactoryClient<'static>) -> Bytes {
         let caller = Address::generate(env);
-        let oracle = Address::generate(env);
+        let oracle = staked_oracle(env, client);
         let now = env.ledger().timestamp();
         client.create_market(
             &caller,
@@ -335,6 +1196,7 @@ mod tests {
             &oracle,
             &(now + 100),
             &(now + 200),
+            &false,
         )
     }
 
@@ -345,7 +1207,7 @@ mod tests {
         let (env, client, admin, treasury) = setup();
         init(&env, &client, &admin, &treasury);
 
-        let wasm_hash = env.deployer().upload_contract_wasm(DUMMY_WASM);
+        let wasm_hash = env.deployer().upload_contract_wasm(MARKET_WASM);
         assert_eq!(client.get_admin(), admin);
         assert_eq!(client.get_treasury(), treasury);
         assert_eq!(client.get_market_wasm_hash(), wasm_hash);
@@ -357,7 +1219,7 @@ mod tests {
         let (env, client, admin, treasury) = setup();
         init(&env, &client, &admin, &treasury);
 
-        let wasm_hash = env.deployer().upload_contract_wasm(DUMMY_WASM);
+        let wasm_hash = env.deployer().upload_contract_wasm(MARKET_WASM);
         let result = client.try_initialize(&admin, &wasm_hash, &treasury);
         assert_eq!(result, Err(Ok(ContractError::AlreadyInitialized)));
     }
@@ -377,6 +1239,62 @@ mod tests {
         assert_eq!(info.fighter_b, String::from_str(&env, "Fighter B"));
     }
 
+    #[test]
+    fn create_market_initializes_the_deployed_market() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+        create_default_market(&env, &client);
+
+        let caller = Address::generate(&env);
+        let oracle = staked_oracle(&env, &client);
+        let now = env.ledger().timestamp();
+        let market_id = client.create_market(
+            &caller,
+            &String::from_str(&env, "Fighter C"),
+            &String::from_str(&env, "Fighter D"),
+            &oracle,
+            &(now + 100),
+            &(now + 200),
+            &true,
+        );
+        let info = client.get_market(&market_id).unwrap();
+        let config = client.get_config();
+
+        let market = market::MarketContractClient::new(&env, &info.market_address);
+        let stored = market.get_market_info();
+        assert_eq!(stored.market_id, market_id);
+        assert_eq!(stored.numeric_id, 2);
+        assert!(stored.draw_enabled);
+        assert_eq!(stored.fighter_a.name, String::from_str(&env, "Fighter C"));
+        assert_eq!(stored.betting_ends_at, now + 100);
+        assert_eq!(stored.scheduled_at, now + 200);
+        assert_eq!(stored.oracle_address, oracle);
+        assert_eq!(stored.protocol_fee_bp, config.default_fee_bp);
+        assert_eq!(stored.treasury, treasury);
+        assert_eq!(stored.bet_token, treasury);
+
+        // Already initialized, so nobody can re-point its oracle or treasury.
+        let attacker = Address::generate(&env);
+        let result = market.try_initialize(
+            &market_id,
+            &2,
+            &stored.fighter_a,
+            &stored.fighter_b,
+            &(now + 200),
+            &(now + 100),
+            &attacker,
+            &client.address,
+            &0,
+            &attacker,
+            &0,
+            &attacker,
+            &attacker,
+            &true,
+        );
+        assert!(result.is_err());
+        assert_eq!(market.get_market_info().oracle_address, oracle);
+    }
+
     #[test]
     fn create_market_rejects_end_time_in_the_past() {
         let (env, client, admin, treasury) = setup();
@@ -393,6 +1311,7 @@ mod tests {
             &oracle,
             &now,
             &now, // end_time == now, not in the future
+            &false,
         );
         assert_eq!(result, Err(Ok(ContractError::InvalidTimestamp)));
     }
@@ -413,6 +1332,7 @@ mod tests {
             &oracle,
             &(now + 200), // lock_time
             &(now + 100), // end_time, before lock_time
+            &false,
         );
         assert_eq!(result, Err(Ok(ContractError::InvalidTimestamp)));
     }
@@ -437,6 +1357,46 @@ mod tests {
         assert!(client.get_market(&market_id).is_some());
     }
 
+    #[test]
+    fn create_market_assigns_sequential_numeric_ids() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        let first = create_default_market(&env, &client);
+        let second = create_default_market(&env, &client);
+        assert_ne!(first, second);
+
+        assert_eq!(client.get_market(&first).unwrap().numeric_id, 1);
+        assert_eq!(client.get_market(&second).unwrap().numeric_id, 2);
+        assert_eq!(client.get_market_by_numeric_id(&2).unwrap().market_id, second);
+        assert_eq!(client.get_market_by_numeric_id(&3), None);
+    }
+
+    #[test]
+    fn create_market_rejects_numeric_id_collision() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        // Simulate a registry that already holds the next numeric id.
+        env.as_contract(&client.address, || {
+            let mut num_map: Map<u64, Bytes> = Map::new(&env);
+            num_map.set(1, Bytes::from_array(&env, &[9u8; 32]));
+            env.storage().persistent().set(&MARKET_NUM_MAP, &num_map);
+        });
+
+        let now = env.ledger().timestamp();
+        let result = client.try_create_market(
+            &Address::generate(&env),
+            &String::from_str(&env, "Fighter A"),
+            &String::from_str(&env, "Fighter B"),
+            &staked_oracle(&env, &client),
+            &(now + 100),
+            &(now + 200),
+            &false,
+        );
+        assert_eq!(result, Err(Ok(ContractError::MarketIdCollision)));
+    }
+
     // ── C-05: list_markets ──────────────────────────────────────────────────
 
     #[test]
@@ -493,7 +1453,7 @@ mod tests {
 
         // A market ending soon...
         let caller = Address::generate(&env);
-        let oracle = Address::generate(&env);
+        let oracle = staked_oracle(&env, &client);
         let now = env.ledger().timestamp();
         let soon_ending = client.create_market(
             &caller,
@@ -502,6 +1462,7 @@ mod tests {
             &oracle,
             &(now + 10),
             &(now + 20),
+            &false,
         );
         // ...and one ending much later.
         let later_ending = client.create_market(
@@ -511,6 +1472,7 @@ mod tests {
             &oracle,
             &(now + 1000),
             &(now + 2000),
+            &false,
         );
 
         let active_before = client.list_active_markets();
@@ -551,6 +1513,50 @@ mod tests {
         assert_eq!(client.get_admin(), admin);
     }
 
+    // ── get_config / update_config ──────────────────────────────────────────
+
+    #[test]
+    fn get_config_serves_defaults_with_live_admin_and_pause() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        let config = client.get_config();
+        assert_eq!(config.admin, admin);
+        assert_eq!(config.fee_collector, treasury);
+        assert_eq!(config.default_fee_bp, DEFAULT_FEE_BP);
+        assert_eq!((config.min_bet_amount, config.max_bet_amount), (DEFAULT_MIN_BET, DEFAULT_MAX_BET));
+        assert!(!config.paused);
+
+        let new_admin = Address::generate(&env);
+        client.pause_factory(&admin);
+        client.set_admin(&admin, &new_admin);
+        let config = client.get_config();
+        assert_eq!(config.admin, new_admin);
+        assert!(config.paused);
+    }
+
+    #[test]
+    fn update_config_replaces_limits() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        let mut config = client.get_config();
+        config.min_bet_amount = 500;
+        config.claim_expiry_sec = 3_600;
+        client.update_config(&admin, &config);
+        assert_eq!(client.get_config().min_bet_amount, 500);
+        assert_eq!(client.get_config().claim_expiry_sec, 3_600);
+
+        let impostor = Address::generate(&env);
+        assert_eq!(client.try_update_config(&impostor, &config), Err(Ok(ContractError::Unauthorized)));
+
+        config.min_bet_amount = config.max_bet_amount + 1;
+        assert_eq!(client.try_update_config(&admin, &config), Err(Ok(ContractError::InvalidConfig)));
+        config.min_bet_amount = 500;
+        config.default_fee_bp = 10_001;
+        assert_eq!(client.try_update_config(&admin, &config), Err(Ok(ContractError::InvalidConfig)));
+    }
+
     // ── C-08: pause_factory / unpause_factory ───────────────────────────────
 
     #[test]
@@ -583,6 +1589,7 @@ mod tests {
             &oracle,
             &(now + 100),
             &(now + 200),
+            &false,
         );
         assert_eq!(result, Err(Ok(ContractError::FactoryPaused)));
     }
@@ -600,6 +1607,382 @@ mod tests {
         assert!(client.get_market(&market_id).is_some());
     }
 
+    // ── Events (fight cards) ─────────────────────────────────────────────────
+
+    fn create_default_event(env: &Env, client: &MarketFactoryClient<'static>, admin: &Address) -> u64 {
+        client.create_event(
+            admin,
+            &String::from_str(env, "Fight Night 12"),
+            &String::from_str(env, "MSG"),
+            &(env.ledger().timestamp() + 300),
+        )
+    }
+
+    #[test]
+    fn create_event_and_add_markets() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        let event_id = create_default_event(&env, &client, &admin);
+        let undercard = create_default_market(&env, &client);
+        let headliner = create_default_market(&env, &client);
+
+        client.add_market_to_event(&admin, &event_id, &undercard, &false);
+        client.add_market_to_event(&admin, &event_id, &headliner, &true);
+
+        let event = client.get_event(&event_id).unwrap();
+        assert_eq!(event.name, String::from_str(&env, "Fight Night 12"));
+        assert_eq!(event.market_ids.len(), 2);
+        assert_eq!(event.main_event_id, headliner);
+        assert_eq!(client.get_market_event(&undercard), Some(event_id));
+
+        let markets = client.list_event_markets(&event_id);
+        assert_eq!(markets.len(), 2);
+        assert_eq!(markets.get(0).unwrap().market_id, undercard);
+        assert_eq!(markets.get(1).unwrap().market_id, headliner);
+    }
+
+    #[test]
+    fn add_market_to_event_rejects_second_card() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        let first = create_default_event(&env, &client, &admin);
+        let second = create_default_event(&env, &client, &admin);
+        let market_id = create_default_market(&env, &client);
+
+        client.add_market_to_event(&admin, &first, &market_id, &false);
+        let result = client.try_add_market_to_event(&admin, &second, &market_id, &false);
+        assert_eq!(result, Err(Ok(ContractError::MarketAlreadyInEvent)));
+    }
+
+    #[test]
+    fn add_market_to_event_rejects_unknown_ids() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        let event_id = create_default_event(&env, &client, &admin);
+        let market_id = create_default_market(&env, &client);
+
+        let result = client.try_add_market_to_event(&admin, &99, &market_id, &false);
+        assert_eq!(result, Err(Ok(ContractError::EventNotFound)));
+
+        let unknown = Bytes::from_array(&env, &[9u8; 32]);
+        let result = client.try_add_market_to_event(&admin, &event_id, &unknown, &false);
+        assert_eq!(result, Err(Ok(ContractError::MarketNotFound)));
+    }
+
+    #[test]
+    fn create_event_rejects_non_admin_caller() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        let impostor = Address::generate(&env);
+        let result = client.try_create_event(
+            &impostor,
+            &String::from_str(&env, "Fight Night 12"),
+            &String::from_str(&env, "MSG"),
+            &0,
+        );
+        assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+    }
+
+    #[test]
+    fn bulk_event_operations_skip_markets_that_refuse() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        let event_id = create_default_event(&env, &client, &admin);
+        let market_id = create_default_market(&env, &client);
+        client.add_market_to_event(&admin, &event_id, &market_id, &false);
+
+        assert_eq!(client.lock_event_markets(&admin, &event_id), 1);
+        assert_eq!(client.cancel_event_markets(&admin, &event_id), 1);
+        // Already cancelled, so the market refuses and is skipped instead of
+        // reverting the whole card.
+        assert_eq!(client.lock_event_markets(&admin, &event_id), 0);
+        assert_eq!(client.cancel_event_markets(&admin, &event_id), 0);
+
+        let info = client.get_market(&market_id).unwrap();
+        let market = market::MarketContractClient::new(&env, &info.market_address);
+        assert_eq!(market.get_market_info().status, market::types::MarketStatus::Cancelled);
+
+        let result = client.try_cancel_event_markets(&admin, &99);
+        assert_eq!(result, Err(Ok(ContractError::EventNotFound)));
+    }
+
+    // ── claim_many ────────────────────────────────────────────────────────
+
+    /// Mirrors the Market's `BatchClaim` receipt encoding.
+    #[soroban_sdk::contracttype]
+    struct MockBatchClaim {
+        bettor: Address,
+        bet_ids: Vec<Bytes>,
+        total_payout: i128,
+        next_cursor: u32,
+        done: bool,
+        claimed_at: u64,
+    }
+
+    /// Market stand-in that pays a fixed amount for two bets per claim.
+    #[soroban_sdk::contract]
+    struct MockMarket;
+
+    #[contractimpl]
+    impl MockMarket {
+        pub fn claim_all(env: Env, bettor: Address, cursor: u32) -> MockBatchClaim {
+            let id = Bytes::from_array(&env, &[1u8; 4]);
+            MockBatchClaim {
+                bettor,
+                bet_ids: soroban_sdk::vec![&env, id.clone(), id],
+                total_payout: 750,
+                next_cursor: cursor + 2,
+                done: true,
+                claimed_at: env.ledger().timestamp(),
+            }
+        }
+    }
+
+    /// Points `market_id`'s registry entry at `market_address`.
+    fn register_market_at(env: &Env, client: &MarketFactoryClient<'static>, market_id: &Bytes, market_address: &Address) {
+        env.as_contract(&client.address, || {
+            let mut map: Map<Bytes, MarketInfo> = env.storage().persistent().get(&MARKET_MAP).unwrap();
+            let mut info = map.get(market_id.clone()).unwrap();
+            info.market_address = market_address.clone();
+            map.set(market_id.clone(), info);
+            env.storage().persistent().set(&MARKET_MAP, &map);
+        });
+    }
+
+    #[test]
+    fn claim_many_returns_per_market_breakdown() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        let settled = create_default_market(&env, &client);
+        let refusing = create_default_market(&env, &client);
+        let mock = env.register(MockMarket, ());
+        register_market_at(&env, &client, &settled, &mock);
+
+        let bettor = Address::generate(&env);
+        let ids = soroban_sdk::vec![&env, settled.clone(), refusing.clone()];
+        let breakdown = client.claim_many(&bettor, &ids);
+
+        assert_eq!(breakdown.len(), 2);
+        let first = breakdown.get(0).unwrap();
+        assert_eq!(first.market_id, settled);
+        assert_eq!(first.payout, 750);
+        assert_eq!(first.bets_claimed, 2);
+        assert!(first.done && first.succeeded);
+
+        // That market is still open, so its claim fails and is reported, not fatal.
+        let second = breakdown.get(1).unwrap();
+        assert_eq!(second.market_id, refusing);
+        assert!(!second.succeeded);
+        assert_eq!(second.payout, 0);
+    }
+
+    #[test]
+    fn claim_many_rejects_too_many_or_unknown_markets() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        let bettor = Address::generate(&env);
+        let market_id = create_default_market(&env, &client);
+        let mut ids = Vec::new(&env);
+        for _ in 0..21 {
+            ids.push_back(market_id.clone());
+        }
+        let result = client.try_claim_many(&bettor, &ids);
+        assert_eq!(result, Err(Ok(ContractError::TooManyMarkets)));
+
+        let unknown = soroban_sdk::vec![&env, Bytes::from_array(&env, &[9u8; 32])];
+        let result = client.try_claim_many(&bettor, &unknown);
+        assert_eq!(result, Err(Ok(ContractError::MarketNotFound)));
+    }
+
+    #[test]
+    fn set_arbiter_panel_stores_committee() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        assert!(client.get_arbiter_panel().members.is_empty());
+        let members = soroban_sdk::vec![&env, Address::generate(&env), Address::generate(&env)];
+        client.set_arbiter_panel(&admin, &members, &3_600);
+
+        let panel = client.get_arbiter_panel();
+        assert_eq!(panel.members, members);
+        assert_eq!(panel.vote_window_sec, 3_600);
+    }
+
+    #[test]
+    fn set_arbiter_panel_rejects_bad_panels() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        let arbiter = Address::generate(&env);
+        let twice = soroban_sdk::vec![&env, arbiter.clone(), arbiter.clone()];
+        let result = client.try_set_arbiter_panel(&admin, &twice, &3_600);
+        assert_eq!(result, Err(Ok(ContractError::InvalidArbiterPanel)));
+
+        let no_window = soroban_sdk::vec![&env, arbiter.clone()];
+        let result = client.try_set_arbiter_panel(&admin, &no_window, &0);
+        assert_eq!(result, Err(Ok(ContractError::InvalidArbiterPanel)));
+
+        let impostor = Address::generate(&env);
+        let result = client.try_set_arbiter_panel(&impostor, &no_window, &3_600);
+        assert_eq!(result, Err(Ok(ContractError::Unauthorized)));
+    }
+
+    #[test]
+    fn arbiter_votes_are_kept_per_arbiter_across_markets() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+        let first = create_default_market(&env, &client);
+        let second = create_default_market(&env, &client);
+        let arbiter = Address::generate(&env);
+
+        assert!(client.get_arbiter_votes(&arbiter).is_empty());
+        client.record_arbiter_vote(&first, &arbiter, &0, &Outcome::FighterA);
+        client.record_arbiter_vote(&second, &arbiter, &1, &Outcome::Draw);
+        client.record_arbiter_vote(&second, &Address::generate(&env), &1, &Outcome::FighterB);
+
+        let votes = client.get_arbiter_votes(&arbiter);
+        assert_eq!(votes.len(), 2);
+        assert_eq!(votes.get(0).unwrap().market_id, first);
+        assert_eq!(votes.get(0).unwrap().outcome, Outcome::FighterA);
+        assert_eq!(votes.get(1).unwrap().market_id, second);
+        assert_eq!(votes.get(1).unwrap().dispute_id, 1);
+
+        let unknown = Bytes::from_array(&env, &[9u8; 32]);
+        let result = client.try_record_arbiter_vote(&unknown, &arbiter, &0, &Outcome::FighterA);
+        assert_eq!(result, Err(Ok(ContractError::MarketNotFound)));
+    }
+
+    // ── oracle stake & reputation ─────────────────────────────────────────
+
+    /// Creates a market whose oracle bonds exactly `MIN_ORACLE_STAKE`.
+    fn setup_staked_oracle(env: &Env, client: &MarketFactoryClient<'static>) -> (Bytes, Address) {
+        let market_id = create_default_market(env, client);
+        let oracle = client.get_market(&market_id).unwrap().oracle;
+        (market_id, oracle)
+    }
+
+    #[test]
+    fn stake_oracle_registers_and_tops_up() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        let oracle = Address::generate(&env);
+        let result = client.try_stake_oracle(&oracle, &(MIN_ORACLE_STAKE - 1));
+        assert_eq!(result, Err(Ok(ContractError::OracleStakeTooLow)));
+        assert_eq!(client.get_oracle_reputation(&oracle), None);
+
+        client.stake_oracle(&oracle, &MIN_ORACLE_STAKE);
+        let record = client.stake_oracle(&oracle, &1);
+        assert_eq!(record.stake, MIN_ORACLE_STAKE + 1);
+        assert_eq!(record.resolutions, 0);
+        assert_eq!(client.list_oracles().len(), 1);
+    }
+
+    #[test]
+    fn overturned_resolution_slashes_oracle_stake() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+        let (market_id, oracle) = setup_staked_oracle(&env, &client);
+
+        client.record_oracle_resolution(&market_id);
+        let slashed = client.report_oracle_overturned(&market_id);
+        assert_eq!(slashed, MIN_ORACLE_STAKE / 10);
+        assert_eq!(MockTreasuryClient::new(&env, &treasury).slashed(), slashed);
+
+        let record = client.get_oracle_reputation(&oracle).unwrap();
+        assert_eq!(record.resolutions, 1);
+        assert_eq!(record.overturned, 1);
+        assert_eq!(record.stake, MIN_ORACLE_STAKE - slashed);
+        assert_eq!(record.total_slashed, slashed);
+
+        client.set_oracle_slash_bps(&admin, &10_000);
+        assert_eq!(client.report_oracle_overturned(&market_id), MIN_ORACLE_STAKE - slashed);
+        assert_eq!(client.get_oracle_reputation(&oracle).unwrap().stake, 0);
+
+        let result = client.try_set_oracle_slash_bps(&admin, &10_001);
+        assert_eq!(result, Err(Ok(ContractError::InvalidSlashRate)));
+    }
+
+    #[test]
+    fn create_market_requires_a_bonded_oracle() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+        let now = env.ledger().timestamp();
+        let create = |oracle: &Address| {
+            client.try_create_market(
+                &Address::generate(&env),
+                &String::from_str(&env, "Fighter A"),
+                &String::from_str(&env, "Fighter B"),
+                oracle,
+                &(now + 100),
+                &(now + 200),
+                &false,
+            )
+        };
+
+        let unstaked = Address::generate(&env);
+        assert_eq!(create(&unstaked), Err(Ok(ContractError::OracleNotWhitelisted)));
+
+        // Slashed below the minimum.
+        let (market_id, slashed) = setup_staked_oracle(&env, &client);
+        client.report_oracle_overturned(&market_id);
+        assert_eq!(create(&slashed), Err(Ok(ContractError::OracleStakeTooLow)));
+        client.stake_oracle(&slashed, &MIN_ORACLE_STAKE);
+        assert!(create(&slashed).is_ok());
+
+        let leaving = staked_oracle(&env, &client);
+        client.deregister_oracle(&leaving);
+        assert_eq!(create(&leaving), Err(Ok(ContractError::OracleStakeTooLow)));
+        assert_eq!(client.list_markets(&0, &10).len(), 2);
+    }
+
+    #[test]
+    fn oracle_reports_reject_unknown_market() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+
+        let unknown = Bytes::from_array(&env, &[9u8; 32]);
+        let result = client.try_report_oracle_overturned(&unknown);
+        assert_eq!(result, Err(Ok(ContractError::MarketNotFound)));
+    }
+
+    #[test]
+    fn oracle_stake_withdraws_only_after_unbonding_and_final_markets() {
+        let (env, client, admin, treasury) = setup();
+        init(&env, &client, &admin, &treasury);
+        let (market_id, oracle) = setup_staked_oracle(&env, &client);
+
+        let result = client.try_withdraw_oracle_stake(&oracle);
+        assert_eq!(result, Err(Ok(ContractError::OracleStakeLocked)));
+
+        let unbonds_at = client.deregister_oracle(&oracle);
+        // Still slashable while unbonding.
+        assert!(client.report_oracle_overturned(&market_id) > 0);
+        env.ledger().with_mut(|li| li.timestamp = unbonds_at - 1);
+        let result = client.try_withdraw_oracle_stake(&oracle);
+        assert_eq!(result, Err(Ok(ContractError::OracleStakeLocked)));
+
+        // Unbonded, but the oracle's market is not final yet.
+        env.ledger().with_mut(|li| li.timestamp = unbonds_at);
+        let result = client.try_withdraw_oracle_stake(&oracle);
+        assert_eq!(result, Err(Ok(ContractError::OracleStakeLocked)));
+
+        let info = client.get_market(&market_id).unwrap();
+        market::MarketContractClient::new(&env, &info.market_address).cancel_market(&admin);
+        let remaining = client.get_oracle_reputation(&oracle).unwrap().stake;
+        assert_eq!(client.withdraw_oracle_stake(&oracle), remaining);
+        let record = client.get_oracle_reputation(&oracle).unwrap();
+        assert_eq!(record.stake, 0);
+        assert_eq!(record.overturned, 1);
+    }
+
     #[test]
     fn pause_factory_rejects_non_admin_caller() {
         let (env, client, admin, treasury) = setup();
@@ -37,5 +37,6 @@ pub struct ProtocolConfig {
     pub min_bet_amount: i128,
     pub max_bet_amount: i128,
     pub dispute_window_sec: u64,
+    pub claim_expiry_sec: u64,
     pub paused: bool,
 }
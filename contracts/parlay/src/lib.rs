@@ -0,0 +1,411 @@
+#![no_std]
+//! ============================================================
+//! BOXMEOUT — Parlay Contract
+//! Accumulator bets across several markets on a fight card.
+//! Stakes are escrowed in the Treasury, which also reserves the
+//! most each parlay can win; each leg is graded by reading its
+//! Market contract's `get_market_info`, once the market reports
+//! its settlement final.
+//! ============================================================
+
+pub mod types;
+
+use shared::events::{self, ParlayPlacedEvent, ParlaySettledEvent};
+use shared::types::{MarketInfo, OptionalOutcome, ProtocolConfig};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, Address, Bytes, Env, IntoVal, Map, Symbol, TryFromVal,
+    Val, Vec,
+};
+use types::{BetSide, LegResult, MarketStatus, MarketView, Outcome, Parlay, ParlayLeg, ParlayStatus};
+
+/// Fewest legs a parlay may have; a single leg is just a bet.
+pub const MIN_LEGS: u32 = 2;
+
+/// Most legs a parlay may have. Bounds the cross-contract reads per settlement.
+pub const MAX_LEGS: u32 = 8;
+
+/// Most a parlay pays, as a multiple of its stake. Bounds what the Treasury
+/// has to reserve for it when it is placed.
+pub const MAX_PAYOUT_MULTIPLIER: i128 = 50;
+
+// ─── STORAGE KEYS ─────────────────────────────────────────────────────────────
+// DataKey::Admin             -> Address
+// DataKey::Factory           -> Address  (MarketFactory contract address)
+// DataKey::Treasury          -> Address
+// DataKey::ParlayCount       -> u64
+// DataKey::Parlay(id)        -> Parlay
+// DataKey::ParlaysByAddr(a)  -> Vec<u64>  (all parlay_ids for an address)
+
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    Factory,
+    Treasury,
+    ParlayCount,
+    Parlay(u64),
+    ParlaysByAddr(Address),
+}
+
+/// Decodes one field of a Market's `get_market_info` result.
+fn field<T: TryFromVal<Env, Val>>(env: &Env, fields: &Map<Symbol, Val>, name: &str) -> T {
+    let val = fields
+        .get(Symbol::new(env, name))
+        .expect("market field missing");
+    T::try_from_val(env, &val).unwrap_or_else(|_| panic!("malformed market field"))
+}
+
+/// Like [`field`], for fields older markets may not have.
+fn field_or<T: TryFromVal<Env, Val>>(env: &Env, fields: &Map<Symbol, Val>, name: &str, default: T) -> T {
+    match fields.get(Symbol::new(env, name)) {
+        Some(val) => T::try_from_val(env, &val).unwrap_or_else(|_| panic!("malformed market field")),
+        None => default,
+    }
+}
+
+/// Grades a single leg against the current state of its market. A resolved
+/// market stays pending until its resolution is finalized, since a dispute
+/// could still change the outcome.
+pub fn grade_leg(leg: &ParlayLeg, market: &MarketView) -> LegResult {
+    match market.status {
+        MarketStatus::Cancelled => LegResult::Void,
+        MarketStatus::Resolved if !market.finalized => LegResult::Pending,
+        MarketStatus::Resolved => {
+            let outcome = match &market.outcome {
+                Some(o) => o,
+                None => return LegResult::Pending,
+            };
+            let winning_pool = match (outcome, &leg.side) {
+                (Outcome::NoContest, _) => return LegResult::Void,
+                (Outcome::Draw, _) if !market.draw_enabled => return LegResult::Void,
+                (Outcome::FighterA, BetSide::FighterA) => market.pool_a,
+                (Outcome::FighterB, BetSide::FighterB) => market.pool_b,
+                (Outcome::Draw, BetSide::Draw) => market.pool_draw,
+                _ => return LegResult::Lost,
+            };
+            // Nobody in the market backed the winning side, so there are no odds to apply.
+            if winning_pool == 0 {
+                return LegResult::Void;
+            }
+            let fee = shared::types::calculate_fee(market.total_pool, market.protocol_fee_bp);
+            LegResult::Won {
+                net_pool: market.total_pool.checked_sub(fee).expect("net pool underflow"),
+                winning_pool,
+            }
+        }
+        _ => LegResult::Pending,
+    }
+}
+
+#[contract]
+pub struct ParlayContract;
+
+#[contractimpl]
+impl ParlayContract {
+    fn read_factory(env: &Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Factory)
+            .expect("not initialized")
+    }
+
+    fn read_treasury(env: &Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Treasury)
+            .expect("not initialized")
+    }
+
+    /// Reads a market's live state through the factory registry.
+    fn read_market_view(env: &Env, market_id: &Bytes) -> MarketView {
+        let info: Option<MarketInfo> = env.invoke_contract(
+            &Self::read_factory(env),
+            &Symbol::new(env, "get_market"),
+            soroban_sdk::vec![env, market_id.clone().into_val(env)],
+        );
+        let info = info.expect("market not found");
+
+        let fields: Map<Symbol, Val> = env.invoke_contract(
+            &info.market_address,
+            &Symbol::new(env, "get_market_info"),
+            soroban_sdk::vec![env],
+        );
+        let status: MarketStatus = field(env, &fields, "status");
+        let finalized = match status {
+            MarketStatus::Resolved => env.invoke_contract(
+                &info.market_address,
+                &Symbol::new(env, "is_finalized"),
+                soroban_sdk::vec![env],
+            ),
+            MarketStatus::Cancelled => true,
+            _ => false,
+        };
+        MarketView {
+            status,
+            finalized,
+            outcome: field::<OptionalOutcome>(env, &fields, "outcome").outcome(),
+            betting_ends_at: field(env, &fields, "betting_ends_at"),
+            pool_a: field(env, &fields, "pool_a"),
+            pool_b: field(env, &fields, "pool_b"),
+            pool_draw: field_or(env, &fields, "pool_draw", 0),
+            total_pool: field(env, &fields, "total_pool"),
+            protocol_fee_bp: field(env, &fields, "protocol_fee_bp"),
+            draw_enabled: field_or(env, &fields, "draw_enabled", false),
+        }
+    }
+
+    /// One-time setup. Stores the admin, the MarketFactory used to look up
+    /// leg markets, and the Treasury that escrows stakes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the contract has already been initialized.
+    pub fn initialize(env: Env, admin: Address, factory: Address, treasury: Address) {
+        if env.storage().persistent().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        env.storage().persistent().set(&DataKey::Admin, &admin);
+        env.storage().persistent().set(&DataKey::Factory, &factory);
+        env.storage().persistent().set(&DataKey::Treasury, &treasury);
+        env.storage().persistent().set(&DataKey::ParlayCount, &0u64);
+    }
+
+    /// Places a parlay across several markets.
+    ///
+    /// Every leg's market must still be accepting bets. The stake is escrowed
+    /// through the Treasury's `deposit_parlay`, which also reserves the most the
+    /// parlay can pay (`amount × MAX_PAYOUT_MULTIPLIER`); the stake does not
+    /// enter any market's pools. Emits a `parlay_placed` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `bettor` - Address of the user placing the parlay. Must authorize this call.
+    /// * `legs` - One `(market_id, side)` pick per market, `MIN_LEGS..=MAX_LEGS` of them.
+    /// * `amount` - Stake in stroops. Must satisfy `min_bet_amount ≤ amount ≤ max_bet_amount`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `parlay_id` assigned to this parlay.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - The number of legs is outside `MIN_LEGS..=MAX_LEGS`.
+    /// - Two legs name the same market.
+    /// - A leg's market is unknown to the factory, not `Open`, or past `betting_ends_at`.
+    /// - A leg backs `Draw` on a market without draw betting.
+    /// - `amount` is outside the configured bet limits.
+    /// - The Treasury cannot cover the parlay's maximum payout.
+    pub fn place_parlay(env: Env, bettor: Address, legs: Vec<ParlayLeg>, amount: i128) -> u64 {
+        bettor.require_auth();
+
+        if legs.len() < MIN_LEGS {
+            panic!("too few legs");
+        }
+        if legs.len() > MAX_LEGS {
+            panic!("too many legs");
+        }
+
+        let now = env.ledger().timestamp();
+        for i in 0..legs.len() {
+            let leg = legs.get(i).unwrap();
+            for j in (i + 1)..legs.len() {
+                if legs.get(j).unwrap().market_id == leg.market_id {
+                    panic!("duplicate market in parlay");
+                }
+            }
+
+            let market = Self::read_market_view(&env, &leg.market_id);
+            if market.status != MarketStatus::Open || now >= market.betting_ends_at {
+                panic!("leg market not open");
+            }
+            if leg.side == BetSide::Draw && !market.draw_enabled {
+                panic!("draw betting not enabled");
+            }
+        }
+
+        let config: ProtocolConfig = env.invoke_contract(
+            &Self::read_factory(&env),
+            &Symbol::new(&env, "get_config"),
+            soroban_sdk::vec![&env],
+        );
+        if amount < config.min_bet_amount {
+            panic!("below minimum bet");
+        }
+        if amount > config.max_bet_amount {
+            panic!("above maximum bet");
+        }
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+
+        let max_payout = amount
+            .checked_mul(MAX_PAYOUT_MULTIPLIER)
+            .expect("payout overflow");
+        env.invoke_contract::<()>(
+            &Self::read_treasury(&env),
+            &Symbol::new(&env, "deposit_parlay"),
+            soroban_sdk::vec![
+                &env,
+                env.current_contract_address().into_val(&env),
+                bettor.clone().into_val(&env),
+                amount.into_val(&env),
+                max_payout.into_val(&env),
+            ],
+        );
+
+        let count: u64 = env.storage().persistent()
+            .get(&DataKey::ParlayCount)
+            .unwrap_or(0);
+        let parlay_id = count + 1;
+        env.storage().persistent().set(&DataKey::ParlayCount, &parlay_id);
+
+        let parlay = Parlay {
+            parlay_id,
+            bettor: bettor.clone(),
+            legs: legs.clone(),
+            amount,
+            max_payout,
+            placed_at: now,
+            status: ParlayStatus::Open,
+            payout: 0,
+        };
+        env.storage().persistent().set(&DataKey::Parlay(parlay_id), &parlay);
+
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ParlaysByAddr(bettor.clone()))
+            .unwrap_or(Vec::new(&env));
+        ids.push_back(parlay_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ParlaysByAddr(bettor.clone()), &ids);
+
+        events::emit_parlay_placed(&env, ParlayPlacedEvent { parlay_id, bettor, legs, amount });
+
+        parlay_id
+    }
+
+    /// Settles a parlay once its outcome is decided, paying the bettor.
+    ///
+    /// Permissionless — funds only ever go to the parlay's bettor.
+    /// - Any losing leg settles the parlay as `Lost`, even if other legs are pending.
+    /// - Void legs (cancelled market, NoContest, refunded Draw, or no winning
+    ///   stake in the market) count at 1x.
+    /// - If every leg is void the stake is refunded (`Refunded`).
+    /// - Otherwise the payout is `amount × Π(net_pool / winning_pool)` over the
+    ///   winning legs, using each market's final parimutuel odds, capped at
+    ///   `max_payout` (`Won`).
+    ///
+    /// Either way the Treasury releases the parlay's reservation. Emits a `parlay_settled` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `parlay_id` - Identifier returned by `place_parlay`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the amount paid to the bettor, in stroops (0 for a lost parlay).
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `parlay_id` does not exist or the parlay is already settled.
+    /// - No leg has lost and at least one leg's market is not yet settled.
+    pub fn settle_parlay(env: Env, parlay_id: u64) -> i128 {
+        let mut parlay: Parlay = env.storage().persistent()
+            .get(&DataKey::Parlay(parlay_id))
+            .expect("parlay not found");
+        if parlay.status != ParlayStatus::Open {
+            panic!("parlay already settled");
+        }
+
+        let mut pending = false;
+        let mut lost = false;
+        let mut any_won = false;
+        let mut payout = parlay.amount;
+        for leg in parlay.legs.iter() {
+            let market = Self::read_market_view(&env, &leg.market_id);
+            match grade_leg(&leg, &market) {
+                LegResult::Pending => pending = true,
+                LegResult::Lost => {
+                    lost = true;
+                    break;
+                }
+                LegResult::Void => {}
+                LegResult::Won { net_pool, winning_pool } => {
+                    any_won = true;
+                    payout = payout
+                        .checked_mul(net_pool)
+                        .expect("payout overflow")
+                        .checked_div(winning_pool)
+                        .expect("payout div zero");
+                }
+            }
+        }
+
+        if lost {
+            parlay.status = ParlayStatus::Lost;
+            payout = 0;
+        } else if pending {
+            panic!("parlay legs not settled");
+        } else if any_won {
+            parlay.status = ParlayStatus::Won;
+            payout = payout.min(parlay.max_payout);
+        } else {
+            parlay.status = ParlayStatus::Refunded;
+        }
+        parlay.payout = payout;
+
+        // Record settlement BEFORE any transfer (re-entrancy guard).
+        env.storage().persistent().set(&DataKey::Parlay(parlay_id), &parlay);
+
+        env.invoke_contract::<()>(
+            &Self::read_treasury(&env),
+            &Symbol::new(&env, "pay_parlay"),
+            soroban_sdk::vec![
+                &env,
+                env.current_contract_address().into_val(&env),
+                parlay.bettor.clone().into_val(&env),
+                parlay.max_payout.into_val(&env),
+                payout.into_val(&env),
+            ],
+        );
+
+        events::emit_parlay_settled(&env, ParlaySettledEvent {
+            parlay_id,
+            bettor: parlay.bettor,
+            status: parlay.status,
+            payout,
+        });
+
+        payout
+    }
+
+    /// Returns the [`Parlay`] identified by `parlay_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parlay_id` does not correspond to any parlay.
+    pub fn get_parlay(env: Env, parlay_id: u64) -> Parlay {
+        env.storage().persistent()
+            .get(&DataKey::Parlay(parlay_id))
+            .expect("parlay not found")
+    }
+
+    /// Returns all parlays placed by `bettor`, in placement order.
+    pub fn get_parlays_by_address(env: Env, bettor: Address) -> Vec<Parlay> {
+        let ids: Vec<u64> = env.storage().persistent()
+            .get(&DataKey::ParlaysByAddr(bettor))
+            .unwrap_or(Vec::new(&env));
+        let mut parlays = Vec::new(&env);
+        for id in ids.iter() {
+            if let Some(parlay) = env.storage().persistent().get(&DataKey::Parlay(id)) {
+                parlays.push_back(parlay);
+            }
+        }
+        parlays
+    }
+}
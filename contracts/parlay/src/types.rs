@@ -0,0 +1,61 @@
+use soroban_sdk::{contracttype, Address, Vec};
+
+pub use shared::types::{BetSide, Outcome, ParlayLeg, ParlayStatus};
+
+// ─── MARKET MIRRORS ───────────────────────────────────────────────────────────
+// Encoded exactly like the enums in `market::types`, so values read from a
+// Market contract decode into these without depending on the market crate.
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum MarketStatus {
+    Open,
+    Locked,
+    Resolved,
+    Cancelled,
+    Disputed,
+}
+
+/// The fields of a Market's `get_market_info` a parlay needs to grade a leg,
+/// plus whether its settlement is final (`is_finalized`).
+#[derive(Clone, Debug)]
+pub struct MarketView {
+    pub status: MarketStatus,
+    pub finalized: bool,
+    pub outcome: Option<Outcome>,
+    pub betting_ends_at: u64,
+    pub pool_a: i128,
+    pub pool_b: i128,
+    pub pool_draw: i128,
+    pub total_pool: i128,
+    pub protocol_fee_bp: u32,
+    pub draw_enabled: bool,
+}
+
+// ─── PARLAY ───────────────────────────────────────────────────────────────────
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Parlay {
+    pub parlay_id: u64,
+    pub bettor: Address,
+    pub legs: Vec<ParlayLeg>,
+    pub amount: i128,
+    /// Most the parlay can pay; reserved in the Treasury until it settles.
+    pub max_payout: i128,
+    pub placed_at: u64,
+    pub status: ParlayStatus,
+    pub payout: i128,
+}
+
+/// How a single leg graded once its market settled.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LegResult {
+    /// Market not yet resolved or cancelled (or under dispute).
+    Pending,
+    /// Leg won; odds are `net_pool / winning_pool` of its market.
+    Won { net_pool: i128, winning_pool: i128 },
+    Lost,
+    /// Market cancelled, refunded outcome, or nobody else backed the side.
+    Void,
+}
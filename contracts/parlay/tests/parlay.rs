@@ -0,0 +1,528 @@
+//! =============================================================================
+//! BOXMEOUT — Parlay Tests
+//! =============================================================================
+//!
+//! Runs parlays against the real Treasury, with mock Market contracts whose
+//! state each test drives directly:
+//!   - All legs win → multiplied parimutuel odds
+//!   - A losing leg settles the parlay even while others are pending
+//!   - Legs stay pending until their market's resolution is finalized
+//!   - Void legs count at 1x; an all-void parlay is refunded
+//!   - The Treasury reserves each parlay's maximum payout until it settles
+//!   - Placement validation
+
+use parlay::types::{BetSide, MarketStatus, Outcome, ParlayLeg, ParlayStatus};
+use parlay::{ParlayContract, ParlayContractClient, MAX_PAYOUT_MULTIPLIER};
+use shared::types::{MarketInfo, OptionalOutcome, ProtocolConfig};
+use soroban_sdk::{
+    contract, contractimpl, testutils::Address as _, token, Address, Bytes, Env, IntoVal, Map,
+    String, Symbol, TryFromVal, Val, Vec,
+};
+use treasury::{Treasury, TreasuryClient};
+
+// ─── Mock Factory ─────────────────────────────────────────────────────────────
+
+#[contract]
+struct MockFactory;
+
+#[contractimpl]
+impl MockFactory {
+    pub fn __constructor(env: Env, admin: Address) {
+        env.storage()
+            .persistent()
+            .set(&Symbol::new(&env, "admin"), &admin);
+    }
+
+    pub fn register(env: Env, market_id: Bytes, market_address: Address) {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, "admin"))
+            .unwrap();
+        let info = MarketInfo {
+            market_id: market_id.clone(),
+            numeric_id: market_id.get(0).unwrap_or(0) as u64,
+            market_address,
+            creator: admin.clone(),
+            fighter_a: String::from_str(&env, "A"),
+            fighter_b: String::from_str(&env, "B"),
+            oracle: admin,
+            lock_time: 1_000_000,
+            end_time: 2_000_000,
+            created_at: 0,
+        };
+        env.storage().persistent().set(&market_id, &info);
+    }
+
+    pub fn get_market(env: Env, market_id: Bytes) -> Option<MarketInfo> {
+        env.storage().persistent().get(&market_id)
+    }
+
+    pub fn get_config(env: Env) -> ProtocolConfig {
+        let admin: Address = env
+            .storage()
+            .persistent()
+            .get(&Symbol::new(&env, "admin"))
+            .unwrap();
+        ProtocolConfig {
+            admin: admin.clone(),
+            fee_collector: admin,
+            default_fee_bp: 200,
+            min_bet_amount: 100,
+            max_bet_amount: 100_000_000_000,
+            dispute_window_sec: 86_400,
+            claim_expiry_sec: 0,
+            paused: false,
+        }
+    }
+}
+
+// ─── Mock Market ──────────────────────────────────────────────────────────────
+
+/// Serves `get_market_info` in the same shape as the real Market contract.
+#[contract]
+struct MockMarket;
+
+#[contractimpl]
+impl MockMarket {
+    pub fn set_state(
+        env: Env,
+        status: MarketStatus,
+        outcome: Option<Outcome>,
+        pool_a: i128,
+        pool_b: i128,
+        pool_draw: i128,
+        draw_enabled: bool,
+    ) {
+        let total_pool = pool_a + pool_b + pool_draw;
+        let mut m: Map<Symbol, Val> = Map::new(&env);
+        m.set(Symbol::new(&env, "status"), status.into_val(&env));
+        let outcome = match outcome {
+            Some(o) => OptionalOutcome::Some(o),
+            None => OptionalOutcome::None,
+        };
+        m.set(Symbol::new(&env, "outcome"), outcome.into_val(&env));
+        m.set(Symbol::new(&env, "betting_ends_at"), 1_000_000u64.into_val(&env));
+        m.set(Symbol::new(&env, "pool_a"), pool_a.into_val(&env));
+        m.set(Symbol::new(&env, "pool_b"), pool_b.into_val(&env));
+        m.set(Symbol::new(&env, "pool_draw"), pool_draw.into_val(&env));
+        m.set(Symbol::new(&env, "total_pool"), total_pool.into_val(&env));
+        m.set(Symbol::new(&env, "protocol_fee_bp"), 200u32.into_val(&env));
+        m.set(Symbol::new(&env, "draw_enabled"), draw_enabled.into_val(&env));
+        env.storage().instance().set(&Symbol::new(&env, "info"), &m);
+    }
+
+    pub fn get_market_info(env: Env) -> Map<Symbol, Val> {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "info"))
+            .unwrap()
+    }
+
+    /// Test hook: keeps a `Resolved` state inside its dispute window, so it is
+    /// not final yet.
+    pub fn set_disputable(env: Env, disputable: bool) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "disputable"), &disputable);
+    }
+
+    pub fn is_finalized(env: Env) -> bool {
+        let info = Self::get_market_info(env.clone());
+        let status =
+            MarketStatus::try_from_val(&env, &info.get(Symbol::new(&env, "status")).unwrap()).unwrap();
+        let disputable: bool = env
+            .storage()
+            .instance()
+            .get(&Symbol::new(&env, "disputable"))
+            .unwrap_or(false);
+        match status {
+            MarketStatus::Resolved => !disputable,
+            MarketStatus::Cancelled => true,
+            _ => false,
+        }
+    }
+}
+
+// ─── Harness ──────────────────────────────────────────────────────────────────
+
+struct Setup<'a> {
+    env: Env,
+    parlay: ParlayContractClient<'a>,
+    treasury: TreasuryClient<'a>,
+    token: token::Client<'a>,
+    token_admin: token::StellarAssetClient<'a>,
+    factory: Address,
+    admin: Address,
+}
+
+impl<'a> Setup<'a> {
+    fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let admin = Address::generate(&env);
+        let factory = env.register(MockFactory, (admin.clone(),));
+
+        let token_id = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+
+        let treasury_id = env.register(Treasury, ());
+        let treasury = TreasuryClient::new(&env, &treasury_id);
+        treasury.initialize(&admin, &200u32, &admin, &factory, &token_id);
+
+        let parlay_id = env.register(ParlayContract, ());
+        let parlay = ParlayContractClient::new(&env, &parlay_id);
+        parlay.initialize(&admin, &factory, &treasury_id);
+        treasury.set_parlay_contract(&admin, &parlay_id);
+
+        Setup {
+            parlay,
+            treasury,
+            token: token::Client::new(&env, &token_id),
+            token_admin: token::StellarAssetClient::new(&env, &token_id),
+            factory,
+            admin,
+            env,
+        }
+    }
+
+    /// Registers an open market and returns `(market_id, mock client)`.
+    fn market(&self, seed: u8, draw_enabled: bool) -> (Bytes, MockMarketClient<'a>) {
+        let market_id = Bytes::from_array(&self.env, &[seed; 32]);
+        let addr = self.env.register(MockMarket, ());
+        let client = MockMarketClient::new(&self.env, &addr);
+        client.set_state(&MarketStatus::Open, &None, &0, &0, &0, &draw_enabled);
+        MockFactoryClient::new(&self.env, &self.factory).register(&market_id, &addr);
+        (market_id, client)
+    }
+
+    fn bettor(&self, funds: i128) -> Address {
+        let bettor = Address::generate(&self.env);
+        self.token_admin.mint(&bettor, &funds);
+        bettor
+    }
+
+    /// Stands in for the fees that back what parlays can win.
+    fn fund_treasury(&self, amount: i128) {
+        self.token_admin.mint(&self.treasury.address, &amount);
+        self.env.as_contract(&self.treasury.address, || {
+            let key = Symbol::new(&self.env, "BALANCE");
+            let balance: i128 = self.env.storage().persistent().get(&key).unwrap_or(0);
+            self.env.storage().persistent().set(&key, &(balance + amount));
+        });
+    }
+}
+
+fn leg(market_id: &Bytes, side: BetSide) -> ParlayLeg {
+    ParlayLeg { market_id: market_id.clone(), side }
+}
+
+fn legs(env: &Env, items: &[ParlayLeg]) -> Vec<ParlayLeg> {
+    let mut v = Vec::new(env);
+    for l in items {
+        v.push_back(l.clone());
+    }
+    v
+}
+
+// ─── Settlement ───────────────────────────────────────────────────────────────
+
+#[test]
+fn parlay_all_legs_win_pays_multiplied_odds() {
+    let s = Setup::new();
+    let (m1, c1) = s.market(1, false);
+    let (m2, c2) = s.market(2, true);
+    s.fund_treasury(1_000_000);
+
+    let bettor = s.bettor(1_000);
+    let id = s.parlay.place_parlay(
+        &bettor,
+        &legs(&s.env, &[leg(&m1, BetSide::FighterA), leg(&m2, BetSide::Draw)]),
+        &1_000,
+    );
+    assert_eq!(s.token.balance(&bettor), 0);
+
+    // Leg 1: 10_000 total, 4_000 on A → net 9_800 / 4_000.
+    c1.set_state(&MarketStatus::Resolved, &Some(Outcome::FighterA), &4_000, &6_000, &0, &false);
+    // Leg 2: 10_000 total, 1_000 on Draw → net 9_800 / 1_000.
+    c2.set_state(&MarketStatus::Resolved, &Some(Outcome::Draw), &5_000, &4_000, &1_000, &true);
+
+    let expected = 1_000 * 9_800 / 4_000 * 9_800 / 1_000;
+    assert_eq!(s.parlay.settle_parlay(&id), expected);
+    assert_eq!(s.token.balance(&bettor), expected);
+
+    let p = s.parlay.get_parlay(&id);
+    assert_eq!(p.status, ParlayStatus::Won);
+    assert_eq!(p.payout, expected);
+    assert!(s.parlay.try_settle_parlay(&id).is_err());
+}
+
+#[test]
+fn parlay_losing_leg_settles_while_others_pending() {
+    let s = Setup::new();
+    let (m1, c1) = s.market(1, false);
+    let (m2, _c2) = s.market(2, false);
+    s.fund_treasury(1_000_000);
+
+    let bettor = s.bettor(500);
+    let id = s.parlay.place_parlay(
+        &bettor,
+        &legs(&s.env, &[leg(&m1, BetSide::FighterA), leg(&m2, BetSide::FighterB)]),
+        &500,
+    );
+
+    c1.set_state(&MarketStatus::Resolved, &Some(Outcome::FighterB), &200, &300, &0, &false);
+
+    assert_eq!(s.parlay.settle_parlay(&id), 0);
+    assert_eq!(s.parlay.get_parlay(&id).status, ParlayStatus::Lost);
+    // The house keeps the stake and its reservation is released.
+    assert_eq!(s.treasury.get_balance(), 1_000_500);
+    assert_eq!(s.treasury.check_invariants().parlay_reserved, 0);
+}
+
+#[test]
+fn parlay_pending_leg_cannot_settle() {
+    let s = Setup::new();
+    let (m1, c1) = s.market(1, false);
+    let (m2, c2) = s.market(2, false);
+    s.fund_treasury(1_000_000);
+
+    let bettor = s.bettor(500);
+    let id = s.parlay.place_parlay(
+        &bettor,
+        &legs(&s.env, &[leg(&m1, BetSide::FighterA), leg(&m2, BetSide::FighterA)]),
+        &500,
+    );
+
+    c1.set_state(&MarketStatus::Resolved, &Some(Outcome::FighterA), &200, &300, &0, &false);
+    c2.set_state(&MarketStatus::Disputed, &Some(Outcome::FighterA), &200, &300, &0, &false);
+
+    assert!(s.parlay.try_settle_parlay(&id).is_err());
+    assert_eq!(s.parlay.get_parlay(&id).status, ParlayStatus::Open);
+}
+
+#[test]
+fn parlay_waits_for_finalized_resolutions() {
+    let s = Setup::new();
+    let (m1, c1) = s.market(1, false);
+    let (m2, c2) = s.market(2, false);
+    s.fund_treasury(1_000_000);
+
+    let bettor = s.bettor(500);
+    let id = s.parlay.place_parlay(
+        &bettor,
+        &legs(&s.env, &[leg(&m1, BetSide::FighterA), leg(&m2, BetSide::FighterA)]),
+        &500,
+    );
+
+    // Both legs look decided, but a dispute could still overturn them.
+    c1.set_state(&MarketStatus::Resolved, &Some(Outcome::FighterA), &200, &300, &0, &false);
+    c2.set_state(&MarketStatus::Resolved, &Some(Outcome::FighterB), &200, &300, &0, &false);
+    c1.set_disputable(&true);
+    c2.set_disputable(&true);
+    assert!(s.parlay.try_settle_parlay(&id).is_err());
+
+    // The dispute on leg 2 flips it to a win before it is finalized.
+    c2.set_state(&MarketStatus::Resolved, &Some(Outcome::FighterA), &200, &300, &0, &false);
+    c1.set_disputable(&false);
+    assert!(s.parlay.try_settle_parlay(&id).is_err());
+    c2.set_disputable(&false);
+
+    // Each leg: 500 total, 200 on A → net 490 / 200.
+    assert_eq!(s.parlay.settle_parlay(&id), 500 * 490 / 200 * 490 / 200);
+    assert_eq!(s.parlay.get_parlay(&id).status, ParlayStatus::Won);
+}
+
+#[test]
+fn parlay_void_leg_counts_at_even_odds() {
+    let s = Setup::new();
+    let (m1, c1) = s.market(1, false);
+    let (m2, c2) = s.market(2, false);
+    let (m3, c3) = s.market(3, false);
+    s.fund_treasury(1_000_000);
+
+    let bettor = s.bettor(1_000);
+    let id = s.parlay.place_parlay(
+        &bettor,
+        &legs(
+            &s.env,
+            &[leg(&m1, BetSide::FighterB), leg(&m2, BetSide::FighterA), leg(&m3, BetSide::FighterA)],
+        ),
+        &1_000,
+    );
+
+    c1.set_state(&MarketStatus::Resolved, &Some(Outcome::FighterB), &8_000, &2_000, &0, &false);
+    c2.set_state(&MarketStatus::Cancelled, &None, &100, &100, &0, &false);
+    // Draw on a market without draw betting is a refund outcome.
+    c3.set_state(&MarketStatus::Cancelled, &Some(Outcome::Draw), &100, &100, &0, &false);
+
+    assert_eq!(s.parlay.settle_parlay(&id), 1_000 * 9_800 / 2_000);
+}
+
+#[test]
+fn parlay_all_void_refunds_stake() {
+    let s = Setup::new();
+    let (m1, c1) = s.market(1, false);
+    let (m2, c2) = s.market(2, false);
+    s.fund_treasury(1_000_000);
+
+    let bettor = s.bettor(700);
+    let id = s.parlay.place_parlay(
+        &bettor,
+        &legs(&s.env, &[leg(&m1, BetSide::FighterA), leg(&m2, BetSide::FighterB)]),
+        &700,
+    );
+
+    c1.set_state(&MarketStatus::Resolved, &Some(Outcome::NoContest), &100, &100, &0, &false);
+    // Nobody in the market backed the winner — no odds to apply.
+    c2.set_state(&MarketStatus::Resolved, &Some(Outcome::FighterB), &100, &0, &0, &false);
+
+    assert_eq!(s.parlay.settle_parlay(&id), 700);
+    assert_eq!(s.token.balance(&bettor), 700);
+    assert_eq!(s.parlay.get_parlay(&id).status, ParlayStatus::Refunded);
+}
+
+#[test]
+fn parlay_payout_is_capped_at_its_reservation() {
+    let s = Setup::new();
+    let (m1, c1) = s.market(1, false);
+    let (m2, c2) = s.market(2, false);
+    s.fund_treasury(1_000_000);
+
+    let bettor = s.bettor(1_000);
+    let id = s.parlay.place_parlay(
+        &bettor,
+        &legs(&s.env, &[leg(&m1, BetSide::FighterA), leg(&m2, BetSide::FighterA)]),
+        &1_000,
+    );
+    let max_payout = 1_000 * MAX_PAYOUT_MULTIPLIER;
+    assert_eq!(s.parlay.get_parlay(&id).max_payout, max_payout);
+    assert_eq!(s.treasury.check_invariants().parlay_reserved, max_payout);
+
+    // Each leg: 10_000 total, 100 on A → net 9_800 / 100, far past the cap.
+    c1.set_state(&MarketStatus::Resolved, &Some(Outcome::FighterA), &100, &9_900, &0, &false);
+    c2.set_state(&MarketStatus::Resolved, &Some(Outcome::FighterA), &100, &9_900, &0, &false);
+
+    assert_eq!(s.parlay.settle_parlay(&id), max_payout);
+    assert_eq!(s.token.balance(&bettor), max_payout);
+    let report = s.treasury.check_invariants();
+    assert_eq!(report.parlay_reserved, 0);
+    assert!(report.ok);
+}
+
+#[test]
+fn parlay_refund_is_not_paid_from_other_stakes() {
+    let s = Setup::new();
+    let (m1, c1) = s.market(1, false);
+    let (m2, c2) = s.market(2, false);
+    s.fund_treasury(100_000);
+
+    let first = s.bettor(1_000);
+    let second = s.bettor(1_000);
+    let two = legs(&s.env, &[leg(&m1, BetSide::FighterA), leg(&m2, BetSide::FighterB)]);
+    let refunded = s.parlay.place_parlay(&first, &two, &1_000);
+    s.parlay.place_parlay(&second, &two, &1_000);
+
+    // The house's 100_000 is now fully committed to the two reservations.
+    s.treasury.withdraw_fees(&s.admin, &s.admin, &(100_000 - 2 * 49_000));
+    assert!(s.treasury.try_withdraw_fees(&s.admin, &s.admin, &1).is_err());
+
+    c1.set_state(&MarketStatus::Cancelled, &None, &100, &100, &0, &false);
+    c2.set_state(&MarketStatus::Cancelled, &None, &100, &100, &0, &false);
+    assert_eq!(s.parlay.settle_parlay(&refunded), 1_000);
+
+    // The other parlay's reservation is untouched.
+    let report = s.treasury.check_invariants();
+    assert_eq!(report.parlay_reserved, 1_000 * MAX_PAYOUT_MULTIPLIER);
+    assert!(report.ok);
+}
+
+// ─── Placement validation ─────────────────────────────────────────────────────
+
+#[test]
+#[should_panic(expected = "too few legs")]
+fn parlay_single_leg_rejected() {
+    let s = Setup::new();
+    let (m1, _c1) = s.market(1, false);
+    let bettor = s.bettor(500);
+    s.parlay
+        .place_parlay(&bettor, &legs(&s.env, &[leg(&m1, BetSide::FighterA)]), &500);
+}
+
+#[test]
+#[should_panic(expected = "duplicate market in parlay")]
+fn parlay_duplicate_market_rejected() {
+    let s = Setup::new();
+    let (m1, _c1) = s.market(1, false);
+    let bettor = s.bettor(500);
+    s.parlay.place_parlay(
+        &bettor,
+        &legs(&s.env, &[leg(&m1, BetSide::FighterA), leg(&m1, BetSide::FighterB)]),
+        &500,
+    );
+}
+
+#[test]
+#[should_panic(expected = "draw betting not enabled")]
+fn parlay_draw_leg_requires_draw_market() {
+    let s = Setup::new();
+    let (m1, _c1) = s.market(1, false);
+    let (m2, _c2) = s.market(2, false);
+    let bettor = s.bettor(500);
+    s.parlay.place_parlay(
+        &bettor,
+        &legs(&s.env, &[leg(&m1, BetSide::FighterA), leg(&m2, BetSide::Draw)]),
+        &500,
+    );
+}
+
+#[test]
+#[should_panic(expected = "house cannot cover parlay")]
+fn parlay_rejected_when_the_house_cannot_cover_it() {
+    let s = Setup::new();
+    let (m1, _c1) = s.market(1, false);
+    let (m2, _c2) = s.market(2, false);
+    s.fund_treasury(49_000 - 1);
+    let bettor = s.bettor(1_000);
+    s.parlay.place_parlay(
+        &bettor,
+        &legs(&s.env, &[leg(&m1, BetSide::FighterA), leg(&m2, BetSide::FighterA)]),
+        &1_000,
+    );
+}
+
+#[test]
+#[should_panic(expected = "leg market not open")]
+fn parlay_locked_leg_rejected() {
+    let s = Setup::new();
+    let (m1, _c1) = s.market(1, false);
+    let (m2, c2) = s.market(2, false);
+    c2.set_state(&MarketStatus::Locked, &None, &0, &0, &0, &false);
+    let bettor = s.bettor(500);
+    s.parlay.place_parlay(
+        &bettor,
+        &legs(&s.env, &[leg(&m1, BetSide::FighterA), leg(&m2, BetSide::FighterA)]),
+        &500,
+    );
+}
+
+#[test]
+fn parlays_indexed_by_address() {
+    let s = Setup::new();
+    let (m1, _c1) = s.market(1, false);
+    let (m2, _c2) = s.market(2, false);
+    s.fund_treasury(1_000_000);
+    let bettor = s.bettor(1_000);
+
+    let two = legs(&s.env, &[leg(&m1, BetSide::FighterA), leg(&m2, BetSide::FighterB)]);
+    let first = s.parlay.place_parlay(&bettor, &two, &400);
+    let second = s.parlay.place_parlay(&bettor, &two, &600);
+
+    let parlays = s.parlay.get_parlays_by_address(&bettor);
+    assert_eq!(parlays.len(), 2);
+    assert_eq!(parlays.get(0).unwrap().parlay_id, first);
+    assert_eq!(parlays.get(1).unwrap().parlay_id, second);
+    assert_eq!(s.treasury.get_balance(), 1_001_000);
+}
@@ -0,0 +1,27 @@
+#!/usr/bin/env bash
+# ==============================================================================
+# BOXMEOUT — Refresh the Market wasm test fixture
+# ==============================================================================
+#
+# MarketFactory deploys and initializes a real Market in its tests, and so
+# does the integration harness. Both load the wasm from
+# market_factory/test_fixtures/market.wasm. Run this after changing the
+# Market contract so those tests exercise the current code. CI runs it before
+# `cargo test`.
+#
+# Requirements:
+#   - Rust + wasm32v1-none target (rustup target add wasm32v1-none)
+#
+# Usage:
+#   ./build_market_fixture.sh
+# ==============================================================================
+
+set -euo pipefail
+
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+CONTRACTS_DIR="$(cd "$SCRIPT_DIR/.." && pwd)"
+
+cd "$CONTRACTS_DIR"
+cargo build --release --target wasm32v1-none -p market
+cp target/wasm32v1-none/release/market.wasm market_factory/test_fixtures/market.wasm
+echo "==> Updated market_factory/test_fixtures/market.wasm"
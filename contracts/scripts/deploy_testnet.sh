@@ -7,7 +7,7 @@
 # single command. Wires all contract addresses together so they can communicate.
 #
 # Requirements:
-#   - Rust + wasm32-unknown-unknown target
+#   - Rust + wasm32v1-none target (rustup target add wasm32v1-none)
 #   - Stellar CLI (soroban) v21+
 #   - Funded Testnet keypair
 #   - jq (for JSON output)
@@ -59,11 +59,11 @@ echo "==> Admin public key: $ADMIN_PUBKEY"
 # ─── BUILD ────────────────────────────────────────────────────────────────────
 
 echo ""
-echo "==> Building all contracts (release, wasm32-unknown-unknown)..."
+echo "==> Building all contracts (release, wasm32v1-none)..."
 cd "$CONTRACTS_DIR"
-cargo build --release --target wasm32-unknown-unknown 2>&1
+cargo build --release --target wasm32v1-none 2>&1
 
-WASM_DIR="$CONTRACTS_DIR/target/wasm32-unknown-unknown/release"
+WASM_DIR="$CONTRACTS_DIR/target/wasm32v1-none/release"
 
 # ─── OPTIMIZE ─────────────────────────────────────────────────────────────────
 
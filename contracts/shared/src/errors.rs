@@ -38,6 +38,8 @@ pub enum ContractError {
     AlreadyClaimed = 22,
     /// Bettor placed no bets in this market
     NoBetsFound = 23,
+    /// The market's claim deadline has passed
+    ClaimExpired = 24,
 
     // ── Oracle / Resolution ────────────────────────────────
     /// Oracle signature verification failed
@@ -62,6 +64,22 @@ pub enum ContractError {
     OracleAlreadyWhitelisted = 51,
     /// Vec of market IDs exceeds the maximum allowed (20)
     TooManyMarkets = 52,
+    /// Requested event ID does not exist
+    EventNotFound = 53,
+    /// Market already belongs to an event
+    MarketAlreadyInEvent = 54,
+    /// Arbiter panel has duplicate members or no voting window
+    InvalidArbiterPanel = 55,
+    /// Oracle stake is below the factory's minimum
+    OracleStakeTooLow = 56,
+    /// Slash rate exceeds 10_000 basis points
+    InvalidSlashRate = 57,
+    /// Oracle stake is still bonded or unbonding
+    OracleStakeLocked = 58,
+    /// Market hash or numeric id is already registered
+    MarketIdCollision = 59,
+    /// Protocol config sets a fee above 10_000 bp or a minimum bet above the maximum
+    InvalidConfig = 61,
 
     // ── Reentrancy ─────────────────────────────────────────
     /// A claim or refund transfer is already in progress
@@ -1,216 +1,413 @@
 //! ============================================================
 //! BOXMEOUT — Event Parsing Utilities
-//! Parse raw Soroban event payloads into typed event structs.
+//! Parse raw Soroban events back into the payload types defined
+//! in `events`. Every event the protocol emits has a parser.
 //! ============================================================
 
-use soroban_sdk::{Address, Env, String, TryFromVal, Val, Vec};
+use soroban_sdk::{Env, Symbol, TryFromVal, Val, Vec};
 
-use crate::types::{BetRecord, ClaimReceipt, Outcome};
+use crate::events::*;
+use crate::types::{ArbiterPanel, Event, MarketInfo};
 
-// ─── Typed event structs ──────────────────────────────────────────────────────
+// ─── Error type ───────────────────────────────────────────────────────────────
 
-#[derive(Clone, Debug)]
-pub struct MarketCreatedEvent {
-    pub market_id: u64,
-    pub contract_address: Address,
-    pub match_id: String,
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// Topics or data Vec does not have the expected length
+    InvalidLength,
+    /// A Val could not be converted to the expected type
+    InvalidType,
+    /// The first topic names a different event
+    TopicMismatch,
+    /// The second topic is not `EVENT_SCHEMA_VERSION`
+    UnsupportedVersion,
+    /// The first topic is not an event the protocol emits
+    UnknownTopic,
 }
 
-#[derive(Clone, Debug)]
-pub struct MarketLockedEvent {
-    pub market_id: u64,
+// ─── Internal helpers ─────────────────────────────────────────────────────────
+
+fn get_topic<T: TryFromVal<Env, Val>>(env: &Env, topics: &Vec<Val>, idx: u32) -> Result<T, ParseError> {
+    let val = topics.get(idx).ok_or(ParseError::InvalidLength)?;
+    T::try_from_val(env, &val).map_err(|_| ParseError::InvalidType)
 }
 
-#[derive(Clone, Debug)]
-pub struct MarketResolvedEvent {
-    pub market_id: u64,
-    pub outcome: Outcome,
-    pub oracle_address: Address,
+fn decode_data<T: TryFromVal<Env, Val>>(env: &Env, data: &Val) -> Result<T, ParseError> {
+    T::try_from_val(env, data).map_err(|_| ParseError::InvalidType)
 }
 
-#[derive(Clone, Debug)]
-pub struct BetPlacedEvent {
-    pub market_id: u64,
-    pub bet: BetRecord,
+/// Checks `topics` is `(Symbol(name), EVENT_SCHEMA_VERSION)` and decodes `data`.
+fn parse<T: TryFromVal<Env, Val>>(
+    env: &Env,
+    name: &str,
+    topics: &Vec<Val>,
+    data: &Val,
+) -> Result<T, ParseError> {
+    if topics.len() != 2 {
+        return Err(ParseError::InvalidLength);
+    }
+    let topic: Symbol = get_topic(env, topics, 0)?;
+    if topic != Symbol::new(env, name) {
+        return Err(ParseError::TopicMismatch);
+    }
+    let version: u32 = get_topic(env, topics, 1)?;
+    if version != EVENT_SCHEMA_VERSION {
+        return Err(ParseError::UnsupportedVersion);
+    }
+    decode_data(env, data)
 }
 
-#[derive(Clone, Debug)]
-pub struct WinningsClaimedEvent {
-    pub market_id: u64,
-    pub receipt: ClaimReceipt,
+// ─── Parsers ──────────────────────────────────────────────────────────────────
+// Topics: `(Symbol(name), EVENT_SCHEMA_VERSION)`. Data: the payload type.
+
+// Market
+
+/// Parses a `market_initialized` event into its `MarketInitializedEvent` payload.
+pub fn parse_market_initialized_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<MarketInitializedEvent, ParseError> {
+    parse(env, MARKET_INITIALIZED, topics, data)
 }
 
-#[derive(Clone, Debug)]
-pub struct RefundClaimedEvent {
-    pub market_id: u64,
-    pub bettor: Address,
-    pub amount: i128,
+/// Parses a `market_locked` event into its `MarketLockedEvent` payload.
+pub fn parse_market_locked_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<MarketLockedEvent, ParseError> {
+    parse(env, MARKET_LOCKED, topics, data)
 }
 
-#[derive(Clone, Debug)]
-pub struct MarketCancelledEvent {
-    pub market_id: u64,
-    pub reason: String,
+/// Parses a `bet_placed` event into its `BetPlacedEvent` payload.
+pub fn parse_bet_placed_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<BetPlacedEvent, ParseError> {
+    parse(env, BET_PLACED, topics, data)
 }
 
-#[derive(Clone, Debug)]
-pub struct MarketDisputedEvent {
-    pub market_id: u64,
-    pub reason: String,
+/// Parses a `market_cancelled` event into its `MarketCancelledEvent` payload.
+pub fn parse_market_cancelled_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<MarketCancelledEvent, ParseError> {
+    parse(env, MARKET_CANCELLED, topics, data)
 }
 
-#[derive(Clone, Debug)]
-pub struct DisputeResolvedEvent {
-    pub market_id: u64,
-    pub final_outcome: Outcome,
+/// Parses a `resolution_timed_out` event into its `ResolutionTimedOutEvent` payload.
+pub fn parse_resolution_timed_out_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<ResolutionTimedOutEvent, ParseError> {
+    parse(env, RESOLUTION_TIMED_OUT, topics, data)
 }
 
-// ─── Error type ───────────────────────────────────────────────────────────────
+/// Parses a `market_rescheduled` event into its `MarketRescheduledEvent` payload.
+pub fn parse_market_rescheduled_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<MarketRescheduledEvent, ParseError> {
+    parse(env, MARKET_RESCHEDULED, topics, data)
+}
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-pub enum ParseError {
-    /// Topics or data Vec does not have the expected length
-    InvalidLength,
-    /// A Val could not be converted to the expected type
-    InvalidType,
+/// Parses a `fighter_substituted` event into its `FighterSubstitutedEvent` payload.
+pub fn parse_fighter_substituted_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<FighterSubstitutedEvent, ParseError> {
+    parse(env, FIGHTER_SUBSTITUTED, topics, data)
 }
 
-// ─── Internal helpers ─────────────────────────────────────────────────────────
+/// Parses a `bet_withdrawn` event into its `BetWithdrawnEvent` payload.
+pub fn parse_bet_withdrawn_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<BetWithdrawnEvent, ParseError> {
+    parse(env, BET_WITHDRAWN, topics, data)
+}
 
-fn get_topic<T: TryFromVal<Env, Val>>(env: &Env, topics: &Vec<Val>, idx: u32) -> Result<T, ParseError> {
-    let val = topics.get(idx).ok_or(ParseError::InvalidLength)?;
-    T::try_from_val(env, &val).map_err(|_| ParseError::InvalidType)
+/// Parses a `bet_cashed_out` event into its `BetCashedOutEvent` payload.
+pub fn parse_bet_cashed_out_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<BetCashedOutEvent, ParseError> {
+    parse(env, BET_CASHED_OUT, topics, data)
 }
 
-fn decode_data<T: TryFromVal<Env, Val>>(env: &Env, data: &Val) -> Result<T, ParseError> {
-    T::try_from_val(env, data).map_err(|_| ParseError::InvalidType)
+/// Parses a `market_resolved` event into its `MarketResolvedEvent` payload.
+pub fn parse_market_resolved_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<MarketResolvedEvent, ParseError> {
+    parse(env, MARKET_RESOLVED, topics, data)
 }
 
-// ─── Parsers ──────────────────────────────────────────────────────────────────
+/// Parses a `winnings_claimed` event into its `WinningsClaimedEvent` payload.
+pub fn parse_winnings_claimed_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<WinningsClaimedEvent, ParseError> {
+    parse(env, WINNINGS_CLAIMED, topics, data)
+}
 
-/// Parses a raw `market_created` event.
-///
-/// Topics: `(Symbol("market_created"), market_id: u64)`
-/// Data:   `(contract_address: Address, match_id: String)`
-pub fn parse_market_created_event(
-    env: &Env,
-    topics: &Vec<Val>,
-    data: &Val,
-) -> Result<MarketCreatedEvent, ParseError> {
-    let market_id: u64 = get_topic(env, topics, 1)?;
-    let (contract_address, match_id): (Address, String) = decode_data(env, data)?;
-    Ok(MarketCreatedEvent { market_id, contract_address, match_id })
+/// Parses a `refund_claimed` event into its `RefundClaimedEvent` payload.
+pub fn parse_refund_claimed_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<RefundClaimedEvent, ParseError> {
+    parse(env, REFUND_CLAIMED, topics, data)
 }
 
-/// Parses a raw `market_locked` event.
-///
-/// Topics: `(Symbol("market_locked"), market_id: u64)`
-/// Data:   `()`
-pub fn parse_market_locked_event(
-    env: &Env,
-    topics: &Vec<Val>,
-    _data: &Val,
-) -> Result<MarketLockedEvent, ParseError> {
-    let market_id: u64 = get_topic(env, topics, 1)?;
-    Ok(MarketLockedEvent { market_id })
+/// Parses a `resolution_disputed` event into its `ResolutionDisputedEvent` payload.
+pub fn parse_resolution_disputed_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<ResolutionDisputedEvent, ParseError> {
+    parse(env, RESOLUTION_DISPUTED, topics, data)
 }
 
-/// Parses a raw `market_resolved` event.
-///
-/// Topics: `(Symbol("market_resolved"), market_id: u64)`
-/// Data:   `(outcome: Outcome, oracle_address: Address)`
-pub fn parse_market_resolved_event(
-    env: &Env,
-    topics: &Vec<Val>,
-    data: &Val,
-) -> Result<MarketResolvedEvent, ParseError> {
-    let market_id: u64 = get_topic(env, topics, 1)?;
-    let (outcome, oracle_address): (Outcome, Address) = decode_data(env, data)?;
-    Ok(MarketResolvedEvent { market_id, outcome, oracle_address })
+/// Parses a `dispute_resolved` event into its `DisputeResolvedEvent` payload.
+pub fn parse_dispute_resolved_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<DisputeResolvedEvent, ParseError> {
+    parse(env, DISPUTE_RESOLVED, topics, data)
 }
 
-/// Parses a raw `bet_placed` event.
-///
-/// Topics: `(Symbol("bet_placed"), market_id: u64)`
-/// Data:   `BetRecord`
-pub fn parse_bet_placed_event(
-    env: &Env,
-    topics: &Vec<Val>,
-    data: &Val,
-) -> Result<BetPlacedEvent, ParseError> {
-    let market_id: u64 = get_topic(env, topics, 1)?;
-    let bet: BetRecord = decode_data(env, data)?;
-    Ok(BetPlacedEvent { market_id, bet })
+/// Parses a `resolution_finalized` event into its `ResolutionFinalizedEvent` payload.
+pub fn parse_resolution_finalized_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<ResolutionFinalizedEvent, ParseError> {
+    parse(env, RESOLUTION_FINALIZED, topics, data)
 }
 
-/// Parses a raw `winnings_claimed` event.
-///
-/// Topics: `(Symbol("winnings_claimed"), market_id: u64)`
-/// Data:   `ClaimReceipt`
-pub fn parse_winnings_claimed_event(
-    env: &Env,
-    topics: &Vec<Val>,
-    data: &Val,
-) -> Result<WinningsClaimedEvent, ParseError> {
-    let market_id: u64 = get_topic(env, topics, 1)?;
-    let receipt: ClaimReceipt = decode_data(env, data)?;
-    Ok(WinningsClaimedEvent { market_id, receipt })
+/// Parses a `batch_claimed` event into its `BatchClaimedEvent` payload.
+pub fn parse_batch_claimed_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<BatchClaimedEvent, ParseError> {
+    parse(env, BATCH_CLAIMED, topics, data)
 }
 
-/// Parses a raw `refund_claimed` event.
-///
-/// Topics: `(Symbol("refund_claimed"), market_id: u64)`
-/// Data:   `(bettor: Address, amount: i128)`
-pub fn parse_refund_claimed_event(
-    env: &Env,
-    topics: &Vec<Val>,
-    data: &Val,
-) -> Result<RefundClaimedEvent, ParseError> {
-    let market_id: u64 = get_topic(env, topics, 1)?;
-    let (bettor, amount): (Address, i128) = decode_data(env, data)?;
-    Ok(RefundClaimedEvent { market_id, bettor, amount })
+/// Parses a `unclaimed_swept` event into its `UnclaimedSweptEvent` payload.
+pub fn parse_unclaimed_swept_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<UnclaimedSweptEvent, ParseError> {
+    parse(env, UNCLAIMED_SWEPT, topics, data)
 }
 
-/// Parses a raw `market_cancelled` event.
-///
-/// Topics: `(Symbol("market_cancelled"), market_id: u64)`
-/// Data:   `String` (reason)
-pub fn parse_market_cancelled_event(
-    env: &Env,
-    topics: &Vec<Val>,
-    data: &Val,
-) -> Result<MarketCancelledEvent, ParseError> {
-    let market_id: u64 = get_topic(env, topics, 1)?;
-    let reason: String = decode_data(env, data)?;
-    Ok(MarketCancelledEvent { market_id, reason })
+/// Parses a `prop_bet_placed` event into its `PropBetPlacedEvent` payload.
+pub fn parse_prop_bet_placed_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<PropBetPlacedEvent, ParseError> {
+    parse(env, PROP_BET_PLACED, topics, data)
 }
 
-/// Parses a raw `market_disputed` event.
-///
-/// Topics: `(Symbol("market_disputed"), market_id: u64)`
-/// Data:   `String` (reason)
-pub fn parse_market_disputed_event(
-    env: &Env,
-    topics: &Vec<Val>,
-    data: &Val,
-) -> Result<MarketDisputedEvent, ParseError> {
-    let market_id: u64 = get_topic(env, topics, 1)?;
-    let reason: String = decode_data(env, data)?;
-    Ok(MarketDisputedEvent { market_id, reason })
+/// Parses a `fight_result_reported` event into its `FightResultReportedEvent` payload.
+pub fn parse_fight_result_reported_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<FightResultReportedEvent, ParseError> {
+    parse(env, FIGHT_RESULT_REPORTED, topics, data)
 }
 
-/// Parses a raw `dispute_resolved` event.
-///
-/// Topics: `(Symbol("dispute_resolved"), market_id: u64)`
-/// Data:   `Outcome`
-pub fn parse_dispute_resolved_event(
-    env: &Env,
-    topics: &Vec<Val>,
-    data: &Val,
-) -> Result<DisputeResolvedEvent, ParseError> {
-    let market_id: u64 = get_topic(env, topics, 1)?;
-    let final_outcome: Outcome = decode_data(env, data)?;
-    Ok(DisputeResolvedEvent { market_id, final_outcome })
+/// Parses a `prop_claimed` event into its `PropClaimedEvent` payload.
+pub fn parse_prop_claimed_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<PropClaimedEvent, ParseError> {
+    parse(env, PROP_CLAIMED, topics, data)
+}
+
+/// Parses a `dispute_bond_posted` event into its `DisputeBondPostedEvent` payload.
+pub fn parse_dispute_bond_posted_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<DisputeBondPostedEvent, ParseError> {
+    parse(env, DISPUTE_BOND_POSTED, topics, data)
+}
+
+/// Parses a `dispute_bonds_settled` event into its `DisputeBondsSettledEvent` payload.
+pub fn parse_dispute_bonds_settled_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<DisputeBondsSettledEvent, ParseError> {
+    parse(env, DISPUTE_BONDS_SETTLED, topics, data)
+}
+
+/// Parses a `arbiter_voted` event into its `ArbiterVotedEvent` payload.
+pub fn parse_arbiter_voted_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<ArbiterVotedEvent, ParseError> {
+    parse(env, ARBITER_VOTED, topics, data)
+}
+
+/// Parses a `attestation_accepted` event into its `AttestationAcceptedEvent` payload.
+pub fn parse_attestation_accepted_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<AttestationAcceptedEvent, ParseError> {
+    parse(env, ATTESTATION_ACCEPTED, topics, data)
+}
+
+// MarketFactory
+
+/// Parses a `market_created` event into its `MarketInfo` payload.
+pub fn parse_market_created_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<MarketInfo, ParseError> {
+    parse(env, MARKET_CREATED, topics, data)
+}
+
+/// Parses a `event_created` event into its `Event` payload.
+pub fn parse_event_created_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<Event, ParseError> {
+    parse(env, EVENT_CREATED, topics, data)
+}
+
+/// Parses a `event_market_added` event into its `EventMarketAddedEvent` payload.
+pub fn parse_event_market_added_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<EventMarketAddedEvent, ParseError> {
+    parse(env, EVENT_MARKET_ADDED, topics, data)
+}
+
+/// Parses a `event_markets_locked` event into its `EventMarketsUpdatedEvent` payload.
+pub fn parse_event_markets_locked_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<EventMarketsUpdatedEvent, ParseError> {
+    parse(env, EVENT_MARKETS_LOCKED, topics, data)
+}
+
+/// Parses a `event_markets_cancelled` event into its `EventMarketsUpdatedEvent` payload.
+pub fn parse_event_markets_cancelled_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<EventMarketsUpdatedEvent, ParseError> {
+    parse(env, EVENT_MARKETS_CANCELLED, topics, data)
+}
+
+/// Parses a `claims_aggregated` event into its `ClaimsAggregatedEvent` payload.
+pub fn parse_claims_aggregated_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<ClaimsAggregatedEvent, ParseError> {
+    parse(env, CLAIMS_AGGREGATED, topics, data)
+}
+
+/// Parses a `arbiter_panel_set` event into its `ArbiterPanel` payload.
+pub fn parse_arbiter_panel_set_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<ArbiterPanel, ParseError> {
+    parse(env, ARBITER_PANEL_SET, topics, data)
+}
+
+/// Parses a `oracle_staked` event into its `OracleStakedEvent` payload.
+pub fn parse_oracle_staked_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<OracleStakedEvent, ParseError> {
+    parse(env, ORACLE_STAKED, topics, data)
+}
+
+/// Parses a `oracle_unbonding` event into its `OracleUnbondingEvent` payload.
+pub fn parse_oracle_unbonding_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<OracleUnbondingEvent, ParseError> {
+    parse(env, ORACLE_UNBONDING, topics, data)
+}
+
+/// Parses a `oracle_stake_withdrawn` event into its `OracleStakeWithdrawnEvent` payload.
+pub fn parse_oracle_stake_withdrawn_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<OracleStakeWithdrawnEvent, ParseError> {
+    parse(env, ORACLE_STAKE_WITHDRAWN, topics, data)
+}
+
+/// Parses a `oracle_resolution_recorded` event into its `OracleResolutionRecordedEvent` payload.
+pub fn parse_oracle_resolution_recorded_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<OracleResolutionRecordedEvent, ParseError> {
+    parse(env, ORACLE_RESOLUTION_RECORDED, topics, data)
+}
+
+/// Parses a `oracle_slashed` event into its `OracleSlashedEvent` payload.
+pub fn parse_oracle_slashed_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<OracleSlashedEvent, ParseError> {
+    parse(env, ORACLE_SLASHED, topics, data)
+}
+
+// Treasury
+
+/// Parses a `bet_deposited` event into its `BetDepositedEvent` payload.
+pub fn parse_bet_deposited_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<BetDepositedEvent, ParseError> {
+    parse(env, BET_DEPOSITED, topics, data)
+}
+
+/// Parses a `escrow_released` event into its `EscrowReleasedEvent` payload.
+pub fn parse_escrow_released_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<EscrowReleasedEvent, ParseError> {
+    parse(env, ESCROW_RELEASED, topics, data)
+}
+
+/// Parses a `fees_deposited` event into its `FeesDepositedEvent` payload.
+pub fn parse_fees_deposited_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<FeesDepositedEvent, ParseError> {
+    parse(env, FEES_DEPOSITED, topics, data)
+}
+
+/// Parses a `escrow_swept` event into its `EscrowSweptEvent` payload.
+pub fn parse_escrow_swept_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<EscrowSweptEvent, ParseError> {
+    parse(env, ESCROW_SWEPT, topics, data)
+}
+
+/// Parses a `oracle_stake_deposited` event into its `OracleStakeMovedEvent` payload.
+pub fn parse_oracle_stake_deposited_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<OracleStakeMovedEvent, ParseError> {
+    parse(env, ORACLE_STAKE_DEPOSITED, topics, data)
+}
+
+/// Parses a `oracle_stake_slashed` event into its `OracleStakeMovedEvent` payload.
+pub fn parse_oracle_stake_slashed_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<OracleStakeMovedEvent, ParseError> {
+    parse(env, ORACLE_STAKE_SLASHED, topics, data)
+}
+
+/// Parses a `oracle_stake_released` event into its `OracleStakeMovedEvent` payload.
+pub fn parse_oracle_stake_released_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<OracleStakeMovedEvent, ParseError> {
+    parse(env, ORACLE_STAKE_RELEASED, topics, data)
+}
+
+/// Parses a `parlay_deposited` event into its `ParlayDepositedEvent` payload.
+pub fn parse_parlay_deposited_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<ParlayDepositedEvent, ParseError> {
+    parse(env, PARLAY_DEPOSITED, topics, data)
+}
+
+/// Parses a `parlay_paid` event into its `ParlayPaidEvent` payload.
+pub fn parse_parlay_paid_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<ParlayPaidEvent, ParseError> {
+    parse(env, PARLAY_PAID, topics, data)
+}
+
+/// Parses a `fees_withdrawn` event into its `FundsWithdrawnEvent` payload.
+pub fn parse_fees_withdrawn_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<FundsWithdrawnEvent, ParseError> {
+    parse(env, FEES_WITHDRAWN, topics, data)
+}
+
+/// Parses a `emergency_drain` event into its `FundsWithdrawnEvent` payload.
+pub fn parse_emergency_drain_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<FundsWithdrawnEvent, ParseError> {
+    parse(env, EMERGENCY_DRAIN, topics, data)
+}
+
+// Parlay
+
+/// Parses a `parlay_placed` event into its `ParlayPlacedEvent` payload.
+pub fn parse_parlay_placed_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<ParlayPlacedEvent, ParseError> {
+    parse(env, PARLAY_PLACED, topics, data)
+}
+
+/// Parses a `parlay_settled` event into its `ParlaySettledEvent` payload.
+pub fn parse_parlay_settled_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<ParlaySettledEvent, ParseError> {
+    parse(env, PARLAY_SETTLED, topics, data)
+}
+
+// ─── Dispatch ─────────────────────────────────────────────────────────────────
+
+/// Declares `BoxmeoutEvent` and `parse_event` from one table, so every
+/// parser above is reachable through the generic entry point.
+macro_rules! boxmeout_events {
+    ($($variant:ident($payload:ty) = $name:ident => $parser:ident,)*) => {
+        /// Any event the protocol emits, decoded into its payload type.
+        #[derive(Clone, Debug, PartialEq)]
+        pub enum BoxmeoutEvent {
+            $($variant($payload),)*
+        }
+
+        impl BoxmeoutEvent {
+            /// The event's topic name, e.g. `"bet_placed"`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(BoxmeoutEvent::$variant(_) => $name,)*
+                }
+            }
+        }
+
+        /// Decodes any protocol event by dispatching on its topic name.
+        ///
+        /// Returns `ParseError::UnknownTopic` when the name is not one the
+        /// factory, market, treasury or parlay contracts emit; otherwise the
+        /// same checks as the per-event parsers apply.
+        pub fn parse_event(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<BoxmeoutEvent, ParseError> {
+            if topics.len() != 2 {
+                return Err(ParseError::InvalidLength);
+            }
+            let topic: Symbol = get_topic(env, topics, 0)?;
+            $(
+                if topic == Symbol::new(env, $name) {
+                    return $parser(env, topics, data).map(BoxmeoutEvent::$variant);
+                }
+            )*
+            Err(ParseError::UnknownTopic)
+        }
+    };
+}
+
+boxmeout_events! {
+    // Market
+    MarketInitialized(MarketInitializedEvent) = MARKET_INITIALIZED => parse_market_initialized_event,
+    MarketLocked(MarketLockedEvent) = MARKET_LOCKED => parse_market_locked_event,
+    BetPlaced(BetPlacedEvent) = BET_PLACED => parse_bet_placed_event,
+    MarketCancelled(MarketCancelledEvent) = MARKET_CANCELLED => parse_market_cancelled_event,
+    ResolutionTimedOut(ResolutionTimedOutEvent) = RESOLUTION_TIMED_OUT => parse_resolution_timed_out_event,
+    MarketRescheduled(MarketRescheduledEvent) = MARKET_RESCHEDULED => parse_market_rescheduled_event,
+    FighterSubstituted(FighterSubstitutedEvent) = FIGHTER_SUBSTITUTED => parse_fighter_substituted_event,
+    BetWithdrawn(BetWithdrawnEvent) = BET_WITHDRAWN => parse_bet_withdrawn_event,
+    BetCashedOut(BetCashedOutEvent) = BET_CASHED_OUT => parse_bet_cashed_out_event,
+    MarketResolved(MarketResolvedEvent) = MARKET_RESOLVED => parse_market_resolved_event,
+    WinningsClaimed(WinningsClaimedEvent) = WINNINGS_CLAIMED => parse_winnings_claimed_event,
+    RefundClaimed(RefundClaimedEvent) = REFUND_CLAIMED => parse_refund_claimed_event,
+    ResolutionDisputed(ResolutionDisputedEvent) = RESOLUTION_DISPUTED => parse_resolution_disputed_event,
+    DisputeResolved(DisputeResolvedEvent) = DISPUTE_RESOLVED => parse_dispute_resolved_event,
+    ResolutionFinalized(ResolutionFinalizedEvent) = RESOLUTION_FINALIZED => parse_resolution_finalized_event,
+    BatchClaimed(BatchClaimedEvent) = BATCH_CLAIMED => parse_batch_claimed_event,
+    UnclaimedSwept(UnclaimedSweptEvent) = UNCLAIMED_SWEPT => parse_unclaimed_swept_event,
+    PropBetPlaced(PropBetPlacedEvent) = PROP_BET_PLACED => parse_prop_bet_placed_event,
+    FightResultReported(FightResultReportedEvent) = FIGHT_RESULT_REPORTED => parse_fight_result_reported_event,
+    PropClaimed(PropClaimedEvent) = PROP_CLAIMED => parse_prop_claimed_event,
+    DisputeBondPosted(DisputeBondPostedEvent) = DISPUTE_BOND_POSTED => parse_dispute_bond_posted_event,
+    DisputeBondsSettled(DisputeBondsSettledEvent) = DISPUTE_BONDS_SETTLED => parse_dispute_bonds_settled_event,
+    ArbiterVoted(ArbiterVotedEvent) = ARBITER_VOTED => parse_arbiter_voted_event,
+    AttestationAccepted(AttestationAcceptedEvent) = ATTESTATION_ACCEPTED => parse_attestation_accepted_event,
+    // MarketFactory
+    MarketCreated(MarketInfo) = MARKET_CREATED => parse_market_created_event,
+    EventCreated(Event) = EVENT_CREATED => parse_event_created_event,
+    EventMarketAdded(EventMarketAddedEvent) = EVENT_MARKET_ADDED => parse_event_market_added_event,
+    EventMarketsLocked(EventMarketsUpdatedEvent) = EVENT_MARKETS_LOCKED => parse_event_markets_locked_event,
+    EventMarketsCancelled(EventMarketsUpdatedEvent) = EVENT_MARKETS_CANCELLED => parse_event_markets_cancelled_event,
+    ClaimsAggregated(ClaimsAggregatedEvent) = CLAIMS_AGGREGATED => parse_claims_aggregated_event,
+    ArbiterPanelSet(ArbiterPanel) = ARBITER_PANEL_SET => parse_arbiter_panel_set_event,
+    OracleStaked(OracleStakedEvent) = ORACLE_STAKED => parse_oracle_staked_event,
+    OracleUnbonding(OracleUnbondingEvent) = ORACLE_UNBONDING => parse_oracle_unbonding_event,
+    OracleStakeWithdrawn(OracleStakeWithdrawnEvent) = ORACLE_STAKE_WITHDRAWN => parse_oracle_stake_withdrawn_event,
+    OracleResolutionRecorded(OracleResolutionRecordedEvent) = ORACLE_RESOLUTION_RECORDED => parse_oracle_resolution_recorded_event,
+    OracleSlashed(OracleSlashedEvent) = ORACLE_SLASHED => parse_oracle_slashed_event,
+    // Treasury
+    BetDeposited(BetDepositedEvent) = BET_DEPOSITED => parse_bet_deposited_event,
+    EscrowReleased(EscrowReleasedEvent) = ESCROW_RELEASED => parse_escrow_released_event,
+    FeesDeposited(FeesDepositedEvent) = FEES_DEPOSITED => parse_fees_deposited_event,
+    EscrowSwept(EscrowSweptEvent) = ESCROW_SWEPT => parse_escrow_swept_event,
+    OracleStakeDeposited(OracleStakeMovedEvent) = ORACLE_STAKE_DEPOSITED => parse_oracle_stake_deposited_event,
+    OracleStakeSlashed(OracleStakeMovedEvent) = ORACLE_STAKE_SLASHED => parse_oracle_stake_slashed_event,
+    OracleStakeReleased(OracleStakeMovedEvent) = ORACLE_STAKE_RELEASED => parse_oracle_stake_released_event,
+    ParlayDeposited(ParlayDepositedEvent) = PARLAY_DEPOSITED => parse_parlay_deposited_event,
+    ParlayPaid(ParlayPaidEvent) = PARLAY_PAID => parse_parlay_paid_event,
+    FeesWithdrawn(FundsWithdrawnEvent) = FEES_WITHDRAWN => parse_fees_withdrawn_event,
+    EmergencyDrain(FundsWithdrawnEvent) = EMERGENCY_DRAIN => parse_emergency_drain_event,
+    // Parlay
+    ParlayPlaced(ParlayPlacedEvent) = PARLAY_PLACED => parse_parlay_placed_event,
+    ParlaySettled(ParlaySettledEvent) = PARLAY_SETTLED => parse_parlay_settled_event,
 }
 
 // ─── Tests ────────────────────────────────────────────────────────────────────
@@ -220,13 +417,17 @@ mod tests {
     use soroban_sdk::{
         contract, contractimpl,
         testutils::{Address as _, Events},
-        Address, Env, IntoVal,
+        vec,
+        xdr::ContractEventBody,
+        Address, Bytes, BytesN, Env, IntoVal, String, Symbol, TryFromVal, Val, Vec,
     };
 
     use crate::{
         event_parser::*,
-        events::*,
-        types::{BetRecord, BetSide, ClaimReceipt, Outcome},
+        types::{
+            ArbiterPanel, BetSide, Event, FightResult, MarketInfo, Outcome, ParlayLeg,
+            ParlayStatus, PropSelection, VictoryMethod,
+        },
     };
 
     #[contract]
@@ -236,7 +437,7 @@ mod tests {
 
     fn setup() -> (Env, Address) {
         let env = Env::default();
-        let id = env.register_contract(None, Dummy);
+        let id = env.register(Dummy, ());
         (env, id)
     }
 
@@ -244,141 +445,427 @@ mod tests {
         Address::generate(env)
     }
 
-    fn s(env: &Env, v: &str) -> soroban_sdk::String {
-        soroban_sdk::String::from_str(env, v)
+    fn s(env: &Env, v: &str) -> String {
+        String::from_str(env, v)
     }
 
-    macro_rules! last_event {
-        ($env:expr) => {{
-            let all = $env.events().all();
-            all.last().unwrap()
-        }};
+    const MARKET_NUM: u64 = 7;
+
+    fn mid(env: &Env) -> Bytes {
+        Bytes::from_array(env, &[0xbe; 32])
     }
 
-    #[test]
-    fn test_parse_market_created_event() {
-        let (env, id) = setup();
-        let contract = addr(&env);
-        env.as_contract(&id, || {
-            emit_market_created(&env, 1, contract.clone(), s(&env, "FURY-USYK"));
-        });
-        let ev = last_event!(env);
-        let parsed = parse_market_created_event(&env, &ev.1, &ev.2).unwrap();
-        assert_eq!(parsed.market_id, 1);
-        assert_eq!(parsed.contract_address, contract);
-        assert_eq!(parsed.match_id, s(&env, "FURY-USYK"));
+    /// Topics and data of the last event emitted, as the parsers receive them.
+    fn last_event(env: &Env) -> (Vec<Val>, Val) {
+        let all = env.events().all();
+        let ContractEventBody::V0(body) = &all.events().last().unwrap().body;
+        let mut topics = Vec::new(env);
+        for topic in body.topics.iter() {
+            topics.push_back(Val::try_from_val(env, topic).unwrap());
+        }
+        (topics, Val::try_from_val(env, &body.data).unwrap())
+    }
+
+    /// Emits `$payload` with `$emit`, parses it back with `$parse` and
+    /// asserts the round trip is lossless. Also checks `parse_event`
+    /// dispatches the event to the variant named by its topic.
+    macro_rules! assert_round_trip {
+        ($env:expr, $id:expr, $emit:ident, $parse:ident, $payload:expr) => {{
+            let payload = $payload;
+            $env.as_contract(&$id, || $emit(&$env, payload.clone()));
+            let (topics, data) = last_event(&$env);
+            assert_eq!($parse(&$env, &topics, &data), Ok(payload));
+            let event = parse_event(&$env, &topics, &data).unwrap();
+            let name = Symbol::try_from_val(&$env, &topics.get(0).unwrap()).unwrap();
+            assert_eq!(Symbol::new(&$env, event.name()), name);
+        }};
     }
 
     #[test]
-    fn test_parse_market_locked_event() {
+    fn test_market_events_round_trip() {
         let (env, id) = setup();
-        env.as_contract(&id, || { emit_market_locked(&env, 2); });
-        let ev = last_event!(env);
-        let parsed = parse_market_locked_event(&env, &ev.1, &ev.2).unwrap();
-        assert_eq!(parsed.market_id, 2);
+        let bettor = addr(&env);
+        let bet_id = Bytes::from_array(&env, &[7; 32]);
+
+        assert_round_trip!(env, id, emit_market_initialized, parse_market_initialized_event,
+            MarketInitializedEvent {
+                market_id: MARKET_NUM,
+                fighter_a: s(&env, "Fury"),
+                fighter_b: s(&env, "Usyk"),
+                oracle: addr(&env),
+                scheduled_at: 10_000,
+                betting_ends_at: 9_000,
+                protocol_fee_bp: 200,
+                draw_enabled: true,
+                created_at: 1_000,
+            });
+        assert_round_trip!(env, id, emit_market_locked, parse_market_locked_event,
+            MarketLockedEvent { market_id: MARKET_NUM, locked_at: 9_000 });
+        assert_round_trip!(env, id, emit_bet_placed, parse_bet_placed_event,
+            BetPlacedEvent {
+                market_id: MARKET_NUM,
+                bet_id: bet_id.clone(),
+                bettor: bettor.clone(),
+                side: BetSide::Draw,
+                amount: 5_000_000,
+                placed_at: 2_000,
+            });
+        assert_round_trip!(env, id, emit_market_cancelled, parse_market_cancelled_event,
+            MarketCancelledEvent { market_id: MARKET_NUM, cancelled_at: 3_000 });
+        assert_round_trip!(env, id, emit_resolution_timed_out, parse_resolution_timed_out_event,
+            ResolutionTimedOutEvent { market_id: MARKET_NUM, deadline: 20_000 });
+        assert_round_trip!(env, id, emit_market_rescheduled, parse_market_rescheduled_event,
+            MarketRescheduledEvent {
+                market_id: MARKET_NUM,
+                old_scheduled_at: 10_000,
+                new_scheduled_at: 50_000,
+                new_betting_ends_at: 49_000,
+                withdrawals_end_at: 20_000,
+            });
+        assert_round_trip!(env, id, emit_fighter_substituted, parse_fighter_substituted_event,
+            FighterSubstitutedEvent {
+                market_id: MARKET_NUM,
+                side: BetSide::FighterB,
+                replacement: s(&env, "Joshua"),
+                opt_out_ends_at: 30_000,
+            });
+        assert_round_trip!(env, id, emit_bet_withdrawn, parse_bet_withdrawn_event,
+            BetWithdrawnEvent {
+                market_id: MARKET_NUM,
+                bet_id: bet_id.clone(),
+                bettor: bettor.clone(),
+                amount: 5_000_000,
+            });
+        assert_round_trip!(env, id, emit_bet_cashed_out, parse_bet_cashed_out_event,
+            BetCashedOutEvent {
+                market_id: MARKET_NUM,
+                bet_id: bet_id.clone(),
+                bettor: bettor.clone(),
+                refund: 4_750_000,
+                fee: 250_000,
+            });
+        assert_round_trip!(env, id, emit_market_resolved, parse_market_resolved_event,
+            MarketResolvedEvent { market_id: MARKET_NUM, outcome: Outcome::FighterA, resolved_at: 11_000 });
+        assert_round_trip!(env, id, emit_winnings_claimed, parse_winnings_claimed_event,
+            WinningsClaimedEvent {
+                market_id: MARKET_NUM,
+                bet_id: bet_id.clone(),
+                bettor: bettor.clone(),
+                payout: 9_800_000,
+                claimed_at: 12_000,
+            });
+        assert_round_trip!(env, id, emit_refund_claimed, parse_refund_claimed_event,
+            RefundClaimedEvent {
+                market_id: MARKET_NUM,
+                bet_id: bet_id.clone(),
+                bettor: bettor.clone(),
+                amount: 5_000_000,
+            });
+        assert_round_trip!(env, id, emit_resolution_disputed, parse_resolution_disputed_event,
+            ResolutionDisputedEvent {
+                market_id: MARKET_NUM,
+                dispute_id: 1,
+                disputer: bettor.clone(),
+                reason: Bytes::from_slice(&env, b"wrong winner"),
+            });
+        assert_round_trip!(env, id, emit_dispute_resolved, parse_dispute_resolved_event,
+            DisputeResolvedEvent { market_id: MARKET_NUM, final_outcome: Outcome::NoContest });
+        assert_round_trip!(env, id, emit_resolution_finalized, parse_resolution_finalized_event,
+            ResolutionFinalizedEvent { market_id: MARKET_NUM, finalized_at: 13_000 });
+        assert_round_trip!(env, id, emit_batch_claimed, parse_batch_claimed_event,
+            BatchClaimedEvent {
+                market_id: MARKET_NUM,
+                bettor: bettor.clone(),
+                bet_ids: vec![&env, bet_id.clone()],
+                total_payout: 9_800_000,
+                claimed_at: 12_000,
+            });
+        assert_round_trip!(env, id, emit_unclaimed_swept, parse_unclaimed_swept_event,
+            UnclaimedSweptEvent { market_id: MARKET_NUM, amount: 1_000 });
+        assert_round_trip!(env, id, emit_prop_bet_placed, parse_prop_bet_placed_event,
+            PropBetPlacedEvent {
+                market_id: MARKET_NUM,
+                bet_id: bet_id.clone(),
+                bettor: bettor.clone(),
+                selection: PropSelection::WinsByMethod(BetSide::FighterA, VictoryMethod::Knockout),
+                amount: 2_000_000,
+            });
+        assert_round_trip!(env, id, emit_fight_result_reported, parse_fight_result_reported_event,
+            FightResultReportedEvent {
+                market_id: MARKET_NUM,
+                result: FightResult { winner: Outcome::FighterA, method: VictoryMethod::Knockout, round: 3 },
+            });
+        assert_round_trip!(env, id, emit_prop_claimed, parse_prop_claimed_event,
+            PropClaimedEvent {
+                market_id: MARKET_NUM,
+                bet_id: bet_id.clone(),
+                bettor: bettor.clone(),
+                payout: 6_000_000,
+            });
+        assert_round_trip!(env, id, emit_dispute_bond_posted, parse_dispute_bond_posted_event,
+            DisputeBondPostedEvent {
+                market_id: MARKET_NUM,
+                bonder: bettor.clone(),
+                challenges: true,
+                amount: 1_000_000,
+            });
+        assert_round_trip!(env, id, emit_dispute_bonds_settled, parse_dispute_bonds_settled_event,
+            DisputeBondsSettledEvent {
+                market_id: MARKET_NUM,
+                challenge_upheld: false,
+                forfeited: 1_000_000,
+                awarded: 900_000,
+            });
+        assert_round_trip!(env, id, emit_arbiter_voted, parse_arbiter_voted_event,
+            ArbiterVotedEvent {
+                market_id: MARKET_NUM,
+                arbiter: addr(&env),
+                dispute_id: 1,
+                outcome: Outcome::FighterB,
+            });
+        assert_round_trip!(env, id, emit_attestation_accepted, parse_attestation_accepted_event,
+            AttestationAcceptedEvent {
+                market_id: MARKET_NUM,
+                public_key: BytesN::from_array(&env, &[9; 32]),
+                nonce: 42,
+                timestamp: 11_000,
+            });
     }
 
     #[test]
-    fn test_parse_market_resolved_event() {
+    fn test_factory_events_round_trip() {
         let (env, id) = setup();
         let oracle = addr(&env);
-        env.as_contract(&id, || {
-            emit_market_resolved(&env, 3, Outcome::FighterA, oracle.clone());
-        });
-        let ev = last_event!(env);
-        let parsed = parse_market_resolved_event(&env, &ev.1, &ev.2).unwrap();
-        assert_eq!(parsed.market_id, 3);
-        assert_eq!(parsed.outcome, Outcome::FighterA);
-        assert_eq!(parsed.oracle_address, oracle);
+
+        assert_round_trip!(env, id, emit_market_created, parse_market_created_event,
+            MarketInfo {
+                market_id: mid(&env),
+                numeric_id: MARKET_NUM,
+                market_address: addr(&env),
+                creator: addr(&env),
+                fighter_a: s(&env, "Fury"),
+                fighter_b: s(&env, "Usyk"),
+                oracle: oracle.clone(),
+                lock_time: 9_000,
+                end_time: 10_000,
+                created_at: 1_000,
+            });
+        assert_round_trip!(env, id, emit_event_created, parse_event_created_event,
+            Event {
+                event_id: 1,
+                name: s(&env, "Riyadh Season"),
+                venue: s(&env, "Kingdom Arena"),
+                date: 10_000,
+                market_ids: vec![&env, mid(&env)],
+                main_event_id: mid(&env),
+            });
+        assert_round_trip!(env, id, emit_event_market_added, parse_event_market_added_event,
+            EventMarketAddedEvent { event_id: 1, market_id: MARKET_NUM, main_event: true });
+        assert_round_trip!(env, id, emit_event_markets_locked, parse_event_markets_locked_event,
+            EventMarketsUpdatedEvent { event_id: 1, count: 4 });
+        assert_round_trip!(env, id, emit_event_markets_cancelled, parse_event_markets_cancelled_event,
+            EventMarketsUpdatedEvent { event_id: 1, count: 3 });
+        assert_round_trip!(env, id, emit_claims_aggregated, parse_claims_aggregated_event,
+            ClaimsAggregatedEvent { bettor: addr(&env), total_payout: 19_600_000 });
+        assert_round_trip!(env, id, emit_arbiter_panel_set, parse_arbiter_panel_set_event,
+            ArbiterPanel { members: vec![&env, addr(&env), addr(&env)], vote_window_sec: 86_400 });
+        assert_round_trip!(env, id, emit_oracle_staked, parse_oracle_staked_event,
+            OracleStakedEvent { oracle: oracle.clone(), amount: 100_000_000, stake: 150_000_000 });
+        assert_round_trip!(env, id, emit_oracle_unbonding, parse_oracle_unbonding_event,
+            OracleUnbondingEvent { oracle: oracle.clone(), unbonds_at: 604_800 });
+        assert_round_trip!(env, id, emit_oracle_stake_withdrawn, parse_oracle_stake_withdrawn_event,
+            OracleStakeWithdrawnEvent { oracle: oracle.clone(), amount: 150_000_000 });
+        assert_round_trip!(env, id, emit_oracle_resolution_recorded, parse_oracle_resolution_recorded_event,
+            OracleResolutionRecordedEvent { oracle: oracle.clone(), market_id: MARKET_NUM });
+        assert_round_trip!(env, id, emit_oracle_slashed, parse_oracle_slashed_event,
+            OracleSlashedEvent { oracle, market_id: MARKET_NUM, amount: 10_000_000 });
     }
 
     #[test]
-    fn test_parse_bet_placed_event() {
+    fn test_treasury_events_round_trip() {
         let (env, id) = setup();
-        let bettor = addr(&env);
-        let bet = BetRecord {
-            bettor: bettor.clone(),
-            market_id: 4,
-            side: BetSide::FighterB,
-            amount: 5_000_000,
-            placed_at: 1_000,
-            claimed: false,
-        };
-        env.as_contract(&id, || { emit_bet_placed(&env, 4, bet.clone()); });
-        let ev = last_event!(env);
-        let parsed = parse_bet_placed_event(&env, &ev.1, &ev.2).unwrap();
-        assert_eq!(parsed.market_id, 4);
-        assert_eq!(parsed.bet.bettor, bettor);
-        assert_eq!(parsed.bet.amount, 5_000_000);
+        let market = addr(&env);
+        let parlay = addr(&env);
+        let user = addr(&env);
+
+        assert_round_trip!(env, id, emit_bet_deposited, parse_bet_deposited_event,
+            BetDepositedEvent {
+                market: market.clone(),
+                market_id: MARKET_NUM,
+                bettor: user.clone(),
+                amount: 5_000_000,
+                timestamp: 2_000,
+            });
+        assert_round_trip!(env, id, emit_escrow_released, parse_escrow_released_event,
+            EscrowReleasedEvent {
+                market: market.clone(),
+                market_id: MARKET_NUM,
+                recipient: user.clone(),
+                amount: 9_800_000,
+                timestamp: 12_000,
+            });
+        assert_round_trip!(env, id, emit_fees_deposited, parse_fees_deposited_event,
+            FeesDepositedEvent { market: market.clone(), amount: 200_000, timestamp: 12_000 });
+        assert_round_trip!(env, id, emit_escrow_swept, parse_escrow_swept_event,
+            EscrowSweptEvent { market, market_id: MARKET_NUM, amount: 1_000, timestamp: 99_000 });
+        assert_round_trip!(env, id, emit_oracle_stake_deposited, parse_oracle_stake_deposited_event,
+            OracleStakeMovedEvent { oracle: user.clone(), amount: 100_000_000, timestamp: 1_000 });
+        assert_round_trip!(env, id, emit_oracle_stake_slashed, parse_oracle_stake_slashed_event,
+            OracleStakeMovedEvent { oracle: user.clone(), amount: 10_000_000, timestamp: 2_000 });
+        assert_round_trip!(env, id, emit_oracle_stake_released, parse_oracle_stake_released_event,
+            OracleStakeMovedEvent { oracle: user.clone(), amount: 90_000_000, timestamp: 3_000 });
+        assert_round_trip!(env, id, emit_parlay_deposited, parse_parlay_deposited_event,
+            ParlayDepositedEvent { parlay: parlay.clone(), bettor: user.clone(), amount: 1_000_000, timestamp: 4_000 });
+        assert_round_trip!(env, id, emit_parlay_paid, parse_parlay_paid_event,
+            ParlayPaidEvent { parlay, recipient: user.clone(), amount: 4_000_000, timestamp: 5_000 });
+        assert_round_trip!(env, id, emit_fees_withdrawn, parse_fees_withdrawn_event,
+            FundsWithdrawnEvent { recipient: user.clone(), amount: 200_000, timestamp: 6_000 });
+        assert_round_trip!(env, id, emit_emergency_drain, parse_emergency_drain_event,
+            FundsWithdrawnEvent { recipient: user, amount: 7_000_000, timestamp: 7_000 });
     }
 
     #[test]
-    fn test_parse_winnings_claimed_event() {
+    fn test_parlay_events_round_trip() {
         let (env, id) = setup();
         let bettor = addr(&env);
-        let receipt = ClaimReceipt {
-            bettor: bettor.clone(),
-            market_id: 5,
-            amount_won: 9_800_000,
-            fee_deducted: 200_000,
-            claimed_at: 2_000,
-        };
-        env.as_contract(&id, || { emit_winnings_claimed(&env, 5, receipt.clone()); });
-        let ev = last_event!(env);
-        let parsed = parse_winnings_claimed_event(&env, &ev.1, &ev.2).unwrap();
-        assert_eq!(parsed.market_id, 5);
-        assert_eq!(parsed.receipt.amount_won, 9_800_000);
-        assert_eq!(parsed.receipt.fee_deducted, 200_000);
+
+        assert_round_trip!(env, id, emit_parlay_placed, parse_parlay_placed_event,
+            ParlayPlacedEvent {
+                parlay_id: 1,
+                bettor: bettor.clone(),
+                legs: vec![
+                    &env,
+                    ParlayLeg { market_id: mid(&env), side: BetSide::FighterA },
+                    ParlayLeg { market_id: Bytes::from_array(&env, &[1; 32]), side: BetSide::Draw },
+                ],
+                amount: 1_000_000,
+            });
+        assert_round_trip!(env, id, emit_parlay_settled, parse_parlay_settled_event,
+            ParlaySettledEvent { parlay_id: 1, bettor, status: ParlayStatus::Won, payout: 4_000_000 });
     }
 
     #[test]
-    fn test_parse_refund_claimed_event() {
+    fn test_topics_carry_name_and_schema_version() {
         let (env, id) = setup();
-        let bettor = addr(&env);
-        env.as_contract(&id, || { emit_refund_claimed(&env, 6, bettor.clone(), 3_000_000); });
-        let ev = last_event!(env);
-        let parsed = parse_refund_claimed_event(&env, &ev.1, &ev.2).unwrap();
-        assert_eq!(parsed.market_id, 6);
-        assert_eq!(parsed.bettor, bettor);
-        assert_eq!(parsed.amount, 3_000_000);
+        env.as_contract(&id, || {
+            emit_market_locked(&env, MarketLockedEvent { market_id: MARKET_NUM, locked_at: 1 })
+        });
+        let (topics, _) = last_event(&env);
+        assert_eq!(topics.len(), 2);
+        let name = Symbol::try_from_val(&env, &topics.get(0).unwrap()).unwrap();
+        let version = u32::try_from_val(&env, &topics.get(1).unwrap()).unwrap();
+        assert_eq!(name, Symbol::new(&env, MARKET_LOCKED));
+        assert_eq!(version, EVENT_SCHEMA_VERSION);
     }
 
     #[test]
-    fn test_parse_market_cancelled_event() {
+    fn test_parse_wrong_topic_returns_error() {
         let (env, id) = setup();
-        env.as_contract(&id, || { emit_market_cancelled(&env, 7, s(&env, "postponed")); });
-        let ev = last_event!(env);
-        let parsed = parse_market_cancelled_event(&env, &ev.1, &ev.2).unwrap();
-        assert_eq!(parsed.market_id, 7);
-        assert_eq!(parsed.reason, s(&env, "postponed"));
+        env.as_contract(&id, || {
+            emit_market_locked(&env, MarketLockedEvent { market_id: MARKET_NUM, locked_at: 1 })
+        });
+        let (topics, data) = last_event(&env);
+        assert_eq!(
+            parse_market_cancelled_event(&env, &topics, &data),
+            Err(ParseError::TopicMismatch)
+        );
     }
 
     #[test]
-    fn test_parse_market_disputed_event() {
-        let (env, id) = setup();
-        env.as_contract(&id, || { emit_market_disputed(&env, 8, s(&env, "conflict")); });
-        let ev = last_event!(env);
-        let parsed = parse_market_disputed_event(&env, &ev.1, &ev.2).unwrap();
-        assert_eq!(parsed.market_id, 8);
-        assert_eq!(parsed.reason, s(&env, "conflict"));
+    fn test_parse_unknown_version_returns_error() {
+        let (env, _id) = setup();
+        let payload = MarketLockedEvent { market_id: MARKET_NUM, locked_at: 1 };
+        let topics: Vec<Val> = vec![
+            &env,
+            Symbol::new(&env, MARKET_LOCKED).into_val(&env),
+            (EVENT_SCHEMA_VERSION + 1).into_val(&env),
+        ];
+        assert_eq!(
+            parse_market_locked_event(&env, &topics, &payload.into_val(&env)),
+            Err(ParseError::UnsupportedVersion)
+        );
     }
 
     #[test]
-    fn test_parse_dispute_resolved_event() {
-        let (env, id) = setup();
-        env.as_contract(&id, || { emit_dispute_resolved(&env, 9, Outcome::Draw); });
-        let ev = last_event!(env);
-        let parsed = parse_dispute_resolved_event(&env, &ev.1, &ev.2).unwrap();
-        assert_eq!(parsed.market_id, 9);
-        assert_eq!(parsed.final_outcome, Outcome::Draw);
+    fn test_parse_mismatched_payload_returns_error() {
+        let (env, _id) = setup();
+        let topics: Vec<Val> = vec![
+            &env,
+            Symbol::new(&env, MARKET_LOCKED).into_val(&env),
+            EVENT_SCHEMA_VERSION.into_val(&env),
+        ];
+        let data: Val = 7u32.into_val(&env);
+        assert_eq!(
+            parse_market_locked_event(&env, &topics, &data),
+            Err(ParseError::InvalidType)
+        );
     }
 
     #[test]
     fn test_parse_invalid_topics_returns_error() {
         let (env, _id) = setup();
-        let empty: soroban_sdk::Vec<soroban_sdk::Val> = soroban_sdk::Vec::new(&env);
-        let dummy_data: soroban_sdk::Val = soroban_sdk::Val::from_void();
+        let empty: Vec<Val> = Vec::new(&env);
+        let dummy_data: Val = Val::from_void().into();
         let result = parse_market_locked_event(&env, &empty, &dummy_data);
         assert_eq!(result.unwrap_err(), ParseError::InvalidLength);
     }
+
+    #[test]
+    fn test_parse_event_dispatches_on_topic() {
+        let (env, id) = setup();
+        let payload = MarketLockedEvent { market_id: MARKET_NUM, locked_at: 9_000 };
+        env.as_contract(&id, || emit_market_locked(&env, payload.clone()));
+        let (topics, data) = last_event(&env);
+        assert_eq!(
+            parse_event(&env, &topics, &data),
+            Ok(BoxmeoutEvent::MarketLocked(payload))
+        );
+
+        let panel = ArbiterPanel { members: Vec::new(&env), vote_window_sec: 3_600 };
+        env.as_contract(&id, || emit_arbiter_panel_set(&env, panel.clone()));
+        let (topics, data) = last_event(&env);
+        assert_eq!(
+            parse_event(&env, &topics, &data),
+            Ok(BoxmeoutEvent::ArbiterPanelSet(panel))
+        );
+    }
+
+    #[test]
+    fn test_parse_event_unknown_topic_returns_error() {
+        let (env, _id) = setup();
+        let topics: Vec<Val> = vec![
+            &env,
+            Symbol::new(&env, "not_an_event").into_val(&env),
+            EVENT_SCHEMA_VERSION.into_val(&env),
+        ];
+        let data: Val = 7u32.into_val(&env);
+        assert_eq!(parse_event(&env, &topics, &data), Err(ParseError::UnknownTopic));
+    }
+
+    #[test]
+    fn test_parse_event_keeps_version_and_payload_checks() {
+        let (env, _id) = setup();
+        let payload = MarketLockedEvent { market_id: MARKET_NUM, locked_at: 1 };
+        let stale: Vec<Val> = vec![
+            &env,
+            Symbol::new(&env, MARKET_LOCKED).into_val(&env),
+            (EVENT_SCHEMA_VERSION - 1).into_val(&env),
+        ];
+        assert_eq!(
+            parse_event(&env, &stale, &payload.into_val(&env)),
+            Err(ParseError::UnsupportedVersion)
+        );
+
+        let current: Vec<Val> = vec![
+            &env,
+            Symbol::new(&env, MARKET_LOCKED).into_val(&env),
+            EVENT_SCHEMA_VERSION.into_val(&env),
+        ];
+        let data: Val = 7u32.into_val(&env);
+        assert_eq!(parse_event(&env, &current, &data), Err(ParseError::InvalidType));
+        assert_eq!(
+            parse_event(&env, &Vec::new(&env), &data),
+            Err(ParseError::InvalidLength)
+        );
+    }
 }
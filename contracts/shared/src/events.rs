@@ -1,484 +1,679 @@
 //! ============================================================
 //! BOXMEOUT — Contract Events
-//! All emitted events are defined here for consistency.
+//! Every event the protocol emits is defined here, and every
+//! contract emits through these functions only.
+//!
+//! Topics are always `(Symbol(name), EVENT_SCHEMA_VERSION)`.
+//! Data is the event's payload struct, so `event_parser` decodes
+//! exactly what was emitted. Market-scoped payloads carry the
+//! factory-assigned numeric `market_id`; `market_created` maps it
+//! to the market's hash id and contract address.
+//!
+//! Bump `EVENT_SCHEMA_VERSION` whenever a payload changes shape.
 //! ============================================================
 
-use soroban_sdk::{Address, Env, String, Symbol};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Val, Vec};
+
+use crate::types::{
+    ArbiterPanel, BetSide, Event, FightResult, MarketInfo, Outcome, ParlayLeg, ParlayStatus,
+    PropSelection,
+};
+
+/// Version carried as the second topic of every event.
+pub const EVENT_SCHEMA_VERSION: u32 = 2;
+
+// ─── Event names ──────────────────────────────────────────────────────────────
+
+// Market
+pub const MARKET_INITIALIZED: &str = "market_initialized";
+pub const MARKET_LOCKED: &str = "market_locked";
+pub const BET_PLACED: &str = "bet_placed";
+pub const MARKET_CANCELLED: &str = "market_cancelled";
+pub const RESOLUTION_TIMED_OUT: &str = "resolution_timed_out";
+pub const MARKET_RESCHEDULED: &str = "market_rescheduled";
+pub const FIGHTER_SUBSTITUTED: &str = "fighter_substituted";
+pub const BET_WITHDRAWN: &str = "bet_withdrawn";
+pub const BET_CASHED_OUT: &str = "bet_cashed_out";
+pub const MARKET_RESOLVED: &str = "market_resolved";
+pub const WINNINGS_CLAIMED: &str = "winnings_claimed";
+pub const REFUND_CLAIMED: &str = "refund_claimed";
+pub const RESOLUTION_DISPUTED: &str = "resolution_disputed";
+pub const DISPUTE_RESOLVED: &str = "dispute_resolved";
+pub const RESOLUTION_FINALIZED: &str = "resolution_finalized";
+pub const BATCH_CLAIMED: &str = "batch_claimed";
+pub const UNCLAIMED_SWEPT: &str = "unclaimed_swept";
+pub const PROP_BET_PLACED: &str = "prop_bet_placed";
+pub const FIGHT_RESULT_REPORTED: &str = "fight_result_reported";
+pub const PROP_CLAIMED: &str = "prop_claimed";
+pub const DISPUTE_BOND_POSTED: &str = "dispute_bond_posted";
+pub const DISPUTE_BONDS_SETTLED: &str = "dispute_bonds_settled";
+pub const ARBITER_VOTED: &str = "arbiter_voted";
+pub const ATTESTATION_ACCEPTED: &str = "attestation_accepted";
+
+// MarketFactory
+pub const MARKET_CREATED: &str = "market_created";
+pub const EVENT_CREATED: &str = "event_created";
+pub const EVENT_MARKET_ADDED: &str = "event_market_added";
+pub const EVENT_MARKETS_LOCKED: &str = "event_markets_locked";
+pub const EVENT_MARKETS_CANCELLED: &str = "event_markets_cancelled";
+pub const CLAIMS_AGGREGATED: &str = "claims_aggregated";
+pub const ARBITER_PANEL_SET: &str = "arbiter_panel_set";
+pub const ORACLE_STAKED: &str = "oracle_staked";
+pub const ORACLE_UNBONDING: &str = "oracle_unbonding";
+pub const ORACLE_STAKE_WITHDRAWN: &str = "oracle_stake_withdrawn";
+pub const ORACLE_RESOLUTION_RECORDED: &str = "oracle_resolution_recorded";
+pub const ORACLE_SLASHED: &str = "oracle_slashed";
+
+// Treasury
+pub const BET_DEPOSITED: &str = "bet_deposited";
+pub const ESCROW_RELEASED: &str = "escrow_released";
+pub const FEES_DEPOSITED: &str = "fees_deposited";
+pub const ESCROW_SWEPT: &str = "escrow_swept";
+pub const ORACLE_STAKE_DEPOSITED: &str = "oracle_stake_deposited";
+pub const ORACLE_STAKE_SLASHED: &str = "oracle_stake_slashed";
+pub const ORACLE_STAKE_RELEASED: &str = "oracle_stake_released";
+pub const PARLAY_DEPOSITED: &str = "parlay_deposited";
+pub const PARLAY_PAID: &str = "parlay_paid";
+pub const FEES_WITHDRAWN: &str = "fees_withdrawn";
+pub const EMERGENCY_DRAIN: &str = "emergency_drain";
+
+// Parlay
+pub const PARLAY_PLACED: &str = "parlay_placed";
+pub const PARLAY_SETTLED: &str = "parlay_settled";
+
+// ─── Market payloads ──────────────────────────────────────────────────────────
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarketInitializedEvent {
+    pub market_id:       u64,
+    pub fighter_a:       String,
+    pub fighter_b:       String,
+    pub oracle:          Address,
+    pub scheduled_at:    u64,
+    pub betting_ends_at: u64,
+    pub protocol_fee_bp: u32,
+    pub draw_enabled:    bool,
+    pub created_at:      u64,
+}
 
-use crate::types::{BetRecord, ClaimReceipt, Outcome};
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarketLockedEvent {
+    pub market_id: u64,
+    pub locked_at: u64,
+}
 
-/// Emits a `market_created` event when a new market is deployed.
-///
-/// Topics: `(Symbol("market_created"), market_id)`
-/// Data:   `(contract_address, match_id)`
-pub fn emit_market_created(env: &Env, market_id: u64, contract_address: Address, match_id: String) {
-    let topics = (Symbol::new(env, "market_created"), market_id);
-    env.events().publish(topics, (contract_address, match_id));
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BetPlacedEvent {
+    pub market_id: u64,
+    pub bet_id:    Bytes,
+    pub bettor:    Address,
+    pub side:      BetSide,
+    pub amount:    i128,
+    pub placed_at: u64,
 }
 
-/// Emits a `market_locked` event when betting is closed.
-///
-/// Topics: `(Symbol("market_locked"), market_id)`
-/// Data:   `()`
-pub fn emit_market_locked(env: &Env, market_id: u64) {
-    let topics = (Symbol::new(env, "market_locked"), market_id);
-    env.events().publish(topics, ());
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarketCancelledEvent {
+    pub market_id:    u64,
+    pub cancelled_at: u64,
 }
 
-/// Emits a `market_resolved` event when an oracle submits a final outcome.
-///
-/// Topics: `(Symbol("market_resolved"), market_id)`
-/// Data:   `(outcome, resolution_time)`
-pub fn emit_market_resolved(env: &Env, market_id: u64, outcome: Outcome, resolution_time: u64) {
-    let topics = (Symbol::new(env, "market_resolved"), market_id);
-    env.events().publish(topics, (outcome, resolution_time));
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResolutionTimedOutEvent {
+    pub market_id: u64,
+    /// The resolution deadline that passed.
+    pub deadline:  u64,
 }
 
-/// Emits a `bet_placed` event when a bettor places a bet.
-///
-/// Topics: `(Symbol("bet_placed"), market_id)`
-/// Data:   `BetRecord`
-pub fn emit_bet_placed(env: &Env, market_id: u64, bet: BetRecord) {
-    let topics = (Symbol::new(env, "bet_placed"), market_id);
-    env.events().publish(topics, bet);
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarketRescheduledEvent {
+    pub market_id:           u64,
+    pub old_scheduled_at:    u64,
+    pub new_scheduled_at:    u64,
+    pub new_betting_ends_at: u64,
+    /// End of the penalty-free withdrawal window.
+    pub withdrawals_end_at:  u64,
 }
 
-/// Emits a `winnings_claimed` event when a winner claims their payout.
-///
-/// Topics: `(Symbol("winnings_claimed"), market_id)`
-/// Data:   `ClaimReceipt`
-pub fn emit_winnings_claimed(env: &Env, market_id: u64, receipt: ClaimReceipt) {
-    let topics = (Symbol::new(env, "winnings_claimed"), market_id);
-    env.events().publish(topics, receipt);
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FighterSubstitutedEvent {
+    pub market_id:       u64,
+    pub side:            BetSide,
+    pub replacement:     String,
+    pub opt_out_ends_at: u64,
 }
 
-/// Emits a `refund_claimed` event when a bettor claims a refund on a cancelled market.
-///
-/// Topics: `(Symbol("refund_claimed"), market_id)`
-/// Data:   `(bettor, amount)`
-pub fn emit_refund_claimed(env: &Env, market_id: u64, bettor: Address, amount: i128) {
-    let topics = (Symbol::new(env, "refund_claimed"), market_id);
-    env.events().publish(topics, (bettor, amount));
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BetWithdrawnEvent {
+    pub market_id: u64,
+    pub bet_id:    Bytes,
+    pub bettor:    Address,
+    pub amount:    i128,
 }
 
-/// Emits a `market_cancelled` event when a market is cancelled.
-///
-/// Topics: `(Symbol("market_cancelled"), market_id)`
-/// Data:   `reason: String`
-pub fn emit_market_cancelled(env: &Env, market_id: u64, reason: String) {
-    let topics = (Symbol::new(env, "market_cancelled"), market_id);
-    env.events().publish(topics, reason);
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BetCashedOutEvent {
+    pub market_id: u64,
+    pub bet_id:    Bytes,
+    pub bettor:    Address,
+    pub refund:    i128,
+    pub fee:       i128,
 }
 
-/// Emits a `market_disputed` event when a resolved market is placed under review.
-///
-/// Topics: `(Symbol("market_disputed"), market_id)`
-/// Data:   `reason: String`
-pub fn emit_market_disputed(env: &Env, market_id: u64, reason: String) {
-    let topics = (Symbol::new(env, "market_disputed"), market_id);
-    env.events().publish(topics, reason);
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarketResolvedEvent {
+    pub market_id:   u64,
+    pub outcome:     Outcome,
+    pub resolved_at: u64,
 }
 
-/// Emits a `resolution_disputed` event when a resolution is disputed.
-///
-/// Topics: `(Symbol("resolution_disputed"), market_id)`
-/// Data:   `(disputer, reason)`
-pub fn emit_resolution_disputed(env: &Env, market_id: u64, disputer: Address, reason: String) {
-    let topics = (Symbol::new(env, "resolution_disputed"), market_id);
-    env.events().publish(topics, (disputer, reason));
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct WinningsClaimedEvent {
+    pub market_id:  u64,
+    pub bet_id:     Bytes,
+    pub bettor:     Address,
+    pub payout:     i128,
+    pub claimed_at: u64,
 }
 
-/// Emits a `dispute_resolved` event when an admin finalises a disputed outcome.
-///
-/// Topics: `(Symbol("dispute_resolved"), market_id)`
-/// Data:   `final_outcome: Outcome`
-pub fn emit_dispute_resolved(env: &Env, market_id: u64, final_outcome: Outcome) {
-    let topics = (Symbol::new(env, "dispute_resolved"), market_id);
-    env.events().publish(topics, final_outcome);
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct RefundClaimedEvent {
+    pub market_id: u64,
+    pub bet_id:    Bytes,
+    pub bettor:    Address,
+    pub amount:    i128,
 }
 
-/// Emits an `admin_transferred` event when admin privileges change hands.
-///
-/// Topics: `(Symbol("admin_transferred"),)`
-/// Data:   `(old_admin, new_admin)`
-pub fn emit_admin_transferred(env: &Env, old_admin: Address, new_admin: Address) {
-    let topics = (Symbol::new(env, "admin_transferred"),);
-    env.events().publish(topics, (old_admin, new_admin));
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResolutionDisputedEvent {
+    pub market_id:  u64,
+    pub dispute_id: u32,
+    pub disputer:   Address,
+    pub reason:     Bytes,
 }
 
-/// Emits a `fee_deposited` event when a market deposits fees into the treasury.
-///
-/// Topics: `(Symbol("fee_deposited"),)`
-/// Data:   `(market, token, amount)`
-pub fn emit_fee_deposited(env: &Env, market: Address, token: Address, amount: i128) {
-    let topics = (Symbol::new(env, "fee_deposited"),);
-    env.events().publish(topics, (market, token, amount));
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisputeResolvedEvent {
+    pub market_id:     u64,
+    pub final_outcome: Outcome,
 }
 
-/// Emits a `fee_withdrawn` event when the admin withdraws accumulated fees.
-///
-/// Topics: `(Symbol("fee_withdrawn"),)`
-/// Data:   `(token, amount, destination)`
-pub fn emit_fee_withdrawn(env: &Env, token: Address, amount: i128, destination: Address) {
-    let topics = (Symbol::new(env, "fee_withdrawn"),);
-    env.events().publish(topics, (token, amount, destination));
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResolutionFinalizedEvent {
+    pub market_id:    u64,
+    pub finalized_at: u64,
 }
 
-/// Emits an `emergency_drain` event when the admin drains all fees for a token.
-///
-/// Topics: `(Symbol("emergency_drain"),)`
-/// Data:   `(token, amount, admin)`
-pub fn emit_emergency_drain(env: &Env, token: Address, amount: i128, admin: Address) {
-    let topics = (Symbol::new(env, "emergency_drain"),);
-    env.events().publish(topics, (token, amount, admin));
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchClaimedEvent {
+    pub market_id:    u64,
+    pub bettor:       Address,
+    pub bet_ids:      Vec<Bytes>,
+    pub total_payout: i128,
+    pub claimed_at:   u64,
 }
 
-/// Emits a `config_updated` event when a configuration parameter is changed.
-///
-/// Topics: `(Symbol("config_updated"),)`
-/// Data:   `(param_name, new_value)`
-pub fn emit_config_updated(env: &Env, param_name: String, new_value: i128) {
-    let topics = (Symbol::new(env, "config_updated"),);
-    env.events().publish(topics, (param_name, new_value));
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnclaimedSweptEvent {
+    pub market_id: u64,
+    pub amount:    i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropBetPlacedEvent {
+    pub market_id: u64,
+    pub bet_id:    Bytes,
+    pub bettor:    Address,
+    pub selection: PropSelection,
+    pub amount:    i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FightResultReportedEvent {
+    pub market_id: u64,
+    pub result:    FightResult,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropClaimedEvent {
+    pub market_id: u64,
+    pub bet_id:    Bytes,
+    pub bettor:    Address,
+    pub payout:    i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisputeBondPostedEvent {
+    pub market_id:  u64,
+    pub bonder:     Address,
+    /// True when the bond backs the challenge, false when it defends the outcome.
+    pub challenges: bool,
+    pub amount:     i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisputeBondsSettledEvent {
+    pub market_id:        u64,
+    pub challenge_upheld: bool,
+    /// Total forfeited by the losing side.
+    pub forfeited:        i128,
+    /// Total awarded to the winning side.
+    pub awarded:          i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArbiterVotedEvent {
+    pub market_id:  u64,
+    pub arbiter:    Address,
+    pub dispute_id: u32,
+    pub outcome:    Outcome,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttestationAcceptedEvent {
+    pub market_id:  u64,
+    pub public_key: BytesN<32>,
+    pub nonce:      u64,
+    pub timestamp:  u64,
+}
+
+// ─── MarketFactory payloads ───────────────────────────────────────────────────
+// `market_created`, `event_created` and `arbiter_panel_set` carry the
+// `MarketInfo`, `Event` and `ArbiterPanel` records themselves.
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventMarketAddedEvent {
+    pub event_id:   u64,
+    pub market_id:  u64,
+    pub main_event: bool,
+}
+
+/// Payload of both `event_markets_locked` and `event_markets_cancelled`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventMarketsUpdatedEvent {
+    pub event_id: u64,
+    /// Markets on the card that accepted the call.
+    pub count:    u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimsAggregatedEvent {
+    pub bettor:       Address,
+    pub total_payout: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OracleStakedEvent {
+    pub oracle: Address,
+    pub amount: i128,
+    /// Stake bonded after this deposit.
+    pub stake:  i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OracleUnbondingEvent {
+    pub oracle:     Address,
+    pub unbonds_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OracleStakeWithdrawnEvent {
+    pub oracle: Address,
+    pub amount: i128,
 }
 
-/// Emits a `conflicting_oracle_report` event when two oracles disagree on the outcome.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OracleResolutionRecordedEvent {
+    pub oracle:    Address,
+    pub market_id: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OracleSlashedEvent {
+    pub oracle:    Address,
+    pub market_id: u64,
+    pub amount:    i128,
+}
+
+// ─── Treasury payloads ────────────────────────────────────────────────────────
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct BetDepositedEvent {
+    pub market:    Address,
+    pub market_id: u64,
+    pub bettor:    Address,
+    pub amount:    i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EscrowReleasedEvent {
+    pub market:    Address,
+    pub market_id: u64,
+    pub recipient: Address,
+    pub amount:    i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeesDepositedEvent {
+    pub market:    Address,
+    pub amount:    i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EscrowSweptEvent {
+    pub market:    Address,
+    pub market_id: u64,
+    pub amount:    i128,
+    pub timestamp: u64,
+}
+
+/// Payload of `oracle_stake_deposited`, `oracle_stake_slashed` and
+/// `oracle_stake_released`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OracleStakeMovedEvent {
+    pub oracle:    Address,
+    pub amount:    i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParlayDepositedEvent {
+    pub parlay:    Address,
+    pub bettor:    Address,
+    pub amount:    i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParlayPaidEvent {
+    pub parlay:    Address,
+    pub recipient: Address,
+    pub amount:    i128,
+    pub timestamp: u64,
+}
+
+/// Payload of both `fees_withdrawn` and `emergency_drain`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FundsWithdrawnEvent {
+    pub recipient: Address,
+    pub amount:    i128,
+    pub timestamp: u64,
+}
+
+// ─── Parlay payloads ──────────────────────────────────────────────────────────
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParlayPlacedEvent {
+    pub parlay_id: u64,
+    pub bettor:    Address,
+    pub legs:      Vec<ParlayLeg>,
+    pub amount:    i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParlaySettledEvent {
+    pub parlay_id: u64,
+    pub bettor:    Address,
+    pub status:    ParlayStatus,
+    pub payout:    i128,
+}
+
+// ─── Emitters ─────────────────────────────────────────────────────────────────
+
+/// Publishes `payload` under `(Symbol(name), EVENT_SCHEMA_VERSION)`.
 ///
-/// Topics: `(Symbol("conflicting_oracle_report"), market_id)`
-/// Data:   `oracle_address`
-pub fn emit_conflicting_oracle_report(env: &Env, market_id: u64, oracle_address: Address) {
-    let topics = (Symbol::new(env, "conflicting_oracle_report"), market_id);
-    env.events().publish(topics, oracle_address);
-}
-
-pub fn emit_contract_upgraded(env: &Env, new_wasm_hash: soroban_sdk::BytesN<32>) {
-    let topics = (Symbol::new(env, "contract_upgraded"),);
-    env.events().publish(topics, new_wasm_hash);
-}
-
-pub fn emit_protocol_paused(env: &Env) {
-    let topics = (Symbol::new(env, "protocol_paused"),);
-    env.events().publish(topics, ());
-}
-
-pub fn emit_protocol_unpaused(env: &Env) {
-    let topics = (Symbol::new(env, "protocol_unpaused"),);
-    env.events().publish(topics, ());
-}
-
-#[cfg(test)]
-mod tests {
-    use soroban_sdk::{
-        contract, contractimpl,
-        testutils::{Address as _, Events},
-        Address, Env, Symbol, TryFromVal,
-    };
-
-    use crate::{
-        events::*,
-        types::{BetRecord, BetSide, ClaimReceipt, Outcome},
-    };
-
-    // Minimal contract needed so env.as_contract() can record events.
-    #[contract]
-    struct Dummy;
-    #[contractimpl]
-    impl Dummy {}
-
-    fn env() -> (Env, Address) {
-        let env = Env::default();
-        let id = env.register_contract(None, Dummy);
-        (env, id)
-    }
-
-    fn addr(env: &Env) -> Address {
-        Address::generate(env)
-    }
-
-    fn str(env: &Env, s: &str) -> soroban_sdk::String {
-        soroban_sdk::String::from_str(env, s)
-    }
-
-    /// Returns the sole event emitted.
-    macro_rules! sole_event {
-        ($env:expr) => {{
-            let all = $env.events().all();
-            assert_eq!(all.len(), 1, "expected exactly 1 event");
-            all.get(0).unwrap()
-        }};
-    }
-
-    macro_rules! topic_sym {
-        ($env:expr, $ev:expr) => {
-            Symbol::try_from_val(&$env, &$ev.1.get(0).unwrap()).unwrap()
-        };
-    }
-
-    // ── market_created ───────────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_market_created() {
-        let (env, id) = env();
-        let contract = addr(&env);
-        env.as_contract(&id, || { emit_market_created(&env, 1, contract.clone(), str(&env, "FURY-USYK-2025")); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "market_created"));
-        let topic_id: u64 = u64::try_from_val(&env, &ev.1.get(1).unwrap()).unwrap();
-        assert_eq!(topic_id, 1_u64);
-        let (ev_contract, ev_match): (Address, soroban_sdk::String) =
-            TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_contract, contract);
-        assert_eq!(ev_match, str(&env, "FURY-USYK-2025"));
-    }
-
-    // ── market_locked ────────────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_market_locked() {
-        let (env, id) = env();
-        env.as_contract(&id, || { emit_market_locked(&env, 2); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "market_locked"));
-        let topic_id: u64 = u64::try_from_val(&env, &ev.1.get(1).unwrap()).unwrap();
-        assert_eq!(topic_id, 2_u64);
-    }
-
-    // ── market_resolved ──────────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_market_resolved() {
-        let (env, id) = env();
-        let resolution_time = 1_000_000u64;
-        env.as_contract(&id, || { emit_market_resolved(&env, 3, Outcome::FighterA, resolution_time); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "market_resolved"));
-        let (ev_outcome, ev_time): (Outcome, u64) =
-            TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_outcome, Outcome::FighterA);
-        assert_eq!(ev_time, resolution_time);
-    }
-
-    // ── bet_placed ───────────────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_bet_placed() {
-        let (env, id) = env();
-        let bettor = addr(&env);
-        let bet = BetRecord {
-            bettor: bettor.clone(),
-            market_id: 4,
-            side: BetSide::FighterA,
-            amount: 5_000_000,
-            placed_at: 1_000_000,
-            claimed: false,
-        };
-        env.as_contract(&id, || { emit_bet_placed(&env, 4, bet.clone()); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "bet_placed"));
-        let ev_bet: BetRecord = TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_bet.bettor, bettor);
-        assert_eq!(ev_bet.amount, 5_000_000);
-    }
-
-    // ── winnings_claimed ─────────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_winnings_claimed() {
-        let (env, id) = env();
-        let bettor = addr(&env);
-        let receipt = ClaimReceipt {
-            bettor: bettor.clone(),
-            market_id: 5,
-            amount_won: 9_800_000,
-            fee_deducted: 200_000,
-            claimed_at: 2_000_000,
-        };
-        env.as_contract(&id, || { emit_winnings_claimed(&env, 5, receipt.clone()); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "winnings_claimed"));
-        let ev_receipt: ClaimReceipt = TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_receipt.bettor, bettor);
-        assert_eq!(ev_receipt.amount_won, 9_800_000);
-        assert_eq!(ev_receipt.fee_deducted, 200_000);
-    }
-
-    // ── refund_claimed ───────────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_refund_claimed() {
-        let (env, id) = env();
-        let bettor = addr(&env);
-        env.as_contract(&id, || { emit_refund_claimed(&env, 6, bettor.clone(), 5_000_000); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "refund_claimed"));
-        let (ev_bettor, ev_amount): (Address, i128) =
-            TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_bettor, bettor);
-        assert_eq!(ev_amount, 5_000_000_i128);
-    }
-
-    // ── market_cancelled ─────────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_market_cancelled() {
-        let (env, id) = env();
-        env.as_contract(&id, || { emit_market_cancelled(&env, 7, str(&env, "fight_postponed")); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "market_cancelled"));
-        let ev_reason: soroban_sdk::String = TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_reason, str(&env, "fight_postponed"));
-    }
-
-    // ── market_disputed ──────────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_market_disputed() {
-        let (env, id) = env();
-        env.as_contract(&id, || { emit_market_disputed(&env, 8, str(&env, "oracle_conflict")); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "market_disputed"));
-        let ev_reason: soroban_sdk::String = TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_reason, str(&env, "oracle_conflict"));
-    }
-
-    // ── dispute_resolved ─────────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_dispute_resolved() {
-        let (env, id) = env();
-        env.as_contract(&id, || { emit_dispute_resolved(&env, 9, Outcome::Draw); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "dispute_resolved"));
-        let ev_outcome: Outcome = TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_outcome, Outcome::Draw);
-    }
-
-    // ── admin_transferred ────────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_admin_transferred() {
-        let (env, id) = env();
-        let old = addr(&env);
-        let new = addr(&env);
-        env.as_contract(&id, || { emit_admin_transferred(&env, old.clone(), new.clone()); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "admin_transferred"));
-        let (ev_old, ev_new): (Address, Address) =
-            TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_old, old);
-        assert_eq!(ev_new, new);
-    }
-
-    // ── fee_deposited ────────────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_fee_deposited() {
-        let (env, id) = env();
-        let market = addr(&env);
-        let token = addr(&env);
-        env.as_contract(&id, || { emit_fee_deposited(&env, market.clone(), token.clone(), 200_000); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "fee_deposited"));
-        let (ev_market, ev_token, ev_amount): (Address, Address, i128) =
-            TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_market, market);
-        assert_eq!(ev_token, token);
-        assert_eq!(ev_amount, 200_000_i128);
-    }
-
-    // ── fee_withdrawn ────────────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_fee_withdrawn() {
-        let (env, id) = env();
-        let token = addr(&env);
-        let dest = addr(&env);
-        env.as_contract(&id, || { emit_fee_withdrawn(&env, token.clone(), 1_000_000, dest.clone()); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "fee_withdrawn"));
-        let (ev_token, ev_amount, ev_dest): (Address, i128, Address) =
-            TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_token, token);
-        assert_eq!(ev_amount, 1_000_000_i128);
-        assert_eq!(ev_dest, dest);
-    }
-
-    // ── emergency_drain ──────────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_emergency_drain() {
-        let (env, id) = env();
-        let token = addr(&env);
-        let admin = addr(&env);
-        env.as_contract(&id, || { emit_emergency_drain(&env, token.clone(), 50_000_000, admin.clone()); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "emergency_drain"));
-        let (ev_token, ev_amount, ev_admin): (Address, i128, Address) =
-            TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_token, token);
-        assert_eq!(ev_amount, 50_000_000_i128);
-        assert_eq!(ev_admin, admin);
-    }
-
-    // ── config_updated ───────────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_config_updated() {
-        let (env, id) = env();
-        env.as_contract(&id, || { emit_config_updated(&env, str(&env, "fee_bps"), 300); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "config_updated"));
-        let (ev_param, ev_value): (soroban_sdk::String, i128) =
-            TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_param, str(&env, "fee_bps"));
-        assert_eq!(ev_value, 300_i128);
-    }
-
-    // ── resolution_disputed ──────────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_resolution_disputed() {
-        let (env, id) = env();
-        let disputer = addr(&env);
-        env.as_contract(&id, || { emit_resolution_disputed(&env, 11, disputer.clone(), str(&env, "oracle_error")); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "resolution_disputed"));
-        let topic_id: u64 = u64::try_from_val(&env, &ev.1.get(1).unwrap()).unwrap();
-        assert_eq!(topic_id, 11_u64);
-        let (ev_disputer, ev_reason): (Address, soroban_sdk::String) =
-            TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_disputer, disputer);
-        assert_eq!(ev_reason, str(&env, "oracle_error"));
-    }
-
-    // ── conflicting_oracle_report ─────────────────────────────────────────────
-
-    #[test]
-    fn test_emit_conflicting_oracle_report() {
-        let (env, id) = env();
-        let oracle = addr(&env);
-        env.as_contract(&id, || { emit_conflicting_oracle_report(&env, 10, oracle.clone()); });
-
-        let ev = sole_event!(env);
-        assert_eq!(topic_sym!(env, ev), Symbol::new(&env, "conflicting_oracle_report"));
-        let topic_id: u64 = u64::try_from_val(&env, &ev.1.get(1).unwrap()).unwrap();
-        assert_eq!(topic_id, 10_u64);
-        let ev_oracle: Address = TryFromVal::try_from_val(&env, &ev.2).unwrap();
-        assert_eq!(ev_oracle, oracle);
-    }
+/// `#[contractevent]` types cannot be decoded back from a `Val`, which the
+/// parsers rely on, so events are still published with `publish`.
+#[allow(deprecated)]
+fn publish<T: IntoVal<Env, Val>>(env: &Env, name: &str, payload: T) {
+    env.events().publish((Symbol::new(env, name), EVENT_SCHEMA_VERSION), payload);
+}
+
+// Market
+
+pub fn emit_market_initialized(env: &Env, event: MarketInitializedEvent) {
+    publish(env, MARKET_INITIALIZED, event);
+}
+
+pub fn emit_market_locked(env: &Env, event: MarketLockedEvent) {
+    publish(env, MARKET_LOCKED, event);
+}
+
+pub fn emit_bet_placed(env: &Env, event: BetPlacedEvent) {
+    publish(env, BET_PLACED, event);
+}
+
+pub fn emit_market_cancelled(env: &Env, event: MarketCancelledEvent) {
+    publish(env, MARKET_CANCELLED, event);
+}
+
+pub fn emit_resolution_timed_out(env: &Env, event: ResolutionTimedOutEvent) {
+    publish(env, RESOLUTION_TIMED_OUT, event);
+}
+
+pub fn emit_market_rescheduled(env: &Env, event: MarketRescheduledEvent) {
+    publish(env, MARKET_RESCHEDULED, event);
+}
+
+pub fn emit_fighter_substituted(env: &Env, event: FighterSubstitutedEvent) {
+    publish(env, FIGHTER_SUBSTITUTED, event);
+}
+
+pub fn emit_bet_withdrawn(env: &Env, event: BetWithdrawnEvent) {
+    publish(env, BET_WITHDRAWN, event);
+}
+
+pub fn emit_bet_cashed_out(env: &Env, event: BetCashedOutEvent) {
+    publish(env, BET_CASHED_OUT, event);
+}
+
+pub fn emit_market_resolved(env: &Env, event: MarketResolvedEvent) {
+    publish(env, MARKET_RESOLVED, event);
+}
+
+pub fn emit_winnings_claimed(env: &Env, event: WinningsClaimedEvent) {
+    publish(env, WINNINGS_CLAIMED, event);
+}
+
+pub fn emit_refund_claimed(env: &Env, event: RefundClaimedEvent) {
+    publish(env, REFUND_CLAIMED, event);
+}
+
+pub fn emit_resolution_disputed(env: &Env, event: ResolutionDisputedEvent) {
+    publish(env, RESOLUTION_DISPUTED, event);
+}
+
+pub fn emit_dispute_resolved(env: &Env, event: DisputeResolvedEvent) {
+    publish(env, DISPUTE_RESOLVED, event);
+}
+
+pub fn emit_resolution_finalized(env: &Env, event: ResolutionFinalizedEvent) {
+    publish(env, RESOLUTION_FINALIZED, event);
+}
+
+pub fn emit_batch_claimed(env: &Env, event: BatchClaimedEvent) {
+    publish(env, BATCH_CLAIMED, event);
+}
+
+pub fn emit_unclaimed_swept(env: &Env, event: UnclaimedSweptEvent) {
+    publish(env, UNCLAIMED_SWEPT, event);
+}
+
+pub fn emit_prop_bet_placed(env: &Env, event: PropBetPlacedEvent) {
+    publish(env, PROP_BET_PLACED, event);
+}
+
+pub fn emit_fight_result_reported(env: &Env, event: FightResultReportedEvent) {
+    publish(env, FIGHT_RESULT_REPORTED, event);
+}
+
+pub fn emit_prop_claimed(env: &Env, event: PropClaimedEvent) {
+    publish(env, PROP_CLAIMED, event);
+}
+
+pub fn emit_dispute_bond_posted(env: &Env, event: DisputeBondPostedEvent) {
+    publish(env, DISPUTE_BOND_POSTED, event);
+}
+
+pub fn emit_dispute_bonds_settled(env: &Env, event: DisputeBondsSettledEvent) {
+    publish(env, DISPUTE_BONDS_SETTLED, event);
+}
+
+pub fn emit_arbiter_voted(env: &Env, event: ArbiterVotedEvent) {
+    publish(env, ARBITER_VOTED, event);
+}
+
+pub fn emit_attestation_accepted(env: &Env, event: AttestationAcceptedEvent) {
+    publish(env, ATTESTATION_ACCEPTED, event);
+}
+
+// MarketFactory
+
+pub fn emit_market_created(env: &Env, info: MarketInfo) {
+    publish(env, MARKET_CREATED, info);
+}
+
+pub fn emit_event_created(env: &Env, event: Event) {
+    publish(env, EVENT_CREATED, event);
+}
+
+pub fn emit_event_market_added(env: &Env, event: EventMarketAddedEvent) {
+    publish(env, EVENT_MARKET_ADDED, event);
+}
+
+pub fn emit_event_markets_locked(env: &Env, event: EventMarketsUpdatedEvent) {
+    publish(env, EVENT_MARKETS_LOCKED, event);
+}
+
+pub fn emit_event_markets_cancelled(env: &Env, event: EventMarketsUpdatedEvent) {
+    publish(env, EVENT_MARKETS_CANCELLED, event);
+}
+
+pub fn emit_claims_aggregated(env: &Env, event: ClaimsAggregatedEvent) {
+    publish(env, CLAIMS_AGGREGATED, event);
+}
+
+pub fn emit_arbiter_panel_set(env: &Env, panel: ArbiterPanel) {
+    publish(env, ARBITER_PANEL_SET, panel);
+}
+
+pub fn emit_oracle_staked(env: &Env, event: OracleStakedEvent) {
+    publish(env, ORACLE_STAKED, event);
+}
+
+pub fn emit_oracle_unbonding(env: &Env, event: OracleUnbondingEvent) {
+    publish(env, ORACLE_UNBONDING, event);
+}
+
+pub fn emit_oracle_stake_withdrawn(env: &Env, event: OracleStakeWithdrawnEvent) {
+    publish(env, ORACLE_STAKE_WITHDRAWN, event);
+}
+
+pub fn emit_oracle_resolution_recorded(env: &Env, event: OracleResolutionRecordedEvent) {
+    publish(env, ORACLE_RESOLUTION_RECORDED, event);
+}
+
+pub fn emit_oracle_slashed(env: &Env, event: OracleSlashedEvent) {
+    publish(env, ORACLE_SLASHED, event);
+}
+
+// Treasury
+
+pub fn emit_bet_deposited(env: &Env, event: BetDepositedEvent) {
+    publish(env, BET_DEPOSITED, event);
+}
+
+pub fn emit_escrow_released(env: &Env, event: EscrowReleasedEvent) {
+    publish(env, ESCROW_RELEASED, event);
+}
+
+pub fn emit_fees_deposited(env: &Env, event: FeesDepositedEvent) {
+    publish(env, FEES_DEPOSITED, event);
+}
+
+pub fn emit_escrow_swept(env: &Env, event: EscrowSweptEvent) {
+    publish(env, ESCROW_SWEPT, event);
+}
+
+pub fn emit_oracle_stake_deposited(env: &Env, event: OracleStakeMovedEvent) {
+    publish(env, ORACLE_STAKE_DEPOSITED, event);
+}
+
+pub fn emit_oracle_stake_slashed(env: &Env, event: OracleStakeMovedEvent) {
+    publish(env, ORACLE_STAKE_SLASHED, event);
+}
+
+pub fn emit_oracle_stake_released(env: &Env, event: OracleStakeMovedEvent) {
+    publish(env, ORACLE_STAKE_RELEASED, event);
+}
+
+pub fn emit_parlay_deposited(env: &Env, event: ParlayDepositedEvent) {
+    publish(env, PARLAY_DEPOSITED, event);
+}
+
+pub fn emit_parlay_paid(env: &Env, event: ParlayPaidEvent) {
+    publish(env, PARLAY_PAID, event);
+}
+
+pub fn emit_fees_withdrawn(env: &Env, event: FundsWithdrawnEvent) {
+    publish(env, FEES_WITHDRAWN, event);
+}
+
+pub fn emit_emergency_drain(env: &Env, event: FundsWithdrawnEvent) {
+    publish(env, EMERGENCY_DRAIN, event);
+}
+
+// Parlay
+
+pub fn emit_parlay_placed(env: &Env, event: ParlayPlacedEvent) {
+    publish(env, PARLAY_PLACED, event);
+}
+
+pub fn emit_parlay_settled(env: &Env, event: ParlaySettledEvent) {
+    publish(env, PARLAY_SETTLED, event);
 }
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, Bytes, String};
+use soroban_sdk::{contracttype, Address, Bytes, String, Vec};
 
 // ─── ENUMS ────────────────────────────────────────────────────────────────────
 
@@ -12,21 +12,76 @@ pub enum MarketStatus {
     Disputed  = 4, // Result under admin review — claims frozen
 }
 
+/// Result of a fight. Shared by every contract and by event payloads, so
+/// each crate encodes it identically.
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub enum Outcome {
-    FighterA     = 0, // Fighter A wins
-    FighterB     = 1, // Fighter B wins
-    Draw         = 2, // Match ends in a draw — status set to Cancelled for full refunds
-    NoContest    = 3, // No contest — DQ or injury ruling
-    Undetermined = 4, // Not yet resolved
+    FighterA,
+    FighterB,
+    Draw,
+    NoContest,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
 pub enum BetSide {
-    FighterA = 0,
-    FighterB = 1,
+    FighterA,
+    FighterB,
+    /// Only accepted on markets created with `draw_enabled`.
+    Draw,
+}
+
+/// How a fight ended, as reported by the oracle in a [`FightResult`].
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum VictoryMethod {
+    Knockout,
+    TechnicalKnockout,
+    Submission,
+    Decision,
+    Disqualification,
+}
+
+/// Structured fight result reported by the oracle. Settles prop bets.
+///
+/// `round` is the round the fight ended in; for a `Decision` it is the
+/// final scheduled round.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FightResult {
+    pub winner: Outcome,
+    pub method: VictoryMethod,
+    pub round: u32,
+}
+
+/// What a prop bet is backing. Graded against the reported [`FightResult`].
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropSelection {
+    /// Fight is stopped (KO, TKO, submission or DQ) in the given round.
+    EndsInRound(u32),
+    /// Fight goes to the scorecards.
+    GoesTheDistance,
+    /// The given fighter wins by the given method.
+    WinsByMethod(BetSide, VictoryMethod),
+}
+
+/// One pick in a parlay: a side on a factory-registered market.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParlayLeg {
+    pub market_id: Bytes,
+    pub side: BetSide,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParlayStatus {
+    Open,     // At least one leg is still unsettled
+    Won,      // Paid out at multiplied odds
+    Lost,     // A leg lost — nothing to claim
+    Refunded, // Every leg was void — stake returned
 }
 
 // ─── STRUCTS ──────────────────────────────────────────────────────────────────
@@ -49,6 +104,16 @@ pub enum OptionalOutcome {
     Some(Outcome),
 }
 
+impl OptionalOutcome {
+    /// The outcome as a plain `Option`, for matching.
+    pub fn outcome(&self) -> Option<Outcome> {
+        match self {
+            OptionalOutcome::None       => None,
+            OptionalOutcome::Some(o)    => Some(o.clone()),
+        }
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct Market {
@@ -66,8 +131,8 @@ pub struct Market {
     pub protocol_fee_bp:        u32,
     pub oracle_address:         Address,
     pub fee_collector_address:  Address,
-    /// `Outcome::Undetermined` until the market is resolved.
-    pub outcome:                Outcome,
+    /// `OptionalOutcome::None` until the market is resolved.
+    pub outcome:                OptionalOutcome,
 }
 
 /// Full on-chain market record, as tracked by an individual Market contract instance.
@@ -80,6 +145,8 @@ pub type MarketData = Market;
 #[derive(Clone, Debug, PartialEq)]
 pub struct MarketInfo {
     pub market_id:      Bytes,
+    /// Sequential id assigned by the factory counter, starting at 1.
+    pub numeric_id:     u64,
     pub market_address: Address,
     pub creator:        Address,
     pub fighter_a:       String,
@@ -90,6 +157,72 @@ pub struct MarketInfo {
     pub created_at:      u64,
 }
 
+/// One market's line in the breakdown returned by `MarketFactory::claim_many`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarketClaim {
+    pub market_id:    Bytes,
+    pub payout:       i128,
+    pub bets_claimed: u32,
+    /// False if a later `claim_all` page was refused, leaving bets unvisited.
+    pub done:         bool,
+    /// False if the market refused the claim (e.g. not yet settled).
+    pub succeeded:    bool,
+}
+
+/// Committee that votes on disputed markets, as registered on MarketFactory.
+/// An empty `members` list leaves disputes to the protocol admin.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArbiterPanel {
+    pub members:         Vec<Address>,
+    /// Seconds after a dispute is raised during which the panel may vote.
+    pub vote_window_sec: u64,
+}
+
+/// One arbiter's vote on a disputed market, as kept in MarketFactory's
+/// arbiter registry.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArbiterVote {
+    pub market_id:  Bytes,
+    pub dispute_id: u32,
+    pub outcome:    Outcome,
+    pub voted_at:   u64,
+}
+
+/// An oracle's bonded stake and track record, as kept by MarketFactory.
+///
+/// The stake itself sits in the Treasury; `stake` mirrors what is still
+/// bonded after slashing.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OracleRecord {
+    pub oracle:        Address,
+    pub stake:         i128,
+    /// Markets this oracle has resolved.
+    pub resolutions:   u32,
+    /// Resolutions a dispute ruling later overrode.
+    pub overturned:    u32,
+    pub total_slashed: i128,
+    pub registered_at: u64,
+    /// When a deregistered oracle may withdraw its stake; 0 while active.
+    pub unbonds_at:    u64,
+}
+
+/// A fight card grouping several markets, as tracked by MarketFactory.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Event {
+    pub event_id:      u64,
+    pub name:          String,
+    pub venue:         String,
+    pub date:          u64,           // Unix timestamp of the card
+    pub market_ids:    Vec<Bytes>,    // Bouts on the card, in the order added
+    /// Market ID of the main event. Empty until one bout is flagged.
+    pub main_event_id: Bytes,
+}
+
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct Bet {
@@ -131,6 +264,8 @@ pub struct ProtocolConfig {
     pub min_bet_amount:     i128,
     pub max_bet_amount:     i128,
     pub dispute_window_sec: u64,
+    /// Seconds after settlement during which claims are accepted. `0` = never expires.
+    pub claim_expiry_sec:   u64,
     pub paused:             bool,
 }
 
@@ -1,7 +1,11 @@
 #![no_std]
-use shared::types::ProtocolConfig;
+use shared::events::{
+    self, BetDepositedEvent, EscrowReleasedEvent, EscrowSweptEvent, FeesDepositedEvent,
+    FundsWithdrawnEvent, OracleStakeMovedEvent, ParlayDepositedEvent, ParlayPaidEvent,
+};
+use shared::types::{MarketInfo, ProtocolConfig};
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, token, Address, Bytes, Env, Symbol, Vec,
+    contract, contractimpl, contracttype, token, Address, Bytes, Env, Symbol, Vec,
 };
 
 // ─── STORAGE KEYS ─────────────────────────────────────────────────────────────
@@ -13,6 +17,13 @@ use soroban_sdk::{
 // "BALANCE"         -> i128
 // "TOTAL_FEES"      -> i128
 // "WITHDRAWAL_LOG"  -> Vec<(Address, i128, u64)>
+// "PARLAY"          -> Address  (Parlay contract allowed to escrow and pay out)
+// ("ESCROW", id)    -> i128  (stake a market still holds in escrow)
+// ("ORACLE_STAKE", a) -> i128 (stake an oracle has bonded via the factory)
+// "ESCROW_TOTAL"    -> i128  (sum of every market's escrow)
+// "ORACLE_STAKE_TOTAL" -> i128 (sum of every oracle's bonded stake)
+// "PARLAY_RESERVED" -> i128  (most the open parlays can still be paid)
+// ("MARKET", id)    -> MarketInfo (factory registration, cached on first lookup)
 
 fn key_admin(env: &Env) -> Symbol {
     Symbol::new(env, "ADMIN")
@@ -46,6 +57,181 @@ fn key_wlog(env: &Env) -> Symbol {
     Symbol::new(env, "WITHDRAWAL_LOG")
 }
 
+fn key_parlay(env: &Env) -> Symbol {
+    Symbol::new(env, "PARLAY")
+}
+
+fn key_escrow(env: &Env, market_id: &Bytes) -> (Symbol, Bytes) {
+    (Symbol::new(env, "ESCROW"), market_id.clone())
+}
+
+fn key_oracle_stake(env: &Env, oracle: &Address) -> (Symbol, Address) {
+    (Symbol::new(env, "ORACLE_STAKE"), oracle.clone())
+}
+
+fn key_market(env: &Env, market_id: &Bytes) -> (Symbol, Bytes) {
+    (Symbol::new(env, "MARKET"), market_id.clone())
+}
+
+fn key_escrow_total(env: &Env) -> Symbol {
+    Symbol::new(env, "ESCROW_TOTAL")
+}
+
+fn key_oracle_stake_total(env: &Env) -> Symbol {
+    Symbol::new(env, "ORACLE_STAKE_TOTAL")
+}
+
+fn key_parlay_reserved(env: &Env) -> Symbol {
+    Symbol::new(env, "PARLAY_RESERVED")
+}
+
+/// What `check_invariants` found. Each flag compares the figures reported
+/// alongside it; `ok` is true when every flag is.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreasuryInvariantReport {
+    /// Bet tokens this contract actually holds.
+    pub token_balance: i128,
+    /// The recorded `BALANCE`.
+    pub balance: i128,
+    /// Sum of every market's escrow.
+    pub escrow_total: i128,
+    /// Sum of every oracle's bonded stake.
+    pub oracle_stake_total: i128,
+    /// Most the open parlays can still be paid.
+    pub parlay_reserved: i128,
+    /// `token_balance >= balance`: every recorded stroop is actually held.
+    pub balance_backed: bool,
+    /// `balance >= escrow_total + oracle_stake_total + parlay_reserved`: fee
+    /// withdrawals and parlay payouts are capped at the rest, so only an
+    /// emergency drain dips into what markets, oracles and parlays are owed.
+    pub escrow_covered: bool,
+    pub ok: bool,
+}
+
+/// Adds `delta` to the running total stored under `key`.
+fn adjust_total(env: &Env, key: &Symbol, delta: i128) {
+    let total: i128 = env.storage().persistent().get(key).unwrap_or(0);
+    env.storage().persistent().set(key, &(total + delta));
+}
+
+/// Part of `BALANCE` still owed to markets' bettors, to oracles and to open
+/// parlays: `escrow_total + oracle_stake_total + parlay_reserved`.
+fn reserved_balance(env: &Env) -> i128 {
+    let escrow_total: i128 = env
+        .storage()
+        .persistent()
+        .get(&key_escrow_total(env))
+        .unwrap_or(0);
+    let oracle_stake_total: i128 = env
+        .storage()
+        .persistent()
+        .get(&key_oracle_stake_total(env))
+        .unwrap_or(0);
+    let parlay_reserved: i128 = env
+        .storage()
+        .persistent()
+        .get(&key_parlay_reserved(env))
+        .unwrap_or(0);
+    escrow_total + oracle_stake_total + parlay_reserved
+}
+
+/// Subtracts `amount` from an oracle's bonded stake. Panics if the oracle
+/// has less than that bonded.
+fn debit_oracle_stake(env: &Env, oracle: &Address, amount: i128) {
+    let stake: i128 = env
+        .storage()
+        .persistent()
+        .get(&key_oracle_stake(env, oracle))
+        .unwrap_or(0);
+    if amount > stake {
+        panic!("amount exceeds oracle stake");
+    }
+    env.storage()
+        .persistent()
+        .set(&key_oracle_stake(env, oracle), &(stake - amount));
+    adjust_total(env, &key_oracle_stake_total(env), -amount);
+}
+
+/// Adds `delta` (negative to debit) to a market's escrow. Panics if the market
+/// would owe more than it deposited.
+fn adjust_escrow(env: &Env, market_id: &Bytes, delta: i128) {
+    let escrow: i128 = env
+        .storage()
+        .persistent()
+        .get(&key_escrow(env, market_id))
+        .unwrap_or(0);
+    let updated = escrow + delta;
+    if updated < 0 {
+        panic!("amount exceeds market escrow");
+    }
+    env.storage()
+        .persistent()
+        .set(&key_escrow(env, market_id), &updated);
+    adjust_total(env, &key_escrow_total(env), delta);
+}
+
+/// Panics unless `caller` is the registered Parlay contract and has authorized the call.
+fn require_parlay(env: &Env, caller: &Address) {
+    caller.require_auth();
+    let parlay: Address = env
+        .storage()
+        .persistent()
+        .get(&key_parlay(env))
+        .expect("parlay contract not set");
+    if parlay != *caller {
+        panic!("unauthorized: caller is not the parlay contract");
+    }
+}
+
+/// Panics unless `caller` is the stored factory and has authorized the call.
+fn require_factory(env: &Env, caller: &Address) {
+    caller.require_auth();
+    let factory: Address = env
+        .storage()
+        .persistent()
+        .get(&key_factory(env))
+        .expect("not initialized");
+    if factory != *caller {
+        panic!("unauthorized: caller is not the factory");
+    }
+}
+
+/// Looks up the `MarketInfo` the factory registered under `market_id`.
+///
+/// The first lookup asks the factory and caches the answer. Later ones must
+/// not: a factory `claim_many` pays out through this contract, and calling
+/// back into the factory there would be a re-entry the host rejects.
+fn registered_market(env: &Env, market_id: &Bytes) -> MarketInfo {
+    if let Some(info) = env.storage().persistent().get(&key_market(env, market_id)) {
+        return info;
+    }
+    let factory: Address = env
+        .storage()
+        .persistent()
+        .get(&key_factory(env))
+        .expect("not initialized");
+    let info: Option<MarketInfo> = env.invoke_contract(
+        &factory,
+        &Symbol::new(env, "get_market"),
+        soroban_sdk::vec![env, market_id.to_val()],
+    );
+    let info = info.expect("unauthorized: caller is not a registered market");
+    env.storage().persistent().set(&key_market(env, market_id), &info);
+    info
+}
+
+/// Panics unless `caller` is the Market the factory registered under `market_id`
+/// and has authorized the call. Returns the market's numeric id.
+fn require_market(env: &Env, market_id: &Bytes, caller: &Address) -> u64 {
+    caller.require_auth();
+    let info = registered_market(env, market_id);
+    if info.market_address != *caller {
+        panic!("unauthorized: caller is not a registered market");
+    }
+    info.numeric_id
+}
+
 #[contract]
 pub struct Treasury;
 
@@ -108,7 +294,7 @@ impl Treasury {
     ///
     /// Called by a `Market` contract when a bettor places a bet. Transfers
     /// `amount` of the configured bet token from `bettor` to this contract and
-    /// credits the treasury balance. Emits a `BetDeposited` event.
+    /// credits the treasury balance. Emits a `bet_deposited` event.
     ///
     /// # Arguments
     ///
@@ -124,22 +310,71 @@ impl Treasury {
     /// - `from_market` has not authorized the call.
     /// - `from_market` does not match the address registered for `market_id` in the factory.
     pub fn deposit(env: Env, from_market: Address, market_id: Bytes, bettor: Address, amount: i128) {
-        from_market.require_auth();
+        let numeric_id = require_market(&env, &market_id, &from_market);
 
-        let factory: Address = env
+        let token_addr: Address = env
             .storage()
             .persistent()
-            .get(&key_factory(&env))
-            .expect("not initialized");
-
-        let registered: Address = env.invoke_contract(
-            &factory,
-            &Symbol::new(&env, "get_market_address"),
-            soroban_sdk::vec![&env, market_id.to_val()],
+            .get(&key_token(&env))
+            .expect("token not set");
+        token::Client::new(&env, &token_addr).transfer(
+            &bettor,
+            env.current_contract_address(),
+            &amount,
         );
-        if registered != from_market {
-            panic!("unauthorized: caller is not a registered market");
+
+        let balance: i128 = env
+            .storage()
+            .persistent()
+            .get(&key_balance(&env))
+            .unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&key_balance(&env), &(balance + amount));
+        adjust_escrow(&env, &market_id, amount);
+
+        events::emit_bet_deposited(&env, BetDepositedEvent {
+            market: from_market,
+            market_id: numeric_id,
+            bettor,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+    }
+
+    /// Pays escrowed funds to a bettor on behalf of a registered `Market`.
+    ///
+    /// Used for withdrawals and cash-outs before a market settles, and for
+    /// batch claims after it does. Emits an `escrow_released` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `from_market` - Address of the calling Market contract. Must authorize this call.
+    /// * `market_id` - Identifier the factory registered `from_market` under.
+    /// * `recipient` - Address that receives the funds.
+    /// * `amount` - Amount to release in stroops. Must not exceed current `BALANCE`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `from_market` is not the registered market for `market_id`.
+    /// - `amount` exceeds the market's escrow or the current `BALANCE`.
+    pub fn release(env: Env, from_market: Address, market_id: Bytes, recipient: Address, amount: i128) {
+        let numeric_id = require_market(&env, &market_id, &from_market);
+        adjust_escrow(&env, &market_id, -amount);
+
+        let balance: i128 = env
+            .storage()
+            .persistent()
+            .get(&key_balance(&env))
+            .unwrap_or(0);
+        if amount > balance {
+            panic!("amount exceeds balance");
         }
+        env.storage()
+            .persistent()
+            .set(&key_balance(&env), &(balance - amount));
 
         let token_addr: Address = env
             .storage()
@@ -147,8 +382,139 @@ impl Treasury {
             .get(&key_token(&env))
             .expect("token not set");
         token::Client::new(&env, &token_addr).transfer(
-            &bettor,
             &env.current_contract_address(),
+            &recipient,
+            &amount,
+        );
+
+        events::emit_escrow_released(&env, EscrowReleasedEvent {
+            market: from_market,
+            market_id: numeric_id,
+            recipient,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+    }
+
+    /// Books part of a market's escrowed stake as protocol fees.
+    ///
+    /// The tokens already sit in the treasury, so `BALANCE` is unchanged; the
+    /// amount leaves the market's escrow and `TOTAL_FEES` grows. Used for
+    /// `cash_out` early-exit fees. Emits a `fees_deposited` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `from_market` - Address of the calling Market contract. Must authorize this call.
+    /// * `market_id` - Identifier the factory registered `from_market` under.
+    /// * `amount` - Fee amount in stroops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from_market` is not the registered market for `market_id`,
+    /// or `amount` exceeds the market's escrow.
+    pub fn collect_fee(env: Env, from_market: Address, market_id: Bytes, amount: i128) {
+        require_market(&env, &market_id, &from_market);
+        adjust_escrow(&env, &market_id, -amount);
+
+        let total: i128 = env
+            .storage()
+            .persistent()
+            .get(&key_total_fees(&env))
+            .unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&key_total_fees(&env), &(total + amount));
+
+        events::emit_fees_deposited(&env, FeesDepositedEvent {
+            market: from_market,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+    }
+
+    /// Sweeps a market's unclaimed escrow into the protocol fee bucket once its
+    /// claim deadline has passed.
+    ///
+    /// The tokens stay in the treasury, so `BALANCE` is unchanged; the market's
+    /// escrow drops to zero and `TOTAL_FEES` grows. Emits an `escrow_swept` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `from_market` - Address of the calling Market contract. Must authorize this call.
+    /// * `market_id` - Identifier the factory registered `from_market` under.
+    ///
+    /// # Returns
+    ///
+    /// Returns the amount swept, in stroops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from_market` is not the registered market for `market_id`.
+    pub fn sweep_market(env: Env, from_market: Address, market_id: Bytes) -> i128 {
+        let numeric_id = require_market(&env, &market_id, &from_market);
+
+        let amount: i128 = env
+            .storage()
+            .persistent()
+            .get(&key_escrow(&env, &market_id))
+            .unwrap_or(0);
+        adjust_escrow(&env, &market_id, -amount);
+
+        let total: i128 = env
+            .storage()
+            .persistent()
+            .get(&key_total_fees(&env))
+            .unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&key_total_fees(&env), &(total + amount));
+
+        events::emit_escrow_swept(&env, EscrowSweptEvent {
+            market: from_market,
+            market_id: numeric_id,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+
+        amount
+    }
+
+    /// Returns the stake a market still holds in escrow, in stroops.
+    pub fn get_market_escrow(env: Env, market_id: Bytes) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&key_escrow(&env, &market_id))
+            .unwrap_or(0)
+    }
+
+    /// Bonds an oracle's stake on behalf of the factory's oracle registry.
+    ///
+    /// Transfers `amount` of the bet token from `oracle` to this contract and
+    /// credits both `BALANCE` and the oracle's stake. Emits an `oracle_stake_deposited` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `from_factory` - Address of the MarketFactory. Must authorize this call.
+    /// * `oracle` - Address of the oracle whose funds are bonded.
+    /// * `amount` - Amount to bond, in stroops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from_factory` is not the stored factory.
+    pub fn stake_oracle(env: Env, from_factory: Address, oracle: Address, amount: i128) {
+        require_factory(&env, &from_factory);
+
+        let token_addr: Address = env
+            .storage()
+            .persistent()
+            .get(&key_token(&env))
+            .expect("token not set");
+        token::Client::new(&env, &token_addr).transfer(
+            &oracle,
+            env.current_contract_address(),
             &amount,
         );
 
@@ -160,17 +526,118 @@ impl Treasury {
         env.storage()
             .persistent()
             .set(&key_balance(&env), &(balance + amount));
+        let stake = Self::get_oracle_stake(env.clone(), oracle.clone());
+        env.storage()
+            .persistent()
+            .set(&key_oracle_stake(&env, &oracle), &(stake + amount));
+        adjust_total(&env, &key_oracle_stake_total(&env), amount);
+
+        events::emit_oracle_stake_deposited(&env, OracleStakeMovedEvent {
+            oracle,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+    }
+
+    /// Books part of an oracle's stake as protocol fees after the factory
+    /// penalizes an overturned resolution.
+    ///
+    /// The tokens already sit in the treasury, so `BALANCE` is unchanged; the
+    /// amount leaves the oracle's stake and `TOTAL_FEES` grows. Emits an
+    /// `oracle_stake_slashed` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `from_factory` - Address of the MarketFactory. Must authorize this call.
+    /// * `oracle` - Address of the penalized oracle.
+    /// * `amount` - Amount to slash, in stroops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from_factory` is not the stored factory, or `amount`
+    /// exceeds the oracle's stake.
+    pub fn slash_oracle(env: Env, from_factory: Address, oracle: Address, amount: i128) {
+        require_factory(&env, &from_factory);
+        debit_oracle_stake(&env, &oracle, amount);
+
+        let total: i128 = env
+            .storage()
+            .persistent()
+            .get(&key_total_fees(&env))
+            .unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&key_total_fees(&env), &(total + amount));
 
-        env.events().publish(
-            (Symbol::new(&env, "BetDeposited"),),
-            (from_market, bettor, market_id, amount, env.ledger().timestamp()),
+        events::emit_oracle_stake_slashed(&env, OracleStakeMovedEvent {
+            oracle,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+    }
+
+    /// Returns part of an oracle's stake once the factory has let it unbond.
+    ///
+    /// Emits an `oracle_stake_released` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `from_factory` - Address of the MarketFactory. Must authorize this call.
+    /// * `oracle` - Address of the oracle, which receives the tokens.
+    /// * `amount` - Amount to return, in stroops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from_factory` is not the stored factory, or `amount`
+    /// exceeds the oracle's stake or `BALANCE`.
+    pub fn release_oracle_stake(env: Env, from_factory: Address, oracle: Address, amount: i128) {
+        require_factory(&env, &from_factory);
+        debit_oracle_stake(&env, &oracle, amount);
+
+        let balance: i128 = env
+            .storage()
+            .persistent()
+            .get(&key_balance(&env))
+            .unwrap_or(0);
+        if amount > balance {
+            panic!("amount exceeds balance");
+        }
+        env.storage()
+            .persistent()
+            .set(&key_balance(&env), &(balance - amount));
+
+        let token_addr: Address = env
+            .storage()
+            .persistent()
+            .get(&key_token(&env))
+            .expect("token not set");
+        token::Client::new(&env, &token_addr).transfer(
+            &env.current_contract_address(),
+            &oracle,
+            &amount,
         );
+
+        events::emit_oracle_stake_released(&env, OracleStakeMovedEvent {
+            oracle,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+    }
+
+    /// Returns the stake an oracle has bonded, in stroops.
+    pub fn get_oracle_stake(env: Env, oracle: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&key_oracle_stake(&env, &oracle))
+            .unwrap_or(0)
     }
 
     /// Receives protocol fees from a registered `Market` contract.
     ///
     /// Only callable by a Market contract address registered with the factory.
-    /// Increments the per-market escrow balance and emits a `BetDeposited` event.
+    /// Increments the per-market escrow balance and emits a `bet_deposited` event.
     ///
     /// # Arguments
     ///
@@ -184,20 +651,9 @@ impl Treasury {
     /// Panics if:
     /// - The invoking contract address does not match the address registered for `market_id` in the factory.
     pub fn deposit_fees(env: Env, market_id: Bytes, amount: i128) {
-        let factory: Address = env
-            .storage()
-            .persistent()
-            .get(&key_factory(&env))
-            .expect("not initialized");
-
         let caller = env.current_contract_address();
 
-        let registered: Address = env.invoke_contract(
-            &factory,
-            &Symbol::new(&env, "get_market_address"),
-            soroban_sdk::vec![&env, market_id.to_val()],
-        );
-        if registered != caller {
+        if registered_market(&env, &market_id).market_address != caller {
             panic!("unauthorized: caller is not a registered market");
         }
 
@@ -218,29 +674,196 @@ impl Treasury {
             .persistent()
             .set(&key_total_fees(&env), &(total + amount));
 
-        env.events().publish(
-            (Symbol::new(&env, "FeesDeposited"),),
-            (caller, amount, env.ledger().timestamp()),
+        events::emit_fees_deposited(&env, FeesDepositedEvent {
+            market: caller,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+    }
+
+    /// Registers the Parlay contract allowed to call `deposit_parlay` and `pay_parlay`.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `admin` - Admin address. Must authorize this call.
+    /// * `parlay` - Address of the deployed Parlay contract.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `admin` has not authorized the call or is not the stored admin.
+    pub fn set_parlay_contract(env: Env, admin: Address, parlay: Address) {
+        admin.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&key_admin(&env))
+            .expect("not initialized");
+        if stored_admin != admin {
+            panic!("not admin");
+        }
+
+        env.storage().persistent().set(&key_parlay(&env), &parlay);
+    }
+
+    /// Escrows a parlay stake on behalf of the registered Parlay contract.
+    ///
+    /// Transfers `amount` of the bet token from `bettor` to this contract,
+    /// credits the treasury balance and reserves `max_payout` for the parlay:
+    /// the stake plus what the house stands to lose, which must fit in the
+    /// balance nobody else is owed. Emits a `parlay_deposited` event.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `from_parlay` - Address of the Parlay contract. Must authorize this call.
+    /// * `bettor` - Address of the bettor whose funds are being escrowed.
+    /// * `amount` - Amount of the bet token to escrow, in stroops.
+    /// * `max_payout` - Most the parlay can pay out, in stroops; at least `amount`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `from_parlay` is not the registered Parlay contract.
+    /// - `max_payout` is below `amount`.
+    /// - `max_payout` exceeds `amount` plus the part of `BALANCE` not owed to
+    ///   bettors, oracles or other parlays.
+    pub fn deposit_parlay(env: Env, from_parlay: Address, bettor: Address, amount: i128, max_payout: i128) {
+        require_parlay(&env, &from_parlay);
+        if max_payout < amount {
+            panic!("max payout below stake");
+        }
+
+        let token_addr: Address = env
+            .storage()
+            .persistent()
+            .get(&key_token(&env))
+            .expect("token not set");
+        token::Client::new(&env, &token_addr).transfer(
+            &bettor,
+            env.current_contract_address(),
+            &amount,
+        );
+
+        let balance: i128 = env
+            .storage()
+            .persistent()
+            .get(&key_balance(&env))
+            .unwrap_or(0);
+        if max_payout > balance + amount - reserved_balance(&env) {
+            panic!("house cannot cover parlay");
+        }
+        env.storage()
+            .persistent()
+            .set(&key_balance(&env), &(balance + amount));
+        adjust_total(&env, &key_parlay_reserved(&env), max_payout);
+
+        events::emit_parlay_deposited(&env, ParlayDepositedEvent {
+            parlay: from_parlay,
+            bettor,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+    }
+
+    /// Settles a parlay on behalf of the Parlay contract: releases the
+    /// `max_payout` reserved when it was placed and pays `amount` of it to
+    /// `recipient`. Whatever is not paid stays with the house. Emits a
+    /// `parlay_paid` event when `amount` is positive.
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - The Soroban execution environment.
+    /// * `from_parlay` - Address of the Parlay contract. Must authorize this call.
+    /// * `recipient` - Address that receives the payout.
+    /// * `max_payout` - The reservation passed to `deposit_parlay` for this parlay.
+    /// * `amount` - Payout or refund in stroops; zero for a lost parlay. Must
+    ///   not exceed `max_payout`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if:
+    /// - `from_parlay` is not the registered Parlay contract.
+    /// - `amount` exceeds `max_payout`, or `max_payout` exceeds what open
+    ///   parlays have reserved.
+    /// - `amount` exceeds the current `BALANCE`, or the part of it not owed to
+    ///   bettors, oracles or other parlays.
+    pub fn pay_parlay(env: Env, from_parlay: Address, recipient: Address, max_payout: i128, amount: i128) {
+        require_parlay(&env, &from_parlay);
+        if amount > max_payout {
+            panic!("amount exceeds parlay reservation");
+        }
+        let reserved: i128 = env
+            .storage()
+            .persistent()
+            .get(&key_parlay_reserved(&env))
+            .unwrap_or(0);
+        if max_payout > reserved {
+            panic!("reservation exceeds parlay reserves");
+        }
+        env.storage()
+            .persistent()
+            .set(&key_parlay_reserved(&env), &(reserved - max_payout));
+        if amount == 0 {
+            return;
+        }
+
+        let balance: i128 = env
+            .storage()
+            .persistent()
+            .get(&key_balance(&env))
+            .unwrap_or(0);
+        if amount > balance {
+            panic!("amount exceeds balance");
+        }
+        if amount > balance - reserved_balance(&env) {
+            panic!("amount exceeds unreserved balance");
+        }
+        env.storage()
+            .persistent()
+            .set(&key_balance(&env), &(balance - amount));
+
+        let token_addr: Address = env
+            .storage()
+            .persistent()
+            .get(&key_token(&env))
+            .expect("token not set");
+        token::Client::new(&env, &token_addr).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &amount,
         );
+
+        events::emit_parlay_paid(&env, ParlayPaidEvent {
+            parlay: from_parlay,
+            recipient,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
     }
 
     /// Transfers collected fees from the treasury to a recipient address.
     ///
-    /// Validates that `amount ≤ BALANCE` and deducts it before transferring XLM.
-    /// Appends an entry to `WITHDRAWAL_LOG`. Emits a `FeesWithdrawn` event.
+    /// Validates that `amount` fits in the part of `BALANCE` not held in market
+    /// escrow, bonded by oracles or reserved for open parlays, and deducts it
+    /// before transferring XLM.
+    /// Appends an entry to `WITHDRAWAL_LOG`. Emits a `fees_withdrawn` event.
     ///
     /// # Arguments
     ///
     /// * `env` - The Soroban execution environment.
     /// * `admin` - Admin address. Must authorize this call.
     /// * `recipient` - Address that will receive the withdrawn XLM.
-    /// * `amount` - Amount to withdraw in stroops. Must not exceed current `BALANCE`.
+    /// * `amount` - Amount to withdraw in stroops. Must not exceed current `BALANCE`
+    ///   minus `escrow_total`, `oracle_stake_total` and `parlay_reserved`.
     ///
     /// # Panics
     ///
     /// Panics if:
     /// - `admin` has not authorized the call.
-    /// - `amount` exceeds the current `BALANCE`.
+    /// - `amount` exceeds the current `BALANCE`, or the part of it not owed to
+    ///   bettors, oracles or parlays.
     pub fn withdraw_fees(env: Env, admin: Address, recipient: Address, amount: i128) {
         admin.require_auth();
 
@@ -261,6 +884,9 @@ impl Treasury {
         if amount > balance {
             panic!("amount exceeds balance");
         }
+        if amount > balance - reserved_balance(&env) {
+            panic!("amount exceeds unreserved balance");
+        }
         env.storage()
             .persistent()
             .set(&key_balance(&env), &(balance - amount));
@@ -285,17 +911,14 @@ impl Treasury {
         log.push_back((recipient.clone(), amount, ts));
         env.storage().persistent().set(&key_wlog(&env), &log);
 
-        env.events().publish(
-            (Symbol::new(&env, "FeesWithdrawn"),),
-            (recipient, amount, ts),
-        );
+        events::emit_fees_withdrawn(&env, FundsWithdrawnEvent { recipient, amount, timestamp: ts });
     }
 
     /// Drains all treasury funds to `recipient` in an emergency.
     ///
     /// Only callable while the protocol is paused (verified via cross-contract call
     /// to the factory's `get_config`). Resets `BALANCE` to zero, logs the drain,
-    /// and emits an `EmergencyDrain` event.
+    /// and emits an `emergency_drain` event.
     ///
     /// # Arguments
     ///
@@ -367,10 +990,7 @@ impl Treasury {
         log.push_back((recipient.clone(), amount, ts));
         env.storage().persistent().set(&key_wlog(&env), &log);
 
-        env.events().publish(
-            (symbol_short!("EmrgDrain"),),
-            (recipient, amount, ts),
-        );
+        events::emit_emergency_drain(&env, FundsWithdrawnEvent { recipient, amount, timestamp: ts });
 
         amount
     }
@@ -434,6 +1054,54 @@ impl Treasury {
             .unwrap_or(0)
     }
 
+    /// Reconciles the recorded balances against each other and against the
+    /// tokens actually held.
+    ///
+    /// Read-only — does not modify state.
+    ///
+    /// # Returns
+    ///
+    /// Returns a [`TreasuryInvariantReport`] with the figures compared and
+    /// whether each invariant holds.
+    pub fn check_invariants(env: Env) -> TreasuryInvariantReport {
+        let token_addr: Address = env
+            .storage()
+            .persistent()
+            .get(&key_token(&env))
+            .expect("token not set");
+        let token_balance =
+            token::Client::new(&env, &token_addr).balance(&env.current_contract_address());
+        let balance = Self::get_balance(env.clone());
+        let escrow_total: i128 = env
+            .storage()
+            .persistent()
+            .get(&key_escrow_total(&env))
+            .unwrap_or(0);
+        let oracle_stake_total: i128 = env
+            .storage()
+            .persistent()
+            .get(&key_oracle_stake_total(&env))
+            .unwrap_or(0);
+        let parlay_reserved: i128 = env
+            .storage()
+            .persistent()
+            .get(&key_parlay_reserved(&env))
+            .unwrap_or(0);
+
+        let balance_backed = token_balance >= balance;
+        let escrow_covered = balance >= escrow_total + oracle_stake_total + parlay_reserved;
+        TreasuryInvariantReport {
+            token_balance,
+            balance,
+            escrow_total,
+            oracle_stake_total,
+            parlay_reserved,
+            balance_backed,
+            escrow_covered,
+            ok: balance_backed && escrow_covered,
+        }
+    }
+
     /// Returns the stored fee recipient address.
     ///
     /// Read-only — does not modify state.
@@ -447,6 +1115,21 @@ impl Treasury {
             .get(&key_fee_recipient(&env))
             .expect("not initialized")
     }
+
+    /// Returns the bet token address.
+    ///
+    /// Read-only — does not modify state. The factory passes it to every
+    /// market it deploys.
+    ///
+    /// # Returns
+    ///
+    /// Returns the `TOKEN` address set during initialization.
+    pub fn get_token(env: Env) -> Address {
+        env.storage()
+            .persistent()
+            .get(&key_token(&env))
+            .expect("not initialized")
+    }
 }
 
 // ─── TESTS ────────────────────────────────────────────────────────────────────
@@ -455,7 +1138,7 @@ impl Treasury {
 mod tests {
     use super::*;
     use shared::test_utils::{create_test_address, create_test_env};
-    use soroban_sdk::IntoVal;
+    use soroban_sdk::symbol_short;
 
     #[test]
     fn test_initialize_success() {
@@ -465,7 +1148,7 @@ mod tests {
         let fee_recipient = create_test_address(&env);
         let token = create_test_address(&env);
 
-        let contract_id = env.register_contract(None, Treasury);
+        let contract_id = env.register(Treasury, ());
         let client = TreasuryClient::new(&env, &contract_id);
 
         client.initialize(&admin, &200u32, &fee_recipient, &factory, &token);
@@ -486,7 +1169,7 @@ mod tests {
         let fee_recipient = create_test_address(&env);
         let token = create_test_address(&env);
 
-        let contract_id = env.register_contract(None, Treasury);
+        let contract_id = env.register(Treasury, ());
         let client = TreasuryClient::new(&env, &contract_id);
 
         client.initialize(&admin, &200u32, &fee_recipient, &factory, &token);
@@ -502,7 +1185,7 @@ mod tests {
         let fee_recipient = create_test_address(&env);
         let token = create_test_address(&env);
 
-        let contract_id = env.register_contract(None, Treasury);
+        let contract_id = env.register(Treasury, ());
         let client = TreasuryClient::new(&env, &contract_id);
 
         client.initialize(&admin, &1001u32, &fee_recipient, &factory, &token);
@@ -516,7 +1199,7 @@ mod tests {
         let fee_recipient = create_test_address(&env);
         let token = create_test_address(&env);
 
-        let contract_id = env.register_contract(None, Treasury);
+        let contract_id = env.register(Treasury, ());
         let client = TreasuryClient::new(&env, &contract_id);
 
         client.initialize(&admin, &1000u32, &fee_recipient, &factory, &token);
@@ -528,7 +1211,7 @@ mod tests {
     /// Helper: registers treasury, initialises with a funded token, and seeds
     /// BALANCE by directly setting storage so we can test withdraw_fees without
     /// needing a real market factory cross-contract call.
-    fn setup_treasury_with_balance(env: &Env, balance: i128) -> (TreasuryClient, Address, Address) {
+    fn setup_treasury_with_balance(env: &Env, balance: i128) -> (TreasuryClient<'_>, Address, Address) {
         use soroban_sdk::testutils::Ledger;
 
         env.ledger().with_mut(|li| li.timestamp = 1_000_000);
@@ -538,7 +1221,7 @@ mod tests {
         let fee_recipient = create_test_address(env);
 
         // Mint `balance` tokens to the treasury contract so the token transfer succeeds.
-        let contract_id = env.register_contract(None, Treasury);
+        let contract_id = env.register(Treasury, ());
         let token_addr = shared::test_utils::fund_address(env, &contract_id, balance);
 
         let client = TreasuryClient::new(env, &contract_id);
@@ -645,6 +1328,399 @@ mod tests {
         assert_eq!(log.get(1).unwrap().1, 2_000i128);
     }
 
+    #[test]
+    #[should_panic(expected = "amount exceeds unreserved balance")]
+    fn test_withdraw_fees_cannot_touch_escrow() {
+        let env = create_test_env();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let (client, admin, contract_id) = setup_treasury_with_balance(&env, 500);
+        let (factory, token_addr): (Address, Address) = env.as_contract(&contract_id, || {
+            (
+                env.storage().persistent().get(&key_factory(&env)).unwrap(),
+                env.storage().persistent().get(&key_token(&env)).unwrap(),
+            )
+        });
+        let oracle = create_test_address(&env);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token_addr).mint(&oracle, &1_000);
+        client.stake_oracle(&factory, &oracle, &1_000);
+        env.as_contract(&contract_id, || {
+            env.storage().persistent().set(&key_escrow_total(&env), &200i128);
+        });
+
+        // 200 of the seeded balance is market escrow; only the other 300 is fees.
+        client.withdraw_fees(&admin, &create_test_address(&env), &300);
+        assert!(client.check_invariants().escrow_covered);
+        client.withdraw_fees(&admin, &create_test_address(&env), &1);
+    }
+
+    // ─── parlay escrow tests ───────────────────────────────────────────────────
+
+    /// Registers `parlay` and mints `amount` to a fresh bettor.
+    fn parlay_bettor(env: &Env, client: &TreasuryClient, admin: &Address, parlay: &Address, amount: i128) -> Address {
+        client.set_parlay_contract(admin, parlay);
+        let token_addr: Address = env.as_contract(&client.address, || {
+            env.storage().persistent().get(&key_token(env)).unwrap()
+        });
+        let bettor = create_test_address(env);
+        soroban_sdk::token::StellarAssetClient::new(env, &token_addr).mint(&bettor, &amount);
+        bettor
+    }
+
+    #[test]
+    fn test_parlay_deposit_and_pay() {
+        let env = create_test_env();
+        // The bettor's transfer auth sits below the parlay's root invocation.
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let (client, admin, contract_id) = setup_treasury_with_balance(&env, 10_000);
+        let parlay = create_test_address(&env);
+        let bettor = parlay_bettor(&env, &client, &admin, &parlay, 1_000);
+
+        client.deposit_parlay(&parlay, &bettor, &1_000, &5_000);
+        assert_eq!(client.get_balance(), 11_000);
+        let report = client.check_invariants();
+        assert_eq!(report.parlay_reserved, 5_000);
+        assert!(report.ok);
+
+        client.pay_parlay(&parlay, &bettor, &5_000, &4_000);
+        assert_eq!(client.get_balance(), 7_000);
+        assert_eq!(client.check_invariants().parlay_reserved, 0);
+        let token_addr: Address = env.as_contract(&contract_id, || {
+            env.storage().persistent().get(&key_token(&env)).unwrap()
+        });
+        assert_eq!(token::Client::new(&env, &token_addr).balance(&bettor), 4_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized: caller is not the parlay contract")]
+    fn test_parlay_deposit_from_unregistered_caller_panics() {
+        let env = create_test_env();
+        env.mock_all_auths();
+
+        let (client, admin, _) = setup_treasury_with_balance(&env, 0);
+        client.set_parlay_contract(&admin, &create_test_address(&env));
+
+        let impostor = create_test_address(&env);
+        client.deposit_parlay(&impostor, &create_test_address(&env), &100, &100);
+    }
+
+    #[test]
+    #[should_panic(expected = "amount exceeds parlay reservation")]
+    fn test_parlay_pay_exceeds_reservation_panics() {
+        let env = create_test_env();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let (client, admin, _) = setup_treasury_with_balance(&env, 5_000);
+        let parlay = create_test_address(&env);
+        let bettor = parlay_bettor(&env, &client, &admin, &parlay, 1_000);
+
+        client.deposit_parlay(&parlay, &bettor, &1_000, &2_000);
+        client.pay_parlay(&parlay, &bettor, &2_000, &2_001);
+    }
+
+    #[test]
+    #[should_panic(expected = "house cannot cover parlay")]
+    fn test_parlay_deposit_cannot_lean_on_bonded_stake() {
+        let env = create_test_env();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let (client, admin, contract_id) = setup_treasury_with_balance(&env, 500);
+        let parlay = create_test_address(&env);
+        let bettor = parlay_bettor(&env, &client, &admin, &parlay, 200);
+        let (factory, token_addr): (Address, Address) = env.as_contract(&contract_id, || {
+            (
+                env.storage().persistent().get(&key_factory(&env)).unwrap(),
+                env.storage().persistent().get(&key_token(&env)).unwrap(),
+            )
+        });
+        let oracle = create_test_address(&env);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token_addr).mint(&oracle, &1_000);
+        client.stake_oracle(&factory, &oracle, &1_000);
+
+        // Only the 500 nobody is owed can back what a parlay might win.
+        client.deposit_parlay(&parlay, &bettor, &100, &600);
+        assert!(client.check_invariants().ok);
+        client.deposit_parlay(&parlay, &bettor, &100, &101);
+    }
+
+    #[test]
+    fn test_parlay_refund_comes_from_its_own_reservation() {
+        let env = create_test_env();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let (client, admin, _) = setup_treasury_with_balance(&env, 0);
+        let parlay = create_test_address(&env);
+        let first = parlay_bettor(&env, &client, &admin, &parlay, 700);
+        let second = parlay_bettor(&env, &client, &admin, &parlay, 500);
+        client.deposit_parlay(&parlay, &first, &700, &700);
+        client.deposit_parlay(&parlay, &second, &500, &500);
+
+        // Neither stake is the admin's to withdraw.
+        assert!(client.try_withdraw_fees(&admin, &admin, &1).is_err());
+
+        client.pay_parlay(&parlay, &first, &700, &700);
+        let report = client.check_invariants();
+        assert_eq!((report.balance, report.parlay_reserved), (500, 500));
+        assert!(report.ok);
+    }
+
+    // ─── market escrow tests ───────────────────────────────────────────────────
+
+    /// Factory stand-in that registers a single market under any id.
+    #[contract]
+    struct MockFactory;
+
+    #[contractimpl]
+    impl MockFactory {
+        pub fn __constructor(env: Env, market: Address) {
+            env.storage().persistent().set(&symbol_short!("MKT"), &market);
+        }
+
+        pub fn get_market(env: Env, market_id: Bytes) -> Option<MarketInfo> {
+            let market: Address = env.storage().persistent().get(&symbol_short!("MKT")).unwrap();
+            Some(MarketInfo {
+                market_id,
+                numeric_id: 1,
+                market_address: market.clone(),
+                creator: market.clone(),
+                fighter_a: soroban_sdk::String::from_str(&env, "A"),
+                fighter_b: soroban_sdk::String::from_str(&env, "B"),
+                oracle: market,
+                lock_time: 0,
+                end_time: 0,
+                created_at: 0,
+            })
+        }
+    }
+
+    /// Returns (client, market, token) for a treasury whose factory knows `market`.
+    fn setup_treasury_with_market(env: &Env) -> (TreasuryClient<'_>, Address, Address) {
+        let admin = create_test_address(env);
+        let market = create_test_address(env);
+        let factory = env.register(MockFactory, (market.clone(),));
+
+        let contract_id = env.register(Treasury, ());
+        let token_addr = shared::test_utils::fund_address(env, &contract_id, 0);
+        let client = TreasuryClient::new(env, &contract_id);
+        client.initialize(&admin, &200u32, &admin, &factory, &token_addr);
+
+        (client, market, token_addr)
+    }
+
+    #[test]
+    fn test_market_deposit_and_release() {
+        let env = create_test_env();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let (client, market, token_addr) = setup_treasury_with_market(&env);
+        let bettor = create_test_address(&env);
+        let market_id = Bytes::from_array(&env, &[1u8; 32]);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token_addr).mint(&bettor, &1_000);
+
+        client.deposit(&market, &market_id, &bettor, &1_000);
+        assert_eq!(client.get_balance(), 1_000);
+
+        client.release(&market, &market_id, &bettor, &1_000);
+        assert_eq!(client.get_balance(), 0);
+        assert_eq!(token::Client::new(&env, &token_addr).balance(&bettor), 1_000);
+    }
+
+    #[test]
+    fn test_collect_fee_books_escrow_as_fees() {
+        let env = create_test_env();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let (client, market, token_addr) = setup_treasury_with_market(&env);
+        let bettor = create_test_address(&env);
+        let market_id = Bytes::from_array(&env, &[1u8; 32]);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token_addr).mint(&bettor, &1_000);
+
+        client.deposit(&market, &market_id, &bettor, &1_000);
+        client.release(&market, &market_id, &bettor, &950);
+        client.collect_fee(&market, &market_id, &50);
+
+        assert_eq!(client.get_balance(), 50);
+        assert_eq!(client.get_total_fees_earned(), 50);
+        assert_eq!(client.get_market_escrow(&market_id), 0);
+    }
+
+    #[test]
+    fn test_sweep_market_moves_escrow_to_fees() {
+        let env = create_test_env();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let (client, market, token_addr) = setup_treasury_with_market(&env);
+        let bettor = create_test_address(&env);
+        let market_id = Bytes::from_array(&env, &[1u8; 32]);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token_addr).mint(&bettor, &1_000);
+
+        client.deposit(&market, &market_id, &bettor, &1_000);
+        client.release(&market, &market_id, &bettor, &600);
+        assert_eq!(client.get_market_escrow(&market_id), 400);
+
+        assert_eq!(client.sweep_market(&market, &market_id), 400);
+        assert_eq!(client.get_market_escrow(&market_id), 0);
+        assert_eq!(client.get_total_fees_earned(), 400);
+        assert_eq!(client.get_balance(), 400);
+    }
+
+    #[test]
+    fn test_release_reuses_cached_market_registration() {
+        let env = create_test_env();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let (client, market, token_addr) = setup_treasury_with_market(&env);
+        let bettor = create_test_address(&env);
+        let market_id = Bytes::from_array(&env, &[1u8; 32]);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token_addr).mint(&bettor, &1_000);
+        client.deposit(&market, &market_id, &bettor, &1_000);
+
+        // The factory can no longer answer `get_market`, as during its own
+        // `claim_many`; the release must not ask it again.
+        let factory: Address = env.as_contract(&client.address, || {
+            env.storage().persistent().get(&key_factory(&env)).unwrap()
+        });
+        env.as_contract(&factory, || env.storage().persistent().remove(&symbol_short!("MKT")));
+
+        client.release(&market, &market_id, &bettor, &1_000);
+        assert_eq!(token::Client::new(&env, &token_addr).balance(&bettor), 1_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "amount exceeds market escrow")]
+    fn test_release_beyond_market_escrow_panics() {
+        let env = create_test_env();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let (client, market, token_addr) = setup_treasury_with_market(&env);
+        let bettor = create_test_address(&env);
+        let market_id = Bytes::from_array(&env, &[1u8; 32]);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token_addr).mint(&bettor, &1_000);
+
+        client.deposit(&market, &market_id, &bettor, &1_000);
+        client.release(&market, &market_id, &bettor, &1_001);
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized: caller is not a registered market")]
+    fn test_release_from_unregistered_market_panics() {
+        let env = create_test_env();
+        env.mock_all_auths();
+
+        let (client, _market, _) = setup_treasury_with_market(&env);
+        let impostor = create_test_address(&env);
+        let market_id = Bytes::from_array(&env, &[1u8; 32]);
+        client.release(&impostor, &market_id, &impostor, &1);
+    }
+
+    #[test]
+    fn test_check_invariants_tracks_market_escrow() {
+        let env = create_test_env();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let (client, market, token_addr) = setup_treasury_with_market(&env);
+        let bettor = create_test_address(&env);
+        let market_a = Bytes::from_array(&env, &[1u8; 32]);
+        let market_b = Bytes::from_array(&env, &[2u8; 32]);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token_addr).mint(&bettor, &1_500);
+
+        client.deposit(&market, &market_a, &bettor, &1_000);
+        client.deposit(&market, &market_b, &bettor, &500);
+        client.release(&market, &market_a, &bettor, &600);
+        client.collect_fee(&market, &market_b, &50);
+
+        let report = client.check_invariants();
+        assert_eq!(
+            report,
+            TreasuryInvariantReport {
+                token_balance: 900,
+                balance: 900,
+                escrow_total: 850,
+                oracle_stake_total: 0,
+                parlay_reserved: 0,
+                balance_backed: true,
+                escrow_covered: true,
+                ok: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_invariants_flags_unbacked_balance() {
+        let env = create_test_env();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let (client, market, token_addr) = setup_treasury_with_market(&env);
+        let bettor = create_test_address(&env);
+        let market_id = Bytes::from_array(&env, &[1u8; 32]);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token_addr).mint(&bettor, &1_000);
+        client.deposit(&market, &market_id, &bettor, &1_000);
+
+        // A balance the treasury does not hold, and less than the escrow.
+        env.as_contract(&client.address, || {
+            env.storage().persistent().set(&key_balance(&env), &1_200i128);
+        });
+        let report = client.check_invariants();
+        assert!(!report.balance_backed);
+        assert!(report.escrow_covered);
+        assert!(!report.ok);
+
+        env.as_contract(&client.address, || {
+            env.storage().persistent().set(&key_balance(&env), &800i128);
+        });
+        let report = client.check_invariants();
+        assert!(report.balance_backed);
+        assert!(!report.escrow_covered);
+        assert!(!report.ok);
+    }
+
+    // ─── oracle stake tests ────────────────────────────────────────────────────
+
+    #[test]
+    fn test_oracle_stake_slash_and_release() {
+        let env = create_test_env();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let (client, _admin, contract_id) = setup_treasury_with_balance(&env, 0);
+        let (factory, token_addr): (Address, Address) = env.as_contract(&contract_id, || {
+            (
+                env.storage().persistent().get(&key_factory(&env)).unwrap(),
+                env.storage().persistent().get(&key_token(&env)).unwrap(),
+            )
+        });
+        let oracle = create_test_address(&env);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token_addr).mint(&oracle, &1_000);
+
+        client.stake_oracle(&factory, &oracle, &1_000);
+        assert_eq!(client.get_oracle_stake(&oracle), 1_000);
+        assert_eq!(client.get_balance(), 1_000);
+
+        client.slash_oracle(&factory, &oracle, &100);
+        assert_eq!(client.get_oracle_stake(&oracle), 900);
+        assert_eq!(client.get_total_fees_earned(), 100);
+        assert_eq!(client.get_balance(), 1_000);
+
+        assert_eq!(client.check_invariants().oracle_stake_total, 900);
+
+        client.release_oracle_stake(&factory, &oracle, &900);
+        assert_eq!(client.get_oracle_stake(&oracle), 0);
+        assert_eq!(client.get_balance(), 100);
+        assert!(client.check_invariants().ok);
+        assert_eq!(token::Client::new(&env, &token_addr).balance(&oracle), 900);
+    }
+
+    #[test]
+    #[should_panic(expected = "unauthorized: caller is not the factory")]
+    fn test_slash_oracle_from_non_factory_panics() {
+        let env = create_test_env();
+        env.mock_all_auths();
+
+        let (client, _admin, _) = setup_treasury_with_balance(&env, 0);
+        let impostor = create_test_address(&env);
+        client.slash_oracle(&impostor, &create_test_address(&env), &1);
+    }
+
     #[test]
     #[should_panic(expected = "amount exceeds balance")]
     fn test_withdraw_fees_zero_balance_panics() {
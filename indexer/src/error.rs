@@ -0,0 +1,53 @@
+//! Errors surfaced while loading, decoding and replaying exported events.
+
+use std::fmt;
+
+use shared::event_parser::ParseError;
+
+#[derive(Debug)]
+pub enum IndexerError {
+    /// An export or snapshot file could not be read
+    Io(std::io::Error),
+    /// An export or snapshot file is not JSON of the expected shape
+    Json(serde_json::Error),
+    /// A topic or value is not valid base64-encoded `ScVal` XDR
+    Xdr { event_id: String },
+    /// A protocol event failed the `shared::event_parser` checks
+    Parse { event_id: String, error: ParseError },
+    /// The SQLite store rejected a read or write
+    Store(rusqlite::Error),
+}
+
+impl fmt::Display for IndexerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexerError::Io(e) => write!(f, "io error: {e}"),
+            IndexerError::Json(e) => write!(f, "invalid json: {e}"),
+            IndexerError::Xdr { event_id } => write!(f, "event {event_id}: invalid xdr"),
+            IndexerError::Parse { event_id, error } => {
+                write!(f, "event {event_id}: cannot parse ({error:?})")
+            }
+            IndexerError::Store(e) => write!(f, "store error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for IndexerError {}
+
+impl From<std::io::Error> for IndexerError {
+    fn from(e: std::io::Error) -> Self {
+        IndexerError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for IndexerError {
+    fn from(e: serde_json::Error) -> Self {
+        IndexerError::Json(e)
+    }
+}
+
+impl From<rusqlite::Error> for IndexerError {
+    fn from(e: rusqlite::Error) -> Self {
+        IndexerError::Store(e)
+    }
+}
@@ -0,0 +1,21 @@
+//! ============================================================
+//! BOXMEOUT — Offline Event Indexer
+//! Replays exported protocol events into an embedded SQLite
+//! store and checks the result against on-chain snapshots.
+//!
+//! Events are decoded with `shared::event_parser::parse_event`,
+//! so the indexer reads exactly the schema the contracts emit.
+//! Runs entirely off-chain; nothing here is deployed.
+//! ============================================================
+
+pub mod error;
+pub mod replay;
+pub mod snapshot;
+pub mod source;
+pub mod store;
+
+pub use error::IndexerError;
+pub use replay::{Applied, Indexer, ReplaySummary};
+pub use snapshot::{diff_market, load_snapshots, MarketSnapshot, Mismatch};
+pub use source::{load_events, RawEvent};
+pub use store::{BetState, BetStatus, ClaimRecord, DisputeState, MarketState, Store, TreasuryState};
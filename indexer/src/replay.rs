@@ -0,0 +1,430 @@
+//! Replays decoded events into the store.
+//!
+//! Each handler mirrors the state change the emitting contract made, so the
+//! store converges on what the contracts hold: market pools and status, bets
+//! and their payouts, disputes, market escrow and the treasury balance.
+
+use std::path::Path;
+
+use shared::event_parser::{parse_event, BoxmeoutEvent, ParseError};
+use shared::types::{MarketStatus, Outcome};
+use soroban_sdk::{xdr::ScAddress, Address, Bytes, Env, Symbol, TryFromVal};
+
+use crate::error::IndexerError;
+use crate::source::{load_events, RawEvent};
+use crate::store::{
+    BetState, BetStatus, ClaimRecord, DisputeState, MarketState, Store, TreasuryState,
+};
+
+/// What happened to one exported event.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Applied {
+    /// Decoded and applied to the store.
+    Applied,
+    /// Already applied by an earlier replay.
+    Duplicate,
+    /// Not a protocol event, e.g. a token transfer from the same export.
+    Skipped,
+}
+
+/// Counts of a replay run.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ReplaySummary {
+    pub applied:    u64,
+    pub duplicates: u64,
+    pub skipped:    u64,
+}
+
+pub struct Indexer {
+    env:   Env,
+    store: Store,
+}
+
+impl Indexer {
+    pub fn new(store: Store) -> Self {
+        let env = Env::default();
+        // Decoding a long export would otherwise exhaust the test budget.
+        env.cost_estimate().budget().reset_unlimited();
+        Indexer { env, store }
+    }
+
+    pub fn store(&self) -> &Store {
+        &self.store
+    }
+
+    /// Loads every export in `paths` and replays them as one ordered stream.
+    pub fn replay_files<P: AsRef<Path>>(&self, paths: &[P]) -> Result<ReplaySummary, IndexerError> {
+        let mut events = Vec::new();
+        for path in paths {
+            events.extend(load_events(path.as_ref())?);
+        }
+        self.replay(events)
+    }
+
+    /// Replays `events` in ledger order. Events already in the store are
+    /// counted as duplicates, so overlapping exports can be replayed safely.
+    pub fn replay(&self, mut events: Vec<RawEvent>) -> Result<ReplaySummary, IndexerError> {
+        events.sort_by(|a, b| (a.ledger, &a.id).cmp(&(b.ledger, &b.id)));
+        let mut summary = ReplaySummary::default();
+        for event in &events {
+            match self.apply(event)? {
+                Applied::Applied => summary.applied += 1,
+                Applied::Duplicate => summary.duplicates += 1,
+                Applied::Skipped => summary.skipped += 1,
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Decodes and applies one event atomically.
+    pub fn apply(&self, raw: &RawEvent) -> Result<Applied, IndexerError> {
+        let (topics, data) = raw.to_vals(&self.env)?;
+        // Only `(Symbol, u32)` topics can be protocol events.
+        let named = topics.len() == 2
+            && Symbol::try_from_val(&self.env, &topics.get_unchecked(0)).is_ok();
+        if !named {
+            return Ok(Applied::Skipped);
+        }
+        let event = match parse_event(&self.env, &topics, &data) {
+            Ok(event) => event,
+            Err(ParseError::UnknownTopic) => return Ok(Applied::Skipped),
+            Err(error) => return Err(IndexerError::Parse { event_id: raw.id.clone(), error }),
+        };
+
+        self.store.atomically(|store| {
+            if !store.record_event(raw, event.name())? {
+                return Ok(Applied::Duplicate);
+            }
+            if let Some(ts) = raw.closed_at() {
+                store.advance_clock(ts)?;
+            }
+            self.apply_event(store, raw, event)?;
+            Ok(Applied::Applied)
+        })
+    }
+
+    fn apply_event(&self, store: &Store, raw: &RawEvent, event: BoxmeoutEvent) -> Result<(), IndexerError> {
+        let kind = event.name();
+        match event {
+            // ── Factory ──
+            BoxmeoutEvent::MarketCreated(info) => {
+                let mut m = market(store, info.numeric_id)?;
+                m.market_id = Some(hex(&info.market_id));
+                m.address = Some(address(&info.market_address));
+                m.fighter_a = text(&info.fighter_a);
+                m.fighter_b = text(&info.fighter_b);
+                store.put_market(&m)?;
+            }
+
+            // ── Market ──
+            BoxmeoutEvent::MarketInitialized(e) => {
+                let mut m = market(store, e.market_id)?;
+                m.fighter_a = text(&e.fighter_a);
+                m.fighter_b = text(&e.fighter_b);
+                m.betting_ends_at = e.betting_ends_at;
+                m.draw_enabled = e.draw_enabled;
+                store.put_market(&m)?;
+            }
+            BoxmeoutEvent::MarketLocked(e) => {
+                let mut m = market(store, e.market_id)?;
+                m.status = MarketStatus::Locked;
+                store.put_market(&m)?;
+            }
+            BoxmeoutEvent::BetPlaced(e) => {
+                let mut m = market(store, e.market_id)?;
+                *m.pool_mut(&e.side) += e.amount;
+                m.total_pool += e.amount;
+                store.put_market(&m)?;
+                store.put_bet(&BetState {
+                    bet_id: hex(&e.bet_id),
+                    numeric_id: e.market_id,
+                    bettor: address(&e.bettor),
+                    prop: false,
+                    selection: format!("{:?}", e.side),
+                    amount: e.amount,
+                    state: BetStatus::Open,
+                    payout: 0,
+                })?;
+            }
+            BoxmeoutEvent::BetWithdrawn(e) => {
+                unwind_bet(store, e.market_id, &e.bet_id, BetStatus::Withdrawn, e.amount)?;
+                claim(store, raw, kind, e.market_id, &e.bettor, 1, e.amount)?;
+            }
+            BoxmeoutEvent::BetCashedOut(e) => {
+                unwind_bet(store, e.market_id, &e.bet_id, BetStatus::CashedOut, e.refund)?;
+                claim(store, raw, kind, e.market_id, &e.bettor, 1, e.refund)?;
+            }
+            BoxmeoutEvent::MarketRescheduled(e) => {
+                let mut m = market(store, e.market_id)?;
+                m.betting_ends_at = e.new_betting_ends_at;
+                if m.status == MarketStatus::Locked && e.new_betting_ends_at > store.clock()? {
+                    m.status = MarketStatus::Open;
+                }
+                store.put_market(&m)?;
+            }
+            BoxmeoutEvent::MarketCancelled(e) => set_status(store, e.market_id, MarketStatus::Cancelled)?,
+            BoxmeoutEvent::ResolutionTimedOut(e) => set_status(store, e.market_id, MarketStatus::Cancelled)?,
+            BoxmeoutEvent::MarketResolved(e) => {
+                let mut m = market(store, e.market_id)?;
+                m.status = settled_status(&m, &e.outcome);
+                m.outcome = Some(e.outcome);
+                store.put_market(&m)?;
+            }
+            BoxmeoutEvent::WinningsClaimed(e) => {
+                settle_bet(store, &e.bet_id, BetStatus::Claimed, e.payout)?;
+                add_paid_out(store, e.market_id, e.payout)?;
+                claim(store, raw, kind, e.market_id, &e.bettor, 1, e.payout)?;
+            }
+            BoxmeoutEvent::RefundClaimed(e) => {
+                settle_bet(store, &e.bet_id, BetStatus::Refunded, e.amount)?;
+                add_paid_out(store, e.market_id, e.amount)?;
+                claim(store, raw, kind, e.market_id, &e.bettor, 1, e.amount)?;
+            }
+            BoxmeoutEvent::BatchClaimed(e) => {
+                // The batch reports one total, not a per-bet split.
+                for bet_id in e.bet_ids.iter() {
+                    settle_bet(store, &bet_id, BetStatus::Claimed, 0)?;
+                }
+                add_paid_out(store, e.market_id, e.total_payout)?;
+                claim(store, raw, kind, e.market_id, &e.bettor, e.bet_ids.len(), e.total_payout)?;
+            }
+            BoxmeoutEvent::PropBetPlaced(e) => {
+                let mut m = market(store, e.market_id)?;
+                m.prop_pool += e.amount;
+                store.put_market(&m)?;
+                store.put_bet(&BetState {
+                    bet_id: hex(&e.bet_id),
+                    numeric_id: e.market_id,
+                    bettor: address(&e.bettor),
+                    prop: true,
+                    selection: format!("{:?}", e.selection),
+                    amount: e.amount,
+                    state: BetStatus::Open,
+                    payout: 0,
+                })?;
+            }
+            BoxmeoutEvent::PropClaimed(e) => {
+                settle_bet(store, &e.bet_id, BetStatus::Claimed, e.payout)?;
+                add_paid_out(store, e.market_id, e.payout)?;
+                claim(store, raw, kind, e.market_id, &e.bettor, 1, e.payout)?;
+            }
+            BoxmeoutEvent::UnclaimedSwept(e) => {
+                let mut m = market(store, e.market_id)?;
+                m.swept += e.amount;
+                store.put_market(&m)?;
+            }
+            BoxmeoutEvent::ResolutionDisputed(e) => {
+                set_status(store, e.market_id, MarketStatus::Disputed)?;
+                store.put_dispute(&DisputeState {
+                    numeric_id: e.market_id,
+                    dispute_id: e.dispute_id,
+                    disputer: address(&e.disputer),
+                    reason: hex(&e.reason),
+                    status: "open".into(),
+                    final_outcome: None,
+                })?;
+            }
+            BoxmeoutEvent::DisputeResolved(e) => {
+                let mut m = market(store, e.market_id)?;
+                let upheld = m.outcome.as_ref() != Some(&e.final_outcome);
+                close_dispute(store, e.market_id, upheld, Some(e.final_outcome.clone()))?;
+                m.status = MarketStatus::Resolved;
+                m.outcome = Some(e.final_outcome);
+                store.put_market(&m)?;
+            }
+            BoxmeoutEvent::ResolutionFinalized(e) => {
+                // Finalizing a disputed market keeps the oracle's outcome.
+                let mut m = market(store, e.market_id)?;
+                if m.status == MarketStatus::Disputed {
+                    close_dispute(store, e.market_id, false, m.outcome.clone())?;
+                }
+                m.status = MarketStatus::Resolved;
+                store.put_market(&m)?;
+            }
+
+            // ── Treasury ──
+            BoxmeoutEvent::BetDeposited(e) => {
+                adjust_escrow(store, e.market_id, e.amount)?;
+                adjust_treasury(store, e.amount, 0)?;
+            }
+            BoxmeoutEvent::EscrowReleased(e) => {
+                adjust_escrow(store, e.market_id, -e.amount)?;
+                adjust_treasury(store, -e.amount, 0)?;
+            }
+            BoxmeoutEvent::FeesDeposited(e) => {
+                // Stake booked as fees: it leaves the market's escrow but stays
+                // in the treasury.
+                if let Some(mut m) = store.market_by_address(&address(&e.market))? {
+                    m.escrow -= e.amount;
+                    store.put_market(&m)?;
+                }
+                adjust_treasury(store, 0, e.amount)?;
+            }
+            BoxmeoutEvent::EscrowSwept(e) => {
+                adjust_escrow(store, e.market_id, -e.amount)?;
+                adjust_treasury(store, 0, e.amount)?;
+            }
+            BoxmeoutEvent::OracleStakeDeposited(e) => {
+                adjust_stake(store, &e.oracle, e.amount)?;
+                adjust_treasury(store, e.amount, 0)?;
+            }
+            BoxmeoutEvent::OracleStakeSlashed(e) => {
+                adjust_stake(store, &e.oracle, -e.amount)?;
+                adjust_treasury(store, 0, e.amount)?;
+            }
+            BoxmeoutEvent::OracleStakeReleased(e) => {
+                adjust_stake(store, &e.oracle, -e.amount)?;
+                adjust_treasury(store, -e.amount, 0)?;
+            }
+            BoxmeoutEvent::ParlayDeposited(e) => adjust_treasury(store, e.amount, 0)?,
+            BoxmeoutEvent::ParlayPaid(e) => adjust_treasury(store, -e.amount, 0)?,
+            BoxmeoutEvent::FeesWithdrawn(e) | BoxmeoutEvent::EmergencyDrain(e) => {
+                adjust_treasury(store, -e.amount, 0)?
+            }
+
+            // Cards, oracle bookkeeping, parlays and audit-only market events
+            // do not change the state this indexer tracks.
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+// ─── Handlers ─────────────────────────────────────────────────────────────────
+
+/// The stored market, or a fresh row if this is its first event.
+fn market(store: &Store, numeric_id: u64) -> Result<MarketState, IndexerError> {
+    Ok(store.market(numeric_id)?.unwrap_or_else(|| MarketState::new(numeric_id)))
+}
+
+/// Takes a withdrawn or cashed-out bet's stake back out of its pool.
+fn unwind_bet(
+    store: &Store,
+    numeric_id: u64,
+    bet_id: &Bytes,
+    state: BetStatus,
+    payout: i128,
+) -> Result<(), IndexerError> {
+    let Some(mut bet) = store.bet(&hex(bet_id))? else {
+        return Ok(());
+    };
+    let mut m = market(store, numeric_id)?;
+    if let Some(side) = bet.side() {
+        *m.pool_mut(&side) -= bet.amount;
+        m.total_pool -= bet.amount;
+    }
+    store.put_market(&m)?;
+    bet.state = state;
+    bet.payout = payout;
+    store.put_bet(&bet)
+}
+
+fn set_status(store: &Store, numeric_id: u64, status: MarketStatus) -> Result<(), IndexerError> {
+    let mut m = market(store, numeric_id)?;
+    m.status = status;
+    store.put_market(&m)
+}
+
+/// Mirrors the Market contract: a resolution with no winning stake settles
+/// into full refunds.
+fn settled_status(m: &MarketState, outcome: &Outcome) -> MarketStatus {
+    let winning_pool = match outcome {
+        Outcome::FighterA => Some(m.pool_a),
+        Outcome::FighterB => Some(m.pool_b),
+        Outcome::Draw if m.draw_enabled => Some(m.pool_draw),
+        _ => None,
+    };
+    match winning_pool {
+        Some(pool) if pool > 0 => MarketStatus::Resolved,
+        _ => MarketStatus::Cancelled,
+    }
+}
+
+fn settle_bet(store: &Store, bet_id: &Bytes, state: BetStatus, payout: i128) -> Result<(), IndexerError> {
+    if let Some(mut bet) = store.bet(&hex(bet_id))? {
+        bet.state = state;
+        bet.payout = payout;
+        store.put_bet(&bet)?;
+    }
+    Ok(())
+}
+
+fn add_paid_out(store: &Store, numeric_id: u64, amount: i128) -> Result<(), IndexerError> {
+    let mut m = market(store, numeric_id)?;
+    m.paid_out += amount;
+    store.put_market(&m)
+}
+
+/// Records a payout under the name of the event that made it.
+fn claim(
+    store: &Store,
+    raw: &RawEvent,
+    kind: &str,
+    numeric_id: u64,
+    bettor: &Address,
+    bet_count: u32,
+    amount: i128,
+) -> Result<(), IndexerError> {
+    store.insert_claim(&ClaimRecord {
+        event_id: raw.id.clone(),
+        numeric_id,
+        bettor: address(bettor),
+        kind: kind.into(),
+        bet_count,
+        amount,
+    })
+}
+
+/// Closes the open dispute on a market, if there is one.
+fn close_dispute(
+    store: &Store,
+    numeric_id: u64,
+    upheld: bool,
+    final_outcome: Option<Outcome>,
+) -> Result<(), IndexerError> {
+    let open = store.disputes(numeric_id)?.into_iter().rev().find(|d| d.status == "open");
+    if let Some(mut d) = open {
+        d.status = if upheld { "upheld" } else { "rejected" }.into();
+        d.final_outcome = final_outcome;
+        store.put_dispute(&d)?;
+    }
+    Ok(())
+}
+
+fn adjust_escrow(store: &Store, numeric_id: u64, delta: i128) -> Result<(), IndexerError> {
+    let mut m = market(store, numeric_id)?;
+    m.escrow += delta;
+    store.put_market(&m)
+}
+
+fn adjust_treasury(store: &Store, balance: i128, fees: i128) -> Result<(), IndexerError> {
+    let t = store.treasury()?;
+    store.put_treasury(&TreasuryState {
+        balance: t.balance + balance,
+        total_fees: t.total_fees + fees,
+    })
+}
+
+fn adjust_stake(store: &Store, oracle: &Address, delta: i128) -> Result<(), IndexerError> {
+    let oracle = address(oracle);
+    let stake = store.oracle_stake(&oracle)?;
+    store.put_oracle_stake(&oracle, stake + delta)
+}
+
+// ─── Value rendering ──────────────────────────────────────────────────────────
+
+/// Lowercase hex, as the Stellar CLI renders `Bytes`.
+pub fn hex(bytes: &Bytes) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// `G...` or `C...` strkey.
+pub fn address(a: &Address) -> String {
+    ScAddress::from(a).to_string()
+}
+
+fn text(s: &soroban_sdk::String) -> String {
+    let mut buf = vec![0u8; s.len() as usize];
+    s.copy_into_slice(&mut buf);
+    String::from_utf8_lossy(&buf).into_owned()
+}
@@ -0,0 +1,132 @@
+//! Diffing the reconstruction against `get_market_info` snapshots.
+//!
+//! A snapshot is the JSON the Stellar CLI prints for
+//! `stellar contract invoke --id <market> -- get_market_info`: enums as
+//! their variant names, `Bytes` as hex and `i128` as decimal strings. The
+//! market's `OptionalOutcome` prints as `"None"` or `{"Some": "FighterA"}`. Only
+//! the fields events can reconstruct are compared; others are ignored.
+
+use std::path::Path;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::error::IndexerError;
+use crate::store::{outcome_str, status_str, MarketState};
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct MarketSnapshot {
+    pub market_id:  String,
+    pub numeric_id: u64,
+    pub status:     String,
+    #[serde(default, deserialize_with = "optional_outcome")]
+    pub outcome:    Option<String>,
+    #[serde(deserialize_with = "amount")]
+    pub pool_a:     i128,
+    #[serde(deserialize_with = "amount")]
+    pub pool_b:     i128,
+    #[serde(deserialize_with = "amount")]
+    pub pool_draw:  i128,
+    #[serde(deserialize_with = "amount")]
+    pub total_pool: i128,
+}
+
+/// One field where the reconstruction and the snapshot disagree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch {
+    pub numeric_id: u64,
+    pub field:      &'static str,
+    pub indexed:    String,
+    pub snapshot:   String,
+}
+
+/// Loads a snapshot file holding one market or an array of them.
+pub fn load_snapshots(path: &Path) -> Result<Vec<MarketSnapshot>, IndexerError> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum File {
+        One(MarketSnapshot),
+        Many(Vec<MarketSnapshot>),
+    }
+    let text = std::fs::read_to_string(path)?;
+    Ok(match serde_json::from_str(&text)? {
+        File::One(s) => vec![s],
+        File::Many(s) => s,
+    })
+}
+
+/// Compares `state` with `snapshot`, reading the status as the contract would
+/// report it at `now` (see `MarketState::effective_status`).
+pub fn diff_market(state: Option<&MarketState>, snapshot: &MarketSnapshot, now: u64) -> Vec<Mismatch> {
+    let mismatch = |field, indexed: String, snap: String| Mismatch {
+        numeric_id: snapshot.numeric_id,
+        field,
+        indexed,
+        snapshot: snap,
+    };
+    let Some(m) = state else {
+        return vec![mismatch("numeric_id", "missing".into(), snapshot.numeric_id.to_string())];
+    };
+
+    let mut out = Vec::new();
+    let mut check = |field, indexed: String, snap: String| {
+        if indexed != snap {
+            out.push(mismatch(field, indexed, snap));
+        }
+    };
+    check(
+        "market_id",
+        m.market_id.clone().unwrap_or_default(),
+        snapshot.market_id.to_lowercase(),
+    );
+    check("status", status_str(&m.effective_status(now)).into(), snapshot.status.clone());
+    check(
+        "outcome",
+        m.outcome.as_ref().map(outcome_str).unwrap_or("null").into(),
+        snapshot.outcome.clone().unwrap_or_else(|| "null".into()),
+    );
+    check("pool_a", m.pool_a.to_string(), snapshot.pool_a.to_string());
+    check("pool_b", m.pool_b.to_string(), snapshot.pool_b.to_string());
+    check("pool_draw", m.pool_draw.to_string(), snapshot.pool_draw.to_string());
+    check("total_pool", m.total_pool.to_string(), snapshot.total_pool.to_string());
+    out
+}
+
+/// Accepts an `OptionalOutcome` as `"None"`, `{"Some": "X"}` or
+/// `{"Some": ["X"]}`; a bare variant name or `null` is read as-is.
+fn optional_outcome<'de, D: Deserializer<'de>>(d: D) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Inner {
+        Name(String),
+        Tuple([String; 1]),
+    }
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Outcome {
+        Name(String),
+        Some {
+            #[serde(rename = "Some")]
+            some: Inner,
+        },
+    }
+    Ok(match Option::<Outcome>::deserialize(d)? {
+        None => None,
+        Some(Outcome::Name(name)) if name == "None" => None,
+        Some(Outcome::Name(name)) => Some(name),
+        Some(Outcome::Some { some: Inner::Name(name) | Inner::Tuple([name]) }) => Some(name),
+    })
+}
+
+/// Accepts an `i128` as a JSON number or decimal string.
+fn amount<'de, D: Deserializer<'de>>(d: D) -> Result<i128, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Amount {
+        Number(i64),
+        Text(String),
+    }
+    match Amount::deserialize(d)? {
+        Amount::Number(n) => Ok(n.into()),
+        Amount::Text(s) => s.parse().map_err(serde::de::Error::custom),
+    }
+}
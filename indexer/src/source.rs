@@ -0,0 +1,105 @@
+//! Reading exported events.
+//!
+//! Exports use the Soroban RPC `getEvents` shape: either the whole response
+//! (`{ "events": [...] }`) or just the array. Each topic and the value are
+//! base64-encoded `ScVal` XDR, exactly as the RPC returns them.
+
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use soroban_sdk::{
+    xdr::{Limits, ReadXdr, ScVal},
+    Env, TryFromVal, Val, Vec,
+};
+
+use crate::error::IndexerError;
+
+/// One exported contract event.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawEvent {
+    /// RPC event id; unique, and sorts in emission order within a ledger.
+    pub id: String,
+    pub ledger: u32,
+    /// RFC 3339 close time of the ledger, e.g. `2026-03-01T20:00:00Z`.
+    #[serde(default)]
+    pub ledger_closed_at: String,
+    pub contract_id: String,
+    pub topic: std::vec::Vec<String>,
+    pub value: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Export {
+    Page { events: std::vec::Vec<RawEvent> },
+    List(std::vec::Vec<RawEvent>),
+}
+
+/// Loads every event in an export file, in file order.
+pub fn load_events(path: &Path) -> Result<std::vec::Vec<RawEvent>, IndexerError> {
+    let text = std::fs::read_to_string(path)?;
+    Ok(match serde_json::from_str(&text)? {
+        Export::Page { events } => events,
+        Export::List(events) => events,
+    })
+}
+
+impl RawEvent {
+    /// Decodes the XDR topics and value into host values for `event_parser`.
+    pub fn to_vals(&self, env: &Env) -> Result<(Vec<Val>, Val), IndexerError> {
+        let mut topics = Vec::new(env);
+        for topic in &self.topic {
+            topics.push_back(self.decode(env, topic)?);
+        }
+        Ok((topics, self.decode(env, &self.value)?))
+    }
+
+    fn decode(&self, env: &Env, b64: &str) -> Result<Val, IndexerError> {
+        let err = || IndexerError::Xdr { event_id: self.id.clone() };
+        let bytes = STANDARD.decode(b64).map_err(|_| err())?;
+        let scval = ScVal::from_xdr(bytes, Limits::none()).map_err(|_| err())?;
+        Val::try_from_val(env, &scval).map_err(|_| err())
+    }
+
+    /// Ledger close time in unix seconds, if the export carries it.
+    pub fn closed_at(&self) -> Option<u64> {
+        parse_rfc3339(&self.ledger_closed_at)
+    }
+}
+
+/// Parses a UTC `YYYY-MM-DDTHH:MM:SSZ` timestamp (fractional seconds ignored).
+fn parse_rfc3339(s: &str) -> Option<u64> {
+    let b = s.as_bytes();
+    if b.len() < 20 || b[4] != b'-' || b[7] != b'-' || b[10] != b'T' || !s.ends_with('Z') {
+        return None;
+    }
+    let num = |from: usize, to: usize| s.get(from..to)?.parse::<i64>().ok();
+    let (y, m, d) = (num(0, 4)?, num(5, 7)?, num(8, 10)?);
+    let (hh, mm, ss) = (num(11, 13)?, num(14, 16)?, num(17, 19)?);
+
+    // Days since 1970-01-01 for a proleptic Gregorian date.
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    u64::try_from(days * 86_400 + hh * 3_600 + mm * 60 + ss).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_rfc3339;
+
+    #[test]
+    fn test_parse_rfc3339() {
+        assert_eq!(parse_rfc3339("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_rfc3339("2024-02-29T12:30:15Z"), Some(1_709_209_815));
+        assert_eq!(parse_rfc3339("2026-03-01T20:00:00.250Z"), Some(1_772_395_200));
+        assert_eq!(parse_rfc3339(""), None);
+        assert_eq!(parse_rfc3339("2026-03-01 20:00:00"), None);
+    }
+}
@@ -0,0 +1,593 @@
+//! Embedded SQLite store for the reconstructed protocol state.
+//!
+//! Amounts are `i128` on-chain and exceed SQLite's 64-bit integers, so they
+//! are stored as decimal text and all arithmetic happens in Rust: replay
+//! loads a row, applies the event and writes the row back.
+
+use std::path::Path;
+
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use shared::types::{BetSide, MarketStatus, Outcome};
+
+use crate::error::IndexerError;
+use crate::source::RawEvent;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    id          TEXT PRIMARY KEY,
+    ledger      INTEGER NOT NULL,
+    contract_id TEXT NOT NULL,
+    name        TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS markets (
+    numeric_id      INTEGER PRIMARY KEY,
+    market_id       TEXT,
+    address         TEXT,
+    fighter_a       TEXT NOT NULL DEFAULT '',
+    fighter_b       TEXT NOT NULL DEFAULT '',
+    betting_ends_at INTEGER NOT NULL DEFAULT 0,
+    draw_enabled    INTEGER NOT NULL DEFAULT 0,
+    status          TEXT NOT NULL DEFAULT 'Open',
+    outcome         TEXT,
+    pool_a          TEXT NOT NULL DEFAULT '0',
+    pool_b          TEXT NOT NULL DEFAULT '0',
+    pool_draw       TEXT NOT NULL DEFAULT '0',
+    total_pool      TEXT NOT NULL DEFAULT '0',
+    prop_pool       TEXT NOT NULL DEFAULT '0',
+    paid_out        TEXT NOT NULL DEFAULT '0',
+    swept           TEXT NOT NULL DEFAULT '0',
+    escrow          TEXT NOT NULL DEFAULT '0'
+);
+CREATE TABLE IF NOT EXISTS bets (
+    bet_id     TEXT PRIMARY KEY,
+    numeric_id INTEGER NOT NULL,
+    bettor     TEXT NOT NULL,
+    prop       INTEGER NOT NULL,
+    selection  TEXT NOT NULL,
+    amount     TEXT NOT NULL,
+    state      TEXT NOT NULL,
+    payout     TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS claims (
+    id         INTEGER PRIMARY KEY AUTOINCREMENT,
+    event_id   TEXT NOT NULL,
+    numeric_id INTEGER NOT NULL,
+    bettor     TEXT NOT NULL,
+    kind       TEXT NOT NULL,
+    bet_count  INTEGER NOT NULL,
+    amount     TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS disputes (
+    numeric_id    INTEGER NOT NULL,
+    dispute_id    INTEGER NOT NULL,
+    disputer      TEXT NOT NULL,
+    reason        TEXT NOT NULL,
+    status        TEXT NOT NULL,
+    final_outcome TEXT,
+    PRIMARY KEY (numeric_id, dispute_id)
+);
+CREATE TABLE IF NOT EXISTS treasury (
+    id         INTEGER PRIMARY KEY CHECK (id = 1),
+    balance    TEXT NOT NULL,
+    total_fees TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS oracle_stakes (
+    oracle TEXT PRIMARY KEY,
+    stake  TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS clock (
+    id        INTEGER PRIMARY KEY CHECK (id = 1),
+    timestamp INTEGER NOT NULL
+);
+";
+
+// ─── Rows ─────────────────────────────────────────────────────────────────────
+
+/// A market as rebuilt from its events. Mirrors the fields of the Market
+/// contract's `get_market_info` that events can reconstruct.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MarketState {
+    pub numeric_id:      u64,
+    /// Hex hash id and contract address, known once `market_created` is seen.
+    pub market_id:       Option<String>,
+    pub address:         Option<String>,
+    pub fighter_a:       String,
+    pub fighter_b:       String,
+    pub betting_ends_at: u64,
+    pub draw_enabled:    bool,
+    /// Stored status; see `effective_status` for the lazy lock.
+    pub status:          MarketStatus,
+    pub outcome:         Option<Outcome>,
+    pub pool_a:          i128,
+    pub pool_b:          i128,
+    pub pool_draw:       i128,
+    pub total_pool:      i128,
+    pub prop_pool:       i128,
+    /// Winnings and refunds claimed, including prop payouts.
+    pub paid_out:        i128,
+    pub swept:           i128,
+    /// Stake the treasury holds in escrow for this market.
+    pub escrow:          i128,
+}
+
+impl MarketState {
+    pub fn new(numeric_id: u64) -> Self {
+        MarketState {
+            numeric_id,
+            market_id: None,
+            address: None,
+            fighter_a: String::new(),
+            fighter_b: String::new(),
+            betting_ends_at: 0,
+            draw_enabled: false,
+            status: MarketStatus::Open,
+            outcome: None,
+            pool_a: 0,
+            pool_b: 0,
+            pool_draw: 0,
+            total_pool: 0,
+            prop_pool: 0,
+            paid_out: 0,
+            swept: 0,
+            escrow: 0,
+        }
+    }
+
+    /// Status as the contract reports it at `now`: an Open market reads as
+    /// Locked once `betting_ends_at` passes, before anyone stores the lock.
+    pub fn effective_status(&self, now: u64) -> MarketStatus {
+        if self.status == MarketStatus::Open && now >= self.betting_ends_at {
+            MarketStatus::Locked
+        } else {
+            self.status.clone()
+        }
+    }
+
+    /// Pool backing `side`.
+    pub fn pool_mut(&mut self, side: &BetSide) -> &mut i128 {
+        match side {
+            BetSide::FighterA => &mut self.pool_a,
+            BetSide::FighterB => &mut self.pool_b,
+            BetSide::Draw => &mut self.pool_draw,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BetStatus {
+    Open,
+    Withdrawn,
+    CashedOut,
+    Claimed,
+    Refunded,
+}
+
+/// A winner or prop bet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BetState {
+    /// Hex bet id.
+    pub bet_id:     String,
+    pub numeric_id: u64,
+    pub bettor:     String,
+    pub prop:       bool,
+    /// `BetSide` for winner bets, `PropSelection` for props, in `Debug` form.
+    pub selection:  String,
+    pub amount:     i128,
+    pub state:      BetStatus,
+    /// Amount paid back out: winnings, refund, withdrawal or cash-out.
+    pub payout:     i128,
+}
+
+impl BetState {
+    /// The side of a winner bet.
+    pub fn side(&self) -> Option<BetSide> {
+        match (self.prop, self.selection.as_str()) {
+            (false, "FighterA") => Some(BetSide::FighterA),
+            (false, "FighterB") => Some(BetSide::FighterB),
+            (false, "Draw") => Some(BetSide::Draw),
+            _ => None,
+        }
+    }
+}
+
+/// One payout event: a single claim, a batch claim, a withdrawal or a cash-out.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClaimRecord {
+    pub event_id:   String,
+    pub numeric_id: u64,
+    pub bettor:     String,
+    /// Event name the payout came from, e.g. `winnings_claimed`.
+    pub kind:       String,
+    pub bet_count:  u32,
+    pub amount:     i128,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisputeState {
+    pub numeric_id:    u64,
+    pub dispute_id:    u32,
+    pub disputer:      String,
+    /// Hex reason bytes.
+    pub reason:        String,
+    /// `open`, `upheld` (the outcome changed) or `rejected`.
+    pub status:        String,
+    pub final_outcome: Option<Outcome>,
+}
+
+/// Treasury token accounting, as `get_balance` and `get_total_fees_earned` report it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TreasuryState {
+    pub balance:    i128,
+    pub total_fees: i128,
+}
+
+// ─── Store ────────────────────────────────────────────────────────────────────
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    /// Opens (or creates) the store at `path`.
+    pub fn open(path: &Path) -> Result<Self, IndexerError> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self, IndexerError> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self, IndexerError> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Store { conn })
+    }
+
+    /// Runs `f` in one SQLite transaction, so an event applies fully or not at all.
+    pub fn atomically<T>(
+        &self,
+        f: impl FnOnce(&Self) -> Result<T, IndexerError>,
+    ) -> Result<T, IndexerError> {
+        let tx = self.conn.unchecked_transaction()?;
+        let out = f(self)?;
+        tx.commit()?;
+        Ok(out)
+    }
+
+    /// Records `event` as applied. Returns false if it was already recorded.
+    pub fn record_event(&self, event: &RawEvent, name: &str) -> Result<bool, IndexerError> {
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO events (id, ledger, contract_id, name) VALUES (?1, ?2, ?3, ?4)",
+            params![event.id, event.ledger, event.contract_id, name],
+        )?;
+        Ok(inserted == 1)
+    }
+
+    pub fn event_count(&self) -> Result<u64, IndexerError> {
+        Ok(self.conn.query_row("SELECT COUNT(*) FROM events", [], |r| r.get(0))?)
+    }
+
+    /// Latest ledger close time seen, in unix seconds (`0` before any).
+    pub fn clock(&self) -> Result<u64, IndexerError> {
+        let ts: Option<u64> = self
+            .conn
+            .query_row("SELECT timestamp FROM clock WHERE id = 1", [], |r| r.get(0))
+            .optional()?;
+        Ok(ts.unwrap_or(0))
+    }
+
+    pub fn advance_clock(&self, timestamp: u64) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT INTO clock (id, timestamp) VALUES (1, ?1)
+             ON CONFLICT(id) DO UPDATE SET timestamp = MAX(timestamp, excluded.timestamp)",
+            params![timestamp],
+        )?;
+        Ok(())
+    }
+
+    // ── Markets ──
+
+    pub fn market(&self, numeric_id: u64) -> Result<Option<MarketState>, IndexerError> {
+        Ok(self
+            .conn
+            .query_row("SELECT * FROM markets WHERE numeric_id = ?1", params![numeric_id], market_row)
+            .optional()?)
+    }
+
+    pub fn market_by_address(&self, address: &str) -> Result<Option<MarketState>, IndexerError> {
+        Ok(self
+            .conn
+            .query_row("SELECT * FROM markets WHERE address = ?1", params![address], market_row)
+            .optional()?)
+    }
+
+    pub fn markets(&self) -> Result<Vec<MarketState>, IndexerError> {
+        let mut stmt = self.conn.prepare("SELECT * FROM markets ORDER BY numeric_id")?;
+        let rows = stmt.query_map([], market_row)?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    pub fn put_market(&self, m: &MarketState) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO markets VALUES
+             (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
+            params![
+                m.numeric_id,
+                m.market_id,
+                m.address,
+                m.fighter_a,
+                m.fighter_b,
+                m.betting_ends_at,
+                m.draw_enabled,
+                status_str(&m.status),
+                m.outcome.as_ref().map(outcome_str),
+                m.pool_a.to_string(),
+                m.pool_b.to_string(),
+                m.pool_draw.to_string(),
+                m.total_pool.to_string(),
+                m.prop_pool.to_string(),
+                m.paid_out.to_string(),
+                m.swept.to_string(),
+                m.escrow.to_string(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    // ── Bets and claims ──
+
+    pub fn bet(&self, bet_id: &str) -> Result<Option<BetState>, IndexerError> {
+        Ok(self
+            .conn
+            .query_row("SELECT * FROM bets WHERE bet_id = ?1", params![bet_id], bet_row)
+            .optional()?)
+    }
+
+    /// Every bet on a market, in bet id order.
+    pub fn bets(&self, numeric_id: u64) -> Result<Vec<BetState>, IndexerError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT * FROM bets WHERE numeric_id = ?1 ORDER BY bet_id")?;
+        let rows = stmt.query_map(params![numeric_id], bet_row)?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    pub fn put_bet(&self, b: &BetState) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO bets VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                b.bet_id,
+                b.numeric_id,
+                b.bettor,
+                b.prop,
+                b.selection,
+                b.amount.to_string(),
+                bet_status_str(b.state),
+                b.payout.to_string(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn insert_claim(&self, c: &ClaimRecord) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT INTO claims (event_id, numeric_id, bettor, kind, bet_count, amount)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![c.event_id, c.numeric_id, c.bettor, c.kind, c.bet_count, c.amount.to_string()],
+        )?;
+        Ok(())
+    }
+
+    /// Every payout on a market, in replay order.
+    pub fn claims(&self, numeric_id: u64) -> Result<Vec<ClaimRecord>, IndexerError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT event_id, numeric_id, bettor, kind, bet_count, amount
+             FROM claims WHERE numeric_id = ?1 ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![numeric_id], |r| {
+            Ok(ClaimRecord {
+                event_id: r.get(0)?,
+                numeric_id: r.get(1)?,
+                bettor: r.get(2)?,
+                kind: r.get(3)?,
+                bet_count: r.get(4)?,
+                amount: amount(r, 5)?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    // ── Disputes ──
+
+    pub fn disputes(&self, numeric_id: u64) -> Result<Vec<DisputeState>, IndexerError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT * FROM disputes WHERE numeric_id = ?1 ORDER BY dispute_id")?;
+        let rows = stmt.query_map(params![numeric_id], |r| {
+            Ok(DisputeState {
+                numeric_id: r.get(0)?,
+                dispute_id: r.get(1)?,
+                disputer: r.get(2)?,
+                reason: r.get(3)?,
+                status: r.get(4)?,
+                final_outcome: r
+                    .get::<_, Option<String>>(5)?
+                    .map(|s| parse_outcome(&s))
+                    .transpose()
+                    .map_err(|e| bad_text(5, e))?,
+            })
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    pub fn put_dispute(&self, d: &DisputeState) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO disputes VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                d.numeric_id,
+                d.dispute_id,
+                d.disputer,
+                d.reason,
+                d.status,
+                d.final_outcome.as_ref().map(outcome_str),
+            ],
+        )?;
+        Ok(())
+    }
+
+    // ── Treasury ──
+
+    pub fn treasury(&self) -> Result<TreasuryState, IndexerError> {
+        let row = self
+            .conn
+            .query_row("SELECT balance, total_fees FROM treasury WHERE id = 1", [], |r| {
+                Ok(TreasuryState { balance: amount(r, 0)?, total_fees: amount(r, 1)? })
+            })
+            .optional()?;
+        Ok(row.unwrap_or_default())
+    }
+
+    pub fn put_treasury(&self, t: &TreasuryState) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO treasury VALUES (1, ?1, ?2)",
+            params![t.balance.to_string(), t.total_fees.to_string()],
+        )?;
+        Ok(())
+    }
+
+    pub fn oracle_stake(&self, oracle: &str) -> Result<i128, IndexerError> {
+        let row = self
+            .conn
+            .query_row("SELECT stake FROM oracle_stakes WHERE oracle = ?1", params![oracle], |r| {
+                amount(r, 0)
+            })
+            .optional()?;
+        Ok(row.unwrap_or(0))
+    }
+
+    pub fn put_oracle_stake(&self, oracle: &str, stake: i128) -> Result<(), IndexerError> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO oracle_stakes VALUES (?1, ?2)",
+            params![oracle, stake.to_string()],
+        )?;
+        Ok(())
+    }
+}
+
+// ─── Column codecs ────────────────────────────────────────────────────────────
+
+fn bad_text(idx: usize, text: String) -> rusqlite::Error {
+    rusqlite::Error::FromSqlConversionFailure(
+        idx,
+        rusqlite::types::Type::Text,
+        format!("unexpected value `{text}`").into(),
+    )
+}
+
+fn amount(r: &Row, idx: usize) -> rusqlite::Result<i128> {
+    let text: String = r.get(idx)?;
+    text.parse().map_err(|_| bad_text(idx, text))
+}
+
+fn market_row(r: &Row) -> rusqlite::Result<MarketState> {
+    let status: String = r.get(7)?;
+    Ok(MarketState {
+        numeric_id: r.get(0)?,
+        market_id: r.get(1)?,
+        address: r.get(2)?,
+        fighter_a: r.get(3)?,
+        fighter_b: r.get(4)?,
+        betting_ends_at: r.get(5)?,
+        draw_enabled: r.get(6)?,
+        status: parse_status(&status).map_err(|e| bad_text(7, e))?,
+        outcome: r
+            .get::<_, Option<String>>(8)?
+            .map(|s| parse_outcome(&s))
+            .transpose()
+            .map_err(|e| bad_text(8, e))?,
+        pool_a: amount(r, 9)?,
+        pool_b: amount(r, 10)?,
+        pool_draw: amount(r, 11)?,
+        total_pool: amount(r, 12)?,
+        prop_pool: amount(r, 13)?,
+        paid_out: amount(r, 14)?,
+        swept: amount(r, 15)?,
+        escrow: amount(r, 16)?,
+    })
+}
+
+fn bet_row(r: &Row) -> rusqlite::Result<BetState> {
+    let state: String = r.get(6)?;
+    Ok(BetState {
+        bet_id: r.get(0)?,
+        numeric_id: r.get(1)?,
+        bettor: r.get(2)?,
+        prop: r.get(3)?,
+        selection: r.get(4)?,
+        amount: amount(r, 5)?,
+        state: parse_bet_status(&state).map_err(|e| bad_text(6, e))?,
+        payout: amount(r, 7)?,
+    })
+}
+
+/// `MarketStatus` as the Stellar CLI renders it, e.g. `Locked`.
+pub fn status_str(s: &MarketStatus) -> &'static str {
+    match s {
+        MarketStatus::Open => "Open",
+        MarketStatus::Locked => "Locked",
+        MarketStatus::Resolved => "Resolved",
+        MarketStatus::Cancelled => "Cancelled",
+        MarketStatus::Disputed => "Disputed",
+    }
+}
+
+pub fn parse_status(s: &str) -> Result<MarketStatus, String> {
+    Ok(match s {
+        "Open" => MarketStatus::Open,
+        "Locked" => MarketStatus::Locked,
+        "Resolved" => MarketStatus::Resolved,
+        "Cancelled" => MarketStatus::Cancelled,
+        "Disputed" => MarketStatus::Disputed,
+        other => return Err(other.into()),
+    })
+}
+
+/// `Outcome` as the Stellar CLI renders it, e.g. `FighterA`.
+pub fn outcome_str(o: &Outcome) -> &'static str {
+    match o {
+        Outcome::FighterA => "FighterA",
+        Outcome::FighterB => "FighterB",
+        Outcome::Draw => "Draw",
+        Outcome::NoContest => "NoContest",
+    }
+}
+
+pub fn parse_outcome(s: &str) -> Result<Outcome, String> {
+    Ok(match s {
+        "FighterA" => Outcome::FighterA,
+        "FighterB" => Outcome::FighterB,
+        "Draw" => Outcome::Draw,
+        "NoContest" => Outcome::NoContest,
+        other => return Err(other.into()),
+    })
+}
+
+fn bet_status_str(s: BetStatus) -> &'static str {
+    match s {
+        BetStatus::Open => "open",
+        BetStatus::Withdrawn => "withdrawn",
+        BetStatus::CashedOut => "cashed_out",
+        BetStatus::Claimed => "claimed",
+        BetStatus::Refunded => "refunded",
+    }
+}
+
+fn parse_bet_status(s: &str) -> Result<BetStatus, String> {
+    Ok(match s {
+        "open" => BetStatus::Open,
+        "withdrawn" => BetStatus::Withdrawn,
+        "cashed_out" => BetStatus::CashedOut,
+        "claimed" => BetStatus::Claimed,
+        "refunded" => BetStatus::Refunded,
+        other => return Err(other.into()),
+    })
+}
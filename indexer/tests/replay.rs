@@ -0,0 +1,227 @@
+//! =============================================================================
+//! BOXMEOUT — Indexer Replay
+//! =============================================================================
+//!
+//! Replays the exports in `tests/fixtures` (Soroban RPC `getEvents` pages):
+//!   - Market 1: four winner bets, a prop bet, a cash-out, lock, resolution,
+//!     a rejected dispute, a claim, a batch claim and a sweep
+//!   - Market 2: two bets, cancelled, one refund claimed
+//!   - Token `transfer` events in the same export are skipped
+//!   - Page 2 repeats the last event of page 1
+//!
+//! The reconstruction is checked against `market_snapshots.json`, taken
+//! with `get_market_info` after the same history.
+
+use std::path::PathBuf;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use indexer::{
+    diff_market, load_snapshots, BetStatus, Indexer, IndexerError, MarketState, RawEvent,
+    ReplaySummary, Store, TreasuryState,
+};
+use shared::event_parser::ParseError;
+use shared::types::{MarketStatus, Outcome};
+use soroban_sdk::xdr::{Limits, ScSymbol, ScVal, WriteXdr};
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name)
+}
+
+fn pages() -> [PathBuf; 2] {
+    [fixture("events_page1.json"), fixture("events_page2.json")]
+}
+
+fn replayed() -> Indexer {
+    let indexer = Indexer::new(Store::open_in_memory().unwrap());
+    indexer.replay_files(&pages()).unwrap();
+    indexer
+}
+
+fn market(indexer: &Indexer, numeric_id: u64) -> MarketState {
+    indexer.store().market(numeric_id).unwrap().expect("market indexed")
+}
+
+fn b64(v: &ScVal) -> String {
+    STANDARD.encode(v.to_xdr(Limits::none()).unwrap())
+}
+
+// ─── Replay ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn replay_counts_applied_duplicate_and_foreign_events() {
+    let indexer = Indexer::new(Store::open_in_memory().unwrap());
+    let summary = indexer.replay_files(&pages()).unwrap();
+    assert_eq!(summary, ReplaySummary { applied: 37, duplicates: 1, skipped: 6 });
+    assert_eq!(indexer.store().event_count().unwrap(), 37);
+}
+
+#[test]
+fn replay_rebuilds_market_pools_and_status() {
+    let indexer = replayed();
+
+    let m1 = market(&indexer, 1);
+    assert_eq!(m1.market_id.as_deref(), Some("11".repeat(32).as_str()));
+    assert_eq!((m1.fighter_a.as_str(), m1.fighter_b.as_str()), ("Fury", "Usyk"));
+    assert_eq!(m1.status, MarketStatus::Resolved);
+    assert_eq!(m1.outcome, Some(Outcome::FighterA));
+    // Dave's 500 on FighterB was cashed out before the lock.
+    assert_eq!((m1.pool_a, m1.pool_b, m1.pool_draw, m1.total_pool), (4_000, 1_000, 0, 5_000));
+    assert_eq!(m1.prop_pool, 400);
+    assert_eq!(m1.paid_out, 4_900);
+    assert_eq!(m1.swept, 400);
+    assert_eq!(m1.escrow, 0);
+
+    let m2 = market(&indexer, 2);
+    assert!(m2.draw_enabled);
+    assert_eq!(m2.status, MarketStatus::Cancelled);
+    assert_eq!(m2.outcome, None);
+    assert_eq!((m2.pool_a, m2.pool_b, m2.pool_draw, m2.total_pool), (2_000, 0, 700, 2_700));
+    assert_eq!(m2.paid_out, 2_000);
+    // Frank has not claimed his refund yet.
+    assert_eq!(m2.escrow, 700);
+}
+
+#[test]
+fn replay_tracks_bets_and_claims() {
+    let indexer = replayed();
+    let store = indexer.store();
+
+    let bets = store.bets(1).unwrap();
+    assert_eq!(bets.len(), 5);
+    let states: Vec<_> = bets.iter().map(|b| (b.prop, b.state, b.payout)).collect();
+    assert_eq!(
+        states,
+        vec![
+            (false, BetStatus::Claimed, 3_675), // alice, single claim
+            (false, BetStatus::Open, 0),        // bob, lost
+            (false, BetStatus::Claimed, 0),     // carol, paid by the batch total
+            (false, BetStatus::CashedOut, 475), // dave
+            (true, BetStatus::Open, 0),         // bob's prop, swept
+        ]
+    );
+
+    let claims = store.claims(1).unwrap();
+    let kinds: Vec<_> = claims.iter().map(|c| (c.kind.as_str(), c.bet_count, c.amount)).collect();
+    assert_eq!(
+        kinds,
+        vec![("bet_cashed_out", 1, 475), ("winnings_claimed", 1, 3_675), ("batch_claimed", 1, 1_225)]
+    );
+
+    let refunds = store.claims(2).unwrap();
+    assert_eq!(refunds.len(), 1);
+    assert_eq!((refunds[0].kind.as_str(), refunds[0].amount), ("refund_claimed", 2_000));
+    assert_eq!(store.bets(2).unwrap()[0].state, BetStatus::Refunded);
+}
+
+#[test]
+fn replay_records_dispute_ruling() {
+    let indexer = replayed();
+    let disputes = indexer.store().disputes(1).unwrap();
+    assert_eq!(disputes.len(), 1);
+    assert_eq!(disputes[0].dispute_id, 0);
+    // The ruling kept FighterA, so the challenge was rejected.
+    assert_eq!(disputes[0].status, "rejected");
+    assert_eq!(disputes[0].final_outcome, Some(Outcome::FighterA));
+    assert!(indexer.store().disputes(2).unwrap().is_empty());
+}
+
+#[test]
+fn replay_rebuilds_treasury_balance() {
+    let indexer = replayed();
+    // 10_000_000 oracle stake + 8_600 staked - 7_375 paid out; fees are the
+    // cash-out fee, the protocol fee and the swept prop stake.
+    assert_eq!(
+        indexer.store().treasury().unwrap(),
+        TreasuryState { balance: 10_001_225, total_fees: 525 }
+    );
+}
+
+#[test]
+fn replay_is_idempotent() {
+    let indexer = replayed();
+    let before = indexer.store().markets().unwrap();
+
+    let again = indexer.replay_files(&pages()).unwrap();
+    assert_eq!(again, ReplaySummary { applied: 0, duplicates: 38, skipped: 6 });
+    assert_eq!(indexer.store().markets().unwrap(), before);
+    assert_eq!(indexer.store().treasury().unwrap().balance, 10_001_225);
+}
+
+#[test]
+fn replay_resumes_from_a_persisted_store() {
+    let path = std::env::temp_dir().join(format!("boxmeout-indexer-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let [page1, page2] = pages();
+    Indexer::new(Store::open(&path).unwrap()).replay_files(&[page1]).unwrap();
+    let resumed = Indexer::new(Store::open(&path).unwrap());
+    let summary = resumed.replay_files(&[page2]).unwrap();
+    assert_eq!(summary, ReplaySummary { applied: 14, duplicates: 1, skipped: 0 });
+    assert_eq!(market(&resumed, 1).status, MarketStatus::Resolved);
+
+    drop(resumed);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn replay_rejects_unsupported_schema_version() {
+    let indexer = Indexer::new(Store::open_in_memory().unwrap());
+    let event = RawEvent {
+        id: "0000000001-0000000001".into(),
+        ledger: 1,
+        ledger_closed_at: String::new(),
+        contract_id: String::new(),
+        topic: vec![
+            b64(&ScVal::Symbol(ScSymbol("market_locked".try_into().unwrap()))),
+            b64(&ScVal::U32(1)),
+        ],
+        value: b64(&ScVal::Void),
+    };
+    match indexer.apply(&event) {
+        Err(IndexerError::Parse { error, .. }) => assert_eq!(error, ParseError::UnsupportedVersion),
+        other => panic!("expected a parse error, got {other:?}"),
+    }
+    assert_eq!(indexer.store().event_count().unwrap(), 0);
+}
+
+// ─── Snapshots ────────────────────────────────────────────────────────────────
+
+#[test]
+fn reconstruction_matches_market_snapshots() {
+    let indexer = replayed();
+    let now = indexer.store().clock().unwrap();
+    let snapshots = load_snapshots(&fixture("market_snapshots.json")).unwrap();
+    assert_eq!(snapshots.len(), 2);
+    for snapshot in &snapshots {
+        let state = indexer.store().market(snapshot.numeric_id).unwrap();
+        assert_eq!(diff_market(state.as_ref(), snapshot, now), vec![]);
+    }
+}
+
+#[test]
+fn diff_reports_diverging_fields() {
+    let indexer = replayed();
+    let mut snapshot = load_snapshots(&fixture("market_snapshots.json")).unwrap().remove(0);
+    snapshot.pool_a += 1;
+    snapshot.status = "Disputed".into();
+
+    let diff = diff_market(Some(&market(&indexer, 1)), &snapshot, 0);
+    let fields: Vec<_> = diff.iter().map(|m| (m.field, m.indexed.as_str(), m.snapshot.as_str())).collect();
+    assert_eq!(fields, vec![("status", "Resolved", "Disputed"), ("pool_a", "4000", "4001")]);
+
+    snapshot.numeric_id = 99;
+    assert_eq!(diff_market(None, &snapshot, 0)[0].field, "numeric_id");
+}
+
+#[test]
+fn diff_reads_open_market_as_locked_after_deadline() {
+    let mut state = MarketState::new(1);
+    state.betting_ends_at = 1_000;
+    assert_eq!(state.effective_status(999), MarketStatus::Open);
+    assert_eq!(state.effective_status(1_000), MarketStatus::Locked);
+
+    state.status = MarketStatus::Cancelled;
+    assert_eq!(state.effective_status(1_000), MarketStatus::Cancelled);
+}